@@ -1,22 +1,35 @@
 //! Authentication middleware
 //!
-//! This module provides JWT-based authentication middleware for protecting API routes.
+//! This module provides authentication middleware for protecting API routes.
+//! Callers authenticate with a JWT bearer token in the `Authorization` header
+//! or with a managed API key in the `X-API-Key` header.
 
 use axum::{
     body::Body,
     extract::{Request, State},
-    http::{header::AUTHORIZATION, StatusCode},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use llm_registry_service::{ApiKey, ApiKeyService, ServiceError, UserService};
 use std::sync::Arc;
 use tracing::{debug, warn};
 
 use crate::{
     error::ErrorResponse,
     jwt::{Claims, JwtManager, TokenError},
+    rbac::Permission,
 };
 
+/// Header carrying a managed API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Custom claim holding the ID of the API key a caller authenticated with
+const API_KEY_ID_CLAIM: &str = "api_key_id";
+
+/// Custom claim restricting a caller to a set of `resource:action` permissions
+const SCOPES_CLAIM: &str = "scopes";
+
 /// Extension for storing authenticated user claims in requests
 #[derive(Debug, Clone)]
 pub struct AuthUser {
@@ -54,12 +67,41 @@ impl AuthUser {
     pub fn has_all_roles(&self, roles: &[&str]) -> bool {
         self.claims.has_all_roles(roles)
     }
+
+    /// ID of the API key the user authenticated with, if any
+    pub fn api_key_id(&self) -> Option<&str> {
+        self.claims.custom.get(API_KEY_ID_CLAIM)?.as_str()
+    }
+
+    /// Permissions the user is restricted to, if any
+    ///
+    /// `None` means the user is only limited by their roles.
+    pub fn scopes(&self) -> Option<Vec<Permission>> {
+        let scopes = self.claims.custom.get(SCOPES_CLAIM)?.as_array()?;
+        Some(
+            scopes
+                .iter()
+                .filter_map(|scope| scope.as_str())
+                .filter_map(|scope| Permission::from_string(scope).ok())
+                .collect(),
+        )
+    }
+
+    /// Whether the user's scopes allow a permission
+    ///
+    /// Roles are checked separately; this only applies the scope restriction.
+    pub fn permits(&self, permission: &Permission) -> bool {
+        self.scopes()
+            .map_or(true, |scopes| scopes.iter().any(|scope| scope.matches(permission)))
+    }
 }
 
 /// Authentication state containing JWT manager
 #[derive(Clone)]
 pub struct AuthState {
     jwt_manager: Arc<JwtManager>,
+    api_keys: Option<Arc<dyn ApiKeyService>>,
+    users: Option<Arc<dyn UserService>>,
 }
 
 impl AuthState {
@@ -67,13 +109,123 @@ impl AuthState {
     pub fn new(jwt_manager: JwtManager) -> Self {
         Self {
             jwt_manager: Arc::new(jwt_manager),
+            api_keys: None,
+            users: None,
         }
     }
 
+    /// Accept managed API keys in the `X-API-Key` header
+    pub fn with_api_key_service(mut self, api_keys: Arc<dyn ApiKeyService>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    /// Check the owners of API keys against their current accounts
+    pub fn with_user_service(mut self, users: Arc<dyn UserService>) -> Self {
+        self.users = Some(users);
+        self
+    }
+
     /// Get JWT manager reference
     pub fn jwt_manager(&self) -> &JwtManager {
         &self.jwt_manager
     }
+
+    /// Authenticate a user from an `Authorization` header value
+    ///
+    /// Revoked tokens are rejected.
+    pub async fn authenticate(&self, auth_header: &str) -> Result<AuthUser, AuthError> {
+        let token = JwtManager::extract_token_from_header(auth_header)
+            .map_err(|_| AuthError::InvalidToken)?;
+
+        let claims = self
+            .jwt_manager
+            .verify_token(token)
+            .await
+            .map_err(AuthError::from)?;
+
+        Ok(AuthUser::new(claims))
+    }
+
+    /// Authenticate a user from an `X-API-Key` header value
+    ///
+    /// The user acts as the key's owner, with the owner's roles at key
+    /// creation, restricted to the key's permissions. Owners with an account
+    /// must not be disabled and keep only the roles they still hold. Keys
+    /// are revoked along with their owner's tokens.
+    pub async fn authenticate_api_key(&self, api_key: &str) -> Result<AuthUser, AuthError> {
+        let Some(api_keys) = &self.api_keys else {
+            return Err(AuthError::InvalidToken);
+        };
+
+        let key = api_keys.authenticate(api_key).await.map_err(|e| {
+            warn!("API key authentication failed: {}", e);
+            AuthError::InvalidToken
+        })?;
+
+        let mut roles = key.roles.clone();
+        if let Some(users) = &self.users {
+            match users.get_user(&key.owner).await {
+                Ok(owner) if owner.disabled => {
+                    warn!("API key {} belongs to disabled user {}", key.id, owner.id);
+                    return Err(AuthError::InvalidToken);
+                }
+                Ok(owner) => roles.retain(|role| owner.roles.contains(role)),
+                // Principals without an account, such as OIDC subjects,
+                // keep the roles recorded on the key
+                Err(ServiceError::NotFound(_)) => {}
+                Err(e) => {
+                    warn!("Failed to load owner of API key {}: {}", key.id, e);
+                    return Err(AuthError::InvalidToken);
+                }
+            }
+        }
+
+        let claims = self.api_key_claims(&key, roles);
+        self.jwt_manager
+            .check_not_revoked(&claims)
+            .await
+            .map_err(AuthError::from)?;
+        Ok(AuthUser::new(claims))
+    }
+
+    /// Authenticate a request from its headers
+    ///
+    /// An `X-API-Key` header takes precedence over `Authorization`. Returns
+    /// `None` if the request carries no credentials.
+    pub async fn authenticate_headers(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<Option<AuthUser>, AuthError> {
+        if let Some(api_key) = headers.get(API_KEY_HEADER) {
+            let api_key = api_key.to_str().map_err(|_| AuthError::InvalidToken)?;
+            return self.authenticate_api_key(api_key).await.map(Some);
+        }
+
+        match headers.get(AUTHORIZATION) {
+            Some(auth_header) => {
+                let auth_header = auth_header.to_str().map_err(|_| AuthError::InvalidToken)?;
+                self.authenticate(auth_header).await.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Build the claims of a caller using an API key with the given roles
+    fn api_key_claims(&self, key: &ApiKey, roles: Vec<String>) -> Claims {
+        let config = &self.jwt_manager.config;
+        let mut claims = Claims::new(&key.owner, &config.issuer, &config.audience, 0)
+            .with_roles(roles)
+            .with_custom(API_KEY_ID_CLAIM, serde_json::json!(key.id))
+            .with_custom(SCOPES_CLAIM, serde_json::json!(key.permissions));
+        claims.jti = key.id.clone();
+        claims.iat = key.created_at.timestamp();
+        claims.iat_micros = Some(key.created_at.timestamp_micros());
+        claims.exp = key
+            .expires_at
+            .map_or(i64::MAX, |expires| expires.timestamp());
+        claims
+    }
 }
 
 /// Required authentication middleware
@@ -104,31 +256,16 @@ pub async fn require_auth(
 ) -> Result<Response, AuthError> {
     debug!("Authenticating request");
 
-    // Extract Authorization header
-    let auth_header = request
-        .headers()
-        .get(AUTHORIZATION)
-        .and_then(|h| h.to_str().ok())
+    // Validate the API key or bearer token
+    let user = auth_state
+        .authenticate_headers(request.headers())
+        .await?
         .ok_or(AuthError::MissingToken)?;
 
-    // Extract token from header
-    let token = JwtManager::extract_token_from_header(auth_header)
-        .map_err(|_| AuthError::InvalidToken)?;
-
-    // Validate token
-    let claims = auth_state
-        .jwt_manager
-        .validate_token(token)
-        .map_err(|e| match e {
-            TokenError::Expired => AuthError::ExpiredToken,
-            TokenError::NotYetValid => AuthError::InvalidToken,
-            _ => AuthError::InvalidToken,
-        })?;
-
-    debug!("User authenticated: {}", claims.sub);
+    debug!("User authenticated: {}", user.user_id());
 
     // Add user to request extensions
-    request.extensions_mut().insert(AuthUser::new(claims));
+    request.extensions_mut().insert(user);
 
     Ok(next.run(request).await)
 }
@@ -162,16 +299,10 @@ pub async fn optional_auth(
 ) -> Response {
     debug!("Attempting optional authentication");
 
-    // Try to extract and validate token
-    if let Some(auth_header) = request.headers().get(AUTHORIZATION) {
-        if let Ok(header_str) = auth_header.to_str() {
-            if let Ok(token) = JwtManager::extract_token_from_header(header_str) {
-                if let Ok(claims) = auth_state.jwt_manager.validate_token(token) {
-                    debug!("User optionally authenticated: {}", claims.sub);
-                    request.extensions_mut().insert(AuthUser::new(claims));
-                }
-            }
-        }
+    // Try to validate the API key or bearer token
+    if let Ok(Some(user)) = auth_state.authenticate_headers(request.headers()).await {
+        debug!("User optionally authenticated: {}", user.user_id());
+        request.extensions_mut().insert(user);
     }
 
     next.run(request).await
@@ -211,33 +342,20 @@ pub async fn require_role(
     debug!("Authenticating request with role check");
 
     // First authenticate
-    let auth_header = request
-        .headers()
-        .get(AUTHORIZATION)
-        .and_then(|h| h.to_str().ok())
+    let user = auth_state
+        .authenticate_headers(request.headers())
+        .await?
         .ok_or(AuthError::MissingToken)?;
 
-    let token = JwtManager::extract_token_from_header(auth_header)
-        .map_err(|_| AuthError::InvalidToken)?;
-
-    let claims = auth_state
-        .jwt_manager
-        .validate_token(token)
-        .map_err(|e| match e {
-            TokenError::Expired => AuthError::ExpiredToken,
-            TokenError::NotYetValid => AuthError::InvalidToken,
-            _ => AuthError::InvalidToken,
-        })?;
-
     // Check roles
     let role_refs: Vec<&str> = allowed_roles.iter().map(|s| s.as_str()).collect();
-    if !claims.has_any_role(&role_refs) {
-        warn!("User {} lacks required role", claims.sub);
+    if !user.has_any_role(&role_refs) {
+        warn!("User {} lacks required role", user.user_id());
         return Err(AuthError::InsufficientPermissions);
     }
 
-    debug!("User authenticated with role: {}", claims.sub);
-    request.extensions_mut().insert(AuthUser::new(claims));
+    debug!("User authenticated with role: {}", user.user_id());
+    request.extensions_mut().insert(user);
 
     Ok(next.run(request).await)
 }
@@ -276,6 +394,9 @@ pub enum AuthError {
     /// Token has expired
     ExpiredToken,
 
+    /// Token has been revoked
+    RevokedToken,
+
     /// User is not authenticated
     Unauthenticated,
 
@@ -298,6 +419,10 @@ impl IntoResponse for AuthError {
                 StatusCode::UNAUTHORIZED,
                 "Authentication token has expired",
             ),
+            AuthError::RevokedToken => (
+                StatusCode::UNAUTHORIZED,
+                "Authentication token has been revoked",
+            ),
             AuthError::Unauthenticated => (
                 StatusCode::UNAUTHORIZED,
                 "Authentication required",
@@ -325,6 +450,7 @@ impl std::fmt::Display for AuthError {
             AuthError::MissingToken => write!(f, "Missing authentication token"),
             AuthError::InvalidToken => write!(f, "Invalid authentication token"),
             AuthError::ExpiredToken => write!(f, "Authentication token has expired"),
+            AuthError::RevokedToken => write!(f, "Authentication token has been revoked"),
             AuthError::Unauthenticated => write!(f, "Authentication required"),
             AuthError::InsufficientPermissions => write!(f, "Insufficient permissions"),
         }
@@ -333,6 +459,16 @@ impl std::fmt::Display for AuthError {
 
 impl std::error::Error for AuthError {}
 
+impl From<TokenError> for AuthError {
+    fn from(err: TokenError) -> Self {
+        match err {
+            TokenError::Expired => AuthError::ExpiredToken,
+            TokenError::Revoked => AuthError::RevokedToken,
+            _ => AuthError::InvalidToken,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -479,6 +615,175 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_require_auth_with_api_key() {
+        use llm_registry_service::{
+            CreateApiKeyRequest, DefaultApiKeyService, InMemoryApiKeyStore,
+        };
+
+        let api_keys = Arc::new(DefaultApiKeyService::new(Arc::new(InMemoryApiKeyStore::new())));
+        let created = api_keys
+            .create_key(CreateApiKeyRequest {
+                name: "ci".to_string(),
+                owner: "user123".to_string(),
+                roles: vec!["developer".to_string()],
+                permissions: vec!["asset:read".to_string()],
+                expires_at: None,
+            })
+            .await
+            .unwrap();
+        let auth_state =
+            AuthState::new(create_test_jwt_manager()).with_api_key_service(api_keys.clone());
+
+        let user = auth_state.authenticate_api_key(&created.api_key).await.unwrap();
+        assert_eq!(user.user_id(), "user123");
+        assert_eq!(user.api_key_id(), Some(created.key.id.as_str()));
+        assert!(user.has_role("developer"));
+        assert!(user.permits(&Permission::new("asset", "read")));
+        assert!(!user.permits(&Permission::new("asset", "write")));
+
+        let app = Router::new()
+            .route("/protected", get(protected_handler))
+            .layer(middleware::from_fn_with_state(auth_state, require_auth));
+        let request = |key: &str| {
+            Request::builder()
+                .uri("/protected")
+                .header(API_KEY_HEADER, key)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(&created.api_key))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        api_keys.revoke_key(&created.key.id).await.unwrap();
+        let response = app.oneshot(request(&created.api_key)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_follows_owner_account() {
+        use llm_registry_service::{
+            CreateApiKeyRequest, CreateUserRequest, DefaultApiKeyService, DefaultUserService,
+            InMemoryApiKeyStore, InMemoryTokenRevocationStore, InMemoryUserStore,
+        };
+
+        let users = Arc::new(DefaultUserService::new(Arc::new(InMemoryUserStore::new())));
+        let owner = users
+            .create_user(CreateUserRequest {
+                username: "alice".to_string(),
+                password: "correct horse battery".to_string(),
+                email: None,
+                roles: vec!["developer".to_string(), "admin".to_string()],
+            })
+            .await
+            .unwrap();
+        let api_keys = Arc::new(DefaultApiKeyService::new(Arc::new(
+            InMemoryApiKeyStore::new(),
+        )));
+        let created = api_keys
+            .create_key(CreateApiKeyRequest {
+                name: "ci".to_string(),
+                owner: owner.id.clone(),
+                roles: owner.roles.clone(),
+                permissions: vec!["asset:read".to_string()],
+                expires_at: None,
+            })
+            .await
+            .unwrap();
+        let jwt_manager = create_test_jwt_manager()
+            .with_revocation_store(Arc::new(InMemoryTokenRevocationStore::new()));
+        let auth_state = AuthState::new(jwt_manager)
+            .with_api_key_service(api_keys)
+            .with_user_service(users.clone());
+
+        let user = auth_state
+            .authenticate_api_key(&created.api_key)
+            .await
+            .unwrap();
+        assert!(user.has_role("admin"));
+
+        users
+            .set_roles(&owner.id, vec!["developer".to_string()])
+            .await
+            .unwrap();
+        let user = auth_state
+            .authenticate_api_key(&created.api_key)
+            .await
+            .unwrap();
+        assert!(user.has_role("developer"));
+        assert!(!user.has_role("admin"));
+
+        users.set_disabled(&owner.id, true).await.unwrap();
+        let app = Router::new()
+            .route("/protected", get(protected_handler))
+            .layer(middleware::from_fn_with_state(
+                auth_state.clone(),
+                require_auth,
+            ));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/protected")
+                    .header(API_KEY_HEADER, created.api_key.as_str())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        users.set_disabled(&owner.id, false).await.unwrap();
+        assert!(auth_state
+            .authenticate_api_key(&created.api_key)
+            .await
+            .is_ok());
+        auth_state
+            .jwt_manager()
+            .revoke_all_for_subject(&owner.id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            auth_state.authenticate_api_key(&created.api_key).await,
+            Err(AuthError::RevokedToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_require_auth_with_revoked_token() {
+        let jwt_manager = create_test_jwt_manager().with_revocation_store(Arc::new(
+            llm_registry_service::InMemoryTokenRevocationStore::new(),
+        ));
+        let token = jwt_manager.generate_token("user123").unwrap();
+        let claims = jwt_manager.validate_token(&token).unwrap();
+        jwt_manager.revoke(&claims).await.unwrap();
+        let auth_state = AuthState::new(jwt_manager);
+
+        let app = Router::new()
+            .route("/protected", get(protected_handler))
+            .layer(middleware::from_fn_with_state(
+                auth_state.clone(),
+                require_auth,
+            ));
+
+        let request = Request::builder()
+            .uri("/protected")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(matches!(
+            auth_state.authenticate(&format!("Bearer {}", token)).await,
+            Err(AuthError::RevokedToken)
+        ));
+    }
+
     #[test]
     fn test_auth_user() {
         let claims = crate::jwt::Claims::new("user123", "test", "test", 3600)
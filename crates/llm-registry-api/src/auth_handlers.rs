@@ -1,28 +1,38 @@
 //! Authentication API handlers
 //!
 //! This module provides HTTP handlers for authentication endpoints including
-//! login, token refresh, and user information.
+//! login, token refresh, user information and API key management.
 
 use axum::{
-    extract::{Extension, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     Json,
 };
+use chrono::{DateTime, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use llm_registry_service::{
+    ApiKey, ApiKeyService, CreateApiKeyRequest, CreatedApiKey, User, UserService,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::{
     auth::AuthUser,
+    authorization::{Authorizer, Operation},
     error::{ApiError, ApiResult},
-    jwt::{Claims, JwtManager, TokenPair},
+    jwt::{Claims, JwtManager, TokenError, TokenPair},
+    rbac::Permission,
     responses::{ok, ApiResponse},
 };
 
 /// Authentication state for handlers
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct AuthHandlerState {
     jwt_manager: Arc<JwtManager>,
+    authorizer: Arc<Authorizer>,
+    users: Option<Arc<dyn UserService>>,
+    api_keys: Option<Arc<dyn ApiKeyService>>,
 }
 
 impl AuthHandlerState {
@@ -30,13 +40,94 @@ impl AuthHandlerState {
     pub fn new(jwt_manager: JwtManager) -> Self {
         Self {
             jwt_manager: Arc::new(jwt_manager),
+            authorizer: Arc::new(Authorizer::default()),
+            users: None,
+            api_keys: None,
         }
     }
 
+    /// Use a user service to verify logins
+    pub fn with_user_service(mut self, users: Arc<dyn UserService>) -> Self {
+        self.users = Some(users);
+        self
+    }
+
+    /// Use an API key service to manage API keys
+    pub fn with_api_key_service(mut self, api_keys: Arc<dyn ApiKeyService>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    /// Use a custom authorizer
+    pub fn with_authorizer(mut self, authorizer: Arc<Authorizer>) -> Self {
+        self.authorizer = authorizer;
+        self
+    }
+
     /// Get JWT manager reference
     pub fn jwt_manager(&self) -> &JwtManager {
         &self.jwt_manager
     }
+
+    /// Get a shared handle to the JWT manager
+    pub(crate) fn shared_jwt_manager(&self) -> Arc<JwtManager> {
+        Arc::clone(&self.jwt_manager)
+    }
+
+    /// Get authorizer reference
+    pub fn authorizer(&self) -> &Authorizer {
+        &self.authorizer
+    }
+
+    /// Get the user service or fail if user accounts are not configured
+    fn users(&self) -> ApiResult<&Arc<dyn UserService>> {
+        self.users.as_ref().ok_or_else(|| {
+            ApiError::with_code(
+                StatusCode::NOT_IMPLEMENTED,
+                "User accounts are not configured",
+                "USERS_DISABLED",
+            )
+        })
+    }
+
+    /// Get the API key service or fail if API keys are not configured
+    fn api_keys(&self) -> ApiResult<&Arc<dyn ApiKeyService>> {
+        self.api_keys.as_ref().ok_or_else(|| {
+            ApiError::with_code(
+                StatusCode::NOT_IMPLEMENTED,
+                "API keys are not configured",
+                "API_KEYS_DISABLED",
+            )
+        })
+    }
+
+    /// Build access token claims for a user from their stored account
+    fn claims_for(&self, user: &User) -> Claims {
+        let config = &self.jwt_manager.config;
+        let mut claims = Claims::new(
+            &user.id,
+            config.issuer.clone(),
+            config.audience.clone(),
+            config.expiration_seconds,
+        )
+        .with_roles(user.roles.clone())
+        .with_custom("username", serde_json::json!(user.username));
+        if let Some(email) = &user.email {
+            claims = claims.with_email(email.clone());
+        }
+        claims
+    }
+}
+
+impl std::fmt::Debug for AuthHandlerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthHandlerState")
+            .field("jwt_manager", &self.jwt_manager)
+            .field("authorizer", &self.authorizer)
+            .field("users", &self.users.is_some())
+            .field("api_keys", &self.api_keys.is_some())
+            .finish()
+    }
 }
 
 /// Login request
@@ -103,45 +194,41 @@ pub struct RefreshTokenResponse {
 
 /// Login handler
 ///
-/// NOTE: This is a simplified login handler for demonstration.
-/// In production, you would:
-/// 1. Validate credentials against a database
-/// 2. Use proper password hashing (bcrypt, argon2)
-/// 3. Implement rate limiting
-/// 4. Add audit logging
-/// 5. Handle MFA/2FA if required
-#[instrument(skip(state, request))]
+/// Verifies the username and password against the user store. The issued
+/// access token carries the user's ID as subject and the roles stored with
+/// the account. Failed logins count towards the account lockout.
+#[instrument(skip(state, request), fields(username = %request.username))]
 pub async fn login(
     State(state): State<AuthHandlerState>,
     Json(request): Json<LoginRequest>,
 ) -> ApiResult<(StatusCode, Json<ApiResponse<LoginResponse>>)> {
     info!("Login attempt for user: {}", request.username);
 
-    // TODO: In production, validate against database
-    // For now, this is a stub implementation
     if request.username.is_empty() || request.password.is_empty() {
         return Err(ApiError::bad_request("Username and password are required"));
     }
 
-    // Create claims for the user
-    let claims = Claims::new(
-        &request.username,
-        state.jwt_manager().config.issuer.clone(),
-        state.jwt_manager().config.audience.clone(),
-        state.jwt_manager().config.expiration_seconds,
-    )
-    .with_email(format!("{}@example.com", request.username))
-    .with_role("user");
+    let user = state
+        .users()?
+        .authenticate(&request.username, &request.password)
+        .await
+        .map_err(|e| {
+            warn!("Login failed for user {}: {}", request.username, e);
+            ApiError::from(e)
+        })?;
+
+    let claims = state.claims_for(&user);
+    let user_info = UserInfo::from_claims(&claims);
 
     // Generate token pair
     let token_pair = state
         .jwt_manager()
-        .generate_token_pair(&request.username)
+        .generate_token_pair_with_claims(claims)
         .map_err(|e| ApiError::internal_server_error(format!("Failed to generate token: {}", e)))?;
 
     let response = LoginResponse {
         token_pair,
-        user: UserInfo::from_claims(&claims),
+        user: user_info,
     };
 
     info!("User logged in successfully: {}", request.username);
@@ -149,6 +236,9 @@ pub async fn login(
 }
 
 /// Refresh token handler
+///
+/// When user accounts are configured, the roles in the new access token are
+/// reloaded from the user store, and disabled accounts cannot refresh.
 #[instrument(skip(state, request))]
 pub async fn refresh_token(
     State(state): State<AuthHandlerState>,
@@ -156,20 +246,35 @@ pub async fn refresh_token(
 ) -> ApiResult<Json<ApiResponse<RefreshTokenResponse>>> {
     debug!("Token refresh requested");
 
-    // Refresh the token
-    let token_pair = state
+    let claims = state
         .jwt_manager()
-        .refresh_access_token(&request.refresh_token)
+        .validate_refresh_token(&request.refresh_token)
+        .await
         .map_err(|e| match e {
-            crate::jwt::TokenError::Expired => {
-                ApiError::unauthorized("Refresh token has expired")
-            }
-            crate::jwt::TokenError::InvalidClaims(_) => {
-                ApiError::bad_request("Invalid refresh token")
-            }
+            TokenError::Expired => ApiError::unauthorized("Refresh token has expired"),
+            TokenError::Revoked => ApiError::unauthorized("Refresh token has been revoked"),
+            TokenError::InvalidClaims(_) => ApiError::bad_request("Invalid refresh token"),
             _ => ApiError::unauthorized("Invalid refresh token"),
         })?;
 
+    // Refresh the token
+    let token_pair = match &state.users {
+        Some(users) => {
+            let user = users
+                .get_user(&claims.sub)
+                .await
+                .map_err(|_| ApiError::unauthorized("Invalid refresh token"))?;
+            if user.disabled {
+                return Err(ApiError::unauthorized("Account is disabled"));
+            }
+            state
+                .jwt_manager()
+                .generate_token_pair_with_claims(state.claims_for(&user))
+        }
+        None => state.jwt_manager().generate_token_pair(&claims.sub),
+    }
+    .map_err(|e| ApiError::internal_server_error(format!("Failed to generate token: {}", e)))?;
+
     let response = RefreshTokenResponse { token_pair };
 
     debug!("Token refreshed successfully");
@@ -188,21 +293,50 @@ pub async fn me(
     Ok(Json(ok(user_info)))
 }
 
+/// Publish the public keys our tokens are signed with
+///
+/// Served at `/.well-known/jwks.json`. The key set is empty when tokens are
+/// signed with a shared secret.
+pub async fn jwks(State(state): State<AuthHandlerState>) -> Json<JwkSet> {
+    Json(state.jwt_manager().jwks())
+}
+
+/// Logout request
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct LogoutRequest {
+    /// Refresh token to revoke along with the access token
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
 /// Logout handler
 ///
-/// NOTE: JWT tokens are stateless, so logout is primarily client-side.
-/// In production, you might:
-/// 1. Maintain a token blacklist in Redis
-/// 2. Use short-lived tokens with refresh tokens
-/// 3. Implement token revocation
-#[instrument(skip(user))]
+/// Revokes the access token used for the request and, if given, the refresh
+/// token issued with it. Without a revocation store, tokens stay valid until
+/// they expire and logout is client-side only.
+#[instrument(skip(state, user, request))]
 pub async fn logout(
+    State(state): State<AuthHandlerState>,
     Extension(user): Extension<AuthUser>,
+    request: Option<Json<LogoutRequest>>,
 ) -> ApiResult<Json<ApiResponse<LogoutResponse>>> {
     info!("User logout: {}", user.user_id());
 
-    // TODO: In production, add token to blacklist
-    // For now, just acknowledge the logout
+    let jwt_manager = state.jwt_manager();
+    jwt_manager.revoke(&user.claims).await.map_err(revocation_error)?;
+
+    if let Some(refresh_token) = request.and_then(|Json(r)| r.refresh_token) {
+        // Only the caller's own refresh tokens can be revoked this way
+        let claims = jwt_manager
+            .validate_token(&refresh_token)
+            .map_err(|_| ApiError::bad_request("Invalid refresh token"))?;
+        if claims.sub != user.user_id() {
+            return Err(ApiError::forbidden(
+                "Refresh token belongs to a different user",
+            ));
+        }
+        jwt_manager.revoke(&claims).await.map_err(revocation_error)?;
+    }
 
     let response = LogoutResponse {
         message: "Logged out successfully".to_string(),
@@ -211,6 +345,72 @@ pub async fn logout(
     Ok(Json(ok(response)))
 }
 
+/// Log out of every session of the current user
+#[instrument(skip(state, user))]
+pub async fn logout_all(
+    State(state): State<AuthHandlerState>,
+    Extension(user): Extension<AuthUser>,
+) -> ApiResult<Json<ApiResponse<LogoutResponse>>> {
+    info!("Revoking all sessions of user: {}", user.user_id());
+
+    revoke_sessions(&state, user.user_id()).await?;
+
+    let response = LogoutResponse {
+        message: "All sessions revoked".to_string(),
+    };
+
+    Ok(Json(ok(response)))
+}
+
+/// Revoke every session of another user
+#[instrument(skip(state, user))]
+pub async fn revoke_user_sessions(
+    State(state): State<AuthHandlerState>,
+    Extension(user): Extension<AuthUser>,
+    Path(user_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<LogoutResponse>>> {
+    state
+        .authorizer()
+        .authorize(Some(&user), Operation::RevokeUserSessions)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to revoke sessions"))?;
+
+    info!("User {} revoking all sessions of user {}", user.user_id(), user_id);
+    revoke_sessions(&state, &user_id).await?;
+
+    let response = LogoutResponse {
+        message: "All sessions revoked".to_string(),
+    };
+
+    Ok(Json(ok(response)))
+}
+
+/// Revoke every token of a subject, failing if revocation is not configured
+async fn revoke_sessions(state: &AuthHandlerState, subject: &str) -> ApiResult<()> {
+    let jwt_manager = state.jwt_manager();
+    if jwt_manager.revocation_store().is_none() {
+        return Err(ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "Token revocation is not configured",
+            "REVOCATION_DISABLED",
+        ));
+    }
+
+    jwt_manager
+        .revoke_all_for_subject(subject)
+        .await
+        .map_err(revocation_error)
+}
+
+/// Map a failure to record a revocation to a server error
+pub(crate) fn revocation_error(e: TokenError) -> ApiError {
+    ApiError::with_code(
+        StatusCode::SERVICE_UNAVAILABLE,
+        format!("Failed to revoke token: {}", e),
+        "REVOCATION_UNAVAILABLE",
+    )
+}
+
 /// Logout response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LogoutResponse {
@@ -218,53 +418,139 @@ pub struct LogoutResponse {
     pub message: String,
 }
 
-/// Generate API key handler (example of protected endpoint)
-#[instrument(skip(user))]
-pub async fn generate_api_key(
+/// API key creation request
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApiKeyRequest {
+    /// Human readable name
+    pub name: String,
+
+    /// Permissions (`resource:action`) to grant, a subset of the caller's
+    pub permissions: Vec<String>,
+
+    /// When the key stops working; never if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// API key listing parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct ListApiKeysQuery {
+    /// List the keys of every user instead of only the caller's
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Create an API key for the current user
+///
+/// The key acts as the caller with the caller's current roles, restricted to
+/// the requested permissions. Each permission must already be granted to the
+/// caller. The secret is part of the response and cannot be retrieved again.
+#[instrument(skip(state, user, request), fields(name = %request.name))]
+pub async fn create_api_key(
     State(state): State<AuthHandlerState>,
     Extension(user): Extension<AuthUser>,
-) -> ApiResult<Json<ApiResponse<ApiKeyResponse>>> {
-    info!("Generating API key for user: {}", user.user_id());
-
-    // Check if user has permission
-    if !user.has_role("admin") && !user.has_role("developer") {
-        return Err(ApiError::forbidden(
-            "Only admin or developer roles can generate API keys",
-        ));
+    Json(request): Json<ApiKeyRequest>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<CreatedApiKey>>)> {
+    info!("Creating API key for user: {}", user.user_id());
+
+    state
+        .authorizer()
+        .authorize(Some(&user), Operation::CreateApiKey)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to create API keys"))?;
+    let api_keys = state.api_keys()?;
+
+    for permission in &request.permissions {
+        let parsed = Permission::from_string(permission).map_err(|_| {
+            ApiError::bad_request(format!(
+                "Invalid permission '{}', expected resource:action",
+                permission
+            ))
+        })?;
+        if !state.authorizer().holds_permission(&user, &parsed) {
+            return Err(ApiError::forbidden(format!(
+                "Cannot grant permission '{}' you do not have",
+                permission
+            )));
+        }
     }
 
-    // Create a long-lived token for API access
-    let claims = Claims::new(
-        user.user_id(),
-        state.jwt_manager().config.issuer.clone(),
-        state.jwt_manager().config.audience.clone(),
-        86400 * 30, // 30 days
-    )
-    .with_roles(user.claims.roles.clone())
-    .with_custom("api_key", serde_json::json!(true));
+    let created = api_keys
+        .create_key(CreateApiKeyRequest {
+            name: request.name,
+            owner: user.user_id().to_string(),
+            roles: user.claims.roles.clone(),
+            permissions: request.permissions,
+            expires_at: request.expires_at,
+        })
+        .await?;
+
+    info!("API key {} created for user: {}", created.key.id, user.user_id());
+    Ok((StatusCode::CREATED, Json(ok(created))))
+}
 
-    let api_key = state
-        .jwt_manager()
-        .generate_token_with_claims(claims)
-        .map_err(|e| ApiError::internal_server_error(format!("Failed to generate API key: {}", e)))?;
+/// List API keys
+///
+/// Lists the caller's own keys, or with `?all=true` the keys of every user.
+#[instrument(skip(state, user))]
+pub async fn list_api_keys(
+    State(state): State<AuthHandlerState>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<ListApiKeysQuery>,
+) -> ApiResult<Json<ApiResponse<Vec<ApiKey>>>> {
+    let operation = if query.all {
+        Operation::ManageApiKeys
+    } else {
+        Operation::ListApiKeys
+    };
+    state
+        .authorizer()
+        .authorize(Some(&user), operation)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to list API keys"))?;
 
-    let response = ApiKeyResponse { api_key };
+    let owner = (!query.all).then(|| user.user_id());
+    let keys = state.api_keys()?.list_keys(owner).await?;
 
-    info!("API key generated for user: {}", user.user_id());
-    Ok(Json(ok(response)))
+    Ok(Json(ok(keys)))
 }
 
-/// API key response
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiKeyResponse {
-    /// Generated API key
-    pub api_key: String,
+/// Revoke an API key
+///
+/// Users can revoke their own keys; revoking another user's key requires the
+/// `api-key:admin` permission.
+#[instrument(skip(state, user))]
+pub async fn revoke_api_key(
+    State(state): State<AuthHandlerState>,
+    Extension(user): Extension<AuthUser>,
+    Path(key_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<ApiKey>>> {
+    let api_keys = state.api_keys()?;
+    let key = api_keys.get_key(&key_id).await?;
+
+    if key.owner != user.user_id() {
+        state
+            .authorizer()
+            .authorize(Some(&user), Operation::ManageApiKeys)
+            .await
+            .map_err(|_| ApiError::forbidden("Insufficient permissions to revoke API key"))?;
+    }
+
+    let key = api_keys.revoke_key(&key.id).await?;
+    info!("User {} revoked API key {}", user.user_id(), key.id);
+
+    Ok(Json(ok(key)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::jwt::JwtConfig;
+    use axum::response::IntoResponse;
+    use llm_registry_service::{
+        CreateUserRequest, DefaultApiKeyService, DefaultUserService, InMemoryApiKeyStore,
+        InMemoryTokenRevocationStore, InMemoryUserStore,
+    };
 
     fn create_test_state() -> AuthHandlerState {
         let config = JwtConfig::new("test-secret")
@@ -299,4 +585,199 @@ mod tests {
         let result = login(State(state), Json(request)).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_login_without_user_store() {
+        let request = LoginRequest {
+            username: "alice".to_string(),
+            password: "password".to_string(),
+        };
+
+        let err = login(State(create_test_state()), Json(request))
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[tokio::test]
+    async fn test_login_and_refresh_use_stored_roles() {
+        let users: Arc<dyn UserService> = Arc::new(DefaultUserService::new(Arc::new(
+            InMemoryUserStore::new(),
+        )));
+        let user = users
+            .create_user(CreateUserRequest {
+                username: "alice".to_string(),
+                password: "correct horse".to_string(),
+                email: Some("alice@example.org".to_string()),
+                roles: vec!["developer".to_string()],
+            })
+            .await
+            .unwrap();
+        let state = create_test_state().with_user_service(users.clone());
+
+        let bad = LoginRequest {
+            username: "alice".to_string(),
+            password: "wrong password".to_string(),
+        };
+        let err = login(State(state.clone()), Json(bad)).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+
+        let good = LoginRequest {
+            username: "alice".to_string(),
+            password: "correct horse".to_string(),
+        };
+        let (_, Json(response)) = login(State(state.clone()), Json(good)).await.unwrap();
+        let response = response.data;
+        assert_eq!(response.user.id, user.id);
+        assert_eq!(response.user.roles, vec!["developer"]);
+        let claims = state
+            .jwt_manager()
+            .validate_token(&response.token_pair.access_token)
+            .unwrap();
+        assert_eq!(claims.roles, vec!["developer"]);
+        assert_eq!(claims.email.as_deref(), Some("alice@example.org"));
+
+        // Role changes apply on the next refresh
+        users
+            .set_roles(&user.id, vec!["admin".to_string()])
+            .await
+            .unwrap();
+        let refresh = RefreshTokenRequest {
+            refresh_token: response.token_pair.refresh_token.clone(),
+        };
+        let Json(refreshed) = refresh_token(State(state.clone()), Json(refresh))
+            .await
+            .unwrap();
+        let claims = state
+            .jwt_manager()
+            .validate_token(&refreshed.data.token_pair.access_token)
+            .unwrap();
+        assert_eq!(claims.roles, vec!["admin"]);
+
+        // Disabled accounts can no longer refresh
+        users.set_disabled(&user.id, true).await.unwrap();
+        let refresh = RefreshTokenRequest {
+            refresh_token: response.token_pair.refresh_token,
+        };
+        let err = refresh_token(State(state), Json(refresh)).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_tokens() {
+        let config = JwtConfig::new("test-secret")
+            .with_issuer("test")
+            .with_audience("test");
+        let jwt_manager = JwtManager::new(config)
+            .unwrap()
+            .with_revocation_store(Arc::new(InMemoryTokenRevocationStore::new()));
+        let state = AuthHandlerState::new(jwt_manager);
+
+        let pair = state.jwt_manager().generate_token_pair("user123").unwrap();
+        let claims = state.jwt_manager().validate_token(&pair.access_token).unwrap();
+        let request = LogoutRequest {
+            refresh_token: Some(pair.refresh_token.clone()),
+        };
+
+        let Json(response) = logout(
+            State(state.clone()),
+            Extension(AuthUser::new(claims)),
+            Some(Json(request)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data.message, "Logged out successfully");
+
+        assert!(state.jwt_manager().verify_token(&pair.access_token).await.is_err());
+        let refresh = RefreshTokenRequest {
+            refresh_token: pair.refresh_token,
+        };
+        let err = refresh_token(State(state), Json(refresh)).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_logout_all_requires_revocation_store() {
+        let state = create_test_state();
+        let claims = Claims::new("user123", "test", "test", 3600);
+
+        let err = logout_all(State(state), Extension(AuthUser::new(claims)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_lifecycle() {
+        let state = create_test_state().with_api_key_service(Arc::new(
+            DefaultApiKeyService::new(Arc::new(InMemoryApiKeyStore::new())),
+        ));
+        let alice = AuthUser::new(Claims::new("alice", "test", "test", 3600).with_role("developer"));
+        let bob = AuthUser::new(Claims::new("bob", "test", "test", 3600).with_role("developer"));
+        let request = |permissions: &[&str]| ApiKeyRequest {
+            name: "ci".to_string(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            expires_at: None,
+        };
+
+        // Keys cannot exceed the owner's permissions
+        let err = create_api_key(
+            State(state.clone()),
+            Extension(alice.clone()),
+            Json(request(&["user:write"])),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+
+        let (status, Json(created)) = create_api_key(
+            State(state.clone()),
+            Extension(alice.clone()),
+            Json(request(&["asset:read"])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        let key_id = created.data.key.id;
+
+        let Json(keys) = list_api_keys(
+            State(state.clone()),
+            Extension(alice.clone()),
+            Query(ListApiKeysQuery::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(keys.data.len(), 1);
+        let err = list_api_keys(
+            State(state.clone()),
+            Extension(alice.clone()),
+            Query(ListApiKeysQuery { all: true }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+
+        // Only the owner (or an admin) can revoke a key
+        let err = revoke_api_key(State(state.clone()), Extension(bob), Path(key_id.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+        let Json(revoked) = revoke_api_key(State(state), Extension(alice), Path(key_id))
+            .await
+            .unwrap();
+        assert!(revoked.data.revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_disabled() {
+        let user = AuthUser::new(Claims::new("alice", "test", "test", 3600).with_role("developer"));
+        let err = list_api_keys(
+            State(create_test_state()),
+            Extension(user),
+            Query(ListApiKeysQuery::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::NOT_IMPLEMENTED);
+    }
 }
@@ -0,0 +1,1455 @@
+//! Permission enforcement for the REST, GraphQL and gRPC APIs
+//!
+//! Every operation exposed by the registry maps to exactly one [`Permission`]
+//! (see [`Operation::required_permission`]). The [`Authorizer`] checks the
+//! caller's roles against the [`RbacPolicy`] and records each decision as a
+//! [`GovernanceEvent::AccessDecision`].
+
+use axum::{
+    extract::{rejection::RawPathParamsRejection, MatchedPath, RawPathParams, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use futures::StreamExt;
+use llm_registry_core::{Asset, AssetId};
+use llm_registry_service::adapters::observatory::{GovernanceEvent, TelemetryEmitter};
+use llm_registry_service::{
+    AclEntry, AclReadFilter, AclStore, AclTarget, ClosureNode, DependencyGraphResponse,
+    EventStream, Lockfile, ObservatoryAdapter, SearchAssetsRequest, SearchAssetsResponse,
+    SearchService, ServiceError, ServiceResult, UploadSessionService, WatchedEvent,
+};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::{debug, warn};
+
+use crate::{
+    auth::{AuthError, AuthUser},
+    rbac::{Permission, RbacPolicy},
+};
+
+/// Principal recorded for requests without an authenticated user
+pub const ANONYMOUS_PRINCIPAL: &str = "anonymous";
+
+/// Registry operation subject to authorization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Register a single asset
+    RegisterAsset,
+    /// Register a batch of assets
+    BatchRegister,
+    /// Fetch an asset by ID
+    GetAsset,
+    /// List assets
+    ListAssets,
+    /// Search assets
+    SearchAssets,
+    /// Update asset metadata
+    UpdateAsset,
+    /// Delete an asset
+    DeleteAsset,
+    /// List the dependencies of an asset
+    GetDependencies,
+    /// List the dependents of an asset
+    GetDependents,
+    /// Generate a lockfile for an asset
+    GenerateLockfile,
+    /// Verify a lockfile against the registry
+    VerifyLockfile,
+    /// List all tags
+    ListTags,
+    /// Upload asset content in a single request
+    UploadContent,
+    /// Download asset content
+    DownloadContent,
+    /// Start a chunked upload session
+    CreateUploadSession,
+    /// Inspect a chunked upload session
+    GetUploadSession,
+    /// Upload a chunk to a session
+    UploadChunk,
+    /// Commit a chunked upload session
+    CommitUploadSession,
+    /// Abort a chunked upload session
+    AbortUploadSession,
+    /// Stream registry events
+    WatchAssets,
+    /// Create an API key
+    CreateApiKey,
+    /// List one's own API keys
+    ListApiKeys,
+    /// List and revoke any user's API keys
+    ManageApiKeys,
+    /// List ACL entries
+    ListAclEntries,
+    /// Create an ACL entry
+    CreateAclEntry,
+    /// Delete an ACL entry
+    DeleteAclEntry,
+    /// List user accounts
+    ListUsers,
+    /// Get a user account
+    GetUser,
+    /// Create a user account
+    CreateUser,
+    /// Reset a user's password
+    ResetUserPassword,
+    /// Replace a user's roles
+    SetUserRoles,
+    /// Disable or re-enable a user
+    SetUserStatus,
+    /// Revoke every session of a user
+    RevokeUserSessions,
+}
+
+impl Operation {
+    /// Name of the operation, as used in logs and governance events
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::RegisterAsset => "register_asset",
+            Operation::BatchRegister => "batch_register",
+            Operation::GetAsset => "get_asset",
+            Operation::ListAssets => "list_assets",
+            Operation::SearchAssets => "search_assets",
+            Operation::UpdateAsset => "update_asset",
+            Operation::DeleteAsset => "delete_asset",
+            Operation::GetDependencies => "get_dependencies",
+            Operation::GetDependents => "get_dependents",
+            Operation::GenerateLockfile => "generate_lockfile",
+            Operation::VerifyLockfile => "verify_lockfile",
+            Operation::ListTags => "list_tags",
+            Operation::UploadContent => "upload_content",
+            Operation::DownloadContent => "download_content",
+            Operation::CreateUploadSession => "create_upload_session",
+            Operation::GetUploadSession => "get_upload_session",
+            Operation::UploadChunk => "upload_chunk",
+            Operation::CommitUploadSession => "commit_upload_session",
+            Operation::AbortUploadSession => "abort_upload_session",
+            Operation::WatchAssets => "watch_assets",
+            Operation::CreateApiKey => "create_api_key",
+            Operation::ListApiKeys => "list_api_keys",
+            Operation::ManageApiKeys => "manage_api_keys",
+            Operation::ListAclEntries => "list_acl_entries",
+            Operation::CreateAclEntry => "create_acl_entry",
+            Operation::DeleteAclEntry => "delete_acl_entry",
+            Operation::ListUsers => "list_users",
+            Operation::GetUser => "get_user",
+            Operation::CreateUser => "create_user",
+            Operation::ResetUserPassword => "reset_user_password",
+            Operation::SetUserRoles => "set_user_roles",
+            Operation::SetUserStatus => "set_user_status",
+            Operation::RevokeUserSessions => "revoke_user_sessions",
+        }
+    }
+
+    /// Permission a caller needs to perform this operation
+    pub fn required_permission(&self) -> Permission {
+        let (resource, action) = match self {
+            Operation::GetAsset
+            | Operation::ListAssets
+            | Operation::SearchAssets
+            | Operation::ListTags
+            | Operation::DownloadContent
+            | Operation::WatchAssets => ("asset", "read"),
+            Operation::RegisterAsset
+            | Operation::BatchRegister
+            | Operation::UpdateAsset
+            | Operation::UploadContent
+            | Operation::CreateUploadSession
+            | Operation::GetUploadSession
+            | Operation::UploadChunk
+            | Operation::CommitUploadSession
+            | Operation::AbortUploadSession => ("asset", "write"),
+            Operation::DeleteAsset => ("asset", "delete"),
+            Operation::GetDependencies
+            | Operation::GetDependents
+            | Operation::GenerateLockfile
+            | Operation::VerifyLockfile => ("dependency", "read"),
+            Operation::CreateApiKey => ("api-key", "create"),
+            Operation::ListApiKeys => ("api-key", "read"),
+            Operation::ManageApiKeys => ("api-key", "admin"),
+            Operation::ListAclEntries => ("acl", "read"),
+            Operation::CreateAclEntry | Operation::DeleteAclEntry => ("acl", "write"),
+            Operation::ListUsers | Operation::GetUser => ("user", "read"),
+            Operation::CreateUser
+            | Operation::ResetUserPassword
+            | Operation::SetUserRoles
+            | Operation::SetUserStatus
+            | Operation::RevokeUserSessions => ("user", "write"),
+        };
+        Permission::new(resource, action)
+    }
+
+    /// Operation served by a REST route, keyed by method and route template
+    ///
+    /// Route templates are relative to the `/v1` prefix.
+    pub fn for_route(method: &Method, route: &str) -> Option<Operation> {
+        let route = route.strip_prefix("/v1").unwrap_or(route);
+        REST_ROUTES
+            .iter()
+            .find(|(m, r, _)| m == method && *r == route)
+            .map(|(_, _, operation)| *operation)
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// REST routes and the operation each one performs
+static REST_ROUTES: &[(Method, &str, Operation)] = &[
+    (Method::POST, "/assets", Operation::RegisterAsset),
+    (Method::GET, "/assets", Operation::ListAssets),
+    (Method::GET, "/assets/{id}", Operation::GetAsset),
+    (Method::PATCH, "/assets/{id}", Operation::UpdateAsset),
+    (Method::DELETE, "/assets/{id}", Operation::DeleteAsset),
+    (Method::GET, "/assets/{id}/dependencies", Operation::GetDependencies),
+    (Method::GET, "/assets/{id}/dependents", Operation::GetDependents),
+    (Method::GET, "/assets/{id}/lockfile", Operation::GenerateLockfile),
+    (Method::POST, "/lockfiles/verify", Operation::VerifyLockfile),
+    (Method::PUT, "/assets/{id}/content", Operation::UploadContent),
+    (Method::GET, "/assets/{id}/content", Operation::DownloadContent),
+    (Method::POST, "/assets/{id}/uploads", Operation::CreateUploadSession),
+    (Method::GET, "/uploads/{session_id}", Operation::GetUploadSession),
+    (Method::DELETE, "/uploads/{session_id}", Operation::AbortUploadSession),
+    (Method::PUT, "/uploads/{session_id}/chunks/{index}", Operation::UploadChunk),
+    (Method::POST, "/uploads/{session_id}/commit", Operation::CommitUploadSession),
+    (Method::GET, "/acls", Operation::ListAclEntries),
+    (Method::POST, "/acls", Operation::CreateAclEntry),
+    (Method::DELETE, "/acls/{acl_id}", Operation::DeleteAclEntry),
+    (Method::GET, "/users", Operation::ListUsers),
+    (Method::POST, "/users", Operation::CreateUser),
+    (Method::GET, "/users/{user_id}", Operation::GetUser),
+    (Method::PUT, "/users/{user_id}/password", Operation::ResetUserPassword),
+    (Method::PUT, "/users/{user_id}/roles", Operation::SetUserRoles),
+    (Method::PUT, "/users/{user_id}/status", Operation::SetUserStatus),
+];
+
+/// Enforces the RBAC policy and records access decisions
+pub struct Authorizer {
+    /// Role and permission definitions
+    policy: Mutex<RbacPolicy>,
+    /// Roles granted to unauthenticated callers
+    anonymous_roles: Vec<String>,
+    /// Sink for access decision governance events
+    emitter: Arc<dyn TelemetryEmitter>,
+    /// Per-asset ACL entries, with the search service used to resolve assets
+    acl: Option<(Arc<dyn AclStore>, Arc<dyn SearchService>)>,
+    /// Upload sessions, resolved to their asset for ACL checks
+    uploads: Option<Arc<dyn UploadSessionService>>,
+}
+
+impl Authorizer {
+    /// Create an authorizer for the given policy
+    pub fn new(policy: RbacPolicy) -> Self {
+        Self {
+            policy: Mutex::new(policy),
+            anonymous_roles: Vec::new(),
+            emitter: Arc::new(ObservatoryAdapter::default()),
+            acl: None,
+            uploads: None,
+        }
+    }
+
+    /// Grant roles to unauthenticated callers (none by default)
+    pub fn with_anonymous_roles(mut self, roles: Vec<String>) -> Self {
+        self.anonymous_roles = roles;
+        self
+    }
+
+    /// Set the sink for access decision governance events
+    pub fn with_emitter(mut self, emitter: Arc<dyn TelemetryEmitter>) -> Self {
+        self.emitter = emitter;
+        self
+    }
+
+    /// Evaluate per-asset ACL entries in addition to roles
+    pub fn with_acl(mut self, store: Arc<dyn AclStore>, search: Arc<dyn SearchService>) -> Self {
+        self.acl = Some((store, search));
+        self
+    }
+
+    /// Resolve upload sessions to the asset they target, so the asset's ACL
+    /// entries apply to upload session routes
+    pub fn with_upload_sessions(mut self, uploads: Arc<dyn UploadSessionService>) -> Self {
+        self.uploads = Some(uploads);
+        self
+    }
+
+    /// ACL store, if ACLs are enabled
+    pub fn acl_store(&self) -> Option<&Arc<dyn AclStore>> {
+        self.acl.as_ref().map(|(store, _)| store)
+    }
+
+    /// Whether a caller holds a permission through their roles and scopes
+    ///
+    /// Used to check that API keys are only granted permissions their owner
+    /// has. Does not record an access decision.
+    pub fn holds_permission(&self, user: &AuthUser, permission: &Permission) -> bool {
+        user.permits(permission) && self.policy().has_permission(&user.claims.roles, permission)
+    }
+
+    /// Check whether a caller may perform an operation
+    ///
+    /// Unauthenticated callers that are denied get [`AuthError::Unauthenticated`],
+    /// authenticated ones get [`AuthError::InsufficientPermissions`].
+    pub async fn authorize(
+        &self,
+        user: Option<&AuthUser>,
+        operation: Operation,
+    ) -> Result<(), AuthError> {
+        let permission = operation.required_permission();
+        let (_, roles) = self.caller(user);
+        let allowed = self.policy().has_permission(roles, &permission);
+
+        let resource = permission.resource.clone();
+        self.record(user, operation, permission, resource, allowed).await
+    }
+
+    /// Check whether a caller may perform an operation on a specific asset
+    ///
+    /// Takes the ACL entries attached to the asset into account (see
+    /// [`RbacPolicy::has_asset_permission`]). Without ACLs, or if the asset
+    /// does not exist, this is the same as [`Authorizer::authorize`].
+    pub async fn authorize_asset(
+        &self,
+        user: Option<&AuthUser>,
+        operation: Operation,
+        asset_id: &AssetId,
+    ) -> Result<(), AuthError> {
+        let Some((store, search)) = &self.acl else {
+            return self.authorize(user, operation).await;
+        };
+
+        let asset = match search.get_asset(asset_id).await {
+            Ok(Some(asset)) => asset,
+            Ok(None) => return self.authorize(user, operation).await,
+            Err(e) => {
+                warn!(%asset_id, "Failed to load asset for authorization: {}", e);
+                return Err(AuthError::InsufficientPermissions);
+            }
+        };
+
+        let permission = operation.required_permission();
+        let (principal, roles) = self.caller(user);
+        let allowed = match store.list_for_asset(&asset.id, &asset.metadata.name).await {
+            Ok(entries) => self
+                .policy()
+                .has_asset_permission(principal, roles, &permission, &entries),
+            Err(e) => {
+                warn!(%asset_id, "Failed to load ACL entries: {}", e);
+                false
+            }
+        };
+
+        let resource = format!("{}/{}", permission.resource, asset_id);
+        self.record(user, operation, permission, resource, allowed).await
+    }
+
+    /// Check whether a caller may manage the ACL entries of a target
+    ///
+    /// Callers whose roles grant the operation may manage every entry. Other
+    /// callers may manage the entries of an asset they own, that is an asset
+    /// whose ACL grants them the operation's permission.
+    pub async fn authorize_acl_target(
+        &self,
+        user: &AuthUser,
+        operation: Operation,
+        target: &AclTarget,
+    ) -> Result<(), AuthError> {
+        match target {
+            AclTarget::Asset(asset_id)
+                if !self.holds_permission(user, &operation.required_permission()) =>
+            {
+                self.authorize_asset(Some(user), operation, asset_id).await
+            }
+            _ => self.authorize(Some(user), operation).await,
+        }
+    }
+
+    /// Check whether a caller may perform an operation on the asset an upload
+    /// session targets
+    ///
+    /// Without upload sessions, or if the session does not exist, this is the
+    /// same as [`Authorizer::authorize`].
+    pub async fn authorize_upload_session(
+        &self,
+        user: Option<&AuthUser>,
+        operation: Operation,
+        session_id: &str,
+    ) -> Result<(), AuthError> {
+        let Some(uploads) = &self.uploads else {
+            return self.authorize(user, operation).await;
+        };
+
+        match uploads.get_session(session_id).await {
+            Ok(response) => {
+                self.authorize_asset(user, operation, &response.session.asset_id)
+                    .await
+            }
+            Err(ServiceError::NotFound(_)) => self.authorize(user, operation).await,
+            Err(e) => {
+                warn!(%session_id, "Failed to load upload session for authorization: {}", e);
+                Err(AuthError::InsufficientPermissions)
+            }
+        }
+    }
+
+    /// Check whether a caller may update every dependent a delete would change
+    ///
+    /// Forced and cascading deletes change the status of the assets depending
+    /// on the deleted one, so each of them is authorized as an update before
+    /// anything is deleted.
+    pub async fn authorize_dependents(
+        &self,
+        user: Option<&AuthUser>,
+        dependents: &[ClosureNode],
+    ) -> Result<(), AuthError> {
+        for node in dependents {
+            self.authorize_asset(user, Operation::UpdateAsset, &node.asset_id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Leave the dependents a caller may not read out of a `HasDependents`
+    /// error, counting them as hidden instead
+    ///
+    /// Other errors are returned unchanged.
+    pub async fn redact_dependents(
+        &self,
+        user: Option<&AuthUser>,
+        error: ServiceError,
+    ) -> ServiceError {
+        let ServiceError::HasDependents {
+            asset,
+            dependents,
+            hidden,
+        } = error
+        else {
+            return error;
+        };
+
+        let total = dependents.len();
+        let dependents = self
+            .filter_readable_by(user, dependents, |node| (node.asset_id, node.name.as_str()))
+            .await;
+        ServiceError::HasDependents {
+            asset,
+            hidden: hidden + total - dependents.len(),
+            dependents,
+        }
+    }
+
+    /// Drop the assets a caller is not allowed to read
+    ///
+    /// Used to filter search results and event streams, so individual checks
+    /// are not recorded as governance events.
+    pub async fn filter_readable(&self, user: Option<&AuthUser>, assets: Vec<Asset>) -> Vec<Asset> {
+        self.filter_readable_by(user, assets, |asset| {
+            (asset.id, asset.metadata.name.as_str())
+        })
+        .await
+    }
+
+    /// Drop the items about assets a caller is not allowed to read, given the
+    /// ID and name of each item's asset
+    pub async fn filter_readable_by<T>(
+        &self,
+        user: Option<&AuthUser>,
+        items: Vec<T>,
+        asset: impl Fn(&T) -> (AssetId, &str),
+    ) -> Vec<T> {
+        let Some((store, _)) = &self.acl else {
+            return items;
+        };
+
+        let entries = match store.list().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to load ACL entries: {}", e);
+                return Vec::new();
+            }
+        };
+
+        items
+            .into_iter()
+            .filter(|item| {
+                let (asset_id, asset_name) = asset(item);
+                self.readable(user, &entries, &asset_id, asset_name)
+            })
+            .collect()
+    }
+
+    /// Search for the assets a caller is allowed to read
+    ///
+    /// The repository applies the ACL entries within the search itself, so
+    /// the total, facets and pages only count readable assets. Callers must
+    /// already be authorized to search.
+    pub async fn search_readable(
+        &self,
+        user: Option<&AuthUser>,
+        search: &dyn SearchService,
+        mut request: SearchAssetsRequest,
+    ) -> ServiceResult<SearchAssetsResponse> {
+        if self.acl.is_some() {
+            let permission = Permission::new("asset", "read");
+            let (principal, roles) = self.caller(user);
+            request.readable_by = Some(AclReadFilter {
+                principal: principal.to_string(),
+                roles: roles.to_vec(),
+                permissions: granting_permissions(&permission),
+                role_allows: permits(user, &permission)
+                    && self.policy().has_permission(roles, &permission),
+            });
+        }
+        search.search_assets(request).await
+    }
+
+    /// Whether a caller may read an asset, without recording the decision
+    pub async fn can_read(&self, user: Option<&AuthUser>, asset: &Asset) -> bool {
+        self.can_read_asset(user, &asset.id, &asset.metadata.name)
+            .await
+    }
+
+    /// Whether a caller may read the asset with an ID and name, which need not
+    /// exist anymore
+    async fn can_read_asset(
+        &self,
+        user: Option<&AuthUser>,
+        asset_id: &AssetId,
+        asset_name: &str,
+    ) -> bool {
+        let Some((store, _)) = &self.acl else {
+            return true;
+        };
+
+        match store.list_for_asset(asset_id, asset_name).await {
+            Ok(entries) => self.readable(user, &entries, asset_id, asset_name),
+            Err(e) => {
+                warn!(%asset_id, "Failed to load ACL entries: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Whether a caller may read the asset an event is about
+    async fn can_read_event(&self, user: Option<&AuthUser>, event: &WatchedEvent) -> bool {
+        if let Some(asset) = &event.asset {
+            return self.can_read(user, asset).await;
+        }
+        match (event.event.asset_id(), event.event.event_type.asset_name()) {
+            (Some(asset_id), Some(asset_name)) => {
+                self.can_read_asset(user, &asset_id, asset_name).await
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop the assets a caller is not allowed to read from a dependency graph
+    ///
+    /// Edges to and from hidden assets are dropped with them. The root is not
+    /// checked, as the caller was authorized for it.
+    pub async fn filter_dependency_graph(
+        &self,
+        user: Option<&AuthUser>,
+        mut graph: DependencyGraphResponse,
+    ) -> DependencyGraphResponse {
+        if self.acl.is_none() {
+            return graph;
+        }
+
+        let nodes = std::mem::take(&mut graph.dependencies);
+        graph.dependencies = self
+            .filter_readable_by(user, nodes, |node| (node.asset_id, node.name.as_str()))
+            .await;
+
+        let visible: HashSet<AssetId> = graph
+            .dependencies
+            .iter()
+            .map(|node| node.asset_id)
+            .chain([graph.root])
+            .collect();
+        for node in &mut graph.dependencies {
+            node.dependencies.retain(|id| visible.contains(id));
+        }
+        graph.edges.retain(|edge| {
+            visible.contains(&edge.asset_id) && visible.contains(&edge.dependency_id)
+        });
+        graph
+    }
+
+    /// Check that a caller may read every asset a lockfile pins
+    ///
+    /// Leaving assets out would no longer pin the whole closure, so unlike
+    /// dependency graphs, lockfiles are refused rather than filtered.
+    pub async fn authorize_lockfile(
+        &self,
+        user: Option<&AuthUser>,
+        lockfile: &Lockfile,
+    ) -> Result<(), AuthError> {
+        if self.acl.is_none() {
+            return Ok(());
+        }
+
+        let readable = self
+            .filter_readable_by(user, lockfile.assets.iter().collect(), |asset| {
+                (asset.id, asset.name.as_str())
+            })
+            .await;
+        if readable.len() == lockfile.assets.len() {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+
+    /// Whether a caller may read an asset, given ACL entries that include all
+    /// those covering it
+    fn readable(
+        &self,
+        user: Option<&AuthUser>,
+        entries: &[AclEntry],
+        asset_id: &AssetId,
+        asset_name: &str,
+    ) -> bool {
+        let permission = Permission::new("asset", "read");
+        if !permits(user, &permission) {
+            return false;
+        }
+
+        let applicable: Vec<AclEntry> = entries
+            .iter()
+            .filter(|entry| entry.target.covers(asset_id, asset_name))
+            .cloned()
+            .collect();
+        let (principal, roles) = self.caller(user);
+        self.policy()
+            .has_asset_permission(principal, roles, &permission, &applicable)
+    }
+
+    /// Drop events about assets a caller is not allowed to read
+    ///
+    /// Events about deleted assets are checked against the ID and name they
+    /// recorded. Events that name no asset are dropped.
+    pub fn filter_events(self: &Arc<Self>, user: Option<AuthUser>, events: EventStream) -> EventStream {
+        if self.acl.is_none() {
+            return events;
+        }
+
+        let authorizer = Arc::clone(self);
+        Box::pin(events.filter_map(move |item| {
+            let authorizer = Arc::clone(&authorizer);
+            let user = user.clone();
+            async move {
+                match item {
+                    Ok(event) => authorizer
+                        .can_read_event(user.as_ref(), &event)
+                        .await
+                        .then_some(Ok(event)),
+                    Err(e) => Some(Err(e)),
+                }
+            }
+        }))
+    }
+
+    /// Principal and roles of a caller
+    fn caller<'a>(&'a self, user: Option<&'a AuthUser>) -> (&'a str, &'a [String]) {
+        match user {
+            Some(user) => (user.user_id(), &user.claims.roles),
+            None => (ANONYMOUS_PRINCIPAL, &self.anonymous_roles),
+        }
+    }
+
+    /// Lock the policy, recovering from a poisoned lock
+    fn policy(&self) -> MutexGuard<'_, RbacPolicy> {
+        self.policy
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Log and record an access decision, and turn it into a result
+    async fn record(
+        &self,
+        user: Option<&AuthUser>,
+        operation: Operation,
+        permission: Permission,
+        resource: String,
+        allowed: bool,
+    ) -> Result<(), AuthError> {
+        // Scoped callers (API keys) never exceed their scopes
+        let allowed = allowed && permits(user, &permission);
+
+        let (principal, _) = self.caller(user);
+        if allowed {
+            debug!(%principal, %operation, %permission, %resource, "Access granted");
+        } else {
+            warn!(%principal, %operation, %permission, %resource, "Access denied");
+        }
+
+        let event = GovernanceEvent::AccessDecision {
+            principal: principal.to_string(),
+            resource,
+            action: permission.action,
+            allowed,
+        };
+        if let Err(e) = self.emitter.emit_governance_event(event).await {
+            warn!("Failed to record access decision: {}", e);
+        }
+
+        match (allowed, user) {
+            (true, _) => Ok(()),
+            (false, Some(_)) => Err(AuthError::InsufficientPermissions),
+            (false, None) => Err(AuthError::Unauthenticated),
+        }
+    }
+}
+
+/// Whether a caller's scopes, if any, allow a permission
+fn permits(user: Option<&AuthUser>, permission: &Permission) -> bool {
+    user.map_or(true, |user| user.permits(permission))
+}
+
+/// ACL entry permissions that match a permission, including wildcards
+fn granting_permissions(permission: &Permission) -> Vec<String> {
+    [permission.resource.as_str(), "*"]
+        .into_iter()
+        .flat_map(|resource| {
+            [permission.action.as_str(), "*"]
+                .into_iter()
+                .map(move |action| format!("{}:{}", resource, action))
+        })
+        .collect()
+}
+
+impl Default for Authorizer {
+    fn default() -> Self {
+        Self::new(RbacPolicy::default())
+    }
+}
+
+impl fmt::Debug for Authorizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authorizer")
+            .field("anonymous_roles", &self.anonymous_roles)
+            .field("acl", &self.acl.is_some())
+            .field("uploads", &self.uploads.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Route parameter holding the asset ID on asset-scoped routes
+const ASSET_ID_PARAM: &str = "id";
+
+/// Route parameter holding the upload session ID on upload session routes
+const UPLOAD_SESSION_PARAM: &str = "session_id";
+
+/// Authorization middleware for REST routes
+///
+/// Resolves the [`Operation`] from the matched route and checks it against the
+/// authenticated user (if any), including the asset's ACL entries on routes
+/// with an `{id}` parameter and on upload session routes, whose `{session_id}`
+/// is resolved to the asset it targets. Must be installed with `route_layer` so the
+/// matched path is available, and inside an authentication layer so the
+/// [`AuthUser`] extension is populated. Routes missing from the permission
+/// table are denied.
+pub async fn authorize_request(
+    State(authorizer): State<Arc<Authorizer>>,
+    params: Result<RawPathParams, RawPathParamsRejection>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let operation = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| Operation::for_route(request.method(), path.as_str()));
+
+    let Some(operation) = operation else {
+        warn!(
+            method = %request.method(),
+            path = %request.uri().path(),
+            "No permission mapped for route"
+        );
+        return Err(AuthError::InsufficientPermissions);
+    };
+
+    let user = request.extensions().get::<AuthUser>();
+    let params = params.ok();
+    let param = |name: &str| {
+        params.as_ref().and_then(|params| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    };
+
+    if let Some(asset_id) = param(ASSET_ID_PARAM).and_then(|id| id.parse::<AssetId>().ok()) {
+        authorizer
+            .authorize_asset(user, operation, &asset_id)
+            .await?;
+    } else if let Some(session_id) = param(UPLOAD_SESSION_PARAM) {
+        authorizer
+            .authorize_upload_session(user, operation, &session_id)
+            .await?;
+    } else {
+        authorizer.authorize(user, operation).await?;
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::Claims;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::{get, put},
+        Extension, Router,
+    };
+    use llm_registry_core::{
+        AssetMetadata, AssetType, Checksum, DependencyKind, HashAlgorithm, StorageBackend,
+        StorageLocation,
+    };
+    use llm_registry_db::{AssetRepository, InMemoryAssetRepository};
+    use llm_registry_service::{
+        AclEffect, AclSubject, AclTarget, ContentStream, CreateUploadSessionRequest,
+        DefaultSearchService, DependencyGraphResponse, GetDependencyGraphRequest, InMemoryAclStore,
+        SearchAssetsRequest, SearchAssetsResponse, ServiceResult, UploadChunk,
+        UploadContentResponse, UploadSession, UploadSessionResponse,
+    };
+    use semver::Version;
+    use tower::ServiceExt;
+
+    struct MockSearch {
+        assets: Vec<Asset>,
+    }
+
+    #[tonic::async_trait]
+    impl SearchService for MockSearch {
+        async fn search_assets(&self, _request: SearchAssetsRequest) -> ServiceResult<SearchAssetsResponse> {
+            unimplemented!()
+        }
+
+        async fn get_asset(&self, asset_id: &AssetId) -> ServiceResult<Option<Asset>> {
+            Ok(self.assets.iter().find(|a| &a.id == asset_id).cloned())
+        }
+
+        async fn get_asset_by_name_version(&self, _name: &str, _version: &str) -> ServiceResult<Option<Asset>> {
+            unimplemented!()
+        }
+
+        async fn get_dependency_graph(&self, _request: GetDependencyGraphRequest) -> ServiceResult<DependencyGraphResponse> {
+            unimplemented!()
+        }
+
+        async fn list_all_tags(&self) -> ServiceResult<Vec<String>> {
+            unimplemented!()
+        }
+
+        async fn search_by_tags(&self, _tags: Vec<String>) -> ServiceResult<Vec<Asset>> {
+            unimplemented!()
+        }
+
+        async fn get_assets_by_type(&self, _asset_type: AssetType) -> ServiceResult<Vec<Asset>> {
+            unimplemented!()
+        }
+
+        async fn get_reverse_dependencies(&self, _asset_id: &AssetId) -> ServiceResult<Vec<Asset>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockUploads {
+        sessions: Vec<UploadSession>,
+    }
+
+    #[tonic::async_trait]
+    impl UploadSessionService for MockUploads {
+        async fn create_session(
+            &self,
+            _request: CreateUploadSessionRequest,
+        ) -> ServiceResult<UploadSessionResponse> {
+            unimplemented!()
+        }
+
+        async fn get_session(&self, session_id: &str) -> ServiceResult<UploadSessionResponse> {
+            self.sessions
+                .iter()
+                .find(|session| session.id == session_id)
+                .cloned()
+                .map(UploadSessionResponse::from)
+                .ok_or_else(|| ServiceError::NotFound(format!("Upload session {}", session_id)))
+        }
+
+        async fn upload_chunk(
+            &self,
+            _session_id: &str,
+            _index: u32,
+            _checksum: Option<Checksum>,
+            _content: ContentStream,
+        ) -> ServiceResult<UploadChunk> {
+            unimplemented!()
+        }
+
+        async fn commit_session(&self, _session_id: &str) -> ServiceResult<UploadContentResponse> {
+            unimplemented!()
+        }
+
+        async fn abort_session(&self, _session_id: &str) -> ServiceResult<()> {
+            unimplemented!()
+        }
+
+        async fn purge_expired_sessions(&self) -> ServiceResult<usize> {
+            unimplemented!()
+        }
+    }
+
+    fn asset(name: &str) -> Asset {
+        let metadata = AssetMetadata::new(name, Version::parse("1.0.0").unwrap());
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(AssetId::new(), AssetType::Model, metadata, storage, checksum).unwrap()
+    }
+
+    fn user_with_role(role: &str) -> AuthUser {
+        AuthUser::new(Claims::new("user-1", "test", "test", 3600).with_role(role))
+    }
+
+    #[test]
+    fn test_route_permissions() {
+        assert_eq!(
+            Operation::for_route(&Method::POST, "/v1/assets"),
+            Some(Operation::RegisterAsset)
+        );
+        assert_eq!(
+            Operation::for_route(&Method::GET, "/assets/{id}/dependents"),
+            Some(Operation::GetDependents)
+        );
+        assert_eq!(
+            Operation::for_route(&Method::POST, "/v1/lockfiles/verify"),
+            Some(Operation::VerifyLockfile)
+        );
+        assert_eq!(Operation::for_route(&Method::PUT, "/assets"), None);
+        assert_eq!(
+            Operation::for_route(&Method::PUT, "/v1/users/{user_id}/roles")
+                .map(|op| op.required_permission().to_string()),
+            Some("user:write".to_string())
+        );
+
+        assert_eq!(
+            Operation::RegisterAsset.required_permission(),
+            Permission::new("asset", "write")
+        );
+        assert_eq!(
+            Operation::GetDependencies.required_permission().to_string(),
+            "dependency:read"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorize_records_decisions() {
+        let observatory = Arc::new(ObservatoryAdapter::default());
+        let authorizer = Authorizer::default().with_emitter(observatory.clone());
+
+        let viewer = user_with_role("viewer");
+        assert!(authorizer
+            .authorize(Some(&viewer), Operation::GetDependencies)
+            .await
+            .is_ok());
+        assert!(matches!(
+            authorizer.authorize(Some(&viewer), Operation::DeleteAsset).await,
+            Err(AuthError::InsufficientPermissions)
+        ));
+        assert!(matches!(
+            authorizer.authorize(None, Operation::GetAsset).await,
+            Err(AuthError::Unauthenticated)
+        ));
+
+        assert_eq!(observatory.pending_events().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_scopes_restrict_roles() {
+        let authorizer = Authorizer::default();
+        let key = AuthUser::new(
+            Claims::new("user-1", "test", "test", 3600)
+                .with_role("developer")
+                .with_custom("scopes", serde_json::json!(["asset:read"])),
+        );
+
+        assert!(authorizer.authorize(Some(&key), Operation::GetAsset).await.is_ok());
+        assert!(matches!(
+            authorizer.authorize(Some(&key), Operation::RegisterAsset).await,
+            Err(AuthError::InsufficientPermissions)
+        ));
+        assert!(authorizer.holds_permission(&key, &Permission::new("asset", "read")));
+        assert!(!authorizer.holds_permission(&key, &Permission::new("asset", "write")));
+        assert!(!authorizer.holds_permission(
+            &user_with_role("viewer"),
+            &Permission::new("asset", "write")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_roles() {
+        let authorizer = Authorizer::default().with_anonymous_roles(vec!["viewer".to_string()]);
+
+        assert!(authorizer.authorize(None, Operation::ListAssets).await.is_ok());
+        assert!(authorizer.authorize(None, Operation::RegisterAsset).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_authorize_request_middleware() {
+        let authorizer = Arc::new(Authorizer::default());
+        let app = |role: &str| {
+            Router::new()
+                .route("/assets", get(|| async { "ok" }).post(|| async { "ok" }))
+                .route_layer(middleware::from_fn_with_state(
+                    authorizer.clone(),
+                    authorize_request,
+                ))
+                .layer(Extension(user_with_role(role)))
+        };
+        let request = |method: Method| {
+            Request::builder()
+                .method(method)
+                .uri("/assets")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app("viewer").oneshot(request(Method::GET)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app("viewer").oneshot(request(Method::POST)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app("developer").oneshot(request(Method::POST)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_acl_restricts_assets() {
+        let tuned = asset("ft/llama");
+        let held = asset("datasets/contracts");
+        let public = asset("base/llama");
+
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::NamePrefix("ft/".to_string()),
+                AclSubject::Role("fine-tuning".to_string()),
+                "asset:read",
+                AclEffect::Allow,
+            ))
+            .await
+            .unwrap();
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(held.id),
+                AclSubject::Everyone,
+                "asset:write",
+                AclEffect::Deny,
+            ))
+            .await
+            .unwrap();
+
+        let search = Arc::new(MockSearch {
+            assets: vec![tuned.clone(), held.clone(), public.clone()],
+        });
+        let authorizer = Authorizer::default().with_acl(store, search);
+
+        let developer = user_with_role("developer");
+        let tuner = AuthUser::new(
+            Claims::new("user-2", "test", "test", 3600)
+                .with_role("viewer")
+                .with_role("fine-tuning"),
+        );
+
+        // Search results only contain readable assets
+        let visible = authorizer
+            .filter_readable(
+                Some(&developer),
+                vec![tuned.clone(), held.clone(), public.clone()],
+            )
+            .await;
+        assert_eq!(visible.len(), 2);
+        assert!(visible.iter().all(|a| a.id != tuned.id));
+        assert!(authorizer.can_read(Some(&tuner), &tuned).await);
+
+        assert!(authorizer
+            .authorize_asset(Some(&developer), Operation::GetAsset, &tuned.id)
+            .await
+            .is_err());
+        assert!(authorizer
+            .authorize_asset(Some(&tuner), Operation::GetAsset, &tuned.id)
+            .await
+            .is_ok());
+
+        // Nobody can write an asset under legal hold
+        assert!(authorizer
+            .authorize_asset(Some(&developer), Operation::UpdateAsset, &held.id)
+            .await
+            .is_err());
+        assert!(authorizer
+            .authorize_asset(Some(&developer), Operation::UpdateAsset, &public.id)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acl_guards_dependents_of_deletes() {
+        let tuned = asset("ft/llama");
+        let held = asset("datasets/contracts");
+        let public = asset("base/llama");
+
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::NamePrefix("ft/".to_string()),
+                AclSubject::Role("fine-tuning".to_string()),
+                "asset:read",
+                AclEffect::Allow,
+            ))
+            .await
+            .unwrap();
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(held.id),
+                AclSubject::Everyone,
+                "asset:write",
+                AclEffect::Deny,
+            ))
+            .await
+            .unwrap();
+
+        let search = Arc::new(MockSearch {
+            assets: vec![tuned.clone(), held.clone(), public.clone()],
+        });
+        let authorizer = Authorizer::default().with_acl(store, search);
+        let developer = user_with_role("developer");
+
+        let node = |asset: &Asset| ClosureNode {
+            asset_id: asset.id,
+            name: asset.metadata.name.clone(),
+            version: asset.metadata.version.clone(),
+            depth: 1,
+        };
+
+        // A delete that would change a held dependent is refused as a whole
+        assert!(authorizer
+            .authorize_dependents(Some(&developer), &[node(&public)])
+            .await
+            .is_ok());
+        assert!(matches!(
+            authorizer
+                .authorize_dependents(Some(&developer), &[node(&public), node(&held)])
+                .await,
+            Err(AuthError::InsufficientPermissions)
+        ));
+
+        // Dependents the caller cannot read are only counted
+        let error = authorizer
+            .redact_dependents(
+                Some(&developer),
+                ServiceError::HasDependents {
+                    asset: "base@1.0.0".to_string(),
+                    dependents: vec![node(&tuned), node(&public)],
+                    hidden: 0,
+                },
+            )
+            .await;
+        match error {
+            ServiceError::HasDependents {
+                dependents, hidden, ..
+            } => {
+                assert_eq!(dependents.len(), 1);
+                assert_eq!(dependents[0].asset_id, public.id);
+                assert_eq!(hidden, 1);
+            }
+            other => panic!("expected HasDependents, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_acl_filters_searches_graphs_and_events() {
+        use llm_registry_core::{EventType, RegistryEvent};
+        use llm_registry_service::{DependencyEdge, DependencyNode, LockedAsset, WatchedEvent};
+
+        let tuned = asset("ft/llama");
+        let public = asset("base/llama");
+        let app = asset("apps/chat");
+
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::NamePrefix("ft/".to_string()),
+                AclSubject::Role("fine-tuning".to_string()),
+                "asset:read",
+                AclEffect::Allow,
+            ))
+            .await
+            .unwrap();
+        let repository = Arc::new(InMemoryAssetRepository::new().with_acl_store(store.clone()));
+        for asset in [&tuned, &public, &app] {
+            repository.create(asset.clone()).await.unwrap();
+        }
+        let search = Arc::new(DefaultSearchService::new(repository));
+        let authorizer = Arc::new(Authorizer::default().with_acl(store, search.clone()));
+        let developer = user_with_role("developer");
+        let tuner = AuthUser::new(
+            Claims::new("user-2", "test", "test", 3600)
+                .with_role("viewer")
+                .with_role("fine-tuning"),
+        );
+
+        // Totals and pages only count readable assets
+        let request = SearchAssetsRequest {
+            limit: 1,
+            ..Default::default()
+        };
+        let first = authorizer
+            .search_readable(Some(&developer), search.as_ref(), request.clone())
+            .await
+            .unwrap();
+        assert_eq!(first.total, 2);
+        assert_eq!(first.assets.len(), 1);
+        let second = authorizer
+            .search_readable(
+                Some(&developer),
+                search.as_ref(),
+                SearchAssetsRequest {
+                    cursor: first.next_cursor.clone(),
+                    ..request.clone()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.assets.len(), 1);
+        assert!(second.next_cursor.is_none());
+        let seen: HashSet<AssetId> = first
+            .assets
+            .iter()
+            .chain(&second.assets)
+            .map(|asset| asset.id)
+            .collect();
+        assert_eq!(seen, HashSet::from([public.id, app.id]));
+
+        let response = authorizer
+            .search_readable(Some(&tuner), search.as_ref(), request)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 3);
+
+        // Dependency graphs lose hidden assets and the edges touching them
+        let node = |asset: &Asset, dependencies: Vec<AssetId>| DependencyNode {
+            asset_id: asset.id,
+            name: asset.metadata.name.clone(),
+            version: asset.metadata.version.clone(),
+            depth: 1,
+            dependencies,
+        };
+        let edge = |from: &Asset, to: &Asset| DependencyEdge {
+            asset_id: from.id,
+            dependency_id: to.id,
+            dependency_type: DependencyKind::Runtime,
+            version_constraint: None,
+            depth: 1,
+            cycle: false,
+        };
+        let graph = DependencyGraphResponse {
+            root: app.id,
+            dependencies: vec![node(&tuned, vec![]), node(&public, vec![tuned.id])],
+            edges: vec![
+                edge(&app, &tuned),
+                edge(&app, &public),
+                edge(&public, &tuned),
+            ],
+            truncated: false,
+            has_cycles: false,
+        };
+        let graph = authorizer
+            .filter_dependency_graph(Some(&developer), graph)
+            .await;
+        assert_eq!(graph.dependencies.len(), 1);
+        assert_eq!(graph.dependencies[0].asset_id, public.id);
+        assert!(graph.dependencies[0].dependencies.is_empty());
+        assert_eq!(graph.edges.len(), 1);
+
+        // Lockfiles are refused as a whole if they pin a hidden asset
+        let locked = |asset: &Asset| LockedAsset {
+            id: asset.id,
+            name: asset.metadata.name.clone(),
+            version: asset.metadata.version.clone(),
+            asset_type: asset.asset_type.clone(),
+            checksum: asset.checksum.clone(),
+            dependencies: Vec::new(),
+        };
+        let mut lockfile = Lockfile {
+            version: 1,
+            root: app.id,
+            generated_at: chrono::Utc::now(),
+            assets: vec![locked(&public), locked(&app)],
+        };
+        assert!(authorizer
+            .authorize_lockfile(Some(&developer), &lockfile)
+            .await
+            .is_ok());
+        lockfile.assets.insert(0, locked(&tuned));
+        assert!(authorizer
+            .authorize_lockfile(Some(&developer), &lockfile)
+            .await
+            .is_err());
+
+        // Events about deleted assets are checked against their recorded
+        // name, and events about no asset are dropped
+        let watched = |event_type: EventType| {
+            Ok(WatchedEvent {
+                event_id: AssetId::new().to_string(),
+                event: RegistryEvent::new(event_type),
+                asset: None,
+            })
+        };
+        let events = || -> EventStream {
+            Box::pin(futures::stream::iter(vec![
+                watched(EventType::AssetDeleted {
+                    asset_id: tuned.id,
+                    asset_name: tuned.metadata.name.clone(),
+                    asset_version: "1.0.0".to_string(),
+                }),
+                watched(EventType::AssetDeleted {
+                    asset_id: public.id,
+                    asset_name: public.metadata.name.clone(),
+                    asset_version: "1.0.0".to_string(),
+                }),
+                watched(EventType::CircularDependencyDetected {
+                    cycle_asset_ids: vec![tuned.id, public.id],
+                }),
+            ]))
+        };
+        let delivered: Vec<_> = authorizer
+            .filter_events(Some(developer.clone()), events())
+            .collect()
+            .await;
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(
+            delivered[0].as_ref().unwrap().event.asset_id(),
+            Some(public.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_asset_owners_manage_acl_entries() {
+        let owned = asset("ft/llama");
+        let other = asset("ft/mistral");
+
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(owned.id),
+                AclSubject::Principal("user-1".to_string()),
+                "acl:write",
+                AclEffect::Allow,
+            ))
+            .await
+            .unwrap();
+        let search = Arc::new(MockSearch {
+            assets: vec![owned.clone(), other.clone()],
+        });
+        let authorizer = Authorizer::default().with_acl(store, search);
+
+        let owner = user_with_role("developer");
+        let admin = AuthUser::new(Claims::new("user-2", "test", "test", 3600).with_role("admin"));
+        let prefix = AclTarget::NamePrefix("ft/".to_string());
+        let (owned, other) = (AclTarget::Asset(owned.id), AclTarget::Asset(other.id));
+        let check =
+            |user, target| authorizer.authorize_acl_target(user, Operation::CreateAclEntry, target);
+
+        // Owners only manage the entries of their own assets
+        assert!(check(&owner, &owned).await.is_ok());
+        assert!(check(&owner, &other).await.is_err());
+        assert!(check(&owner, &prefix).await.is_err());
+
+        // Administrators are not locked out by an asset's owners
+        assert!(check(&admin, &owned).await.is_ok());
+        assert!(check(&admin, &prefix).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acl_applies_to_upload_sessions() {
+        let held = asset("datasets/contracts");
+        let public = asset("base/llama");
+
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(held.id),
+                AclSubject::Everyone,
+                "asset:write",
+                AclEffect::Deny,
+            ))
+            .await
+            .unwrap();
+
+        let ttl = chrono::Duration::hours(1);
+        let held_session = UploadSession::new(held.id, 10, 10, ttl);
+        let public_session = UploadSession::new(public.id, 10, 10, ttl);
+        let uploads = Arc::new(MockUploads {
+            sessions: vec![held_session.clone(), public_session.clone()],
+        });
+        let search = Arc::new(MockSearch {
+            assets: vec![held, public],
+        });
+        let authorizer = Arc::new(
+            Authorizer::default()
+                .with_acl(store, search)
+                .with_upload_sessions(uploads),
+        );
+
+        let app = Router::new()
+            .route(
+                "/uploads/{session_id}/chunks/{index}",
+                put(|| async { "ok" }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                authorizer,
+                authorize_request,
+            ))
+            .layer(Extension(user_with_role("developer")));
+        let upload = |session_id: &str| {
+            Request::builder()
+                .method(Method::PUT)
+                .uri(format!("/uploads/{}/chunks/0", session_id))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(upload(&held_session.id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .clone()
+            .oneshot(upload(&public_session.id))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Unknown sessions are left to the handler to reject
+        let response = app.oneshot(upload("unknown")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -154,6 +154,22 @@ impl From<ServiceError> for ApiError {
                 format!("Version conflict: {}", msg),
                 "VERSION_CONFLICT",
             ),
+            ServiceError::RevisionConflict { expected, actual } => ApiError::with_code(
+                StatusCode::CONFLICT,
+                format!(
+                    "Asset was modified concurrently: expected revision {}, found {}",
+                    expected, actual
+                ),
+                "REVISION_CONFLICT",
+            ),
+            ServiceError::PreconditionFailed { expected, actual } => ApiError::with_code(
+                StatusCode::PRECONDITION_FAILED,
+                format!(
+                    "Asset revision {} does not match the expected revision {}",
+                    actual, expected
+                ),
+                "PRECONDITION_FAILED",
+            ),
             ServiceError::PolicyValidationFailed {
                 policy_name,
                 message,
@@ -168,6 +184,19 @@ impl From<ServiceError> for ApiError {
             ServiceError::NotPermitted(msg) => {
                 ApiError::with_code(StatusCode::FORBIDDEN, msg, "NOT_PERMITTED")
             }
+            err @ ServiceError::HasDependents { .. } => {
+                ApiError::with_code(StatusCode::CONFLICT, err.to_string(), "HAS_DEPENDENTS")
+            }
+            ServiceError::InvalidCredentials => ApiError::with_code(
+                StatusCode::UNAUTHORIZED,
+                "Invalid username or password",
+                "INVALID_CREDENTIALS",
+            ),
+            ServiceError::RangeNotSatisfiable { size } => ApiError::with_code(
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                format!("Requested range not satisfiable for content of {} bytes", size),
+                "RANGE_NOT_SATISFIABLE",
+            ),
             ServiceError::Database(msg) => ApiError::with_code(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Database error: {}", msg),
@@ -216,6 +245,41 @@ mod tests {
         assert_eq!(api_err.status_code, StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_revision_error_conversion() {
+        let api_err: ApiError = ServiceError::RevisionConflict {
+            expected: 1,
+            actual: 2,
+        }
+        .into();
+        assert_eq!(api_err.status_code, StatusCode::CONFLICT);
+
+        let api_err: ApiError = ServiceError::PreconditionFailed {
+            expected: 1,
+            actual: 2,
+        }
+        .into();
+        assert_eq!(api_err.status_code, StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[test]
+    fn test_has_dependents_conversion() {
+        let api_err: ApiError = ServiceError::HasDependents {
+            asset: "base@1.0.0".to_string(),
+            dependents: vec![llm_registry_service::ClosureNode {
+                asset_id: llm_registry_core::AssetId::new(),
+                name: "app".to_string(),
+                version: semver::Version::new(1, 0, 0),
+                depth: 1,
+            }],
+            hidden: 0,
+        }
+        .into();
+        assert_eq!(api_err.status_code, StatusCode::CONFLICT);
+        assert_eq!(api_err.error_code.as_deref(), Some("HAS_DEPENDENTS"));
+        assert!(api_err.message.contains("app@1.0.0"));
+    }
+
     #[test]
     fn test_error_response_serialization() {
         let response = ErrorResponse {
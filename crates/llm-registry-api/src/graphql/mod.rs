@@ -1,33 +1,163 @@
 //! GraphQL API implementation
 //!
 //! This module provides a complete GraphQL API for the LLM Registry using async-graphql.
-//! It supports queries, mutations, authentication, and includes a GraphQL Playground.
+//! It supports queries, mutations, subscriptions over WebSocket (graphql-ws),
+//! authentication, and includes a GraphQL Playground.
 
 pub mod mutation;
 pub mod query;
+pub mod subscription;
 pub mod types;
 
-use async_graphql::{http::GraphiQLSource, EmptySubscription, Schema};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::{extract::State, response::{Html, IntoResponse}, Extension};
-use llm_registry_service::ServiceRegistry;
+use async_graphql::{
+    http::{GraphiQLSource, ALL_WEBSOCKET_PROTOCOLS},
+    Context, Data, ErrorExtensions, Schema,
+};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::{
+    extract::{State, WebSocketUpgrade},
+    response::{Html, IntoResponse, Response},
+    Extension,
+};
+use llm_registry_core::{Asset, AssetId};
+use llm_registry_service::{
+    ClosureNode, DependencyGraphResponse, SearchAssetsRequest, SearchAssetsResponse,
+    ServiceError, ServiceRegistry,
+};
 use std::sync::Arc;
 
-use crate::auth::AuthUser;
+use crate::{
+    auth::{AuthError, AuthState, AuthUser},
+    authorization::{Authorizer, Operation},
+    error::ApiError,
+};
 
 pub use mutation::Mutation;
 pub use query::Query;
+pub use subscription::Subscription;
 
 /// GraphQL schema type
-pub type AppSchema = Schema<Query, Mutation, EmptySubscription>;
+pub type AppSchema = Schema<Query, Mutation, Subscription>;
 
 /// Build the GraphQL schema
 pub fn build_schema(services: Arc<ServiceRegistry>) -> AppSchema {
-    Schema::build(Query, Mutation, EmptySubscription)
+    build_schema_with_authorizer(services, Arc::new(Authorizer::default()))
+}
+
+/// Build the GraphQL schema with a custom authorizer
+pub fn build_schema_with_authorizer(
+    services: Arc<ServiceRegistry>,
+    authorizer: Arc<Authorizer>,
+) -> AppSchema {
+    Schema::build(Query, Mutation, Subscription)
         .data(services)
+        .data(authorizer)
         .finish()
 }
 
+/// Check that the caller may perform an operation
+///
+/// Denials surface as GraphQL errors with a `FORBIDDEN` or `UNAUTHENTICATED`
+/// extension code.
+pub(crate) async fn authorize(ctx: &Context<'_>, operation: Operation) -> async_graphql::Result<()> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    authorizer
+        .authorize(ctx.data_opt::<AuthUser>(), operation)
+        .await
+        .map_err(auth_error)
+}
+
+/// Check that the caller may perform an operation on a specific asset
+pub(crate) async fn authorize_asset(
+    ctx: &Context<'_>,
+    operation: Operation,
+    asset_id: &AssetId,
+) -> async_graphql::Result<()> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    authorizer
+        .authorize_asset(ctx.data_opt::<AuthUser>(), operation, asset_id)
+        .await
+        .map_err(auth_error)
+}
+
+/// Check that the caller may update every dependent a delete would change
+pub(crate) async fn authorize_dependents(
+    ctx: &Context<'_>,
+    dependents: &[ClosureNode],
+) -> async_graphql::Result<()> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    authorizer
+        .authorize_dependents(ctx.data_opt::<AuthUser>(), dependents)
+        .await
+        .map_err(auth_error)
+}
+
+/// Leave the dependents the caller may not read out of a service error
+pub(crate) async fn redact_dependents(
+    ctx: &Context<'_>,
+    error: ServiceError,
+) -> async_graphql::Result<ServiceError> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    Ok(authorizer
+        .redact_dependents(ctx.data_opt::<AuthUser>(), error)
+        .await)
+}
+
+/// Drop the assets the caller is not allowed to read
+pub(crate) async fn readable(
+    ctx: &Context<'_>,
+    assets: Vec<Asset>,
+) -> async_graphql::Result<Vec<Asset>> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    Ok(authorizer
+        .filter_readable(ctx.data_opt::<AuthUser>(), assets)
+        .await)
+}
+
+/// Search for the assets the caller is allowed to read
+pub(crate) async fn search_readable(
+    ctx: &Context<'_>,
+    request: SearchAssetsRequest,
+) -> async_graphql::Result<SearchAssetsResponse> {
+    let services = ctx.data::<Arc<ServiceRegistry>>()?;
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    Ok(authorizer
+        .search_readable(
+            ctx.data_opt::<AuthUser>(),
+            services.search().as_ref(),
+            request,
+        )
+        .await
+        .map_err(ApiError::from)?)
+}
+
+/// Drop the assets the caller is not allowed to read from a dependency graph
+pub(crate) async fn readable_graph(
+    ctx: &Context<'_>,
+    graph: DependencyGraphResponse,
+) -> async_graphql::Result<DependencyGraphResponse> {
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+
+    Ok(authorizer
+        .filter_dependency_graph(ctx.data_opt::<AuthUser>(), graph)
+        .await)
+}
+
+/// Convert an authorization failure into a GraphQL error
+fn auth_error(e: AuthError) -> async_graphql::Error {
+    let code = match e {
+        AuthError::InsufficientPermissions => "FORBIDDEN",
+        _ => "UNAUTHENTICATED",
+    };
+    async_graphql::Error::new(e.to_string()).extend_with(|_, ext| ext.set("code", code))
+}
+
 /// GraphQL handler with optional authentication
 pub async fn graphql_handler(
     State(schema): State<AppSchema>,
@@ -44,11 +174,62 @@ pub async fn graphql_handler(
     schema.execute(request).await.into()
 }
 
+/// GraphQL subscription handler (graphql-ws over WebSocket)
+///
+/// Browsers cannot set headers on a WebSocket, so clients may instead send
+/// their bearer token as `Authorization` in the `connection_init` payload.
+pub async fn graphql_ws_handler(
+    State(schema): State<AppSchema>,
+    auth_user: Option<Extension<AuthUser>>,
+    auth_state: Option<Extension<AuthState>>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let auth_state = auth_state.map(|Extension(auth_state)| auth_state);
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            // Add authenticated user to context if present
+            let mut data = Data::default();
+            if let Some(Extension(user)) = auth_user {
+                data.insert(user);
+            }
+
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .on_connection_init(move |payload| connection_init(auth_state, payload))
+                .serve()
+        })
+}
+
+/// Authenticate a subscription from its `connection_init` payload
+///
+/// A token that fails verification, or has been revoked, closes the
+/// connection; a payload without one leaves the caller as they connected.
+async fn connection_init(
+    auth_state: Option<AuthState>,
+    payload: serde_json::Value,
+) -> async_graphql::Result<Data> {
+    let mut data = Data::default();
+    let header = ["Authorization", "authorization"]
+        .into_iter()
+        .find_map(|key| payload.get(key))
+        .and_then(serde_json::Value::as_str);
+
+    if let (Some(auth_state), Some(header)) = (auth_state, header) {
+        let user = auth_state.authenticate(header).await.map_err(auth_error)?;
+        data.insert(user);
+    }
+    Ok(data)
+}
+
 /// GraphQL Playground handler
 pub async fn graphql_playground() -> impl IntoResponse {
     Html(
         GraphiQLSource::build()
             .endpoint("/graphql")
+            .subscription_endpoint("/graphql/ws")
             .title("LLM Registry GraphQL Playground")
             .finish(),
     )
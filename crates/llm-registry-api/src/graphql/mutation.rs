@@ -6,14 +6,18 @@ use async_graphql::{Context, InputObject, Object, Result};
 use llm_registry_core::{
     AssetId, Checksum, HashAlgorithm, StorageBackend, StorageLocation,
 };
-use llm_registry_service::{RegisterAssetRequest, ServiceRegistry, UpdateAssetRequest};
+use llm_registry_service::{
+    DeleteMode, RegisterAssetRequest, ServiceRegistry, UpdateAssetRequest,
+};
 use semver::Version;
 use std::sync::Arc;
 
 use super::types::{
-    GqlAsset, GqlAssetStatus, GqlAssetType, GqlDeleteResult, GqlRegisterResult, GqlUpdateResult,
+    GqlAsset, GqlAssetStatus, GqlAssetType, GqlDeleteMode, GqlDeleteResult, GqlRegisterResult,
+    GqlUpdateResult,
 };
-use crate::auth::AuthUser;
+use super::{authorize, authorize_asset, authorize_dependents, redact_dependents};
+use crate::authorization::Operation;
 use crate::error::ApiError;
 
 /// Root Mutation type for GraphQL
@@ -79,6 +83,8 @@ pub struct UpdateAssetInput {
     /// Annotation keys to remove
     #[graphql(default)]
     pub remove_annotations: Vec<String>,
+    /// Only update if the asset is still at this revision
+    pub expected_revision: Option<u64>,
 }
 
 /// Annotation key-value pair
@@ -100,8 +106,8 @@ impl Mutation {
     ) -> Result<GqlRegisterResult> {
         let services = ctx.data::<Arc<ServiceRegistry>>()?;
 
-        // Check authentication (optional - can be made required)
-        let _user = ctx.data_opt::<AuthUser>();
+        // Check permissions
+        authorize(ctx, Operation::RegisterAsset).await?;
 
         // Parse version
         let version = Version::parse(&input.version)
@@ -179,15 +185,15 @@ impl Mutation {
     ) -> Result<GqlUpdateResult> {
         let services = ctx.data::<Arc<ServiceRegistry>>()?;
 
-        // Check authentication (optional - can be made required)
-        let _user = ctx.data_opt::<AuthUser>();
-
         // Parse asset ID
         let asset_id = input
             .asset_id
             .parse::<AssetId>()
             .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
 
+        // Check permissions
+        authorize_asset(ctx, Operation::UpdateAsset, &asset_id).await?;
+
         // Build update request
         let request = UpdateAssetRequest {
             asset_id,
@@ -202,6 +208,7 @@ impl Mutation {
                 .map(|a| (a.key, a.value))
                 .collect(),
             remove_annotations: input.remove_annotations,
+            expected_revision: input.expected_revision,
         };
 
         let response = services
@@ -216,27 +223,59 @@ impl Mutation {
         })
     }
 
-    /// Delete an asset
-    async fn delete_asset(&self, ctx: &Context<'_>, id: String) -> Result<GqlDeleteResult> {
+    /// Delete an asset, optionally only if it is still at `expected_revision`
+    async fn delete_asset(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        expected_revision: Option<u64>,
+        #[graphql(desc = "What to do with assets that still depend on it", default)]
+        mode: GqlDeleteMode,
+    ) -> Result<GqlDeleteResult> {
         let services = ctx.data::<Arc<ServiceRegistry>>()?;
 
-        // Check authentication (optional - can be made required)
-        let _user = ctx.data_opt::<AuthUser>();
-
         // Parse asset ID
         let asset_id = id
             .parse::<AssetId>()
             .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
 
-        services
+        // Check permissions
+        authorize_asset(ctx, Operation::DeleteAsset, &asset_id).await?;
+
+        // Forcing or cascading changes the dependents, so the caller must be
+        // allowed to update all of them before anything is deleted
+        let mode = mode.to_core();
+        if mode != DeleteMode::Refuse {
+            let dependents = services
+                .registration()
+                .blocking_dependents(&asset_id, mode)
+                .await
+                .map_err(ApiError::from)?;
+            authorize_dependents(ctx, &dependents).await?;
+        }
+
+        let response = match services
             .registration()
-            .delete_asset(&asset_id)
+            .delete_asset(&asset_id, expected_revision, mode)
             .await
-            .map_err(|e| ApiError::from(e))?;
+        {
+            Ok(response) => response,
+            Err(e) => return Err(ApiError::from(redact_dependents(ctx, e).await?).into()),
+        };
 
         Ok(GqlDeleteResult {
             asset_id: id,
             message: "Asset deleted successfully".to_string(),
+            updated_dependents: response
+                .updated_dependents
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            failed_dependents: response
+                .failed_dependents
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
         })
     }
 }
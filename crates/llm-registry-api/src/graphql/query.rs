@@ -2,13 +2,21 @@
 //!
 //! This module implements all GraphQL query operations.
 
+use async_graphql::connection::Edge;
 use async_graphql::{Context, Object, Result};
 use llm_registry_core::AssetId;
 use llm_registry_service::{SearchAssetsRequest, ServiceRegistry, SortField, SortOrder};
 use std::sync::Arc;
 
-use super::types::{GqlAsset, GqlAssetConnection, GqlAssetFilter, GqlDependencyNode};
-use crate::error::ApiError;
+use super::types::{
+    GqlAsset, GqlAssetConnection, GqlAssetConnectionFields, GqlAssetFilter, GqlDependencyKind,
+    GqlDependencyNode, GqlFacet, GqlFacetCounts,
+};
+use super::{authorize, authorize_asset, readable, readable_graph, search_readable};
+use crate::{authorization::Operation, error::ApiError};
+
+/// Page size of the `assets` connection when `first` is not given
+const DEFAULT_PAGE_SIZE: i64 = 20;
 
 /// Root Query type for GraphQL
 pub struct Query;
@@ -23,6 +31,8 @@ impl Query {
             .parse::<AssetId>()
             .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
 
+        authorize_asset(ctx, Operation::GetAsset, &asset_id).await?;
+
         let asset = services
             .search()
             .get_asset(&asset_id)
@@ -33,14 +43,36 @@ impl Query {
     }
 
     /// Search and list assets with optional filters
+    ///
+    /// Follows the Relay connection spec. Only forward pagination with
+    /// `first` and `after` is supported.
     async fn assets(
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Filter criteria", default)] filter: Option<GqlAssetFilter>,
-        #[graphql(desc = "Number of items to return", default = 20)] limit: i64,
-        #[graphql(desc = "Number of items to skip", default = 0)] offset: i64,
+        #[graphql(desc = "Fields to count the matching assets by", default)] facets: Vec<GqlFacet>,
+        #[graphql(desc = "Number of assets to return (default 20)")] first: Option<i32>,
+        #[graphql(desc = "Return assets after this cursor")] after: Option<String>,
+        #[graphql(desc = "Not supported")] last: Option<i32>,
+        #[graphql(desc = "Not supported")] before: Option<String>,
     ) -> Result<GqlAssetConnection> {
-        let services = ctx.data::<Arc<ServiceRegistry>>()?;
+        authorize(ctx, Operation::SearchAssets).await?;
+
+        if last.is_some() || before.is_some() {
+            return Err(ApiError::bad_request(
+                "Backward pagination with last/before is not supported",
+            )
+            .into());
+        }
+
+        let limit = match first {
+            Some(first) if first < 0 => {
+                return Err(ApiError::bad_request("first must not be negative").into())
+            }
+            Some(first) => first as i64,
+            None => DEFAULT_PAGE_SIZE,
+        };
+        let has_previous_page = after.is_some();
 
         // Build search request
         let mut search_request = SearchAssetsRequest {
@@ -51,9 +83,12 @@ impl Query {
             storage_backend: None,
             exclude_deprecated: true,
             limit,
-            offset,
+            offset: 0,
+            cursor: after,
             sort_by: SortField::CreatedAt,
             sort_order: SortOrder::Descending,
+            facets: facets.into_iter().map(GqlFacet::to_core).collect(),
+            ..Default::default()
         };
 
         // Apply filters if provided
@@ -73,17 +108,34 @@ impl Query {
             }
         }
 
-        let response = services
-            .search()
-            .search_assets(search_request)
-            .await
-            .map_err(|e| ApiError::from(e))?;
+        // Assets the caller cannot read are left out of the search
+        let response = search_readable(ctx, search_request).await?;
+        let total_count = response.total;
 
-        Ok(GqlAssetConnection {
-            nodes: response.assets.into_iter().map(GqlAsset).collect(),
-            total_count: response.total,
-            has_next_page: (response.offset + response.limit) < response.total,
-        })
+        let facets = response
+            .facets
+            .into_iter()
+            .map(|(facet, counts)| GqlFacetCounts {
+                facet: GqlFacet::from_core(facet),
+                counts: counts.into_iter().map(Into::into).collect(),
+            })
+            .collect();
+
+        let mut connection = GqlAssetConnection::with_additional_fields(
+            has_previous_page,
+            response.has_more,
+            GqlAssetConnectionFields {
+                total_count,
+                facets,
+            },
+        );
+        let edges = response
+            .cursors
+            .into_iter()
+            .zip(response.assets)
+            .map(|(cursor, asset)| Edge::new(cursor, GqlAsset(asset)));
+        connection.edges.extend(edges);
+        Ok(connection)
     }
 
     /// Get all dependencies for an asset
@@ -91,8 +143,10 @@ impl Query {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Asset ID")] id: String,
-        #[graphql(desc = "Maximum depth to traverse (-1 for unlimited)", default = -1)]
+        #[graphql(desc = "Maximum depth to traverse, at most 100 (-1 for unlimited)", default = -1)]
         max_depth: i32,
+        #[graphql(desc = "Dependency kinds to follow (all when empty)", default)]
+        kinds: Vec<GqlDependencyKind>,
     ) -> Result<Vec<GqlDependencyNode>> {
         let services = ctx.data::<Arc<ServiceRegistry>>()?;
 
@@ -100,9 +154,15 @@ impl Query {
             .parse::<AssetId>()
             .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
 
+        authorize_asset(ctx, Operation::GetDependencies, &asset_id).await?;
+        // The root itself may be hidden from callers allowed to read graphs
+        authorize_asset(ctx, Operation::GetAsset, &asset_id).await?;
+
         let request = llm_registry_service::GetDependencyGraphRequest {
             asset_id,
             max_depth,
+            direction: llm_registry_service::DependencyDirection::Dependencies,
+            kinds: kinds.into_iter().map(GqlDependencyKind::to_core).collect(),
         };
 
         let response = services
@@ -110,6 +170,7 @@ impl Query {
             .get_dependency_graph(request)
             .await
             .map_err(|e| ApiError::from(e))?;
+        let response = readable_graph(ctx, response).await?;
 
         Ok(response
             .dependencies
@@ -130,17 +191,24 @@ impl Query {
             .parse::<AssetId>()
             .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
 
+        authorize_asset(ctx, Operation::GetDependents, &asset_id).await?;
+        // The root itself may be hidden from callers allowed to read graphs
+        authorize_asset(ctx, Operation::GetAsset, &asset_id).await?;
+
         let dependents = services
             .search()
             .get_reverse_dependencies(&asset_id)
             .await
             .map_err(|e| ApiError::from(e))?;
 
+        let dependents = readable(ctx, dependents).await?;
+
         Ok(dependents.into_iter().map(GqlAsset).collect())
     }
 
     /// Get all unique tags across all assets
     async fn all_tags(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        authorize(ctx, Operation::ListTags).await?;
         let services = ctx.data::<Arc<ServiceRegistry>>()?;
 
         let tags = services
@@ -168,3 +236,120 @@ impl Query {
         env!("CARGO_PKG_VERSION").to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthUser;
+    use crate::authorization::Authorizer;
+    use crate::graphql::{build_schema_with_authorizer, Mutation, Subscription};
+    use crate::jwt::Claims;
+    use async_graphql::{Request, Schema, Value};
+    use llm_registry_core::{
+        Asset, AssetMetadata, AssetType, Checksum, DependencyKind, HashAlgorithm, StorageBackend,
+        StorageLocation,
+    };
+    use llm_registry_db::{AssetRepository, InMemoryAssetRepository, InMemoryEventStore};
+
+    fn create_test_asset(name: &str) -> Asset {
+        let metadata = AssetMetadata::new(name, semver::Version::new(1, 0, 0));
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}/weights.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(
+            AssetId::new(),
+            AssetType::Model,
+            metadata,
+            storage,
+            checksum,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_query_requires_permission() {
+        let schema = Schema::build(Query, Mutation, Subscription)
+            .data(Arc::new(Authorizer::default()))
+            .finish();
+
+        let response = schema.execute(Request::new("{ allTags }")).await;
+        assert_eq!(response.errors.len(), 1);
+
+        let extensions = response.errors[0].extensions.as_ref().unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::from("UNAUTHENTICATED"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependencies_filters_by_kinds() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let mut ids = Vec::new();
+        for name in ["app", "model", "corpus", "suite"] {
+            ids.push(repository.create(create_test_asset(name)).await.unwrap().id);
+        }
+        let [app, model, corpus, suite] = [ids[0], ids[1], ids[2], ids[3]];
+        for (from, to, kind) in [
+            (app, model, DependencyKind::Runtime),
+            (model, corpus, DependencyKind::TrainingData),
+            (app, suite, DependencyKind::Evaluation),
+        ] {
+            repository
+                .add_dependency(&from, &to, None, kind)
+                .await
+                .unwrap();
+        }
+        let services = ServiceRegistry::new(repository, Arc::new(InMemoryEventStore::new()));
+        let schema =
+            build_schema_with_authorizer(Arc::new(services), Arc::new(Authorizer::default()));
+        let developer =
+            AuthUser::new(Claims::new("dev", "test", "test", 3600).with_role("developer"));
+
+        let reached = |kinds: &str| {
+            let query = format!(
+                r#"{{ dependencies(id: "{}", kinds: [{}]) {{ name }} }}"#,
+                app, kinds
+            );
+            let response = schema.execute(Request::new(query).data(developer.clone()));
+            async move {
+                let response = response.await;
+                assert!(response.errors.is_empty(), "{:?}", response.errors);
+                let data = response.data.into_json().unwrap();
+                let mut names: Vec<String> = data["dependencies"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|node| node["name"].as_str().unwrap().to_string())
+                    .collect();
+                names.sort();
+                names
+            }
+        };
+
+        assert_eq!(reached("").await, ["app", "corpus", "model", "suite"]);
+        assert_eq!(
+            reached("RUNTIME, TRAINING_DATA").await,
+            ["app", "corpus", "model"]
+        );
+        assert_eq!(reached("EVALUATION").await, ["app", "suite"]);
+    }
+
+    #[test]
+    fn test_assets_is_relay_connection() {
+        let sdl = Schema::build(Query, Mutation, Subscription).finish().sdl();
+
+        assert!(sdl.contains("type GqlAssetConnection"));
+        assert!(sdl.contains("type GqlAssetEdge"));
+        assert!(sdl.contains("type PageInfo"));
+        assert!(sdl.contains("totalCount: Int!"));
+        assert!(sdl.contains("after: String"));
+        assert!(sdl.contains("facets: [GqlFacetCounts!]!"));
+    }
+}
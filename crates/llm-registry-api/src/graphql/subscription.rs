@@ -0,0 +1,350 @@
+//! GraphQL subscription resolvers
+//!
+//! This module streams registry events to GraphQL clients over WebSocket.
+//! Every subscription is backed by the watch service's event feed.
+
+use async_graphql::{Context, Result, Subscription as SubscriptionObject};
+use futures::{Stream, StreamExt};
+use llm_registry_core::AssetId;
+use llm_registry_service::{EventStream, ServiceRegistry, WatchAssetsRequest};
+use std::sync::Arc;
+
+use super::types::{GqlAsset, GqlAssetEvent, GqlAssetType, GqlEventFilter};
+use super::authorize;
+use crate::{
+    auth::AuthUser,
+    authorization::{Authorizer, Operation},
+    error::ApiError,
+};
+
+/// Root Subscription type for GraphQL
+pub struct Subscription;
+
+#[SubscriptionObject]
+impl Subscription {
+    /// Stream registry events matching a filter
+    async fn asset_events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Filter criteria", default)] filter: GqlEventFilter,
+    ) -> Result<impl Stream<Item = Result<GqlAssetEvent>>> {
+        let request = WatchAssetsRequest {
+            asset_ids: vec![],
+            asset_names: filter.asset_names,
+            asset_types: filter.asset_types.iter().map(GqlAssetType::to_core).collect(),
+            tags: filter.tags,
+            event_types: filter.event_types,
+            resume_after: filter.resume_after,
+        };
+
+        let events = watch(ctx, request).await?;
+        Ok(events.map(|event| {
+            event
+                .map(GqlAssetEvent)
+                .map_err(|e| ApiError::from(e).into())
+        }))
+    }
+
+    /// Stream every event for a single asset
+    async fn asset_changed(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<impl Stream<Item = Result<GqlAssetEvent>>> {
+        let asset_id = id
+            .parse::<AssetId>()
+            .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
+
+        let request = WatchAssetsRequest {
+            asset_ids: vec![asset_id],
+            ..Default::default()
+        };
+
+        let events = watch(ctx, request).await?;
+        Ok(events.map(|event| {
+            event
+                .map(GqlAssetEvent)
+                .map_err(|e| ApiError::from(e).into())
+        }))
+    }
+
+    /// Stream newly registered assets
+    async fn asset_registered(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "type", desc = "Only assets of this type")] asset_type: Option<GqlAssetType>,
+        #[graphql(desc = "Only assets carrying at least one of these tags", default)] tags: Vec<String>,
+    ) -> Result<impl Stream<Item = Result<GqlAsset>>> {
+        let request = WatchAssetsRequest {
+            asset_types: asset_type.iter().map(GqlAssetType::to_core).collect(),
+            tags,
+            event_types: vec!["asset_registered".to_string()],
+            ..Default::default()
+        };
+
+        let events = watch(ctx, request).await?;
+        Ok(events.filter_map(|event| async move {
+            match event {
+                Ok(event) => event.asset.map(|asset| Ok(GqlAsset(asset))),
+                Err(e) => Some(Err(ApiError::from(e).into())),
+            }
+        }))
+    }
+}
+
+/// Start watching registry events
+async fn watch(ctx: &Context<'_>, request: WatchAssetsRequest) -> Result<EventStream> {
+    authorize(ctx, Operation::WatchAssets).await?;
+
+    let services = ctx.data::<Arc<ServiceRegistry>>()?;
+
+    let watch = services
+        .watch()
+        .ok_or_else(|| ApiError::internal_server_error("Event streaming is not configured"))?;
+
+    let events = watch.watch_assets(request).await.map_err(ApiError::from)?;
+
+    // Hide events about assets the caller cannot read
+    let authorizer = ctx.data::<Arc<Authorizer>>()?;
+    Ok(authorizer.filter_events(ctx.data_opt::<AuthUser>().cloned(), events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthState;
+    use crate::graphql::{build_schema_with_authorizer, connection_init, Mutation, Query};
+    use crate::jwt::{Claims, JwtConfig, JwtManager};
+    use async_graphql::{Request, Response, Schema};
+    use llm_registry_core::{
+        Asset, AssetMetadata, AssetType, Checksum, EventType, HashAlgorithm, RegistryEvent,
+        StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::{
+        AssetRepository, EventStore, InMemoryAssetRepository, InMemoryEventStore,
+    };
+    use llm_registry_service::{
+        AclEffect, AclEntry, AclStore, AclSubject, AclTarget, DefaultWatchService,
+        InMemoryAclStore, InMemoryTokenRevocationStore, ServiceRegistryBuilder,
+    };
+    use semver::Version;
+    use std::any::TypeId;
+    use std::time::Duration;
+
+    #[test]
+    fn test_subscription_schema() {
+        let sdl = Schema::build(Query, Mutation, Subscription).finish().sdl();
+
+        assert!(sdl.contains("type Subscription"));
+        assert!(sdl.contains("assetEvents("));
+        assert!(sdl.contains("assetChanged("));
+        assert!(sdl.contains("assetRegistered("));
+        assert!(sdl.contains("type GqlAssetEvent"));
+        assert!(sdl.contains("input GqlEventFilter"));
+    }
+
+    fn asset(name: &str, tags: &[&str]) -> Asset {
+        let mut metadata = AssetMetadata::new(name, Version::parse("1.0.0").unwrap());
+        metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(
+            AssetId::new(),
+            AssetType::Model,
+            metadata,
+            storage,
+            checksum,
+        )
+        .unwrap()
+    }
+
+    fn registered(asset: &Asset) -> RegistryEvent {
+        RegistryEvent::new(EventType::AssetRegistered {
+            asset_id: asset.id,
+            asset_name: asset.metadata.name.clone(),
+            asset_version: asset.metadata.version.to_string(),
+            asset_type: asset.asset_type.to_string(),
+        })
+    }
+
+    fn user(subject: &str, roles: &[&str]) -> AuthUser {
+        let claims = roles.iter().fold(
+            Claims::new(subject, "test", "test", 3600),
+            |claims, role| claims.with_role(*role),
+        );
+        AuthUser::new(claims)
+    }
+
+    /// ID of the asset a delivered subscription item is about
+    fn delivered_asset_id(response: Response, field: &str) -> String {
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data[field]["assetId"].as_str().unwrap().to_string()
+    }
+
+    /// Wait for the next item of a subscription
+    async fn next(stream: &mut (impl Stream<Item = Response> + Unpin)) -> Response {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for event")
+            .expect("stream ended")
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_deliver_filtered_readable_events() {
+        let tuned = asset("ft/llama", &["nlp"]);
+        let public = asset("base/llama", &["nlp"]);
+        let dataset = asset("corpus", &["vision"]);
+
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        for asset in [&tuned, &public, &dataset] {
+            repository.create(asset.clone()).await.unwrap();
+        }
+        let events = Arc::new(InMemoryEventStore::new());
+        let services = Arc::new(
+            ServiceRegistryBuilder::new()
+                .repository(repository.clone())
+                .event_store(events.clone())
+                .watch_service(Arc::new(
+                    DefaultWatchService::new(repository, events.clone())
+                        .with_poll_interval(Duration::from_millis(10)),
+                ))
+                .build()
+                .unwrap(),
+        );
+
+        // Only fine-tuners may read fine-tuned models
+        let acls = Arc::new(InMemoryAclStore::new());
+        acls.create(AclEntry::new(
+            AclTarget::NamePrefix("ft/".to_string()),
+            AclSubject::Role("fine-tuning".to_string()),
+            "asset:read",
+            AclEffect::Allow,
+        ))
+        .await
+        .unwrap();
+        let authorizer = Authorizer::default().with_acl(acls, services.search().clone());
+        let schema = build_schema_with_authorizer(services, Arc::new(authorizer));
+
+        let developer = user("dev", &["developer"]);
+        let tuner = user("tuner", &["viewer", "fine-tuning"]);
+
+        let subscribe = |query: String, caller: &AuthUser| {
+            schema.execute_stream(Request::new(query).data(caller.clone()))
+        };
+        let nlp_events = r#"subscription { assetEvents(filter: { tags: ["nlp"] }) { assetId } }"#;
+        let changed = |asset: &Asset| {
+            format!(
+                r#"subscription {{ assetChanged(id: "{}") {{ assetId }} }}"#,
+                asset.id
+            )
+        };
+
+        let mut developer_events = subscribe(nlp_events.to_string(), &developer);
+        let mut tuner_events = subscribe(nlp_events.to_string(), &tuner);
+        let mut public_changes = subscribe(changed(&public), &developer);
+        let mut tuned_changes = subscribe(changed(&tuned), &developer);
+
+        // Start every watch before appending, as watches only see new events
+        for stream in [
+            &mut developer_events,
+            &mut tuner_events,
+            &mut public_changes,
+            &mut tuned_changes,
+        ] {
+            assert!(
+                tokio::time::timeout(Duration::from_millis(50), stream.next())
+                    .await
+                    .is_err()
+            );
+        }
+        for asset in [&tuned, &dataset, &public] {
+            events.append(registered(asset)).await.unwrap();
+        }
+
+        // The tag filter drops the dataset, and the ACL hides the tuned model
+        let response = next(&mut developer_events).await;
+        assert_eq!(
+            delivered_asset_id(response, "assetEvents"),
+            public.id.to_string()
+        );
+
+        let response = next(&mut tuner_events).await;
+        assert_eq!(
+            delivered_asset_id(response, "assetEvents"),
+            tuned.id.to_string()
+        );
+        let response = next(&mut tuner_events).await;
+        assert_eq!(
+            delivered_asset_id(response, "assetEvents"),
+            public.id.to_string()
+        );
+
+        // Single-asset streams only deliver events for that asset, and only
+        // when the caller can read it
+        let response = next(&mut public_changes).await;
+        assert_eq!(
+            delivered_asset_id(response, "assetChanged"),
+            public.id.to_string()
+        );
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), tuned_changes.next())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_init_authenticates_bearer_tokens() {
+        let revocations = Arc::new(InMemoryTokenRevocationStore::new());
+        let jwt_manager = || {
+            let config = JwtConfig::new("test-secret")
+                .with_issuer("test")
+                .with_audience("test");
+            JwtManager::new(config)
+                .unwrap()
+                .with_revocation_store(revocations.clone())
+        };
+        let tokens = jwt_manager();
+        let auth_state = AuthState::new(jwt_manager());
+        let bearer = |subject: &str| {
+            let claims = Claims::new(subject, "test", "test", 3600).with_role("viewer");
+            let token = tokens.generate_token_with_claims(claims).unwrap();
+            serde_json::json!({ "Authorization": format!("Bearer {}", token) })
+        };
+
+        let data = connection_init(Some(auth_state.clone()), bearer("user-1"))
+            .await
+            .unwrap();
+        let user = data
+            .get(&TypeId::of::<AuthUser>())
+            .and_then(|user| user.downcast_ref::<AuthUser>())
+            .unwrap();
+        assert_eq!(user.user_id(), "user-1");
+
+        // Forged and revoked tokens are refused
+        let forged = serde_json::json!({ "Authorization": "Bearer forged" });
+        assert!(connection_init(Some(auth_state.clone()), forged)
+            .await
+            .is_err());
+        let revoked = bearer("user-2");
+        tokens.revoke_all_for_subject("user-2").await.unwrap();
+        assert!(connection_init(Some(auth_state.clone()), revoked)
+            .await
+            .is_err());
+
+        // Without a token the caller stays anonymous
+        let data = connection_init(Some(auth_state), serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(data.is_empty());
+    }
+}
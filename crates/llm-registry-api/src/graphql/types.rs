@@ -2,10 +2,11 @@
 //!
 //! This module defines GraphQL types that wrap the core domain types.
 
-use async_graphql::{Enum, Object, SimpleObject};
+use async_graphql::connection::Connection;
+use async_graphql::{Enum, Json, Object, SimpleObject};
 use chrono::{DateTime, Utc};
-use llm_registry_core::{Asset, AssetStatus, AssetType};
-use llm_registry_service::DependencyNode;
+use llm_registry_core::{Asset, AssetStatus, AssetType, DependencyKind};
+use llm_registry_service::{DeleteMode, DependencyNode, Facet, FacetCount, WatchedEvent};
 
 /// GraphQL representation of an Asset
 #[derive(Clone)]
@@ -115,6 +116,11 @@ impl GqlAsset {
     async fn deprecated_at(&self) -> Option<DateTime<Utc>> {
         self.0.deprecated_at
     }
+
+    /// Revision, bumped on every update
+    async fn revision(&self) -> u64 {
+        self.0.revision
+    }
 }
 
 /// GraphQL representation of a dependency node
@@ -228,6 +234,43 @@ impl GqlAssetStatus {
     }
 }
 
+/// GraphQL representation of a dependency kind
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GqlDependencyKind {
+    /// Needed to run the asset
+    Runtime,
+    /// Needed to build the asset
+    Build,
+    /// Used to evaluate the asset
+    Evaluation,
+    /// Data the asset was trained on
+    TrainingData,
+    /// Used when available
+    Optional,
+}
+
+impl GqlDependencyKind {
+    pub fn from_core(kind: DependencyKind) -> Self {
+        match kind {
+            DependencyKind::Runtime => GqlDependencyKind::Runtime,
+            DependencyKind::Build => GqlDependencyKind::Build,
+            DependencyKind::Evaluation => GqlDependencyKind::Evaluation,
+            DependencyKind::TrainingData => GqlDependencyKind::TrainingData,
+            DependencyKind::Optional => GqlDependencyKind::Optional,
+        }
+    }
+
+    pub fn to_core(self) -> DependencyKind {
+        match self {
+            GqlDependencyKind::Runtime => DependencyKind::Runtime,
+            GqlDependencyKind::Build => DependencyKind::Build,
+            GqlDependencyKind::Evaluation => DependencyKind::Evaluation,
+            GqlDependencyKind::TrainingData => DependencyKind::TrainingData,
+            GqlDependencyKind::Optional => DependencyKind::Optional,
+        }
+    }
+}
+
 /// Key-value annotation
 #[derive(SimpleObject, Clone)]
 pub struct GqlAnnotation {
@@ -237,15 +280,87 @@ pub struct GqlAnnotation {
     pub value: String,
 }
 
-/// Paginated assets response
+/// Relay connection of assets, paged with opaque search cursors
+pub type GqlAssetConnection = Connection<String, GqlAsset, GqlAssetConnectionFields>;
+
+/// Fields of an asset connection besides edges and page info
 #[derive(SimpleObject)]
-pub struct GqlAssetConnection {
-    /// List of assets
-    pub nodes: Vec<GqlAsset>,
-    /// Total count
+pub struct GqlAssetConnectionFields {
+    /// Total number of matching assets
     pub total_count: i64,
-    /// Whether there are more results
-    pub has_next_page: bool,
+
+    /// Value counts of the requested facets over all matching assets
+    pub facets: Vec<GqlFacetCounts>,
+}
+
+/// Asset field that search results can be counted by
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GqlFacet {
+    /// Asset type
+    AssetType,
+    /// Tags
+    Tag,
+    /// License
+    License,
+    /// Asset status
+    Status,
+    /// Provenance author
+    Author,
+    /// Storage backend type
+    StorageBackend,
+}
+
+impl GqlFacet {
+    pub fn from_core(facet: Facet) -> Self {
+        match facet {
+            Facet::AssetType => GqlFacet::AssetType,
+            Facet::Tag => GqlFacet::Tag,
+            Facet::License => GqlFacet::License,
+            Facet::Status => GqlFacet::Status,
+            Facet::Author => GqlFacet::Author,
+            Facet::StorageBackend => GqlFacet::StorageBackend,
+        }
+    }
+
+    pub fn to_core(self) -> Facet {
+        match self {
+            GqlFacet::AssetType => Facet::AssetType,
+            GqlFacet::Tag => Facet::Tag,
+            GqlFacet::License => Facet::License,
+            GqlFacet::Status => Facet::Status,
+            GqlFacet::Author => Facet::Author,
+            GqlFacet::StorageBackend => Facet::StorageBackend,
+        }
+    }
+}
+
+/// Value counts of one facet
+#[derive(SimpleObject)]
+pub struct GqlFacetCounts {
+    /// Facet the counts belong to
+    pub facet: GqlFacet,
+
+    /// Counts per value, most common first
+    pub counts: Vec<GqlFacetCount>,
+}
+
+/// Number of matching assets with one facet value
+#[derive(SimpleObject)]
+pub struct GqlFacetCount {
+    /// Facet value
+    pub value: String,
+
+    /// Number of matching assets with the value
+    pub count: i64,
+}
+
+impl From<FacetCount> for GqlFacetCount {
+    fn from(count: FacetCount) -> Self {
+        Self {
+            value: count.value,
+            count: count.count,
+        }
+    }
 }
 
 /// Asset search filters
@@ -261,6 +376,66 @@ pub struct GqlAssetFilter {
     pub name: Option<String>,
 }
 
+/// Registry event filters for subscriptions
+#[derive(async_graphql::InputObject, Default)]
+pub struct GqlEventFilter {
+    /// Only events for assets with one of these names
+    #[graphql(default)]
+    pub asset_names: Vec<String>,
+    /// Only events for assets of one of these types
+    #[graphql(default)]
+    pub asset_types: Vec<GqlAssetType>,
+    /// Only events for assets carrying at least one of these tags
+    #[graphql(default)]
+    pub tags: Vec<String>,
+    /// Only events with one of these names (e.g. "asset_registered")
+    #[graphql(default)]
+    pub event_types: Vec<String>,
+    /// Replay every event after this event ID before streaming live events
+    pub resume_after: Option<String>,
+}
+
+/// GraphQL representation of a registry event
+pub struct GqlAssetEvent(pub WatchedEvent);
+
+#[Object]
+impl GqlAssetEvent {
+    /// Event ID, usable as `resumeAfter` when resubscribing
+    async fn event_id(&self) -> &str {
+        &self.0.event_id
+    }
+
+    /// Event name (e.g. "asset_registered")
+    async fn event_type(&self) -> &str {
+        self.0.event.event_type.event_name()
+    }
+
+    /// ID of the asset the event concerns
+    async fn asset_id(&self) -> Option<String> {
+        self.0.event.event_type.asset_id().map(|id| id.to_string())
+    }
+
+    /// Current state of the asset, if it still exists
+    async fn asset(&self) -> Option<GqlAsset> {
+        self.0.asset.clone().map(GqlAsset)
+    }
+
+    /// When the event occurred
+    async fn timestamp(&self) -> DateTime<Utc> {
+        self.0.event.timestamp
+    }
+
+    /// User or service that triggered the event
+    async fn actor(&self) -> Option<&str> {
+        self.0.event.actor.as_deref()
+    }
+
+    /// Event details
+    async fn payload(&self) -> Json<&llm_registry_core::EventType> {
+        Json(&self.0.event.event_type)
+    }
+}
+
 /// Registration result
 #[derive(SimpleObject)]
 pub struct GqlRegisterResult {
@@ -286,4 +461,30 @@ pub struct GqlDeleteResult {
     pub asset_id: String,
     /// Success message
     pub message: String,
+    /// IDs of dependents whose status was changed, nearest first
+    pub updated_dependents: Vec<String>,
+    /// IDs of dependents whose status could not be changed, nearest first
+    pub failed_dependents: Vec<String>,
+}
+
+/// What deleting an asset does to the assets that still need it
+#[derive(Enum, Copy, Clone, Default, Eq, PartialEq)]
+pub enum GqlDeleteMode {
+    /// Refuse while any asset depends on it
+    #[default]
+    Refuse,
+    /// Delete anyway and mark its direct dependents non-compliant
+    Force,
+    /// Delete and deprecate every asset depending on it, transitively
+    Cascade,
+}
+
+impl GqlDeleteMode {
+    pub fn to_core(self) -> DeleteMode {
+        match self {
+            GqlDeleteMode::Refuse => DeleteMode::Refuse,
+            GqlDeleteMode::Force => DeleteMode::Force,
+            GqlDeleteMode::Cascade => DeleteMode::Cascade,
+        }
+    }
 }
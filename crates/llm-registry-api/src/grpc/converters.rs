@@ -7,10 +7,13 @@ use super::proto;
 use crate::error::ApiError;
 use llm_registry_core::{
     Asset, AssetId, AssetMetadata, AssetReference, AssetStatus, AssetType, Checksum,
-    HashAlgorithm, Provenance, StorageBackend, StorageLocation,
+    DependencyKind, EventType, HashAlgorithm, Provenance, StorageBackend, StorageLocation,
 };
-use llm_registry_service::{DependencyNode, SortField, SortOrder};
-use semver::Version;
+use llm_registry_service::{
+    BatchItemResult, DeleteMode, DependencyNode, Facet, FacetCounts, RegisterAssetRequest,
+    ResolvedDependency, SortField, SortOrder, WatchedEvent,
+};
+use semver::{Version, VersionReq};
 
 // ============================================================================
 // Enum Conversions
@@ -77,6 +80,19 @@ impl From<HashAlgorithm> for proto::HashAlgorithm {
     }
 }
 
+impl From<&EventType> for proto::asset_event::EventType {
+    fn from(event_type: &EventType) -> Self {
+        match event_type {
+            EventType::AssetRegistered { .. } => proto::asset_event::EventType::Created,
+            EventType::AssetDeleted { .. } => proto::asset_event::EventType::Deleted,
+            EventType::AssetUpdated { .. }
+            | EventType::AssetStatusChanged { .. }
+            | EventType::DependencyAdded { .. } => proto::asset_event::EventType::Updated,
+            _ => proto::asset_event::EventType::Unspecified,
+        }
+    }
+}
+
 /// Convert i32 to HashAlgorithm (helper function to avoid orphan rule violations)
 pub fn hash_algorithm_from_i32(value: i32) -> Result<HashAlgorithm, ApiError> {
     match proto::HashAlgorithm::try_from(value) {
@@ -97,6 +113,7 @@ impl From<SortField> for proto::SortField {
             SortField::Name => proto::SortField::Name,
             SortField::Version => proto::SortField::Version,
             SortField::SizeBytes => proto::SortField::SizeBytes,
+            SortField::Relevance => proto::SortField::Relevance,
         }
     }
 }
@@ -111,6 +128,7 @@ pub fn sort_field_from_i32(value: i32) -> Result<SortField, ApiError> {
         Ok(proto::SortField::Name) => Ok(SortField::Name),
         Ok(proto::SortField::Version) => Ok(SortField::Version),
         Ok(proto::SortField::SizeBytes) => Ok(SortField::SizeBytes),
+        Ok(proto::SortField::Relevance) => Ok(SortField::Relevance),
         Err(_) => Err(ApiError::bad_request("Invalid sort field")),
     }
 }
@@ -135,6 +153,87 @@ pub fn sort_order_from_i32(value: i32) -> Result<SortOrder, ApiError> {
     }
 }
 
+impl From<Facet> for proto::Facet {
+    fn from(facet: Facet) -> Self {
+        match facet {
+            Facet::AssetType => proto::Facet::AssetType,
+            Facet::Tag => proto::Facet::Tag,
+            Facet::License => proto::Facet::License,
+            Facet::Status => proto::Facet::Status,
+            Facet::Author => proto::Facet::Author,
+            Facet::StorageBackend => proto::Facet::StorageBackend,
+        }
+    }
+}
+
+impl From<DependencyKind> for proto::DependencyKind {
+    fn from(kind: DependencyKind) -> Self {
+        match kind {
+            DependencyKind::Runtime => proto::DependencyKind::Runtime,
+            DependencyKind::Build => proto::DependencyKind::Build,
+            DependencyKind::Evaluation => proto::DependencyKind::Evaluation,
+            DependencyKind::TrainingData => proto::DependencyKind::TrainingData,
+            DependencyKind::Optional => proto::DependencyKind::Optional,
+        }
+    }
+}
+
+/// Convert i32 to DependencyKind (helper function to avoid orphan rule violations)
+pub fn dependency_kind_from_i32(value: i32) -> Result<DependencyKind, ApiError> {
+    match proto::DependencyKind::try_from(value) {
+        Ok(proto::DependencyKind::Unspecified) | Ok(proto::DependencyKind::Runtime) => {
+            Ok(DependencyKind::Runtime)
+        }
+        Ok(proto::DependencyKind::Build) => Ok(DependencyKind::Build),
+        Ok(proto::DependencyKind::Evaluation) => Ok(DependencyKind::Evaluation),
+        Ok(proto::DependencyKind::TrainingData) => Ok(DependencyKind::TrainingData),
+        Ok(proto::DependencyKind::Optional) => Ok(DependencyKind::Optional),
+        Err(_) => Err(ApiError::bad_request("Invalid dependency kind")),
+    }
+}
+
+/// Convert i32 to DeleteMode (helper function to avoid orphan rule violations)
+pub fn delete_mode_from_i32(value: i32) -> Result<DeleteMode, ApiError> {
+    match proto::DeleteMode::try_from(value) {
+        Ok(proto::DeleteMode::Unspecified) | Ok(proto::DeleteMode::Refuse) => {
+            Ok(DeleteMode::Refuse)
+        }
+        Ok(proto::DeleteMode::Force) => Ok(DeleteMode::Force),
+        Ok(proto::DeleteMode::Cascade) => Ok(DeleteMode::Cascade),
+        Err(_) => Err(ApiError::bad_request("Invalid delete mode")),
+    }
+}
+
+/// Convert i32 to Facet (helper function to avoid orphan rule violations)
+pub fn facet_from_i32(value: i32) -> Result<Facet, ApiError> {
+    match proto::Facet::try_from(value) {
+        Ok(proto::Facet::AssetType) => Ok(Facet::AssetType),
+        Ok(proto::Facet::Tag) => Ok(Facet::Tag),
+        Ok(proto::Facet::License) => Ok(Facet::License),
+        Ok(proto::Facet::Status) => Ok(Facet::Status),
+        Ok(proto::Facet::Author) => Ok(Facet::Author),
+        Ok(proto::Facet::StorageBackend) => Ok(Facet::StorageBackend),
+        Ok(proto::Facet::Unspecified) | Err(_) => Err(ApiError::bad_request("Invalid facet")),
+    }
+}
+
+/// Convert facet counts to proto (helper function to avoid orphan rule violations)
+pub fn facet_counts_to_proto(facets: FacetCounts) -> Vec<proto::FacetCounts> {
+    facets
+        .into_iter()
+        .map(|(facet, counts)| proto::FacetCounts {
+            facet: proto::Facet::from(facet) as i32,
+            counts: counts
+                .into_iter()
+                .map(|count| proto::FacetCount {
+                    value: count.value,
+                    count: count.count,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 // ============================================================================
 // Complex Type Conversions
 // ============================================================================
@@ -158,6 +257,7 @@ impl From<Asset> for proto::Asset {
             created_at: asset.created_at.to_rfc3339(),
             updated_at: asset.updated_at.to_rfc3339(),
             deprecated_at: asset.deprecated_at.map(|dt| dt.to_rfc3339()),
+            revision: asset.revision,
         }
     }
 }
@@ -334,18 +434,30 @@ impl TryFrom<proto::Provenance> for Provenance {
 /// Convert domain AssetReference to proto
 impl From<AssetReference> for proto::AssetReference {
     fn from(ref_: AssetReference) -> Self {
+        let kind = proto::DependencyKind::from(ref_.kind());
         let reference = match ref_ {
-            AssetReference::ById { id } => proto::asset_reference::Reference::Id(id.to_string()),
-            AssetReference::ByNameVersion { name, version } => {
+            AssetReference::ById { id, .. } => {
+                proto::asset_reference::Reference::Id(id.to_string())
+            }
+            AssetReference::ByNameVersion { name, version, .. } => {
                 proto::asset_reference::Reference::NameVersion(proto::NameVersion {
                     name,
                     version: version.to_string(),
                 })
             }
+            AssetReference::ByRequirement {
+                name, requirement, ..
+            } => {
+                proto::asset_reference::Reference::NameRequirement(proto::NameRequirement {
+                    name,
+                    requirement: requirement.to_string(),
+                })
+            }
         };
 
         proto::AssetReference {
             reference: Some(reference),
+            kind: kind as i32,
         }
     }
 }
@@ -355,12 +467,13 @@ impl TryFrom<proto::AssetReference> for AssetReference {
     type Error = ApiError;
 
     fn try_from(proto: proto::AssetReference) -> Result<Self, Self::Error> {
+        let kind = dependency_kind_from_i32(proto.kind)?;
         match proto.reference {
             Some(proto::asset_reference::Reference::Id(id)) => {
                 let asset_id = id
                     .parse::<AssetId>()
                     .map_err(|e| ApiError::bad_request(format!("Invalid asset ID: {}", e)))?;
-                Ok(AssetReference::ById { id: asset_id })
+                Ok(AssetReference::ById { id: asset_id, kind })
             }
             Some(proto::asset_reference::Reference::NameVersion(nv)) => {
                 // Validate version format
@@ -369,6 +482,17 @@ impl TryFrom<proto::AssetReference> for AssetReference {
                 Ok(AssetReference::ByNameVersion {
                     name: nv.name,
                     version: nv.version,
+                    kind,
+                })
+            }
+            Some(proto::asset_reference::Reference::NameRequirement(nr)) => {
+                let requirement = VersionReq::parse(&nr.requirement).map_err(|e| {
+                    ApiError::bad_request(format!("Invalid version requirement: {}", e))
+                })?;
+                Ok(AssetReference::ByRequirement {
+                    name: nr.name,
+                    requirement,
+                    kind,
                 })
             }
             None => Err(ApiError::bad_request("Asset reference must be specified")),
@@ -376,7 +500,91 @@ impl TryFrom<proto::AssetReference> for AssetReference {
     }
 }
 
+/// Convert ResolvedDependency to proto
+impl From<ResolvedDependency> for proto::ResolvedDependency {
+    fn from(resolved: ResolvedDependency) -> Self {
+        proto::ResolvedDependency {
+            name: resolved.name,
+            requirement: resolved.requirement.to_string(),
+            asset_id: resolved.asset_id.to_string(),
+            version: resolved.version.to_string(),
+        }
+    }
+}
+
 /// Convert domain DependencyNode to proto
+impl TryFrom<proto::RegisterAssetRequest> for RegisterAssetRequest {
+    type Error = ApiError;
+
+    fn try_from(req: proto::RegisterAssetRequest) -> Result<Self, Self::Error> {
+        let asset_type = asset_type_from_i32(req.asset_type)?;
+        let version = parse_version(&req.version)?;
+
+        let storage = req
+            .storage
+            .ok_or_else(|| ApiError::bad_request("Storage location is required"))?
+            .try_into()?;
+
+        let checksum = req
+            .checksum
+            .ok_or_else(|| ApiError::bad_request("Checksum is required"))?
+            .try_into()?;
+
+        let provenance = req.provenance.map(|p| p.try_into()).transpose()?;
+
+        let dependencies = req
+            .dependencies
+            .into_iter()
+            .map(|d| d.try_into())
+            .collect::<Result<Vec<AssetReference>, ApiError>>()?;
+
+        Ok(RegisterAssetRequest {
+            asset_type,
+            name: req.name,
+            version,
+            description: req.description,
+            license: req.license,
+            tags: req.tags,
+            annotations: req.annotations,
+            storage,
+            checksum,
+            provenance,
+            dependencies,
+            size_bytes: req.size_bytes,
+            content_type: req.content_type,
+        })
+    }
+}
+
+impl From<BatchItemResult> for proto::BatchRegisterResult {
+    fn from(result: BatchItemResult) -> Self {
+        Self {
+            index: result.index as u32,
+            name: result.name,
+            version: result.version.to_string(),
+            success: result.asset.is_some(),
+            asset: result.asset.map(|a| a.into()),
+            error: result.error,
+            warnings: result.warnings,
+        }
+    }
+}
+
+impl From<WatchedEvent> for proto::AssetEvent {
+    fn from(watched: WatchedEvent) -> Self {
+        let event = watched.event;
+        Self {
+            event_type: proto::asset_event::EventType::from(&event.event_type) as i32,
+            asset: watched.asset.map(|a| a.into()),
+            timestamp: event.timestamp.to_rfc3339(),
+            event_id: watched.event_id,
+            event_name: event.event_type.event_name().to_string(),
+            asset_id: event.event_type.asset_id().map(|id| id.to_string()),
+            actor: event.actor,
+        }
+    }
+}
+
 impl From<DependencyNode> for proto::DependencyNode {
     fn from(node: DependencyNode) -> Self {
         proto::DependencyNode {
@@ -4,25 +4,91 @@
 
 use super::converters::*;
 use super::proto::{self, registry_service_server::RegistryService};
-use crate::error::ApiError;
-use llm_registry_core::{AssetId, AssetReference};
+use crate::{
+    auth::{AuthError, AuthState, AuthUser, API_KEY_HEADER},
+    authorization::{Authorizer, Operation},
+    error::ApiError,
+};
+use http::header::AUTHORIZATION;
+use llm_registry_core::AssetId;
+use futures::TryStreamExt;
 use llm_registry_service::{
-    GetDependencyGraphRequest, RegisterAssetRequest, SearchAssetsRequest, ServiceRegistry,
-    UpdateAssetRequest,
+    BatchRegisterRequest, DeleteMode, DependencyDirection, GetDependencyGraphRequest,
+    RegisterAssetRequest, SearchAssetsRequest, ServiceError, ServiceRegistry, UpdateAssetRequest,
+    WatchAssetsRequest, MAX_BATCH_SIZE,
 };
 use std::sync::Arc;
-use tonic::{Request, Response, Status};
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+
+/// Request metadata key that makes `BatchRegister` all-or-nothing
+pub const BATCH_ATOMIC_METADATA: &str = "x-batch-atomic";
 
 /// gRPC service implementation
 #[derive(Clone)]
 pub struct RegistryServiceImpl {
     services: Arc<ServiceRegistry>,
+    auth_state: AuthState,
+    authorizer: Arc<Authorizer>,
 }
 
 impl RegistryServiceImpl {
     /// Create a new gRPC service instance
-    pub fn new(services: Arc<ServiceRegistry>) -> Self {
-        Self { services }
+    ///
+    /// Callers authenticate with bearer tokens or API keys, and every RPC is
+    /// checked against the authorizer. Callers without credentials only get
+    /// the authorizer's anonymous roles.
+    pub fn new(
+        services: Arc<ServiceRegistry>,
+        auth_state: AuthState,
+        authorizer: Arc<Authorizer>,
+    ) -> Self {
+        Self {
+            services,
+            auth_state,
+            authorizer,
+        }
+    }
+
+    /// Check that the caller identified by the request metadata may perform an operation
+    ///
+    /// When `asset_id` names an asset, its ACL entries are evaluated too.
+    /// Returns the authenticated user, if any.
+    async fn authorize(
+        &self,
+        metadata: &MetadataMap,
+        operation: Operation,
+        asset_id: Option<&str>,
+    ) -> Result<Option<AuthUser>, Status> {
+        let auth_state = &self.auth_state;
+
+        // An API key takes precedence over a bearer token, as in `require_auth`
+        let header = |key: &str| metadata.get(key).map(|value| value.to_str());
+        let user = match (header(API_KEY_HEADER), header(AUTHORIZATION.as_str())) {
+            (Some(Ok(api_key)), _) => auth_state.authenticate_api_key(api_key).await.map(Some),
+            (None, Some(Ok(bearer))) => auth_state.authenticate(bearer).await.map(Some),
+            (None, None) => Ok(None),
+            _ => Err(AuthError::InvalidToken),
+        }
+        .map_err(|e| Status::unauthenticated(e.to_string()))?;
+
+        let result = match asset_id.and_then(|id| id.parse::<AssetId>().ok()) {
+            Some(asset_id) => {
+                self.authorizer
+                    .authorize_asset(user.as_ref(), operation, &asset_id)
+                    .await
+            }
+            None => self.authorizer.authorize(user.as_ref(), operation).await,
+        };
+
+        result.map(|_| user).map_err(auth_status)
+    }
+}
+
+/// Convert an authorization failure into a gRPC status
+fn auth_status(e: AuthError) -> Status {
+    match e {
+        AuthError::InsufficientPermissions => Status::permission_denied(e.to_string()),
+        _ => Status::unauthenticated(e.to_string()),
     }
 }
 
@@ -37,56 +103,11 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::RegisterAssetRequest>,
     ) -> Result<Response<proto::RegisterAssetResponse>, Status> {
-        let req = request.into_inner();
-
-        // Convert proto request to domain request
-        let asset_type = asset_type_from_i32(req.asset_type)
-            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        self.authorize(request.metadata(), Operation::RegisterAsset, None).await?;
 
-        let version = parse_version(&req.version)
+        let domain_request = RegisterAssetRequest::try_from(request.into_inner())
             .map_err(|e| Status::invalid_argument(e.to_string()))?;
 
-        let storage = req
-            .storage
-            .ok_or_else(|| Status::invalid_argument("Storage location is required"))?
-            .try_into()
-            .map_err(|e: ApiError| Status::invalid_argument(e.to_string()))?;
-
-        let checksum = req
-            .checksum
-            .ok_or_else(|| Status::invalid_argument("Checksum is required"))?
-            .try_into()
-            .map_err(|e: ApiError| Status::invalid_argument(e.to_string()))?;
-
-        let provenance = req
-            .provenance
-            .map(|p| p.try_into())
-            .transpose()
-            .map_err(|e: ApiError| Status::invalid_argument(e.to_string()))?;
-
-        let dependencies: Result<Vec<AssetReference>, ApiError> = req
-            .dependencies
-            .into_iter()
-            .map(|d| d.try_into())
-            .collect();
-        let dependencies = dependencies.map_err(|e| Status::invalid_argument(e.to_string()))?;
-
-        let domain_request = RegisterAssetRequest {
-            asset_type,
-            name: req.name,
-            version,
-            description: req.description,
-            license: req.license,
-            tags: req.tags,
-            annotations: req.annotations,
-            storage,
-            checksum,
-            provenance,
-            dependencies,
-            size_bytes: req.size_bytes,
-            content_type: req.content_type,
-        };
-
         // Execute registration
         let response = self
             .services
@@ -98,6 +119,11 @@ impl RegistryService for RegistryServiceImpl {
         Ok(Response::new(proto::RegisterAssetResponse {
             asset: Some(response.asset.into()),
             warnings: response.warnings,
+            resolved_dependencies: response
+                .resolved_dependencies
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }))
     }
 
@@ -106,6 +132,13 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::GetAssetRequest>,
     ) -> Result<Response<proto::GetAssetResponse>, Status> {
+        self.authorize(
+            request.metadata(),
+            Operation::GetAsset,
+            Some(&request.get_ref().id),
+        )
+        .await?;
+
         let req = request.into_inner();
 
         let asset_id = req
@@ -130,6 +163,10 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::SearchAssetsRequest>,
     ) -> Result<Response<proto::SearchAssetsResponse>, Status> {
+        let user = self
+            .authorize(request.metadata(), Operation::SearchAssets, None)
+            .await?;
+
         let req = request.into_inner();
 
         let asset_types: Result<Vec<_>, ApiError> = req
@@ -145,6 +182,9 @@ impl RegistryService for RegistryServiceImpl {
         let sort_order = sort_order_from_i32(req.sort_order)
             .map_err(|e| Status::invalid_argument(e.to_string()))?;
 
+        let facets: Result<Vec<_>, ApiError> = req.facets.into_iter().map(facet_from_i32).collect();
+        let facets = facets.map_err(|e| Status::invalid_argument(e.to_string()))?;
+
         let search_request = SearchAssetsRequest {
             text: req.text,
             asset_types,
@@ -154,23 +194,35 @@ impl RegistryService for RegistryServiceImpl {
             exclude_deprecated: req.exclude_deprecated,
             limit: req.limit,
             offset: req.offset,
+            cursor: req.cursor,
             sort_by,
             sort_order,
+            facets,
+            ..Default::default()
         };
 
+        // Assets the caller cannot read are left out of the search
         let response = self
-            .services
-            .search()
-            .search_assets(search_request)
+            .authorizer
+            .search_readable(
+                user.as_ref(),
+                self.services.search().as_ref(),
+                search_request,
+            )
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(|e| match e {
+                ServiceError::InvalidInput(msg) => Status::invalid_argument(msg),
+                e => Status::internal(e.to_string()),
+            })?;
 
         Ok(Response::new(proto::SearchAssetsResponse {
             assets: response.assets.into_iter().map(|a| a.into()).collect(),
             total: response.total,
             offset: response.offset,
             limit: response.limit,
-            has_more: (response.offset + response.limit) < response.total,
+            has_more: response.has_more,
+            next_cursor: response.next_cursor,
+            facets: facet_counts_to_proto(response.facets),
         }))
     }
 
@@ -179,6 +231,13 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::UpdateAssetRequest>,
     ) -> Result<Response<proto::UpdateAssetResponse>, Status> {
+        self.authorize(
+            request.metadata(),
+            Operation::UpdateAsset,
+            Some(&request.get_ref().asset_id),
+        )
+        .await?;
+
         let req = request.into_inner();
 
         let asset_id = req
@@ -201,6 +260,7 @@ impl RegistryService for RegistryServiceImpl {
             remove_tags: req.remove_tags,
             add_annotations: req.add_annotations,
             remove_annotations: req.remove_annotations,
+            expected_revision: req.expected_revision,
         };
 
         let response = self
@@ -208,7 +268,12 @@ impl RegistryService for RegistryServiceImpl {
             .registration()
             .update_asset(update_request)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(|e| match e {
+                ServiceError::RevisionConflict { .. } | ServiceError::PreconditionFailed { .. } => {
+                    Status::aborted(e.to_string())
+                }
+                e => Status::internal(e.to_string()),
+            })?;
 
         Ok(Response::new(proto::UpdateAssetResponse {
             asset: Some(response.asset.into()),
@@ -221,6 +286,14 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::DeleteAssetRequest>,
     ) -> Result<Response<proto::DeleteAssetResponse>, Status> {
+        let user = self
+            .authorize(
+                request.metadata(),
+                Operation::DeleteAsset,
+                Some(&request.get_ref().asset_id),
+            )
+            .await?;
+
         let req = request.into_inner();
 
         let asset_id = req
@@ -228,15 +301,54 @@ impl RegistryService for RegistryServiceImpl {
             .parse::<AssetId>()
             .map_err(|e| Status::invalid_argument(format!("Invalid asset ID: {}", e)))?;
 
-        self.services
-            .registration()
-            .delete_asset(&asset_id)
+        let mode = delete_mode_from_i32(req.mode)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        // Forcing or cascading changes the dependents, so the caller must be
+        // allowed to update all of them before anything is deleted
+        let registration = self.services.registration();
+        if mode != DeleteMode::Refuse {
+            let dependents = registration
+                .blocking_dependents(&asset_id, mode)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+            self.authorizer
+                .authorize_dependents(user.as_ref(), &dependents)
+                .await
+                .map_err(auth_status)?;
+        }
+
+        let response = match registration
+            .delete_asset(&asset_id, req.expected_revision, mode)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        {
+            Ok(response) => response,
+            Err(e) => {
+                let e = self.authorizer.redact_dependents(user.as_ref(), e).await;
+                return Err(match e {
+                    ServiceError::RevisionConflict { .. }
+                    | ServiceError::PreconditionFailed { .. } => Status::aborted(e.to_string()),
+                    ServiceError::HasDependents { .. } => {
+                        Status::failed_precondition(e.to_string())
+                    }
+                    e => Status::internal(e.to_string()),
+                });
+            }
+        };
 
         Ok(Response::new(proto::DeleteAssetResponse {
             asset_id: req.asset_id,
             message: "Asset deleted successfully".to_string(),
+            updated_dependents: response
+                .updated_dependents
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            failed_dependents: response
+                .failed_dependents
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
         }))
     }
 
@@ -245,6 +357,14 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::GetDependenciesRequest>,
     ) -> Result<Response<proto::GetDependenciesResponse>, Status> {
+        let user = self
+            .authorize(
+                request.metadata(),
+                Operation::GetDependencies,
+                Some(&request.get_ref().asset_id),
+            )
+            .await?;
+
         let req = request.into_inner();
 
         let asset_id = req
@@ -252,9 +372,21 @@ impl RegistryService for RegistryServiceImpl {
             .parse::<AssetId>()
             .map_err(|e| Status::invalid_argument(format!("Invalid asset ID: {}", e)))?;
 
+        // The root itself may be hidden from callers allowed to read graphs
+        self.authorizer
+            .authorize_asset(user.as_ref(), Operation::GetAsset, &asset_id)
+            .await
+            .map_err(auth_status)?;
+
+        let kinds: Result<Vec<_>, ApiError> =
+            req.kinds.into_iter().map(dependency_kind_from_i32).collect();
+        let kinds = kinds.map_err(|e| Status::invalid_argument(e.to_string()))?;
+
         let graph_request = GetDependencyGraphRequest {
             asset_id,
             max_depth: req.max_depth,
+            direction: DependencyDirection::Dependencies,
+            kinds,
         };
 
         let response = self
@@ -263,6 +395,10 @@ impl RegistryService for RegistryServiceImpl {
             .get_dependency_graph(graph_request)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
+        let response = self
+            .authorizer
+            .filter_dependency_graph(user.as_ref(), response)
+            .await;
 
         Ok(Response::new(proto::GetDependenciesResponse {
             dependencies: response
@@ -278,6 +414,14 @@ impl RegistryService for RegistryServiceImpl {
         &self,
         request: Request<proto::GetDependentsRequest>,
     ) -> Result<Response<proto::GetDependentsResponse>, Status> {
+        let user = self
+            .authorize(
+                request.metadata(),
+                Operation::GetDependents,
+                Some(&request.get_ref().asset_id),
+            )
+            .await?;
+
         let req = request.into_inner();
 
         let asset_id = req
@@ -285,6 +429,12 @@ impl RegistryService for RegistryServiceImpl {
             .parse::<AssetId>()
             .map_err(|e| Status::invalid_argument(format!("Invalid asset ID: {}", e)))?;
 
+        // The root itself may be hidden from callers allowed to read graphs
+        self.authorizer
+            .authorize_asset(user.as_ref(), Operation::GetAsset, &asset_id)
+            .await
+            .map_err(auth_status)?;
+
         let dependents = self
             .services
             .search()
@@ -292,6 +442,8 @@ impl RegistryService for RegistryServiceImpl {
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
+        let dependents = self.authorizer.filter_readable(user.as_ref(), dependents).await;
+
         Ok(Response::new(proto::GetDependentsResponse {
             dependents: dependents.into_iter().map(|a| a.into()).collect(),
         }))
@@ -300,8 +452,10 @@ impl RegistryService for RegistryServiceImpl {
     /// List all unique tags
     async fn list_tags(
         &self,
-        _request: Request<proto::ListTagsRequest>,
+        request: Request<proto::ListTagsRequest>,
     ) -> Result<Response<proto::ListTagsResponse>, Status> {
+        self.authorize(request.metadata(), Operation::ListTags, None).await?;
+
         let tags = self
             .services
             .search()
@@ -351,26 +505,160 @@ impl RegistryService for RegistryServiceImpl {
     }
 
     /// Watch assets (server streaming)
+    ///
+    /// Streams registry events as they are appended. Clients that reconnect
+    /// with `resume_after` set to the last `event_id` they received first get
+    /// every event they missed.
     async fn watch_assets(
         &self,
-        _request: Request<proto::WatchAssetsRequest>,
+        request: Request<proto::WatchAssetsRequest>,
     ) -> Result<Response<Self::WatchAssetsStream>, Status> {
-        // This would be implemented with actual event streaming
-        // For now, return unimplemented
-        Err(Status::unimplemented(
-            "Asset watching not yet implemented - requires event streaming infrastructure",
-        ))
+        let user = self
+            .authorize(request.metadata(), Operation::WatchAssets, None)
+            .await?;
+
+        let req = request.into_inner();
+
+        let watch = self
+            .services
+            .watch()
+            .ok_or_else(|| Status::unimplemented("Asset watching is not configured"))?;
+
+        let asset_types: Result<Vec<_>, ApiError> = req
+            .asset_types
+            .into_iter()
+            .map(asset_type_from_i32)
+            .collect();
+        let asset_types = asset_types.map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let asset_ids = req
+            .asset_ids
+            .iter()
+            .map(|id| id.parse::<AssetId>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid asset ID: {}", e)))?;
+
+        let watch_request = WatchAssetsRequest {
+            asset_ids,
+            asset_names: req.asset_names,
+            asset_types,
+            tags: req.tags,
+            event_types: req.event_types,
+            resume_after: req.resume_after,
+        };
+
+        let events = watch.watch_assets(watch_request).await.map_err(|e| match e {
+            ServiceError::InvalidInput(msg) => Status::invalid_argument(msg),
+            e => Status::internal(e.to_string()),
+        })?;
+
+        let stream = self
+            .authorizer
+            .filter_events(user, events)
+            .map_ok(proto::AssetEvent::from)
+            .map_err(|e| Status::unavailable(e.to_string()));
+
+        Ok(Response::new(Box::pin(stream)))
     }
 
     /// Batch register assets (client streaming)
+    ///
+    /// Set the `x-batch-atomic: true` metadata entry to roll back every
+    /// registration if any item fails.
     async fn batch_register(
         &self,
-        _request: Request<tonic::Streaming<proto::RegisterAssetRequest>>,
+        request: Request<tonic::Streaming<proto::RegisterAssetRequest>>,
     ) -> Result<Response<proto::BatchRegisterResponse>, Status> {
-        // This would be implemented with actual batch processing
-        // For now, return unimplemented
-        Err(Status::unimplemented(
-            "Batch registration not yet implemented - use individual registration",
-        ))
+        self.authorize(request.metadata(), Operation::BatchRegister, None).await?;
+
+        let atomic = request
+            .metadata()
+            .get(BATCH_ATOMIC_METADATA)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("true") || v == "1");
+
+        let mut stream = request.into_inner();
+
+        // Convert every item up front; malformed items fail on their own
+        let mut results: Vec<Option<proto::BatchRegisterResult>> = Vec::new();
+        let mut positions = Vec::new();
+        let mut items = Vec::new();
+        while let Some(message) = stream.message().await? {
+            if results.len() >= MAX_BATCH_SIZE {
+                return Err(Status::invalid_argument(format!(
+                    "Batch exceeds the limit of {} items",
+                    MAX_BATCH_SIZE
+                )));
+            }
+
+            let index = results.len();
+            let (name, version) = (message.name.clone(), message.version.clone());
+            match RegisterAssetRequest::try_from(message) {
+                Ok(item) => {
+                    positions.push(index);
+                    items.push(item);
+                    results.push(None);
+                }
+                Err(e) => results.push(Some(proto::BatchRegisterResult {
+                    index: index as u32,
+                    name,
+                    version,
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                })),
+            }
+        }
+
+        let malformed = results.iter().any(Option::is_some);
+        let rolled_back = if atomic && malformed {
+            // Nothing from an atomic batch is registered if any item is malformed
+            for (&index, item) in positions.iter().zip(items) {
+                results[index] = Some(proto::BatchRegisterResult {
+                    index: index as u32,
+                    name: item.name,
+                    version: item.version.to_string(),
+                    error: Some("Not attempted: another item of the atomic batch failed".to_string()),
+                    ..Default::default()
+                });
+            }
+            true
+        } else {
+            let response = self
+                .services
+                .registration()
+                .register_batch(BatchRegisterRequest { items, atomic })
+                .await
+                .map_err(|e| match e {
+                    ServiceError::InvalidInput(msg) => Status::invalid_argument(msg),
+                    e => Status::internal(e.to_string()),
+                })?;
+
+            for result in response.results {
+                let index = positions[result.index];
+                let mut result = proto::BatchRegisterResult::from(result);
+                result.index = index as u32;
+                results[index] = Some(result);
+            }
+            response.rolled_back
+        };
+
+        let results: Vec<proto::BatchRegisterResult> = results.into_iter().flatten().collect();
+        let total_registered = results.iter().filter(|r| r.success).count() as u32;
+        let error_messages = results
+            .iter()
+            .filter_map(|r| {
+                r.error
+                    .as_ref()
+                    .map(|e| format!("item {} ({}@{}): {}", r.index, r.name, r.version, e))
+            })
+            .collect();
+
+        Ok(Response::new(proto::BatchRegisterResponse {
+            total_registered,
+            total_failed: results.len() as u32 - total_registered,
+            error_messages,
+            results,
+            rolled_back,
+        }))
     }
 }
@@ -3,21 +3,35 @@
 //! This module implements HTTP request handlers for all API endpoints.
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    body::Body,
+    extract::{Extension, FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
-use llm_registry_core::AssetId;
+use futures::{stream, StreamExt};
+use llm_registry_core::{AssetId, Checksum, DependencyKind, HashAlgorithm};
 use llm_registry_service::{
-    GetDependencyGraphRequest, RegisterAssetRequest, SearchAssetsRequest, ServiceRegistry,
-    UpdateAssetRequest,
+    AclEffect, AclEntry, AclStore, AclSubject, AclTarget, ByteRangeRequest, ContentService,
+    ContentStream, CreateUploadSessionRequest, CreateUserRequest, DeleteAssetResponse,
+    DeleteMode, DependencyDirection, DownloadContentRequest, GetDependencyGraphRequest, Lockfile,
+    LockfileService, LockfileVerification, RegisterAssetRequest, SearchAssetsRequest, ServiceError,
+    ServiceRegistry, ServiceResult, UpdateAssetRequest, UploadChunk,
+    UploadContentResponse, UploadSessionResponse, UploadSessionService, User, UserService,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tracing::{debug, info, instrument};
 
 use crate::{
+    auth::AuthUser,
+    auth_handlers::revocation_error,
+    authorization::{Authorizer, Operation},
     error::{ApiError, ApiResult},
+    jwt::JwtManager,
+    rate_limit::RateLimiterState,
+    rbac::Permission,
     responses::{
         created, deleted, ok, ApiResponse, ComponentHealth, HealthResponse,
         PaginatedResponse,
@@ -29,6 +43,12 @@ use crate::{
 pub struct AppState {
     /// Service registry
     pub services: Arc<ServiceRegistry>,
+    /// Permission enforcement for authenticated routers
+    pub authorizer: Arc<Authorizer>,
+    /// Rate limiting for authenticated routers, if enabled
+    pub rate_limiter: Option<RateLimiterState>,
+    /// Revokes the sessions of disabled users
+    pub sessions: Option<Arc<JwtManager>>,
 }
 
 impl AppState {
@@ -36,8 +56,29 @@ impl AppState {
     pub fn new(services: ServiceRegistry) -> Self {
         Self {
             services: Arc::new(services),
+            authorizer: Arc::new(Authorizer::default()),
+            rate_limiter: None,
+            sessions: None,
         }
     }
+
+    /// Use a custom authorizer
+    pub fn with_authorizer(mut self, authorizer: Arc<Authorizer>) -> Self {
+        self.authorizer = authorizer;
+        self
+    }
+
+    /// Rate limit requests, by principal tier once the caller is authenticated
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiterState) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Revoke every session of a user when the user is disabled
+    pub fn with_session_revocation(mut self, jwt_manager: Arc<JwtManager>) -> Self {
+        self.sessions = Some(jwt_manager);
+        self
+    }
 }
 
 // ============================================================================
@@ -66,11 +107,13 @@ pub async fn register_asset(
 }
 
 /// Get asset by ID
+///
+/// The asset's revision is returned as the `ETag`.
 #[instrument(skip(state))]
 pub async fn get_asset(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> ApiResult<Json<ApiResponse<llm_registry_core::Asset>>> {
+) -> ApiResult<Response> {
     debug!("Getting asset: {}", id);
 
     let asset_id = id.parse::<AssetId>().map_err(|e| {
@@ -85,39 +128,53 @@ pub async fn get_asset(
         .map_err(ApiError::from)?
         .ok_or_else(|| ApiError::not_found(format!("Asset not found: {}", id)))?;
 
-    Ok(Json(ok(asset)))
+    let etag = revision_etag(asset.revision);
+    Ok(([(header::ETAG, etag)], Json(ok(asset))).into_response())
 }
 
 /// List/search assets with pagination
 #[instrument(skip(state))]
 pub async fn list_assets(
     State(state): State<AppState>,
+    user: Option<Extension<AuthUser>>,
     Query(params): Query<SearchAssetsRequest>,
 ) -> ApiResult<Json<PaginatedResponse<llm_registry_core::Asset>>> {
     debug!("Searching assets with filters: {:?}", params);
 
+    // Assets the caller cannot read are left out of the search
     let response = state
-        .services
-        .search()
-        .search_assets(params)
+        .authorizer
+        .search_readable(
+            user.as_ref().map(|Extension(user)| user),
+            state.services.search().as_ref(),
+            params,
+        )
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(PaginatedResponse::new(
-        response.assets,
-        response.total,
-        response.offset,
-        response.limit,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(
+            response.assets,
+            response.total,
+            response.offset,
+            response.limit,
+        )
+        .with_next_cursor(response.next_cursor)
+        .with_facets(response.facets),
+    ))
 }
 
 /// Update asset metadata
-#[instrument(skip(state))]
+///
+/// An `If-Match` header takes precedence over `expected_revision` in the
+/// body. The new revision is returned as the `ETag`.
+#[instrument(skip(state, headers))]
 pub async fn update_asset(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(mut request): Json<UpdateAssetRequest>,
-) -> ApiResult<Json<ApiResponse<llm_registry_service::UpdateAssetResponse>>> {
+) -> ApiResult<Response> {
     info!("Updating asset: {}", id);
 
     let asset_id = id.parse::<AssetId>().map_err(|e| {
@@ -126,6 +183,9 @@ pub async fn update_asset(
 
     // Set asset ID from path
     request.asset_id = asset_id;
+    if let Some(revision) = if_match_revision(&headers)? {
+        request.expected_revision = Some(revision);
+    }
 
     let response = state
         .services
@@ -134,40 +194,126 @@ pub async fn update_asset(
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(ok(response)))
+    let etag = revision_etag(response.asset.revision);
+    Ok(([(header::ETAG, etag)], Json(ok(response))).into_response())
 }
 
 /// Delete asset
-#[instrument(skip(state))]
+///
+/// Honours `If-Match` with the asset's revision. `mode` decides what happens
+/// to assets that still depend on it.
+#[instrument(skip(state, headers))]
 pub async fn delete_asset(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> ApiResult<(StatusCode, Json<crate::responses::EmptyResponse>)> {
+    user: Option<Extension<AuthUser>>,
+    Query(params): Query<DeleteAssetParams>,
+    headers: HeaderMap,
+) -> ApiResult<Json<ApiResponse<DeleteAssetResponse>>> {
     info!("Deleting asset: {}", id);
 
     let asset_id = id.parse::<AssetId>().map_err(|e| {
         ApiError::bad_request(format!("Invalid asset ID: {}", e))
     })?;
+    let user = user.as_ref().map(|Extension(user)| user);
+    let registration = state.services.registration();
 
-    state
-        .services
-        .registration()
-        .delete_asset(&asset_id)
+    // Forcing or cascading changes the dependents, so the caller must be
+    // allowed to update all of them before anything is deleted
+    if params.mode != DeleteMode::Refuse {
+        let dependents = registration
+            .blocking_dependents(&asset_id, params.mode)
+            .await
+            .map_err(ApiError::from)?;
+        state
+            .authorizer
+            .authorize_dependents(user, &dependents)
+            .await
+            .map_err(|_| {
+                ApiError::forbidden("Insufficient permissions to update every dependent")
+            })?;
+    }
+
+    let response = match registration
+        .delete_asset(&asset_id, if_match_revision(&headers)?, params.mode)
         .await
-        .map_err(ApiError::from)?;
+    {
+        Ok(response) => response,
+        Err(e) => return Err(state.authorizer.redact_dependents(user, e).await.into()),
+    };
 
-    Ok(deleted())
+    Ok(Json(ok(response)))
+}
+
+/// Query parameters for deleting an asset
+#[derive(Debug, Deserialize)]
+pub struct DeleteAssetParams {
+    /// `refuse` (default), `force` or `cascade`
+    #[serde(default)]
+    pub mode: DeleteMode,
+}
+
+/// Format an asset revision as an entity tag
+pub fn revision_etag(revision: u64) -> String {
+    format!("\"{}\"", revision)
+}
+
+/// Read the revision required by an `If-Match` header
+///
+/// A missing header and `*` impose no revision. Weak tags and lists of
+/// several tags are rejected, since only one revision can be current.
+fn if_match_revision(headers: &HeaderMap) -> ApiResult<Option<u64>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .and_then(|v| v.parse().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "If-Match must be \"*\" or a single asset revision tag, got {}",
+                value
+            ))
+        })
 }
 
 // ============================================================================
 // Dependency Handlers
 // ============================================================================
 
+/// Check that the caller may read the asset a response is built around
+///
+/// Dependency and lockfile routes only require their own permission, so an
+/// ACL hiding the asset itself is checked here.
+async fn authorize_root(
+    state: &AppState,
+    user: Option<&Extension<AuthUser>>,
+    asset_id: &AssetId,
+) -> ApiResult<()> {
+    state
+        .authorizer
+        .authorize_asset(
+            user.map(|Extension(user)| user),
+            Operation::GetAsset,
+            asset_id,
+        )
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to read asset"))
+}
+
 /// Get dependency graph for an asset
 #[instrument(skip(state))]
 pub async fn get_dependencies(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    user: Option<Extension<AuthUser>>,
     Query(params): Query<DependencyGraphParams>,
 ) -> ApiResult<Json<ApiResponse<llm_registry_service::DependencyGraphResponse>>> {
     debug!("Getting dependency graph for asset: {}", id);
@@ -175,10 +321,23 @@ pub async fn get_dependencies(
     let asset_id = id.parse::<AssetId>().map_err(|e| {
         ApiError::bad_request(format!("Invalid asset ID: {}", e))
     })?;
+    authorize_root(&state, user.as_ref(), &asset_id).await?;
+
+    let kinds = params
+        .kind
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+        .map(|kind| kind.parse::<DependencyKind>().map_err(ApiError::bad_request))
+        .collect::<ApiResult<Vec<_>>>()?;
 
     let request = GetDependencyGraphRequest {
         asset_id,
         max_depth: params.max_depth.unwrap_or(-1),
+        direction: params.direction.unwrap_or_default(),
+        kinds,
     };
 
     let response = state
@@ -188,14 +347,25 @@ pub async fn get_dependencies(
         .await
         .map_err(ApiError::from)?;
 
+    let response = state
+        .authorizer
+        .filter_dependency_graph(user.as_ref().map(|Extension(user)| user), response)
+        .await;
+
     Ok(Json(ok(response)))
 }
 
 /// Query parameters for dependency graph
 #[derive(Debug, Deserialize)]
 pub struct DependencyGraphParams {
-    /// Maximum depth to traverse (-1 for unlimited)
+    /// Maximum depth to traverse, at most 100 (-1 for unlimited)
     pub max_depth: Option<i32>,
+
+    /// Follow `dependencies` (default) or `dependents`
+    pub direction: Option<DependencyDirection>,
+
+    /// Comma-separated dependency kinds to follow (default: all)
+    pub kind: Option<String>,
 }
 
 /// Get reverse dependencies (dependents)
@@ -203,12 +373,14 @@ pub struct DependencyGraphParams {
 pub async fn get_dependents(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    user: Option<Extension<AuthUser>>,
 ) -> ApiResult<Json<ApiResponse<Vec<llm_registry_core::Asset>>>> {
     debug!("Getting dependents for asset: {}", id);
 
     let asset_id = id.parse::<AssetId>().map_err(|e| {
         ApiError::bad_request(format!("Invalid asset ID: {}", e))
     })?;
+    authorize_root(&state, user.as_ref(), &asset_id).await?;
 
     let dependents = state
         .services
@@ -217,9 +389,707 @@ pub async fn get_dependents(
         .await
         .map_err(ApiError::from)?;
 
+    let dependents = state
+        .authorizer
+        .filter_readable(user.as_ref().map(|Extension(user)| user), dependents)
+        .await;
+
     Ok(Json(ok(dependents)))
 }
 
+// ============================================================================
+// Lockfile Handlers
+// ============================================================================
+
+/// Media type of TOML lockfiles
+const TOML_CONTENT_TYPE: &str = "application/toml";
+
+/// Generate a lockfile pinning an asset and its whole dependency closure
+///
+/// Returns the lockfile in the usual JSON envelope, or as a bare TOML
+/// document with `?format=toml`.
+#[instrument(skip(state, user))]
+pub async fn generate_lockfile(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    user: Option<Extension<AuthUser>>,
+    Query(params): Query<LockfileParams>,
+) -> ApiResult<Response> {
+    debug!("Generating lockfile for asset: {}", id);
+
+    let asset_id = id.parse::<AssetId>().map_err(|e| {
+        ApiError::bad_request(format!("Invalid asset ID: {}", e))
+    })?;
+    authorize_root(&state, user.as_ref(), &asset_id).await?;
+
+    let lockfile = lockfile_service(&state)?
+        .generate_lockfile(&asset_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    state
+        .authorizer
+        .authorize_lockfile(user.as_ref().map(|Extension(user)| user), &lockfile)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to read every locked asset"))?;
+
+    match params.format {
+        LockfileFormat::Json => Ok(Json(ok(lockfile)).into_response()),
+        LockfileFormat::Toml => {
+            let body = lockfile.to_toml().map_err(ApiError::from)?;
+            Ok(([(header::CONTENT_TYPE, TOML_CONTENT_TYPE)], body).into_response())
+        }
+    }
+}
+
+/// Query parameters for lockfile generation
+#[derive(Debug, Deserialize)]
+pub struct LockfileParams {
+    /// Encoding of the lockfile
+    #[serde(default)]
+    pub format: LockfileFormat,
+}
+
+/// Encoding of a lockfile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockfileFormat {
+    /// JSON, wrapped in the API response envelope
+    #[default]
+    Json,
+    /// Bare TOML document
+    Toml,
+}
+
+/// Verify a lockfile against the registry and report drift
+///
+/// Accepts a JSON lockfile, or a TOML one sent as `application/toml`.
+#[instrument(skip(state, user, headers, body))]
+pub async fn verify_lockfile(
+    State(state): State<AppState>,
+    user: Option<Extension<AuthUser>>,
+    headers: HeaderMap,
+    body: String,
+) -> ApiResult<Json<ApiResponse<LockfileVerification>>> {
+    let is_toml = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(TOML_CONTENT_TYPE));
+    let lockfile = if is_toml {
+        Lockfile::from_toml(&body).map_err(ApiError::from)?
+    } else {
+        serde_json::from_str::<Lockfile>(&body)
+            .map_err(|e| ApiError::bad_request(format!("Invalid lockfile: {}", e)))?
+    };
+    debug!("Verifying lockfile for asset: {}", lockfile.root);
+
+    state
+        .authorizer
+        .authorize_lockfile(user.as_ref().map(|Extension(user)| user), &lockfile)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to read every locked asset"))?;
+
+    let verification = lockfile_service(&state)?
+        .verify_lockfile(&lockfile)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ok(verification)))
+}
+
+/// Get the lockfile service or fail if it is not configured
+fn lockfile_service(state: &AppState) -> ApiResult<Arc<dyn LockfileService>> {
+    state.services.lockfiles().cloned().ok_or_else(|| {
+        ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "Lockfiles are not configured",
+            "LOCKFILES_DISABLED",
+        )
+    })
+}
+
+// ============================================================================
+// Content Handlers
+// ============================================================================
+
+/// Upload asset content
+///
+/// Accepts either a raw request body or a `multipart/form-data` body, in
+/// which case the first field is used. The content is streamed to storage
+/// and rejected if it does not match the asset's declared checksum.
+#[instrument(skip(state, request))]
+pub async fn upload_content(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    request: Request,
+) -> ApiResult<Json<ApiResponse<UploadContentResponse>>> {
+    info!("Uploading content for asset: {}", id);
+
+    let asset_id = id.parse::<AssetId>().map_err(|e| {
+        ApiError::bad_request(format!("Invalid asset ID: {}", e))
+    })?;
+
+    let content_service = content_service(&state)?;
+
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let content = if is_multipart {
+        let multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| ApiError::bad_request(format!("Invalid multipart body: {}", e)))?;
+        multipart_stream(multipart)
+    } else {
+        body_stream(request.into_body())
+    };
+
+    let response = content_service
+        .upload_content(&asset_id, content)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ok(response)))
+}
+
+/// Download asset content
+///
+/// Supports a single `Range: bytes=` specifier, answering with
+/// `206 Partial Content`. Unsupported range forms are ignored and the full
+/// content is returned.
+#[instrument(skip(state, headers, user))]
+pub async fn download_content(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    user: Option<Extension<AuthUser>>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    debug!("Downloading content for asset: {}", id);
+
+    let asset_id = id.parse::<AssetId>().map_err(|e| {
+        ApiError::bad_request(format!("Invalid asset ID: {}", e))
+    })?;
+
+    let content_service = content_service(&state)?;
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_range_header);
+
+    let request = DownloadContentRequest {
+        asset_id,
+        range,
+        downloader: user.map(|Extension(user)| user.user_id().to_string()),
+    };
+
+    let download = match content_service.download_content(request).await {
+        Ok(download) => download,
+        Err(ServiceError::RangeNotSatisfiable { size }) => {
+            let mut response =
+                ApiError::from(ServiceError::RangeNotSatisfiable { size }).into_response();
+            if let Ok(value) = HeaderValue::from_str(&format!("bytes */{}", size)) {
+                response.headers_mut().insert(header::CONTENT_RANGE, value);
+            }
+            return Ok(response);
+        }
+        Err(e) => return Err(ApiError::from(e)),
+    };
+
+    let content_type = download
+        .asset
+        .metadata
+        .content_type
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let content_length = download.content_length();
+    let etag = format!("\"{}\"", download.asset.checksum.value);
+
+    let mut builder = Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CONTENT_LENGTH, content_length)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ETAG, etag);
+
+    builder = match download.range {
+        Some(range) => builder.status(StatusCode::PARTIAL_CONTENT).header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", range.start, range.end, download.total_size),
+        ),
+        None => builder.status(StatusCode::OK),
+    };
+
+    builder
+        .body(Body::from_stream(download.stream))
+        .map_err(|e| ApiError::internal_server_error(format!("Failed to build response: {}", e)))
+}
+
+/// Get the content service or fail if no blob store is configured
+fn content_service(state: &AppState) -> ApiResult<Arc<dyn ContentService>> {
+    state.services.content().cloned().ok_or_else(|| {
+        ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "Content storage is not configured",
+            "CONTENT_STORAGE_DISABLED",
+        )
+    })
+}
+
+/// Stream a raw request body as asset content
+fn body_stream(body: Body) -> ContentStream {
+    Box::pin(body.into_data_stream().map(|chunk| {
+        chunk
+            .map(|bytes| bytes.to_vec())
+            .map_err(|e| ServiceError::InvalidInput(format!("Failed to read request body: {}", e)))
+    }))
+}
+
+/// Stream the first field of a multipart body as asset content
+///
+/// Multipart fields borrow the parser, so a task reads the field and forwards
+/// its chunks over a channel.
+fn multipart_stream(mut multipart: Multipart) -> ContentStream {
+    let (tx, rx) = mpsc::channel::<ServiceResult<Vec<u8>>>(4);
+
+    tokio::spawn(async move {
+        let mut field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => {
+                let _ = tx
+                    .send(Err(ServiceError::InvalidInput(
+                        "Multipart body contains no content field".to_string(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(Err(ServiceError::InvalidInput(format!("Invalid multipart body: {}", e))))
+                    .await;
+                return;
+            }
+        };
+
+        loop {
+            let item = match field.chunk().await {
+                Ok(Some(chunk)) => Ok(chunk.to_vec()),
+                Ok(None) => return,
+                Err(e) => Err(ServiceError::InvalidInput(format!("Invalid multipart body: {}", e))),
+            };
+            let failed = item.is_err();
+            if tx.send(item).await.is_err() || failed {
+                return;
+            }
+        }
+    });
+
+    Box::pin(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }))
+}
+
+/// Parse a single-range `Range: bytes=` header value
+///
+/// Returns `None` for malformed values and multi-range requests.
+pub fn parse_range_header(value: &str) -> Option<ByteRangeRequest> {
+    let spec = value.trim().strip_prefix("bytes=")?.trim();
+    if spec.contains(',') {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    match (start.is_empty(), end.is_empty()) {
+        (false, false) => {
+            let start = start.parse().ok()?;
+            let end = end.parse().ok()?;
+            (start <= end).then_some(ByteRangeRequest::Bounded { start, end })
+        }
+        (false, true) => Some(ByteRangeRequest::From {
+            start: start.parse().ok()?,
+        }),
+        (true, false) => Some(ByteRangeRequest::Suffix {
+            length: end.parse().ok()?,
+        }),
+        (true, true) => None,
+    }
+}
+
+// ============================================================================
+// Upload Session Handlers
+// ============================================================================
+
+/// Header carrying the checksum of an uploaded chunk, as `ALGORITHM:hex`
+pub const CHUNK_CHECKSUM_HEADER: &str = "x-chunk-checksum";
+
+/// Request body for starting a chunked upload
+#[derive(Debug, Deserialize)]
+pub struct CreateUploadSessionBody {
+    /// Total size of the content in bytes
+    pub total_size: u64,
+
+    /// Chunk size in bytes
+    pub chunk_size: Option<u64>,
+}
+
+/// Start a resumable chunked upload for an asset
+#[instrument(skip(state, user))]
+pub async fn create_upload_session(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    user: Option<Extension<AuthUser>>,
+    Json(body): Json<CreateUploadSessionBody>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<UploadSessionResponse>>)> {
+    info!("Creating upload session for asset: {}", id);
+
+    let asset_id = id.parse::<AssetId>().map_err(|e| {
+        ApiError::bad_request(format!("Invalid asset ID: {}", e))
+    })?;
+
+    let request = CreateUploadSessionRequest {
+        asset_id,
+        total_size: body.total_size,
+        chunk_size: body.chunk_size,
+        created_by: user.map(|Extension(user)| user.user_id().to_string()),
+    };
+
+    let response = upload_service(&state)?
+        .create_session(request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(created(response))
+}
+
+/// Get an upload session and the chunks it is still missing
+#[instrument(skip(state))]
+pub async fn get_upload_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<UploadSessionResponse>>> {
+    debug!("Getting upload session: {}", session_id);
+
+    let response = upload_service(&state)?
+        .get_session(&session_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ok(response)))
+}
+
+/// Upload one chunk of an upload session
+///
+/// The chunk is verified against the `X-Chunk-Checksum` header when present.
+#[instrument(skip(state, headers, body))]
+pub async fn upload_chunk(
+    State(state): State<AppState>,
+    Path((session_id, index)): Path<(String, u32)>,
+    headers: HeaderMap,
+    body: Body,
+) -> ApiResult<Json<ApiResponse<UploadChunk>>> {
+    debug!("Uploading chunk {} of session {}", index, session_id);
+
+    let checksum = headers
+        .get(CHUNK_CHECKSUM_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(parse_checksum_header)
+                .ok_or_else(|| {
+                    ApiError::bad_request(format!(
+                        "Invalid {} header, expected ALGORITHM:hex",
+                        CHUNK_CHECKSUM_HEADER
+                    ))
+                })
+        })
+        .transpose()?;
+
+    let chunk = upload_service(&state)?
+        .upload_chunk(&session_id, index, checksum, body_stream(body))
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ok(chunk)))
+}
+
+/// Commit an upload session, assembling and verifying the asset's content
+#[instrument(skip(state))]
+pub async fn commit_upload_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<UploadContentResponse>>> {
+    info!("Committing upload session: {}", session_id);
+
+    let response = upload_service(&state)?
+        .commit_session(&session_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ok(response)))
+}
+
+/// Abort an upload session and discard its chunks
+#[instrument(skip(state))]
+pub async fn abort_upload_session(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> ApiResult<(StatusCode, Json<crate::responses::EmptyResponse>)> {
+    info!("Aborting upload session: {}", session_id);
+
+    upload_service(&state)?
+        .abort_session(&session_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(deleted())
+}
+
+/// Get the upload session service or fail if chunked uploads are not configured
+fn upload_service(state: &AppState) -> ApiResult<Arc<dyn UploadSessionService>> {
+    state.services.uploads().cloned().ok_or_else(|| {
+        ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "Chunked uploads are not configured",
+            "UPLOADS_DISABLED",
+        )
+    })
+}
+
+/// Parse a checksum header value of the form `ALGORITHM:hex`
+pub fn parse_checksum_header(value: &str) -> Option<Checksum> {
+    let (algorithm, hash) = value.trim().split_once(':')?;
+    let algorithm = algorithm.parse::<HashAlgorithm>().ok()?;
+    Checksum::new(algorithm, hash.to_string()).ok()
+}
+
+// ============================================================================
+// Access Control Handlers
+// ============================================================================
+
+/// Request body for creating an ACL entry
+#[derive(Debug, Deserialize)]
+pub struct CreateAclEntryBody {
+    /// Asset or asset name prefix the entry applies to
+    pub target: AclTarget,
+
+    /// Principal, role or everyone
+    pub subject: AclSubject,
+
+    /// Permission in `resource:action` form
+    pub permission: String,
+
+    /// Allow or deny
+    pub effect: AclEffect,
+}
+
+/// List all ACL entries
+#[instrument(skip(state))]
+pub async fn list_acl_entries(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ApiResponse<Vec<AclEntry>>>> {
+    let entries = acl_store(&state)?
+        .list()
+        .await
+        .map_err(|e| ApiError::from(ServiceError::from(e)))?;
+
+    Ok(Json(ok(entries)))
+}
+
+/// Create an ACL entry
+///
+/// Requires `acl:write`, or owning the asset the entry targets (see
+/// [`Authorizer::authorize_acl_target`]).
+#[instrument(skip(state, user))]
+pub async fn create_acl_entry(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(body): Json<CreateAclEntryBody>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<AclEntry>>)> {
+    info!("Creating ACL entry: {} {} on {:?}", body.effect, body.permission, body.target);
+
+    Permission::from_string(&body.permission).map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let store = acl_store(&state)?;
+    state
+        .authorizer
+        .authorize_acl_target(&user, Operation::CreateAclEntry, &body.target)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to create ACL entry"))?;
+
+    let entry = AclEntry::new(body.target, body.subject, body.permission, body.effect)
+        .with_created_by(user.user_id());
+    let entry = store
+        .create(entry)
+        .await
+        .map_err(|e| ApiError::from(ServiceError::from(e)))?;
+
+    Ok(created(entry))
+}
+
+/// Delete an ACL entry
+///
+/// Requires `acl:write`, or owning the asset the entry targets (see
+/// [`Authorizer::authorize_acl_target`]).
+#[instrument(skip(state, user))]
+pub async fn delete_acl_entry(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(acl_id): Path<String>,
+) -> ApiResult<(StatusCode, Json<crate::responses::EmptyResponse>)> {
+    info!("Deleting ACL entry: {}", acl_id);
+
+    let store = acl_store(&state)?;
+    let entry = store
+        .get(&acl_id)
+        .await
+        .map_err(|e| ApiError::from(ServiceError::from(e)))?;
+    state
+        .authorizer
+        .authorize_acl_target(&user, Operation::DeleteAclEntry, &entry.target)
+        .await
+        .map_err(|_| ApiError::forbidden("Insufficient permissions to delete ACL entry"))?;
+
+    store
+        .delete(&entry.id)
+        .await
+        .map_err(|e| ApiError::from(ServiceError::from(e)))?;
+
+    Ok(deleted())
+}
+
+/// Get the ACL store or fail if ACLs are not configured
+fn acl_store(state: &AppState) -> ApiResult<Arc<dyn AclStore>> {
+    state.authorizer.acl_store().cloned().ok_or_else(|| {
+        ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "Access control lists are not configured",
+            "ACLS_DISABLED",
+        )
+    })
+}
+
+// ============================================================================
+// User Management Handlers
+// ============================================================================
+
+/// Request body for resetting a user's password
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordBody {
+    /// New password
+    pub password: String,
+}
+
+/// Request body for replacing a user's roles
+#[derive(Debug, Deserialize)]
+pub struct SetRolesBody {
+    /// Roles granted to the user
+    pub roles: Vec<String>,
+}
+
+/// Request body for enabling or disabling a user
+#[derive(Debug, Deserialize)]
+pub struct SetUserStatusBody {
+    /// Whether the account is disabled
+    pub disabled: bool,
+}
+
+/// List all users
+#[instrument(skip(state))]
+pub async fn list_users(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ApiResponse<Vec<User>>>> {
+    let users = user_service(&state)?.list_users().await?;
+    Ok(Json(ok(users)))
+}
+
+/// Create a user
+#[instrument(skip(state, request), fields(username = %request.username))]
+pub async fn create_user(
+    State(state): State<AppState>,
+    Json(request): Json<CreateUserRequest>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<User>>)> {
+    info!("Creating user: {}", request.username);
+
+    let user = user_service(&state)?.create_user(request).await?;
+    Ok(created(user))
+}
+
+/// Get a user by ID
+#[instrument(skip(state))]
+pub async fn get_user(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<User>>> {
+    let user = user_service(&state)?.get_user(&user_id).await?;
+    Ok(Json(ok(user)))
+}
+
+/// Reset a user's password
+#[instrument(skip(state, body))]
+pub async fn reset_user_password(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Json(body): Json<ResetPasswordBody>,
+) -> ApiResult<Json<ApiResponse<User>>> {
+    info!("Resetting password of user: {}", user_id);
+
+    let user = user_service(&state)?
+        .reset_password(&user_id, &body.password)
+        .await?;
+    Ok(Json(ok(user)))
+}
+
+/// Replace a user's roles
+#[instrument(skip(state))]
+pub async fn set_user_roles(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Json(body): Json<SetRolesBody>,
+) -> ApiResult<Json<ApiResponse<User>>> {
+    info!("Setting roles of user {}: {:?}", user_id, body.roles);
+
+    let user = user_service(&state)?.set_roles(&user_id, body.roles).await?;
+    Ok(Json(ok(user)))
+}
+
+/// Disable or re-enable a user
+///
+/// Disabling a user also revokes the tokens already issued to them.
+#[instrument(skip(state))]
+pub async fn set_user_status(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Json(body): Json<SetUserStatusBody>,
+) -> ApiResult<Json<ApiResponse<User>>> {
+    info!("Setting user {} disabled={}", user_id, body.disabled);
+
+    let user = user_service(&state)?
+        .set_disabled(&user_id, body.disabled)
+        .await?;
+    if let (true, Some(sessions)) = (user.disabled, &state.sessions) {
+        sessions
+            .revoke_all_for_subject(&user.id)
+            .await
+            .map_err(revocation_error)?;
+    }
+    Ok(Json(ok(user)))
+}
+
+/// Get the user service or fail if user accounts are not configured
+fn user_service(state: &AppState) -> ApiResult<&Arc<dyn UserService>> {
+    state.services.users().ok_or_else(|| {
+        ApiError::with_code(
+            StatusCode::NOT_IMPLEMENTED,
+            "User accounts are not configured",
+            "USERS_DISABLED",
+        )
+    })
+}
+
 // ============================================================================
 // Health & Metrics Handlers
 // ============================================================================
@@ -303,6 +1173,157 @@ pub struct VersionInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::jwt::Claims;
+    use llm_registry_core::{Asset, AssetMetadata, AssetType, StorageBackend, StorageLocation};
+    use llm_registry_db::{AssetRepository, InMemoryAssetRepository, InMemoryEventStore};
+    use llm_registry_service::InMemoryAclStore;
+
+    fn create_test_asset(name: &str) -> Asset {
+        let metadata = AssetMetadata::new(name, semver::Version::new(1, 0, 0));
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}/weights.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(
+            AssetId::new(),
+            AssetType::Model,
+            metadata,
+            storage,
+            checksum,
+        )
+        .unwrap()
+    }
+
+    fn viewer() -> Option<Extension<AuthUser>> {
+        Some(Extension(AuthUser::new(
+            Claims::new("user-1", "test", "test", 3600).with_role("viewer"),
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_get_dependencies_filters_by_kind() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let mut ids = Vec::new();
+        for name in ["app", "model", "corpus", "suite"] {
+            ids.push(repository.create(create_test_asset(name)).await.unwrap().id);
+        }
+        let [app, model, corpus, suite] = [ids[0], ids[1], ids[2], ids[3]];
+        for (from, to, kind) in [
+            (app, model, DependencyKind::Runtime),
+            (model, corpus, DependencyKind::TrainingData),
+            (app, suite, DependencyKind::Evaluation),
+        ] {
+            repository
+                .add_dependency(&from, &to, None, kind)
+                .await
+                .unwrap();
+        }
+        let state = AppState::new(ServiceRegistry::new(
+            repository,
+            Arc::new(InMemoryEventStore::new()),
+        ));
+
+        let graph = |kind: Option<&str>| {
+            get_dependencies(
+                State(state.clone()),
+                Path(app.to_string()),
+                viewer(),
+                Query(DependencyGraphParams {
+                    max_depth: None,
+                    direction: None,
+                    kind: kind.map(str::to_string),
+                }),
+            )
+        };
+        let reached = |kind: Option<&'static str>| {
+            let graph = graph(kind);
+            async move {
+                let mut names: Vec<String> = graph
+                    .await
+                    .unwrap()
+                    .0
+                    .data
+                    .dependencies
+                    .into_iter()
+                    .map(|node| node.name)
+                    .collect();
+                names.sort();
+                names
+            }
+        };
+
+        assert_eq!(reached(None).await, ["app", "corpus", "model", "suite"]);
+        assert_eq!(
+            reached(Some("runtime, training_data")).await,
+            ["app", "corpus", "model"]
+        );
+        assert_eq!(reached(Some("evaluation")).await, ["app", "suite"]);
+
+        let err = graph(Some("runtime,unknown")).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_dependency_routes_require_reading_the_root() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let app = repository
+            .create(create_test_asset("app"))
+            .await
+            .unwrap()
+            .id;
+        let model = repository
+            .create(create_test_asset("model"))
+            .await
+            .unwrap()
+            .id;
+        repository
+            .add_dependency(&app, &model, None, DependencyKind::Runtime)
+            .await
+            .unwrap();
+
+        let services = ServiceRegistry::new(repository, Arc::new(InMemoryEventStore::new()));
+        let store = Arc::new(InMemoryAclStore::new());
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(app),
+                AclSubject::Everyone,
+                "asset:read",
+                AclEffect::Deny,
+            ))
+            .await
+            .unwrap();
+        let authorizer = Authorizer::default().with_acl(store, services.search().clone());
+        let state = AppState::new(services).with_authorizer(Arc::new(authorizer));
+
+        let graph = |id: AssetId| {
+            get_dependencies(
+                State(state.clone()),
+                Path(id.to_string()),
+                viewer(),
+                Query(DependencyGraphParams {
+                    max_depth: None,
+                    direction: None,
+                    kind: None,
+                }),
+            )
+        };
+        let dependents =
+            |id: AssetId| get_dependents(State(state.clone()), Path(id.to_string()), viewer());
+
+        // A hidden asset does not expose its graph to callers who may read graphs
+        let err = graph(app).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+        let err = dependents(app).await.unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+
+        assert!(graph(model).await.is_ok());
+        assert!(dependents(model).await.unwrap().0.data.is_empty());
+    }
 
     #[test]
     fn test_version_info_creation() {
@@ -315,4 +1336,53 @@ mod tests {
         assert_eq!(info.version, "0.1.0");
         assert_eq!(info.api_version, "v1");
     }
+
+    #[test]
+    fn test_parse_range_header() {
+        assert_eq!(
+            parse_range_header("bytes=0-499"),
+            Some(ByteRangeRequest::Bounded { start: 0, end: 499 })
+        );
+        assert_eq!(
+            parse_range_header("bytes=500-"),
+            Some(ByteRangeRequest::From { start: 500 })
+        );
+        assert_eq!(
+            parse_range_header("bytes=-200"),
+            Some(ByteRangeRequest::Suffix { length: 200 })
+        );
+        assert_eq!(parse_range_header("bytes=10-5"), None);
+        assert_eq!(parse_range_header("bytes=0-1,5-6"), None);
+        assert_eq!(parse_range_header("items=0-10"), None);
+        assert_eq!(parse_range_header("bytes=-"), None);
+    }
+
+    #[test]
+    fn test_if_match_revision() {
+        let if_match = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+            if_match_revision(&headers)
+        };
+
+        assert_eq!(if_match_revision(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(if_match("*").unwrap(), None);
+        assert_eq!(if_match(&revision_etag(7)).unwrap(), Some(7));
+        assert_eq!(if_match(" \"12\" ").unwrap(), Some(12));
+        assert!(if_match("7").is_err());
+        assert!(if_match("W/\"7\"").is_err());
+        assert!(if_match("\"1\", \"2\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_checksum_header() {
+        let hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+        let checksum = parse_checksum_header(&format!("SHA256:{}", hash)).unwrap();
+        assert_eq!(checksum.algorithm, HashAlgorithm::SHA256);
+        assert_eq!(checksum.value, hash);
+
+        assert!(parse_checksum_header(hash).is_none());
+        assert!(parse_checksum_header("MD5:abcd").is_none());
+        assert!(parse_checksum_header("SHA256:not-hex").is_none());
+    }
 }
@@ -0,0 +1,303 @@
+//! Asymmetric signing keys and JWKS publishing
+//!
+//! This module provides the keys used to sign tokens with RS256, ES256 or
+//! EdDSA instead of a shared secret. Every key has a key ID (`kid`) that is
+//! written into the header of the tokens it signs. A [`KeyRing`] holds the
+//! current signing key plus older keys that are still accepted for
+//! verification, so keys can be rotated without invalidating issued tokens.
+//! The public half of every key in the ring is published as a JSON Web Key
+//! Set (JWKS) for other services to verify our tokens.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters, Jwk,
+    JwkSet, KeyAlgorithm, OctetKeyPairParameters, PublicKeyUse, RSAKeyParameters,
+};
+use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header};
+use ring::rand::SystemRandom;
+use ring::signature::{self, KeyPair};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::jwt::{JwtConfigError, TokenError};
+
+/// PEM tag of PKCS#8 private keys
+const PKCS8_TAG: &str = "PRIVATE KEY";
+
+/// PEM tag of PKCS#1 RSA private keys
+const PKCS1_RSA_TAG: &str = "RSA PRIVATE KEY";
+
+/// Whether an algorithm uses a shared secret
+pub fn is_hmac(algorithm: Algorithm) -> bool {
+    matches!(
+        algorithm,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    )
+}
+
+/// A private key used to sign tokens, identified by its key ID
+#[derive(Clone)]
+pub struct SigningKey {
+    kid: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    jwk: Jwk,
+}
+
+impl SigningKey {
+    /// Load a private key from PEM
+    ///
+    /// RSA keys may be PKCS#1 or PKCS#8; EC and Ed25519 keys must be PKCS#8.
+    /// The key must match the algorithm: RS*/PS* need an RSA key, ES256 a
+    /// P-256 key, ES384 a P-384 key and EdDSA an Ed25519 key.
+    pub fn from_pem(
+        kid: impl Into<String>,
+        algorithm: Algorithm,
+        pem: &[u8],
+    ) -> Result<Self, JwtConfigError> {
+        let kid = kid.into();
+        let invalid = |reason: &str| JwtConfigError::InvalidKey(format!("{}: {}", kid, reason));
+
+        let parsed = pem::parse(pem).map_err(|e| invalid(&e.to_string()))?;
+        let der = parsed.contents();
+
+        let (params, encoding_key) = match algorithm {
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => {
+                let key_pair = match parsed.tag() {
+                    PKCS8_TAG => signature::RsaKeyPair::from_pkcs8(der),
+                    PKCS1_RSA_TAG => signature::RsaKeyPair::from_der(der),
+                    _ => return Err(invalid("expected an RSA private key")),
+                }
+                .map_err(|e| invalid(&e.to_string()))?;
+                let public = ring::rsa::PublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+                let params = AlgorithmParameters::RSA(RSAKeyParameters {
+                    n: URL_SAFE_NO_PAD.encode(public.n),
+                    e: URL_SAFE_NO_PAD.encode(public.e),
+                    ..Default::default()
+                });
+                (params, EncodingKey::from_rsa_pem(pem))
+            }
+            Algorithm::ES256 | Algorithm::ES384 => {
+                let (signing, curve) = if algorithm == Algorithm::ES256 {
+                    (&signature::ECDSA_P256_SHA256_FIXED_SIGNING, EllipticCurve::P256)
+                } else {
+                    (&signature::ECDSA_P384_SHA384_FIXED_SIGNING, EllipticCurve::P384)
+                };
+                if parsed.tag() != PKCS8_TAG {
+                    return Err(invalid("expected a PKCS#8 EC private key"));
+                }
+                let key_pair =
+                    signature::EcdsaKeyPair::from_pkcs8(signing, der, &SystemRandom::new())
+                        .map_err(|e| invalid(&e.to_string()))?;
+                // Uncompressed point: 0x04 || x || y
+                let point = key_pair.public_key().as_ref();
+                let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+                let params = AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                    curve,
+                    x: URL_SAFE_NO_PAD.encode(x),
+                    y: URL_SAFE_NO_PAD.encode(y),
+                    ..Default::default()
+                });
+                (params, EncodingKey::from_ec_pem(pem))
+            }
+            Algorithm::EdDSA => {
+                if parsed.tag() != PKCS8_TAG {
+                    return Err(invalid("expected a PKCS#8 Ed25519 private key"));
+                }
+                let key_pair = signature::Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+                    .map_err(|e| invalid(&e.to_string()))?;
+                let params = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+                    ..Default::default()
+                });
+                (params, EncodingKey::from_ed_pem(pem))
+            }
+            _ => return Err(JwtConfigError::UnsupportedAlgorithm(algorithm)),
+        };
+
+        let encoding_key = encoding_key.map_err(|e| invalid(&e.to_string()))?;
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: KeyAlgorithm::from_str(&format!("{:?}", algorithm)).ok(),
+                key_id: Some(kid.clone()),
+                ..Default::default()
+            },
+            algorithm: params,
+        };
+        let decoding_key = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(&e.to_string()))?;
+
+        Ok(Self {
+            kid,
+            algorithm,
+            encoding_key,
+            decoding_key,
+            jwk,
+        })
+    }
+
+    /// Key ID
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    /// Signing algorithm
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Public key as a JWK
+    pub fn jwk(&self) -> &Jwk {
+        &self.jwk
+    }
+
+    /// Key for verifying signatures made with this key
+    pub fn decoding_key(&self) -> &DecodingKey {
+        &self.decoding_key
+    }
+
+    /// Sign claims, putting the key ID into the token header
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, TokenError> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.kid.clone());
+        encode(&header, claims, &self.encoding_key).map_err(TokenError::from)
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("kid", &self.kid)
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The current signing key plus older keys still accepted for verification
+#[derive(Debug, Clone)]
+pub struct KeyRing {
+    current: SigningKey,
+    previous: Vec<SigningKey>,
+}
+
+impl KeyRing {
+    /// Create a key ring that signs with the given key
+    pub fn new(current: SigningKey) -> Self {
+        Self {
+            current,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Keep accepting tokens signed with an older key
+    pub fn with_previous_key(mut self, key: SigningKey) -> Self {
+        self.previous.push(key);
+        self
+    }
+
+    /// Sign with a new key from now on, still accepting the current one
+    pub fn rotate(self, key: SigningKey) -> Self {
+        let mut previous = self.previous;
+        previous.insert(0, self.current);
+        Self {
+            current: key,
+            previous,
+        }
+    }
+
+    /// Stop accepting tokens signed with an older key
+    pub fn retire(mut self, kid: &str) -> Self {
+        self.previous.retain(|key| key.kid != kid);
+        self
+    }
+
+    /// Key used to sign new tokens
+    pub fn current(&self) -> &SigningKey {
+        &self.current
+    }
+
+    /// Find a key by ID
+    pub fn find(&self, kid: &str) -> Option<&SigningKey> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .find(|key| key.kid == kid)
+    }
+
+    /// Public keys of every key in the ring
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: std::iter::once(&self.current)
+                .chain(&self.previous)
+                .map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ring::signature::{EcdsaKeyPair, Ed25519KeyPair};
+
+    /// Generate a fresh PKCS#8 PEM key for an algorithm
+    pub(crate) fn generate_pem(algorithm: Algorithm) -> Vec<u8> {
+        let rng = SystemRandom::new();
+        let pkcs8 = match algorithm {
+            Algorithm::ES256 => {
+                EcdsaKeyPair::generate_pkcs8(&signature::ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            }
+            Algorithm::EdDSA => Ed25519KeyPair::generate_pkcs8(&rng),
+            _ => unimplemented!("no test key generation for {:?}", algorithm),
+        }
+        .unwrap();
+        pem::encode(&pem::Pem::new(PKCS8_TAG, pkcs8.as_ref())).into_bytes()
+    }
+
+    pub(crate) fn generate_key(kid: &str, algorithm: Algorithm) -> SigningKey {
+        SigningKey::from_pem(kid, algorithm, &generate_pem(algorithm)).unwrap()
+    }
+
+    #[test]
+    fn test_signing_key_jwk() {
+        let key = generate_key("ec-1", Algorithm::ES256);
+        let jwk = serde_json::to_value(key.jwk()).unwrap();
+        assert_eq!(jwk["kid"], "ec-1");
+        assert_eq!(jwk["alg"], "ES256");
+        assert_eq!(jwk["kty"], "EC");
+        assert_eq!(jwk["crv"], "P-256");
+
+        let key = generate_key("ed-1", Algorithm::EdDSA);
+        let jwk = serde_json::to_value(key.jwk()).unwrap();
+        assert_eq!(jwk["kty"], "OKP");
+        assert_eq!(jwk["crv"], "Ed25519");
+
+        // The key must match the algorithm
+        let ed_pem = generate_pem(Algorithm::EdDSA);
+        assert!(SigningKey::from_pem("bad", Algorithm::ES256, &ed_pem).is_err());
+        assert!(SigningKey::from_pem("bad", Algorithm::RS256, &ed_pem).is_err());
+        assert!(SigningKey::from_pem("bad", Algorithm::HS256, &ed_pem).is_err());
+    }
+
+    #[test]
+    fn test_key_ring_rotation() {
+        let ring = KeyRing::new(generate_key("k1", Algorithm::ES256))
+            .rotate(generate_key("k2", Algorithm::EdDSA));
+
+        assert_eq!(ring.current().kid(), "k2");
+        assert!(ring.find("k1").is_some());
+        assert_eq!(ring.jwks().keys.len(), 2);
+
+        let ring = ring.retire("k1");
+        assert!(ring.find("k1").is_none());
+        assert!(ring.jwks().find("k2").is_some());
+    }
+}
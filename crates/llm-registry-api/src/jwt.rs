@@ -1,22 +1,30 @@
 //! JWT token management
 //!
 //! This module provides JWT token generation, validation, and refresh functionality
-//! for API authentication.
+//! for API authentication. Tokens are signed either with a shared secret
+//! (HS256) or with the asymmetric keys of a [`KeyRing`], and tokens from an
+//! external OpenID Connect issuer can be accepted through an [`OidcProvider`].
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{
-    decode, encode, errors::Error as JwtError, Algorithm, DecodingKey, EncodingKey, Header,
-    Validation,
+    decode, decode_header, encode, errors::Error as JwtError, jwk::JwkSet, Algorithm,
+    DecodingKey, EncodingKey, Header, Validation,
 };
+use llm_registry_service::TokenRevocationStore;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
 use thiserror::Error;
+use tracing::warn;
 use uuid::Uuid;
 
+use crate::jwks::{is_hmac, KeyRing};
+use crate::oidc::{unverified_issuer, OidcProvider};
+
 /// JWT configuration
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
-    /// Secret key for signing tokens
+    /// Secret key for signing tokens with an HMAC algorithm
     pub secret: String,
 
     /// Token expiration in seconds
@@ -106,12 +114,14 @@ impl JwtConfig {
     }
 
     /// Validate configuration
+    ///
+    /// The secret is only required for HMAC algorithms.
     pub fn validate(&self) -> Result<(), JwtConfigError> {
-        if self.secret.is_empty() {
+        if is_hmac(self.algorithm) && self.secret.is_empty() {
             return Err(JwtConfigError::EmptySecret);
         }
 
-        if self.secret == "change-me-in-production" {
+        if is_hmac(self.algorithm) && self.secret == "change-me-in-production" {
             tracing::warn!("Using default JWT secret - change this in production!");
         }
 
@@ -149,6 +159,15 @@ pub enum JwtConfigError {
 
     #[error("JWT audience cannot be empty")]
     EmptyAudience,
+
+    #[error("JWT algorithm {0:?} requires a signing key")]
+    MissingSigningKey(Algorithm),
+
+    #[error("Unsupported JWT signing algorithm: {0:?}")]
+    UnsupportedAlgorithm(Algorithm),
+
+    #[error("Invalid JWT signing key {0}")]
+    InvalidKey(String),
 }
 
 /// JWT claims structure
@@ -169,6 +188,14 @@ pub struct Claims {
     /// Issued at (Unix timestamp)
     pub iat: i64,
 
+    /// Issued at, in microseconds since the Unix epoch
+    ///
+    /// Subject-wide revocations are compared against this, so tokens issued
+    /// right after a revocation are not caught by it. Tokens issued by other
+    /// parties do not carry it, and fall back to `iat`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat_micros: Option<i64>,
+
     /// Not before (Unix timestamp)
     pub nbf: i64,
 
@@ -205,6 +232,7 @@ impl Claims {
             aud: audience.into(),
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            iat_micros: Some(now.timestamp_micros()),
             nbf: now.timestamp(),
             jti: Uuid::new_v4().to_string(),
             email: None,
@@ -213,6 +241,13 @@ impl Claims {
         }
     }
 
+    /// When the token was issued, as precisely as the token records it
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        self.iat_micros
+            .and_then(DateTime::from_timestamp_micros)
+            .unwrap_or_else(|| timestamp(self.iat))
+    }
+
     /// Add email to claims
     pub fn with_email(mut self, email: impl Into<String>) -> Self {
         self.email = Some(email.into());
@@ -307,6 +342,18 @@ pub enum TokenError {
 
     #[error("Invalid token format")]
     InvalidFormat,
+
+    #[error("Token has been revoked")]
+    Revoked,
+
+    #[error("Token revocation store unavailable: {0}")]
+    RevocationUnavailable(String),
+
+    #[error("Unknown signing key: {0}")]
+    UnknownKey(String),
+
+    #[error("Signing keys unavailable: {0}")]
+    KeySetUnavailable(String),
 }
 
 /// JWT token pair (access + refresh)
@@ -337,21 +384,54 @@ impl TokenPair {
     }
 }
 
+/// Keys a JWT manager signs and verifies tokens with
+enum SigningKeys {
+    /// Shared secret for HMAC algorithms
+    Secret {
+        encoding_key: EncodingKey,
+        decoding_key: DecodingKey,
+    },
+    /// Asymmetric keys selected by key ID
+    Ring(Box<KeyRing>),
+}
+
 /// JWT token manager
 pub struct JwtManager {
     pub config: JwtConfig,
-    encoding_key: EncodingKey,
-    decoding_key: DecodingKey,
+    keys: SigningKeys,
     validation: Validation,
+    revocations: Option<Arc<dyn TokenRevocationStore>>,
+    oidc: Option<Arc<OidcProvider>>,
 }
 
 impl JwtManager {
-    /// Create a new JWT manager
+    /// Create a new JWT manager signing with the configured secret
+    ///
+    /// Asymmetric algorithms need keys, see [`JwtManager::with_key_ring`].
     pub fn new(config: JwtConfig) -> Result<Self, JwtConfigError> {
-        config.validate()?;
+        if !is_hmac(config.algorithm) {
+            return Err(JwtConfigError::MissingSigningKey(config.algorithm));
+        }
+
+        let keys = SigningKeys::Secret {
+            encoding_key: EncodingKey::from_secret(config.secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(config.secret.as_bytes()),
+        };
+        Self::with_keys(config, keys)
+    }
 
-        let encoding_key = EncodingKey::from_secret(config.secret.as_bytes());
-        let decoding_key = DecodingKey::from_secret(config.secret.as_bytes());
+    /// Create a new JWT manager signing with the current key of a key ring
+    ///
+    /// Tokens must carry the ID of a key in the ring; tokens signed with the
+    /// shared secret are rejected. The configured algorithm is replaced by
+    /// the current key's.
+    pub fn with_key_ring(mut config: JwtConfig, keys: KeyRing) -> Result<Self, JwtConfigError> {
+        config.algorithm = keys.current().algorithm();
+        Self::with_keys(config, SigningKeys::Ring(Box::new(keys)))
+    }
+
+    fn with_keys(config: JwtConfig, keys: SigningKeys) -> Result<Self, JwtConfigError> {
+        config.validate()?;
 
         let mut validation = Validation::new(config.algorithm);
         validation.set_issuer(&[&config.issuer]);
@@ -361,12 +441,74 @@ impl JwtManager {
 
         Ok(Self {
             config,
-            encoding_key,
-            decoding_key,
+            keys,
             validation,
+            revocations: None,
+            oidc: None,
         })
     }
 
+    /// Also accept tokens from an external OpenID Connect issuer
+    ///
+    /// Tokens whose `iss` claim is the provider's issuer are verified against
+    /// the provider's published keys instead of ours, so the provider's
+    /// issuer must differ from [`JwtConfig::issuer`].
+    pub fn with_oidc_provider(mut self, provider: Arc<OidcProvider>) -> Self {
+        if provider.issuer() == self.config.issuer {
+            warn!("OIDC issuer equals the local issuer; local tokens will be rejected");
+        }
+        self.oidc = Some(provider);
+        self
+    }
+
+    /// Public keys for verifying our tokens, as published at
+    /// `/.well-known/jwks.json`
+    ///
+    /// Empty when signing with a shared secret, which must not be published.
+    pub fn jwks(&self) -> JwkSet {
+        match &self.keys {
+            SigningKeys::Secret { .. } => JwkSet { keys: Vec::new() },
+            SigningKeys::Ring(ring) => ring.jwks(),
+        }
+    }
+
+    /// Sign claims with the current key
+    fn sign(&self, claims: &Claims) -> Result<String, TokenError> {
+        match &self.keys {
+            SigningKeys::Secret { encoding_key, .. } => {
+                encode(&Header::new(self.config.algorithm), claims, encoding_key)
+                    .map_err(TokenError::from)
+            }
+            SigningKeys::Ring(ring) => ring.current().sign(claims),
+        }
+    }
+
+    /// Select the key and algorithm to verify a token with from its header
+    fn decoding_key(&self, token: &str) -> Result<(&DecodingKey, Algorithm), TokenError> {
+        match &self.keys {
+            SigningKeys::Secret { decoding_key, .. } => Ok((decoding_key, self.config.algorithm)),
+            SigningKeys::Ring(ring) => {
+                let header = decode_header(token)?;
+                let kid = header
+                    .kid
+                    .ok_or_else(|| TokenError::InvalidClaims("Missing key ID".to_string()))?;
+                let key = ring.find(&kid).ok_or(TokenError::UnknownKey(kid))?;
+                Ok((key.decoding_key(), key.algorithm()))
+            }
+        }
+    }
+
+    /// Track revoked tokens in a store, enabling server-side logout
+    pub fn with_revocation_store(mut self, store: Arc<dyn TokenRevocationStore>) -> Self {
+        self.revocations = Some(store);
+        self
+    }
+
+    /// Get the revocation store, if configured
+    pub fn revocation_store(&self) -> Option<&Arc<dyn TokenRevocationStore>> {
+        self.revocations.as_ref()
+    }
+
     /// Generate a new access token
     pub fn generate_token(&self, user_id: impl Into<String>) -> Result<String, TokenError> {
         let claims = Claims::new(
@@ -376,14 +518,12 @@ impl JwtManager {
             self.config.expiration_seconds,
         );
 
-        let header = Header::new(self.config.algorithm);
-        encode(&header, &claims, &self.encoding_key).map_err(TokenError::from)
+        self.sign(&claims)
     }
 
     /// Generate a new access token with custom claims
     pub fn generate_token_with_claims(&self, claims: Claims) -> Result<String, TokenError> {
-        let header = Header::new(self.config.algorithm);
-        encode(&header, &claims, &self.encoding_key).map_err(TokenError::from)
+        self.sign(&claims)
     }
 
     /// Generate a new refresh token
@@ -396,8 +536,7 @@ impl JwtManager {
         )
         .with_role("refresh");
 
-        let header = Header::new(self.config.algorithm);
-        encode(&header, &claims, &self.encoding_key).map_err(TokenError::from)
+        self.sign(&claims)
     }
 
     /// Generate a token pair (access + refresh)
@@ -413,17 +552,105 @@ impl JwtManager {
         ))
     }
 
-    /// Validate and decode a token
+    /// Generate a token pair whose access token carries the given claims
+    ///
+    /// The refresh token only identifies the subject, so roles are looked up
+    /// again when it is used.
+    pub fn generate_token_pair_with_claims(&self, claims: Claims) -> Result<TokenPair, TokenError> {
+        let refresh_token = self.generate_refresh_token(&claims.sub)?;
+        let access_token = self.generate_token_with_claims(claims)?;
+
+        Ok(TokenPair::new(
+            access_token,
+            refresh_token,
+            self.config.expiration_seconds,
+        ))
+    }
+
+    /// Validate and decode a token issued by us
     pub fn validate_token(&self, token: &str) -> Result<Claims, TokenError> {
-        let token_data = decode::<Claims>(token, &self.decoding_key, &self.validation)?;
+        let (key, algorithm) = self.decoding_key(token)?;
+        let mut validation = self.validation.clone();
+        validation.algorithms = vec![algorithm];
+
+        let token_data = decode::<Claims>(token, key, &validation)?;
         let claims = token_data.claims;
         claims.validate()?;
         Ok(claims)
     }
 
-    /// Refresh an access token using a refresh token
-    pub fn refresh_access_token(&self, refresh_token: &str) -> Result<TokenPair, TokenError> {
+    /// Validate a token and check that it has not been revoked
+    ///
+    /// Tokens from the configured OIDC provider are verified against its keys.
+    /// If the revocation store cannot be reached the token is rejected.
+    pub async fn verify_token(&self, token: &str) -> Result<Claims, TokenError> {
+        let claims = match &self.oidc {
+            Some(oidc) if unverified_issuer(token).as_deref() == Some(oidc.issuer()) => {
+                oidc.verify(token).await?
+            }
+            _ => self.validate_token(token)?,
+        };
+        self.check_not_revoked(&claims).await?;
+        Ok(claims)
+    }
+
+    /// Fail if the token described by the claims has been revoked
+    pub async fn check_not_revoked(&self, claims: &Claims) -> Result<(), TokenError> {
+        let Some(store) = &self.revocations else {
+            return Ok(());
+        };
+
+        let revoked = store
+            .is_revoked(&claims.jti, &claims.sub, claims.issued_at())
+            .await
+            .map_err(|e| {
+                warn!("Token revocation check failed: {}", e);
+                TokenError::RevocationUnavailable(e.to_string())
+            })?;
+
+        if revoked {
+            return Err(TokenError::Revoked);
+        }
+        Ok(())
+    }
+
+    /// Revoke a single token until it expires
+    ///
+    /// Does nothing if no revocation store is configured.
+    pub async fn revoke(&self, claims: &Claims) -> Result<(), TokenError> {
+        if let Some(store) = &self.revocations {
+            store
+                .revoke(&claims.jti, timestamp(claims.exp))
+                .await
+                .map_err(|e| TokenError::RevocationUnavailable(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Revoke every token issued to a subject so far
+    ///
+    /// Does nothing if no revocation store is configured.
+    pub async fn revoke_all_for_subject(&self, subject: &str) -> Result<(), TokenError> {
+        if let Some(store) = &self.revocations {
+            let now = Utc::now();
+            let longest = self
+                .config
+                .expiration_seconds
+                .max(self.config.refresh_expiration_seconds);
+            store
+                .revoke_subject(subject, now, now + Duration::seconds(longest))
+                .await
+                .map_err(|e| TokenError::RevocationUnavailable(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Validate a refresh token and return its claims
+    ///
+    /// Only refresh tokens issued by us are accepted.
+    pub async fn validate_refresh_token(&self, refresh_token: &str) -> Result<Claims, TokenError> {
         let claims = self.validate_token(refresh_token)?;
+        self.check_not_revoked(&claims).await?;
 
         // Verify it's a refresh token
         if !claims.has_role("refresh") {
@@ -432,17 +659,21 @@ impl JwtManager {
             ));
         }
 
+        Ok(claims)
+    }
+
+    /// Refresh an access token using a refresh token
+    pub async fn refresh_access_token(&self, refresh_token: &str) -> Result<TokenPair, TokenError> {
+        let claims = self.validate_refresh_token(refresh_token).await?;
+
         // Generate new token pair
         self.generate_token_pair(&claims.sub)
     }
 
     /// Decode token without validation (use with caution)
     pub fn decode_unverified(&self, token: &str) -> Result<Claims, TokenError> {
-        let token_data = decode::<Claims>(
-            token,
-            &self.decoding_key,
-            &Validation::new(self.config.algorithm),
-        )?;
+        let (key, algorithm) = self.decoding_key(token)?;
+        let token_data = decode::<Claims>(token, key, &Validation::new(algorithm))?;
         Ok(token_data.claims)
     }
 
@@ -462,6 +693,11 @@ impl JwtManager {
     }
 }
 
+/// Convert a Unix timestamp claim to a date, clamping invalid values
+fn timestamp(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs, 0).unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
 impl fmt::Debug for JwtManager {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("JwtManager")
@@ -469,6 +705,8 @@ impl fmt::Debug for JwtManager {
             .field("audience", &self.config.audience)
             .field("algorithm", &self.config.algorithm)
             .field("expiration_seconds", &self.config.expiration_seconds)
+            .field("revocation", &self.revocations.is_some())
+            .field("oidc", &self.oidc.as_ref().map(|oidc| oidc.issuer()))
             .finish()
     }
 }
@@ -476,6 +714,7 @@ impl fmt::Debug for JwtManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use llm_registry_service::InMemoryTokenRevocationStore;
 
     fn create_test_config() -> JwtConfig {
         JwtConfig::new("test-secret-key-for-testing")
@@ -565,18 +804,76 @@ mod tests {
         assert!(refresh_claims.has_role("refresh"));
     }
 
-    #[test]
-    fn test_refresh_access_token() {
+    #[tokio::test]
+    async fn test_refresh_access_token() {
         let config = create_test_config();
         let manager = JwtManager::new(config).unwrap();
 
         let pair = manager.generate_token_pair("user123").unwrap();
-        let new_pair = manager.refresh_access_token(&pair.refresh_token).unwrap();
+        let new_pair = manager.refresh_access_token(&pair.refresh_token).await.unwrap();
 
         assert!(!new_pair.access_token.is_empty());
         assert_ne!(pair.access_token, new_pair.access_token);
     }
 
+    #[tokio::test]
+    async fn test_token_pair_with_claims() {
+        let manager = JwtManager::new(create_test_config()).unwrap();
+        let claims = Claims::new("user123", "test-issuer", "test-audience", 3600)
+            .with_role("developer");
+
+        let pair = manager.generate_token_pair_with_claims(claims).unwrap();
+        let access = manager.validate_token(&pair.access_token).unwrap();
+        assert_eq!(access.roles, vec!["developer"]);
+
+        let refresh = manager
+            .validate_refresh_token(&pair.refresh_token)
+            .await
+            .unwrap();
+        assert_eq!(refresh.sub, "user123");
+        assert!(manager
+            .validate_refresh_token(&pair.access_token)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_token_revocation() {
+        let manager = JwtManager::new(create_test_config())
+            .unwrap()
+            .with_revocation_store(Arc::new(InMemoryTokenRevocationStore::new()));
+
+        let pair = manager.generate_token_pair("user123").unwrap();
+        let claims = manager.verify_token(&pair.access_token).await.unwrap();
+        manager.revoke(&claims).await.unwrap();
+        assert!(matches!(
+            manager.verify_token(&pair.access_token).await,
+            Err(TokenError::Revoked)
+        ));
+        assert!(manager.refresh_access_token(&pair.refresh_token).await.is_ok());
+
+        manager.revoke_all_for_subject("user123").await.unwrap();
+        assert!(matches!(
+            manager.refresh_access_token(&pair.refresh_token).await,
+            Err(TokenError::Revoked)
+        ));
+
+        // Tokens issued after the cutoff are still accepted, even within the
+        // same second
+        let token = manager.generate_token("user123").unwrap();
+        assert!(manager.verify_token(&token).await.is_ok());
+
+        // Without a precise issue time, tokens issued within the second of
+        // the cutoff are revoked too
+        let mut imprecise = Claims::new("user123", "test-issuer", "test-audience", 3600);
+        imprecise.iat_micros = None;
+        let token = manager.generate_token_with_claims(imprecise).unwrap();
+        assert!(matches!(
+            manager.verify_token(&token).await,
+            Err(TokenError::Revoked)
+        ));
+    }
+
     #[test]
     fn test_extract_token_from_header() {
         let header = "Bearer abc123xyz";
@@ -608,4 +905,47 @@ mod tests {
         assert_eq!(claims.email, Some("user@example.com".to_string()));
         assert_eq!(claims.custom["org_id"], "org-456");
     }
+
+    #[test]
+    fn test_key_ring_signing_and_rotation() {
+        use crate::jwks::tests::generate_key;
+
+        assert!(matches!(
+            JwtManager::new(create_test_config().with_algorithm(Algorithm::RS256)),
+            Err(JwtConfigError::MissingSigningKey(Algorithm::RS256))
+        ));
+
+        let ring = KeyRing::new(generate_key("k1", Algorithm::ES256));
+        let config = JwtConfig {
+            secret: String::new(),
+            ..create_test_config()
+        };
+        let manager = JwtManager::with_key_ring(config.clone(), ring.clone()).unwrap();
+        let old_token = manager.generate_token("user123").unwrap();
+        assert_eq!(decode_header(&old_token).unwrap().kid.as_deref(), Some("k1"));
+        assert_eq!(manager.validate_token(&old_token).unwrap().sub, "user123");
+        assert!(manager.jwks().find("k1").is_some());
+
+        // Tokens signed with the previous key stay valid after rotation
+        let rotated = ring.rotate(generate_key("k2", Algorithm::EdDSA));
+        let manager = JwtManager::with_key_ring(config.clone(), rotated.clone()).unwrap();
+        let new_token = manager.generate_token("user123").unwrap();
+        assert_eq!(decode_header(&new_token).unwrap().alg, Algorithm::EdDSA);
+        assert!(manager.validate_token(&old_token).is_ok());
+        assert!(manager.validate_token(&new_token).is_ok());
+
+        let manager = JwtManager::with_key_ring(config, rotated.retire("k1")).unwrap();
+        assert!(matches!(
+            manager.validate_token(&old_token),
+            Err(TokenError::UnknownKey(_))
+        ));
+
+        // Secret-signed tokens are not accepted by a key ring manager
+        let hs_token = JwtManager::new(create_test_config())
+            .unwrap()
+            .generate_token("user123")
+            .unwrap();
+        assert!(manager.validate_token(&hs_token).is_err());
+        assert!(JwtManager::new(create_test_config()).unwrap().jwks().keys.is_empty());
+    }
 }
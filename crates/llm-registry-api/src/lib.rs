@@ -39,13 +39,16 @@
 
 pub mod auth;
 pub mod auth_handlers;
+pub mod authorization;
 pub mod error;
 pub mod graphql;
 pub mod grpc;
 pub mod handlers;
+pub mod jwks;
 pub mod jwt;
 pub mod metrics_middleware;
 pub mod middleware;
+pub mod oidc;
 pub mod rate_limit;
 pub mod rbac;
 pub mod responses;
@@ -54,16 +57,22 @@ pub mod routes;
 // Re-export main types for convenience
 pub use auth::{AuthState, AuthUser, optional_auth, require_auth, require_role};
 pub use auth_handlers::{AuthHandlerState, LoginRequest, LoginResponse, RefreshTokenRequest};
+pub use authorization::{authorize_request, Authorizer, Operation};
 pub use error::{ApiError, ApiResult, ErrorResponse};
 pub use graphql::{
-    build_schema, graphql_handler, graphql_playground, AppSchema, Mutation as GraphQLMutation,
-    Query as GraphQLQuery,
+    build_schema, build_schema_with_authorizer, graphql_handler, graphql_playground,
+    graphql_ws_handler, AppSchema,
+    Mutation as GraphQLMutation, Query as GraphQLQuery, Subscription as GraphQLSubscription,
 };
 pub use grpc::{build_grpc_server, serve_grpc, RegistryServiceImpl, RegistryServiceServer};
 pub use handlers::{AppState, VersionInfo};
+pub use jwks::{KeyRing, SigningKey};
 pub use jwt::{Claims, JwtConfig, JwtManager, TokenPair};
 pub use middleware::{CorsConfig, MiddlewareConfig, UuidRequestIdGenerator};
-pub use rate_limit::{rate_limit, RateLimitConfig, RateLimiterState};
+pub use oidc::{OidcConfig, OidcProvider};
+pub use rate_limit::{
+    rate_limit, Limit, PrincipalTier, RateLimitConfig, RateLimiterState, RouteGroup,
+};
 pub use rbac::{Permission, RbacPolicy, Role};
 pub use responses::{
     created, deleted, no_content, ok, ApiResponse, ComponentHealth, EmptyResponse, HealthResponse,
@@ -96,9 +105,47 @@ use llm_registry_service::ServiceRegistry;
 /// ```
 pub fn build_api_server(services: ServiceRegistry) -> Router {
     let state = AppState::new(services);
-    let router = build_router(state);
+    with_default_middleware(build_router(state))
+}
+
+/// Build a complete API server with authentication, authorization and
+/// middleware
+///
+/// Routes are served by [`build_router_with_auth`], so every `/v1` route is
+/// checked against the state's authorizer and rate limited by its rate
+/// limiter, if any. Middleware uses default settings.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use llm_registry_api::{
+///     build_api_server_with_auth, AppState, AuthHandlerState, AuthState, JwtConfig, JwtManager,
+/// };
+/// use llm_registry_service::ServiceRegistry;
+///
+/// # async fn example(services: ServiceRegistry) {
+/// let jwt = || JwtManager::new(JwtConfig::new("secret")).unwrap();
+/// let app = build_api_server_with_auth(
+///     AppState::new(services),
+///     AuthHandlerState::new(jwt()),
+///     AuthState::new(jwt()),
+/// );
+/// # }
+/// ```
+pub fn build_api_server_with_auth(
+    state: AppState,
+    auth_handler_state: AuthHandlerState,
+    auth_state: AuthState,
+) -> Router {
+    with_default_middleware(build_router_with_auth(
+        state,
+        auth_handler_state,
+        auth_state,
+    ))
+}
 
-    // Apply middleware layers
+/// Apply CORS, compression, tracing and request ID middleware with default settings
+fn with_default_middleware(router: Router) -> Router {
     router
         .layer(middleware::cors_layer())
         .layer(tower_http::compression::CompressionLayer::new())
@@ -27,7 +27,7 @@ pub async fn metrics_middleware(
     let start = Instant::now();
     let method = req.method().to_string();
 
-    // Try to get the matched path template (e.g., "/api/v1/assets/:id")
+    // Try to get the matched path template (e.g., "/api/v1/assets/{id}")
     // If not available, use the URI path
     let path = req
         .extensions()
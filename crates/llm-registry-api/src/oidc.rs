@@ -0,0 +1,740 @@
+//! External OpenID Connect token verification
+//!
+//! This module accepts access tokens issued by an external identity provider
+//! (Keycloak, Auth0, Entra ID, ...). The provider's signing keys are fetched
+//! from its JWKS endpoint, found through OIDC discovery unless configured
+//! explicitly, and cached. When a token names a key ID that is not in the
+//! cache, the key set is fetched again, since the provider may have rotated
+//! its keys. Concurrent refreshes share a single fetch, and requests to the
+//! provider time out. After a failed fetch the provider is not asked again
+//! for a while, and the previously fetched keys stay in use. Registry roles are taken from a configurable claim of
+//! the token.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::jwks::is_hmac;
+use crate::jwt::{Claims, TokenError};
+
+/// Default time a fetched key set is used before fetching it again (1 hour)
+pub const DEFAULT_JWKS_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// Default minimum time between refreshes caused by unknown key IDs
+pub const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time allowed to connect to the provider
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time allowed for a whole request to the provider
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Role reserved for our own refresh tokens, never granted from external claims
+const REFRESH_ROLE: &str = "refresh";
+
+/// External OIDC provider configuration
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    /// Issuer URL, matched against the `iss` claim
+    pub issuer: String,
+
+    /// Expected `aud` claim
+    pub audience: String,
+
+    /// JWKS URL; discovered from the issuer if not set
+    pub jwks_uri: Option<String>,
+
+    /// Claim holding the user's roles, as a dotted path (e.g. `realm_access.roles`)
+    pub roles_claim: String,
+
+    /// Mapping from provider role names to registry roles
+    ///
+    /// Provider roles that are not mapped are ignored, so with an empty
+    /// mapping external tokens carry no roles.
+    pub role_mapping: HashMap<String, String>,
+
+    /// How long a fetched key set is used before fetching it again
+    pub cache_ttl: Duration,
+
+    /// Minimum time between refreshes caused by unknown key IDs, and
+    /// between attempts to fetch keys after a failed fetch
+    pub min_refresh_interval: Duration,
+
+    /// Time allowed to connect to the provider
+    pub connect_timeout: Duration,
+
+    /// Time allowed for a whole request to the provider
+    pub request_timeout: Duration,
+}
+
+impl OidcConfig {
+    /// Create a configuration for an issuer and audience
+    pub fn new(issuer: impl Into<String>, audience: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audience: audience.into(),
+            jwks_uri: None,
+            roles_claim: "roles".to_string(),
+            role_mapping: HashMap::new(),
+            cache_ttl: DEFAULT_JWKS_CACHE_TTL,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Set the JWKS URL instead of discovering it
+    pub fn with_jwks_uri(mut self, jwks_uri: impl Into<String>) -> Self {
+        self.jwks_uri = Some(jwks_uri.into());
+        self
+    }
+
+    /// Set the claim holding the user's roles
+    pub fn with_roles_claim(mut self, claim: impl Into<String>) -> Self {
+        self.roles_claim = claim.into();
+        self
+    }
+
+    /// Map a provider role to a registry role
+    pub fn with_role_mapping(
+        mut self,
+        provider_role: impl Into<String>,
+        registry_role: impl Into<String>,
+    ) -> Self {
+        self.role_mapping
+            .insert(provider_role.into(), registry_role.into());
+        self
+    }
+
+    /// Set how long a fetched key set is cached
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Set the minimum time between refreshes caused by unknown key IDs or
+    /// after a failed fetch
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// Set the time allowed to connect to the provider
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the time allowed for a whole request to the provider
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+}
+
+/// Cached provider keys
+#[derive(Default)]
+struct KeyCache {
+    jwks_uri: Option<String>,
+    keys: Vec<Jwk>,
+    fetched_at: Option<Instant>,
+    failed_at: Option<Instant>,
+}
+
+/// Verifier for tokens issued by an external OIDC provider
+pub struct OidcProvider {
+    config: OidcConfig,
+    client: reqwest::Client,
+    /// Cached keys, only locked to look up or swap in keys
+    cache: RwLock<KeyCache>,
+    /// Held while fetching keys, so concurrent refreshes make one fetch
+    refreshing: Mutex<()>,
+}
+
+impl OidcProvider {
+    /// Create a provider; keys are fetched on first use
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized, like
+    /// `reqwest::Client::new`.
+    pub fn new(config: OidcConfig) -> Self {
+        let cache = KeyCache {
+            jwks_uri: config.jwks_uri.clone(),
+            ..Default::default()
+        };
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.request_timeout)
+            .build()
+            .expect("Failed to initialize the HTTP client");
+        Self {
+            config,
+            client,
+            cache: RwLock::new(cache),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    /// Issuer whose tokens this provider verifies
+    pub fn issuer(&self) -> &str {
+        &self.config.issuer
+    }
+
+    /// Verify a token and translate it into registry claims
+    pub async fn verify(&self, token: &str) -> Result<Claims, TokenError> {
+        let header = decode_header(token)?;
+        if is_hmac(header.alg) {
+            return Err(TokenError::InvalidClaims(
+                "Symmetric algorithms are not accepted from external issuers".to_string(),
+            ));
+        }
+
+        let jwk = self.key_for(header.kid.as_deref()).await?;
+        if let Some(alg) = jwk.common.key_algorithm {
+            if Algorithm::from_str(&alg.to_string()).ok() != Some(header.alg) {
+                return Err(TokenError::InvalidClaims(format!(
+                    "Token algorithm {:?} does not match key algorithm {}",
+                    header.alg, alg
+                )));
+            }
+        }
+        let key = DecodingKey::from_jwk(&jwk)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let payload = decode::<Value>(token, &key, &validation)?.claims;
+        let claims = self.map_claims(token, &payload)?;
+        claims.validate()?;
+        Ok(claims)
+    }
+
+    /// Find the key a token was signed with, refreshing the cache if needed
+    async fn key_for(&self, kid: Option<&str>) -> Result<Jwk, TokenError> {
+        let (fetched_at, cached) = self.cached_key(kid);
+
+        let age = fetched_at.map(|at| at.elapsed());
+        let stale = age.map_or(true, |age| age >= self.config.cache_ttl);
+        if stale {
+            if let Err(e) = self.refresh(fetched_at).await {
+                // Keep using the old keys while the provider is unreachable
+                if self.cache().keys.is_empty() {
+                    return Err(e);
+                }
+                warn!("Using cached keys of {}: {}", self.config.issuer, e);
+            }
+            if let (_, Some(jwk)) = self.cached_key(kid) {
+                return Ok(jwk);
+            }
+        } else if let Some(jwk) = cached {
+            return Ok(jwk);
+        }
+
+        // The provider may have rotated its keys since the last fetch
+        let may_refresh = age.map_or(true, |age| age >= self.config.min_refresh_interval);
+        if !stale && may_refresh {
+            debug!("Unknown key ID {:?}, refreshing keys of {}", kid, self.config.issuer);
+            self.refresh(fetched_at).await?;
+            if let (_, Some(jwk)) = self.cached_key(kid) {
+                return Ok(jwk);
+            }
+        }
+
+        Err(TokenError::UnknownKey(kid.unwrap_or("<none>").to_string()))
+    }
+
+    /// When the cached keys were fetched, and the cached key with an ID
+    fn cached_key(&self, kid: Option<&str>) -> (Option<Instant>, Option<Jwk>) {
+        let cache = self.cache();
+        (cache.fetched_at, find_key(&cache.keys, kid).cloned())
+    }
+
+    /// Fetch the provider's key set, discovering its URL first if needed
+    ///
+    /// Only one fetch runs at a time. Callers that waited for another fetch
+    /// use its keys instead of fetching again, which they notice by the keys
+    /// no longer being those fetched at `seen`. The cache is not locked while
+    /// fetching, so verifications with cached keys go on meanwhile. Within
+    /// `min_refresh_interval` of a failed fetch, no fetch is made and an
+    /// error is returned right away.
+    async fn refresh(&self, seen: Option<Instant>) -> Result<(), TokenError> {
+        let _refreshing = self.refreshing.lock().await;
+
+        let jwks_uri = {
+            let cache = self.cache();
+            if cache.fetched_at != seen {
+                return Ok(());
+            }
+            if let Some(failed_at) = cache.failed_at {
+                if failed_at.elapsed() < self.config.min_refresh_interval {
+                    return Err(TokenError::KeySetUnavailable(format!(
+                        "Keys of {} could not be fetched {:?} ago",
+                        self.config.issuer,
+                        failed_at.elapsed()
+                    )));
+                }
+            }
+            cache.jwks_uri.clone()
+        };
+
+        match self.fetch_keys(jwks_uri).await {
+            Ok(keys) => {
+                info!(
+                    "Fetched {} signing keys of {}",
+                    keys.len(),
+                    self.config.issuer
+                );
+                let mut cache = self.cache_mut();
+                cache.keys = keys;
+                cache.fetched_at = Some(Instant::now());
+                cache.failed_at = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.cache_mut().failed_at = Some(Instant::now());
+                Err(e)
+            }
+        }
+    }
+
+    /// Fetch the provider's keys, discovering the key set URL first if needed
+    async fn fetch_keys(&self, jwks_uri: Option<String>) -> Result<Vec<Jwk>, TokenError> {
+        let jwks_uri = match jwks_uri {
+            Some(uri) => uri,
+            None => {
+                let uri = self.discover_jwks_uri().await?;
+                self.cache_mut().jwks_uri = Some(uri.clone());
+                uri
+            }
+        };
+
+        let jwks: JwkSet = self.fetch_json(&jwks_uri).await?;
+        Ok(jwks.keys)
+    }
+
+    /// Lock the cache for reading, recovering from a poisoned lock
+    fn cache(&self) -> RwLockReadGuard<'_, KeyCache> {
+        self.cache
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Lock the cache for writing, recovering from a poisoned lock
+    fn cache_mut(&self) -> RwLockWriteGuard<'_, KeyCache> {
+        self.cache
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Look up the JWKS URL in the provider's discovery document
+    async fn discover_jwks_uri(&self) -> Result<String, TokenError> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.config.issuer.trim_end_matches('/')
+        );
+        let document: Value = self.fetch_json(&url).await?;
+        document["jwks_uri"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                TokenError::KeySetUnavailable(format!("No jwks_uri in discovery document {}", url))
+            })
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, TokenError> {
+        let unavailable = |e: reqwest::Error| TokenError::KeySetUnavailable(format!("{}: {}", url, e));
+        self.client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)
+    }
+
+    /// Build registry claims from a verified token payload
+    fn map_claims(&self, token: &str, payload: &Value) -> Result<Claims, TokenError> {
+        let sub = payload["sub"]
+            .as_str()
+            .ok_or_else(|| TokenError::InvalidClaims("Missing subject".to_string()))?;
+        let iat = payload["iat"].as_i64().unwrap_or_else(|| Utc::now().timestamp());
+
+        // Tokens without an ID are identified by their signature, so they can
+        // still be revoked individually
+        let jti = match payload["jti"].as_str() {
+            Some(jti) => jti.to_string(),
+            None => token.rsplit('.').next().unwrap_or_default().to_string(),
+        };
+
+        let mut custom = serde_json::json!({});
+        if let Some(username) = payload["preferred_username"].as_str() {
+            custom["username"] = Value::String(username.to_string());
+        }
+
+        Ok(Claims {
+            sub: sub.to_string(),
+            iss: self.config.issuer.clone(),
+            aud: self.config.audience.clone(),
+            exp: payload["exp"].as_i64().unwrap_or_default(),
+            iat,
+            iat_micros: None,
+            nbf: payload["nbf"].as_i64().unwrap_or(iat),
+            jti,
+            email: payload["email"].as_str().map(str::to_string),
+            roles: self.map_roles(payload),
+            custom,
+        })
+    }
+
+    /// Registry roles granted by the configured roles claim
+    fn map_roles(&self, payload: &Value) -> Vec<String> {
+        let claim = self
+            .config
+            .roles_claim
+            .split('.')
+            .try_fold(payload, |value, key| value.get(key));
+
+        // Either an array of names or a space separated string (like `scope`)
+        let provider_roles: Vec<&str> = match claim {
+            Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+            Some(Value::String(value)) => value.split_whitespace().collect(),
+            _ => Vec::new(),
+        };
+
+        // Provider role names are not trusted as registry roles, which an
+        // identity provider shared with other applications could hand out
+        let mut roles: Vec<String> = Vec::new();
+        for role in provider_roles {
+            if let Some(role) = self.config.role_mapping.get(role) {
+                if role != REFRESH_ROLE && !roles.contains(role) {
+                    roles.push(role.clone());
+                }
+            }
+        }
+        roles
+    }
+}
+
+impl std::fmt::Debug for OidcProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OidcProvider")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Find a key by ID; a key set with a single key also matches tokens without one
+fn find_key<'a>(keys: &'a [Jwk], kid: Option<&str>) -> Option<&'a Jwk> {
+    match kid {
+        Some(kid) => keys
+            .iter()
+            .find(|key| key.common.key_id.as_deref() == Some(kid)),
+        None if keys.len() == 1 => keys.first(),
+        None => None,
+    }
+}
+
+/// Read the `iss` claim of a token without verifying it
+///
+/// Only used to decide which keys to verify the token with.
+pub(crate) fn unverified_issuer(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let payload: Value = serde_json::from_slice(&payload).ok()?;
+    payload["iss"].as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwks::tests::generate_key;
+    use crate::jwks::SigningKey;
+    use crate::jwt::{JwtConfig, JwtManager};
+    use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, RwLock};
+
+    /// A locally served identity provider
+    #[derive(Clone)]
+    struct TestProvider {
+        issuer: String,
+        keys: Arc<RwLock<Vec<Jwk>>>,
+        fetches: Arc<AtomicUsize>,
+        down: Arc<AtomicBool>,
+    }
+
+    impl TestProvider {
+        async fn start() -> Self {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let provider = Self {
+                issuer: format!("http://{}", listener.local_addr().unwrap()),
+                keys: Arc::new(RwLock::new(Vec::new())),
+                fetches: Arc::new(AtomicUsize::new(0)),
+                down: Arc::new(AtomicBool::new(false)),
+            };
+
+            async fn discovery(State(provider): State<TestProvider>) -> Json<Value> {
+                Json(serde_json::json!({
+                    "issuer": provider.issuer,
+                    "jwks_uri": format!("{}/keys", provider.issuer),
+                }))
+            }
+            async fn keys(
+                State(provider): State<TestProvider>,
+            ) -> Result<Json<JwkSet>, StatusCode> {
+                provider.fetches.fetch_add(1, Ordering::SeqCst);
+                if provider.down.load(Ordering::SeqCst) {
+                    return Err(StatusCode::SERVICE_UNAVAILABLE);
+                }
+                Ok(Json(JwkSet {
+                    keys: provider.keys.read().unwrap().clone(),
+                }))
+            }
+            let app = Router::new()
+                .route("/.well-known/openid-configuration", get(discovery))
+                .route("/keys", get(keys))
+                .with_state(provider.clone());
+            tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+            provider
+        }
+
+        fn publish(&self, key: &SigningKey) {
+            self.keys.write().unwrap().push(key.jwk().clone());
+        }
+
+        fn token(&self, key: &SigningKey, extra: Value) -> String {
+            let now = Utc::now().timestamp();
+            let mut claims = serde_json::json!({
+                "sub": "ext-user",
+                "iss": self.issuer,
+                "aud": "registry",
+                "iat": now,
+                "exp": now + 300,
+                "email": "ext@example.org",
+            });
+            for (k, v) in extra.as_object().unwrap() {
+                claims[k] = v.clone();
+            }
+            key.sign(&claims).unwrap()
+        }
+
+        fn fetches(&self) -> usize {
+            self.fetches.load(Ordering::SeqCst)
+        }
+
+        fn set_down(&self, down: bool) {
+            self.down.store(down, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_discovery_and_role_mapping() {
+        let idp = TestProvider::start().await;
+        let key = generate_key("idp-1", Algorithm::ES256);
+        idp.publish(&key);
+
+        let provider = OidcProvider::new(
+            OidcConfig::new(&idp.issuer, "registry")
+                .with_roles_claim("realm_access.roles")
+                .with_role_mapping("registry-admins", "admin")
+                .with_role_mapping("refresh-tokens", "refresh"),
+        );
+        let token = idp.token(
+            &key,
+            serde_json::json!({
+                "realm_access": {"roles": ["registry-admins", "offline_access", "refresh-tokens"]},
+                "preferred_username": "ext",
+            }),
+        );
+
+        let claims = provider.verify(&token).await.unwrap();
+        assert_eq!(claims.sub, "ext-user");
+        assert_eq!(claims.roles, vec!["admin"]);
+        assert_eq!(claims.email.as_deref(), Some("ext@example.org"));
+        assert_eq!(claims.custom["username"], "ext");
+
+        // Keys are cached
+        provider.verify(&token).await.unwrap();
+        assert_eq!(idp.fetches(), 1);
+
+        // Wrong audience and foreign keys are rejected
+        let mut other = idp.token(&key, serde_json::json!({"aud": "someone-else"}));
+        assert!(provider.verify(&other).await.is_err());
+        other = idp.token(
+            &generate_key("idp-1", Algorithm::ES256),
+            serde_json::json!({}),
+        );
+        assert!(provider.verify(&other).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unmapped_roles_grant_nothing() {
+        let idp = TestProvider::start().await;
+        let key = generate_key("idp-1", Algorithm::ES256);
+        idp.publish(&key);
+
+        // Without a mapping, provider role names are not taken as registry roles
+        let provider = OidcProvider::new(OidcConfig::new(&idp.issuer, "registry"));
+        let token = idp.token(&key, serde_json::json!({"roles": ["admin", "developer"]}));
+        let claims = provider.verify(&token).await.unwrap();
+        assert_eq!(claims.sub, "ext-user");
+        assert!(claims.roles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_on_unknown_key() {
+        let idp = TestProvider::start().await;
+        let old = generate_key("idp-1", Algorithm::ES256);
+        idp.publish(&old);
+
+        let provider = OidcProvider::new(
+            OidcConfig::new(&idp.issuer, "registry")
+                .with_jwks_uri(format!("{}/keys", idp.issuer))
+                .with_min_refresh_interval(Duration::ZERO)
+                .with_role_mapping("developer", "developer")
+                .with_role_mapping("viewer", "viewer"),
+        );
+        let token = idp.token(&old, serde_json::json!({"roles": "developer viewer"}));
+        assert_eq!(
+            provider.verify(&token).await.unwrap().roles,
+            vec!["developer", "viewer"]
+        );
+
+        // The provider rotates to a new key
+        let new = generate_key("idp-2", Algorithm::EdDSA);
+        idp.publish(&new);
+        let token = idp.token(&new, serde_json::json!({}));
+        assert!(provider.verify(&token).await.is_ok());
+        assert_eq!(idp.fetches(), 2);
+
+        let unknown = idp.token(&generate_key("idp-3", Algorithm::ES256), serde_json::json!({}));
+        assert!(matches!(
+            provider.verify(&unknown).await,
+            Err(TokenError::UnknownKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_verifications_fetch_keys_once() {
+        let idp = TestProvider::start().await;
+        let key = generate_key("idp-1", Algorithm::ES256);
+        idp.publish(&key);
+
+        let provider = OidcProvider::new(
+            OidcConfig::new(&idp.issuer, "registry").with_jwks_uri(format!("{}/keys", idp.issuer)),
+        );
+        let token = idp.token(&key, serde_json::json!({}));
+        let results = futures::future::join_all((0..8).map(|_| provider.verify(&token))).await;
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(idp.fetches(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_is_not_retried_right_away() {
+        let idp = TestProvider::start().await;
+        let key = generate_key("idp-1", Algorithm::ES256);
+        idp.publish(&key);
+
+        let provider = OidcProvider::new(
+            OidcConfig::new(&idp.issuer, "registry")
+                .with_jwks_uri(format!("{}/keys", idp.issuer))
+                .with_cache_ttl(Duration::ZERO)
+                .with_min_refresh_interval(Duration::from_secs(60)),
+        );
+        let token = idp.token(&key, serde_json::json!({}));
+        provider.verify(&token).await.unwrap();
+        assert_eq!(idp.fetches(), 1);
+
+        // Stale keys are used while the provider is down, asking it only once
+        idp.set_down(true);
+        provider.verify(&token).await.unwrap();
+        provider.verify(&token).await.unwrap();
+        assert_eq!(idp.fetches(), 2);
+
+        // Without keys to fall back on, tokens are rejected without asking again
+        let provider = OidcProvider::new(
+            OidcConfig::new(&idp.issuer, "registry")
+                .with_jwks_uri(format!("{}/keys", idp.issuer))
+                .with_min_refresh_interval(Duration::from_secs(60)),
+        );
+        for _ in 0..2 {
+            assert!(matches!(
+                provider.verify(&token).await,
+                Err(TokenError::KeySetUnavailable(_))
+            ));
+        }
+        assert_eq!(idp.fetches(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_provider_times_out() {
+        // Accept connections but never answer
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let provider = OidcProvider::new(
+            OidcConfig::new(&issuer, "registry")
+                .with_jwks_uri(format!("{}/keys", issuer))
+                .with_request_timeout(Duration::from_millis(100)),
+        );
+        let key = generate_key("idp-1", Algorithm::ES256);
+        let token = key
+            .sign(&serde_json::json!({"sub": "ext-user", "iss": issuer}))
+            .unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), provider.verify(&token))
+            .await
+            .expect("verification did not time out");
+        assert!(matches!(result, Err(TokenError::KeySetUnavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_jwt_manager_accepts_external_tokens() {
+        let idp = TestProvider::start().await;
+        let key = generate_key("idp-1", Algorithm::EdDSA);
+        idp.publish(&key);
+
+        let config = JwtConfig::new("test-secret")
+            .with_issuer("llm-registry")
+            .with_audience("registry");
+        let manager = JwtManager::new(config)
+            .unwrap()
+            .with_oidc_provider(Arc::new(OidcProvider::new(
+                OidcConfig::new(&idp.issuer, "registry")
+                    .with_role_mapping("viewer", "viewer")
+                    .with_role_mapping("refresh", "refresh"),
+            )));
+
+        let external = idp.token(&key, serde_json::json!({"roles": ["viewer"]}));
+        let claims = manager.verify_token(&external).await.unwrap();
+        assert_eq!(claims.roles, vec!["viewer"]);
+
+        let local = manager.generate_token("user123").unwrap();
+        assert_eq!(manager.verify_token(&local).await.unwrap().sub, "user123");
+
+        // External tokens cannot be used as refresh tokens
+        let external = idp.token(&key, serde_json::json!({"roles": ["refresh"]}));
+        assert!(manager.validate_refresh_token(&external).await.is_err());
+    }
+}
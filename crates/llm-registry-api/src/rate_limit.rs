@@ -1,25 +1,190 @@
 //! Rate limiting middleware
 //!
-//! This module provides rate limiting functionality using the token bucket algorithm
-//! with Redis for distributed rate limiting across multiple service instances.
+//! This module provides rate limiting using the token bucket algorithm. The
+//! buckets live in a pluggable [`RateLimitStore`]: in memory for a single
+//! instance, or in Redis to share limits across multiple service instances.
+//!
+//! Limits can differ per route group (for example uploads versus reads) and
+//! per principal tier (anonymous, user, API key, admin). Responses carry the
+//! standard `RateLimit-Limit`, `RateLimit-Remaining`, `RateLimit-Reset` and
+//! `RateLimit-Policy` headers.
 
 use axum::{
     body::Body,
-    extract::{ConnectInfo, Request, State},
-    http::{HeaderValue, StatusCode},
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use llm_registry_service::{InMemoryRateLimitStore, RateLimitDecision, RateLimitStore};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 use tracing::{debug, warn};
 
+use crate::auth::AuthUser;
 use crate::error::ErrorResponse;
 
+/// Maximum number of requests allowed in the bucket
+pub const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+
+/// Requests left in the current window
+pub const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+
+/// Seconds until the bucket is full again
+pub const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// The applied quota, as `<limit>;w=<window seconds>`
+pub const RATE_LIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+/// Name of the route group used when no configured group matches
+const DEFAULT_GROUP: &str = "default";
+
+/// Kind of principal making a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrincipalTier {
+    /// Unauthenticated request
+    Anonymous,
+
+    /// User authenticated with a token
+    User,
+
+    /// Request authenticated with an API key
+    ApiKey,
+
+    /// User holding the admin role
+    Admin,
+}
+
+impl PrincipalTier {
+    /// Tier of the authenticated user, if any
+    ///
+    /// API keys are limited as API keys even when their owner is an admin.
+    pub fn of(user: Option<&AuthUser>) -> Self {
+        match user {
+            None => PrincipalTier::Anonymous,
+            Some(user) if user.api_key_id().is_some() => PrincipalTier::ApiKey,
+            Some(user) if user.has_role("admin") => PrincipalTier::Admin,
+            Some(_) => PrincipalTier::User,
+        }
+    }
+
+    /// Tier name as used in configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrincipalTier::Anonymous => "anonymous",
+            PrincipalTier::User => "user",
+            PrincipalTier::ApiKey => "api_key",
+            PrincipalTier::Admin => "admin",
+        }
+    }
+}
+
+/// A request quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Limit {
+    /// Maximum number of requests allowed
+    pub max_requests: u32,
+
+    /// Time window in seconds
+    pub window_secs: u64,
+}
+
+impl Limit {
+    /// Create a new limit
+    pub fn new(max_requests: u32, window_secs: u64) -> Self {
+        Self {
+            max_requests,
+            window_secs,
+        }
+    }
+
+    /// Time window
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs.max(1))
+    }
+}
+
+/// A group of routes sharing their own limits
+///
+/// Requests in a group draw from buckets separate from other groups, so
+/// heavy uploads cannot exhaust the quota for reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteGroup {
+    /// Group name, part of the bucket key
+    pub name: String,
+
+    /// Path prefix of the routes in the group
+    pub path_prefix: String,
+
+    /// Methods in the group (all methods if empty)
+    #[serde(default)]
+    pub methods: Vec<String>,
+
+    /// Limit for the group, overriding the global limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<Limit>,
+
+    /// Limits for the group by principal tier
+    #[serde(default)]
+    pub tier_limits: HashMap<PrincipalTier, Limit>,
+}
+
+impl RouteGroup {
+    /// Create a group of routes below a path prefix
+    pub fn new(name: impl Into<String>, path_prefix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            path_prefix: path_prefix.into(),
+            methods: Vec::new(),
+            limit: None,
+            tier_limits: HashMap::new(),
+        }
+    }
+
+    /// Restrict the group to a method
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.methods.push(method.to_string());
+        self
+    }
+
+    /// Set the limit for the group
+    pub fn with_limit(mut self, max_requests: u32, window_secs: u64) -> Self {
+        self.limit = Some(Limit::new(max_requests, window_secs));
+        self
+    }
+
+    /// Set the limit for a tier within the group
+    pub fn with_tier_limit(
+        mut self,
+        tier: PrincipalTier,
+        max_requests: u32,
+        window_secs: u64,
+    ) -> Self {
+        self.tier_limits
+            .insert(tier, Limit::new(max_requests, window_secs));
+        self
+    }
+
+    /// Whether a request belongs to the group
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        path.starts_with(&self.path_prefix)
+            && (self.methods.is_empty()
+                || self
+                    .methods
+                    .iter()
+                    .any(|m| m.eq_ignore_ascii_case(method.as_str())))
+    }
+}
+
 /// Rate limit configuration
+///
+/// Fields missing when deserializing take their default values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RateLimitConfig {
     /// Maximum number of requests allowed
     pub max_requests: u32,
@@ -39,6 +204,14 @@ pub struct RateLimitConfig {
     /// Custom identifier header (e.g., API key)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier_header: Option<String>,
+
+    /// Limits by principal tier, overriding the global limit
+    #[serde(default)]
+    pub tier_limits: HashMap<PrincipalTier, Limit>,
+
+    /// Route groups with their own limits; the first matching group applies
+    #[serde(default)]
+    pub route_groups: Vec<RouteGroup>,
 }
 
 impl Default for RateLimitConfig {
@@ -50,6 +223,8 @@ impl Default for RateLimitConfig {
             by_ip: true,
             by_user: true,
             identifier_header: None,
+            tier_limits: HashMap::new(),
+            route_groups: Vec::new(),
         }
     }
 }
@@ -101,22 +276,76 @@ impl RateLimitConfig {
         self.identifier_header = Some(header.into());
         self
     }
+
+    /// Set the limit for a principal tier
+    pub fn with_tier_limit(
+        mut self,
+        tier: PrincipalTier,
+        max_requests: u32,
+        window_secs: u64,
+    ) -> Self {
+        self.tier_limits
+            .insert(tier, Limit::new(max_requests, window_secs));
+        self
+    }
+
+    /// Add a route group
+    pub fn with_route_group(mut self, group: RouteGroup) -> Self {
+        self.route_groups.push(group);
+        self
+    }
+
+    /// Resolve the route group and limit applying to a request
+    ///
+    /// The most specific limit wins: the group's tier limit, then the group
+    /// limit, then the global tier limit, then the global limit.
+    pub fn resolve(&self, method: &Method, path: &str, tier: PrincipalTier) -> (&str, Limit) {
+        let global = || {
+            self.tier_limits
+                .get(&tier)
+                .copied()
+                .unwrap_or_else(|| Limit::new(self.max_requests, self.window_secs))
+        };
+
+        match self
+            .route_groups
+            .iter()
+            .find(|group| group.matches(method, path))
+        {
+            Some(group) => {
+                let limit = group
+                    .tier_limits
+                    .get(&tier)
+                    .copied()
+                    .or(group.limit)
+                    .unwrap_or_else(global);
+                (&group.name, limit)
+            }
+            None => (DEFAULT_GROUP, global()),
+        }
+    }
 }
 
 /// Rate limiter state
 #[derive(Clone)]
 pub struct RateLimiterState {
     config: Arc<RateLimitConfig>,
-    // In-memory storage for rate limiting (in production, use Redis)
-    storage: Arc<tokio::sync::RwLock<std::collections::HashMap<String, TokenBucket>>>,
+    store: Arc<dyn RateLimitStore>,
 }
 
 impl RateLimiterState {
-    /// Create a new rate limiter state
+    /// Create a new rate limiter state with in-memory buckets
     pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_store(config, Arc::new(InMemoryRateLimitStore::new()))
+    }
+
+    /// Create a new rate limiter state with buckets in the given store
+    ///
+    /// Use a shared store such as Redis to enforce limits across instances.
+    pub fn with_store(config: RateLimitConfig, store: Arc<dyn RateLimitStore>) -> Self {
         Self {
             config: Arc::new(config),
-            storage: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            store,
         }
     }
 
@@ -126,90 +355,27 @@ impl RateLimiterState {
     }
 }
 
-/// Token bucket for rate limiting
-#[derive(Debug, Clone)]
-struct TokenBucket {
-    /// Number of tokens currently available
-    tokens: f64,
-
-    /// Last refill timestamp
-    last_refill: u64,
-
-    /// Maximum tokens (capacity)
-    capacity: f64,
-
-    /// Refill rate (tokens per second)
-    refill_rate: f64,
-}
-
-impl TokenBucket {
-    /// Create a new token bucket
-    fn new(capacity: u32, window_secs: u64) -> Self {
-        let refill_rate = capacity as f64 / window_secs as f64;
-        Self {
-            tokens: capacity as f64,
-            last_refill: Self::current_time_secs(),
-            capacity: capacity as f64,
-            refill_rate,
-        }
-    }
-
-    /// Get current time in seconds
-    fn current_time_secs() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-    }
-
-    /// Refill tokens based on elapsed time
-    fn refill(&mut self) {
-        let now = Self::current_time_secs();
-        let elapsed = now - self.last_refill;
-
-        if elapsed > 0 {
-            let new_tokens = elapsed as f64 * self.refill_rate;
-            self.tokens = (self.tokens + new_tokens).min(self.capacity);
-            self.last_refill = now;
-        }
-    }
-
-    /// Try to consume a token
-    fn try_consume(&mut self, count: f64) -> bool {
-        self.refill();
-
-        if self.tokens >= count {
-            self.tokens -= count;
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Get time until next token is available (in seconds)
-    fn time_until_available(&self) -> u64 {
-        if self.tokens >= 1.0 {
-            return 0;
-        }
-
-        let tokens_needed = 1.0 - self.tokens;
-        (tokens_needed / self.refill_rate).ceil() as u64
-    }
-}
-
 /// Rate limiting middleware
 ///
 /// This middleware implements rate limiting using the token bucket algorithm.
-/// It can rate limit by IP address, user ID, or custom identifier.
+/// It can rate limit by IP address, user ID, or custom identifier. The limit
+/// depends on the route group and the principal tier of the request, so the
+/// middleware must run after authentication to see the authenticated user.
+///
+/// If the bucket store fails, the request is let through.
 ///
 /// # Example
 ///
 /// ```rust,no_run
 /// use axum::{Router, routing::get, middleware};
-/// use llm_registry_api::rate_limit::{rate_limit, RateLimiterState, RateLimitConfig};
+/// use llm_registry_api::rate_limit::{
+///     rate_limit, PrincipalTier, RateLimiterState, RateLimitConfig, RouteGroup,
+/// };
 ///
 /// # async fn example() {
-/// let config = RateLimitConfig::new(100, 60); // 100 requests per minute
+/// let config = RateLimitConfig::new(100, 60) // 100 requests per minute
+///     .with_tier_limit(PrincipalTier::Anonymous, 20, 60)
+///     .with_route_group(RouteGroup::new("uploads", "/v1/uploads").with_limit(10, 60));
 /// let rate_limiter = RateLimiterState::new(config);
 ///
 /// let app = Router::new()
@@ -221,34 +387,46 @@ pub async fn rate_limit(
     State(limiter): State<RateLimiterState>,
     request: Request,
     next: Next,
-) -> Result<Response, RateLimitError> {
+) -> Response {
     // Skip if rate limiting is disabled
     if !limiter.config.enabled {
-        return Ok(next.run(request).await);
+        return next.run(request).await;
     }
 
+    // Resolve the limit from the full path, even inside nested routers
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let tier = PrincipalTier::of(request.extensions().get::<AuthUser>());
+    let (group, limit) = limiter.config.resolve(request.method(), &path, tier);
+
     // Extract identifier for rate limiting
     let identifier = extract_identifier(&request, &limiter.config);
+    let key = format!("{}:{}:{}", group, tier.as_str(), identifier);
 
-    debug!("Rate limiting for identifier: {}", identifier);
+    debug!("Rate limiting for key: {}", key);
 
     // Check rate limit
-    let allowed = check_rate_limit(&limiter, &identifier).await;
-
-    if !allowed {
-        warn!("Rate limit exceeded for identifier: {}", identifier);
-        return Err(RateLimitError::LimitExceeded {
-            retry_after: limiter.config.window_secs,
-        });
-    }
-
-    // Continue processing request
-    let mut response = next.run(request).await;
+    let Some(decision) = check_rate_limit(&limiter, &key, limit).await else {
+        return next.run(request).await;
+    };
+
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        warn!("Rate limit exceeded for key: {}", key);
+        RateLimitError::LimitExceeded {
+            retry_after: ceil_secs(decision.retry_after.unwrap_or_default()),
+        }
+        .into_response()
+    };
 
     // Add rate limit headers
-    add_rate_limit_headers(&mut response, &limiter.config);
+    add_rate_limit_headers(&mut response, &decision, limit);
 
-    Ok(response)
+    response
 }
 
 /// Extract identifier for rate limiting
@@ -264,7 +442,7 @@ fn extract_identifier(request: &Request<Body>, config: &RateLimitConfig) -> Stri
 
     // Extract user ID from auth extension
     if config.by_user {
-        if let Some(user) = request.extensions().get::<crate::auth::AuthUser>() {
+        if let Some(user) = request.extensions().get::<AuthUser>() {
             parts.push(format!("user:{}", user.user_id()));
         }
     }
@@ -286,30 +464,49 @@ fn extract_identifier(request: &Request<Body>, config: &RateLimitConfig) -> Stri
     parts.join("|")
 }
 
-/// Check rate limit for an identifier
-async fn check_rate_limit(limiter: &RateLimiterState, identifier: &str) -> bool {
-    let mut storage = limiter.storage.write().await;
-
-    let bucket = storage
-        .entry(identifier.to_string())
-        .or_insert_with(|| {
-            TokenBucket::new(limiter.config.max_requests, limiter.config.window_secs)
-        });
+/// Take a token from the bucket for a key
+///
+/// Returns `None` if the store is unavailable.
+async fn check_rate_limit(
+    limiter: &RateLimiterState,
+    key: &str,
+    limit: Limit,
+) -> Option<RateLimitDecision> {
+    match limiter
+        .store
+        .acquire(key, limit.max_requests, limit.window())
+        .await
+    {
+        Ok(decision) => Some(decision),
+        Err(e) => {
+            warn!("Rate limit store unavailable, allowing request: {}", e);
+            None
+        }
+    }
+}
 
-    bucket.try_consume(1.0)
+/// Whole seconds, rounded up
+fn ceil_secs(duration: Duration) -> u64 {
+    let secs = duration.as_secs();
+    if duration.subsec_nanos() > 0 {
+        secs + 1
+    } else {
+        secs
+    }
 }
 
 /// Add rate limit headers to response
-fn add_rate_limit_headers(response: &mut Response, config: &RateLimitConfig) {
-    // Add standard rate limit headers
-    response.headers_mut().insert(
-        "X-RateLimit-Limit",
-        HeaderValue::from_str(&config.max_requests.to_string()).unwrap(),
+fn add_rate_limit_headers(response: &mut Response, decision: &RateLimitDecision, limit: Limit) {
+    let headers = response.headers_mut();
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(
+        RATE_LIMIT_RESET,
+        HeaderValue::from(ceil_secs(decision.reset_after)),
     );
-
-    response.headers_mut().insert(
-        "X-RateLimit-Window",
-        HeaderValue::from_str(&config.window_secs.to_string()).unwrap(),
+    headers.insert(
+        RATE_LIMIT_POLICY,
+        HeaderValue::from_str(&format!("{};w={}", limit.max_requests, limit.window_secs)).unwrap(),
     );
 }
 
@@ -364,6 +561,13 @@ impl std::error::Error for RateLimitError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::jwt::Claims;
+    use axum::{middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn test_user(role: &str) -> AuthUser {
+        AuthUser::new(Claims::new("user123", "test", "test", 3600).with_role(role))
+    }
 
     #[test]
     fn test_rate_limit_config() {
@@ -390,60 +594,33 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_token_bucket_creation() {
-        let bucket = TokenBucket::new(100, 60);
-        assert_eq!(bucket.capacity, 100.0);
-        assert_eq!(bucket.tokens, 100.0);
-    }
-
-    #[test]
-    fn test_token_bucket_consume() {
-        let mut bucket = TokenBucket::new(10, 60);
-
-        // Should be able to consume up to capacity
-        for _ in 0..10 {
-            assert!(bucket.try_consume(1.0));
-        }
-
-        // Should fail after exhausting tokens
-        assert!(!bucket.try_consume(1.0));
-    }
-
-    #[test]
-    fn test_token_bucket_refill() {
-        let mut bucket = TokenBucket::new(10, 10); // 1 token per second
-
-        // Consume all tokens
-        for _ in 0..10 {
-            bucket.try_consume(1.0);
-        }
-
-        assert_eq!(bucket.tokens, 0.0);
-
-        // Simulate time passing by manually updating last_refill
-        bucket.last_refill -= 5; // 5 seconds ago
-
-        // Refill should add 5 tokens
-        bucket.refill();
-        assert_eq!(bucket.tokens, 5.0);
-    }
-
     #[tokio::test]
     async fn test_rate_limiter_state() {
         let config = RateLimitConfig::new(5, 60);
         let limiter = RateLimiterState::new(config);
+        let limit = Limit::new(5, 60);
 
         // Should allow requests up to the limit
-        for _ in 0..5 {
-            assert!(check_rate_limit(&limiter, "test-user").await);
+        for remaining in (0..5).rev() {
+            let decision = check_rate_limit(&limiter, "test-user", limit)
+                .await
+                .unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
         }
 
         // Should deny additional requests
-        assert!(!check_rate_limit(&limiter, "test-user").await);
+        let decision = check_rate_limit(&limiter, "test-user", limit)
+            .await
+            .unwrap();
+        assert!(!decision.allowed);
+        assert!(decision.retry_after.is_some());
 
         // Different identifier should have its own limit
-        assert!(check_rate_limit(&limiter, "other-user").await);
+        let decision = check_rate_limit(&limiter, "other-user", limit)
+            .await
+            .unwrap();
+        assert!(decision.allowed);
     }
 
     #[test]
@@ -451,4 +628,103 @@ mod tests {
         let config = RateLimitConfig::disabled();
         assert!(!config.enabled);
     }
+
+    #[test]
+    fn test_principal_tier() {
+        assert_eq!(PrincipalTier::of(None), PrincipalTier::Anonymous);
+        assert_eq!(
+            PrincipalTier::of(Some(&test_user("user"))),
+            PrincipalTier::User
+        );
+        assert_eq!(
+            PrincipalTier::of(Some(&test_user("admin"))),
+            PrincipalTier::Admin
+        );
+    }
+
+    #[test]
+    fn test_limit_resolution() {
+        let config = RateLimitConfig::new(100, 60)
+            .with_tier_limit(PrincipalTier::Anonymous, 20, 60)
+            .with_route_group(
+                RouteGroup::new("uploads", "/v1/uploads")
+                    .with_method(Method::PUT)
+                    .with_limit(10, 60)
+                    .with_tier_limit(PrincipalTier::Admin, 50, 60),
+            );
+
+        let resolve = |method: Method, path: &str, tier| {
+            let (group, limit) = config.resolve(&method, path, tier);
+            (group.to_string(), limit.max_requests)
+        };
+
+        // Outside any group, the tier limit overrides the global limit
+        assert_eq!(
+            resolve(Method::GET, "/v1/assets", PrincipalTier::User),
+            ("default".into(), 100)
+        );
+        assert_eq!(
+            resolve(Method::GET, "/v1/assets", PrincipalTier::Anonymous),
+            ("default".into(), 20)
+        );
+
+        // Inside a group, the group limits win
+        assert_eq!(
+            resolve(
+                Method::PUT,
+                "/v1/uploads/1/chunks/0",
+                PrincipalTier::Anonymous
+            ),
+            ("uploads".into(), 10)
+        );
+        assert_eq!(
+            resolve(Method::PUT, "/v1/uploads/1/chunks/0", PrincipalTier::Admin),
+            ("uploads".into(), 50)
+        );
+
+        // Other methods are not part of the group
+        assert_eq!(
+            resolve(Method::GET, "/v1/uploads/1", PrincipalTier::User),
+            ("default".into(), 100)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers() {
+        let config = RateLimitConfig::new(2, 60)
+            .with_route_group(RouteGroup::new("uploads", "/uploads").with_limit(1, 30));
+        let limiter = RateLimiterState::new(config);
+
+        let app = Router::new()
+            .route("/assets", get(|| async { "OK" }))
+            .route("/uploads", get(|| async { "OK" }))
+            .layer(middleware::from_fn_with_state(limiter, rate_limit));
+        let send = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let response = send("/assets").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[RATE_LIMIT_LIMIT], "2");
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING], "1");
+        assert_eq!(response.headers()[RATE_LIMIT_RESET], "30");
+        assert_eq!(response.headers()[RATE_LIMIT_POLICY], "2;w=60");
+
+        // The upload group has its own bucket
+        let response = send("/uploads").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[RATE_LIMIT_POLICY], "1;w=30");
+
+        let response = send("/uploads").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING], "0");
+        assert_eq!(response.headers()["retry-after"], "30");
+
+        assert_eq!(send("/assets").await.status(), StatusCode::OK);
+    }
 }
@@ -3,6 +3,7 @@
 //! This module provides a comprehensive RBAC system with roles, permissions,
 //! and policy-based access control.
 
+use llm_registry_service::{AclEffect, AclEntry};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
@@ -154,6 +155,7 @@ impl RbacPolicy {
             Permission::new("asset", "read"),
             Permission::new("asset", "write"),
             Permission::new("asset", "delete"),
+            Permission::new("dependency", "read"),
             Permission::new("api-key", "create"),
             Permission::new("api-key", "read"),
         ]);
@@ -174,6 +176,7 @@ impl RbacPolicy {
         user.add_permissions(vec![
             Permission::new("asset", "read"),
             Permission::new("asset", "write"),
+            Permission::new("dependency", "read"),
         ]);
         self.add_role(user);
     }
@@ -259,6 +262,51 @@ impl RbacPolicy {
         false
     }
 
+    /// Check if a caller has a permission on an asset, given the ACL entries
+    /// that apply to the asset
+    ///
+    /// ACL entries take precedence over roles. A matching deny entry always
+    /// denies, and a matching allow entry grants the permission even if no
+    /// role does. If allow entries exist for the permission but none matches
+    /// the caller, the asset is restricted to those subjects and the caller is
+    /// denied. Otherwise the roles decide.
+    pub fn has_asset_permission(
+        &mut self,
+        principal: &str,
+        roles: &[String],
+        permission: &Permission,
+        acl: &[AclEntry],
+    ) -> bool {
+        let applicable: Vec<&AclEntry> = acl
+            .iter()
+            .filter(|entry| {
+                Permission::from_string(&entry.permission)
+                    .map(|p| p.matches(permission))
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let matches_caller = |entry: &&AclEntry| entry.subject.matches(principal, roles);
+
+        if applicable
+            .iter()
+            .filter(|entry| entry.effect == AclEffect::Deny)
+            .any(matches_caller)
+        {
+            return false;
+        }
+
+        let mut allows = applicable
+            .iter()
+            .filter(|entry| entry.effect == AclEffect::Allow)
+            .peekable();
+        if allows.peek().is_some() {
+            return allows.any(matches_caller);
+        }
+
+        self.has_permission(roles, permission)
+    }
+
     /// Check if a set of roles has ANY of the specified permissions
     pub fn has_any_permission(
         &mut self,
@@ -419,6 +467,49 @@ mod tests {
         assert!(policy.has_all_permissions(&admin_roles, &permissions));
     }
 
+    #[test]
+    fn test_asset_permission_with_acl() {
+        use llm_registry_core::AssetId;
+        use llm_registry_service::{AclSubject, AclTarget};
+
+        let mut policy = RbacPolicy::new();
+        let target = AclTarget::Asset(AssetId::new());
+        let read = Permission::new("asset", "read");
+        let write = Permission::new("asset", "write");
+        let viewer = vec!["viewer".to_string()];
+        let tuner = vec!["viewer".to_string(), "fine-tuning".to_string()];
+        let admin = vec!["admin".to_string()];
+
+        // No entries: roles decide
+        assert!(policy.has_asset_permission("alice", &viewer, &read, &[]));
+        assert!(!policy.has_asset_permission("alice", &viewer, &write, &[]));
+
+        // Allow entries restrict the asset to their subjects
+        let restricted = vec![AclEntry::new(
+            target.clone(),
+            AclSubject::Role("fine-tuning".to_string()),
+            "asset:read",
+            AclEffect::Allow,
+        )];
+        assert!(policy.has_asset_permission("alice", &tuner, &read, &restricted));
+        assert!(!policy.has_asset_permission("bob", &viewer, &read, &restricted));
+        assert!(!policy.has_asset_permission("root", &admin, &read, &restricted));
+        assert!(policy.has_asset_permission("root", &admin, &write, &restricted));
+
+        // Deny entries override roles and allows
+        let legal_hold = vec![
+            AclEntry::new(target.clone(), AclSubject::Everyone, "asset:write", AclEffect::Deny),
+            AclEntry::new(
+                target,
+                AclSubject::Principal("root".to_string()),
+                "asset:write",
+                AclEffect::Allow,
+            ),
+        ];
+        assert!(!policy.has_asset_permission("root", &admin, &write, &legal_hold));
+        assert!(policy.has_asset_permission("bob", &viewer, &read, &legal_hold));
+    }
+
     #[test]
     fn test_cache_invalidation() {
         let mut policy = RbacPolicy::new();
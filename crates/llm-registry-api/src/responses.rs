@@ -8,6 +8,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use llm_registry_service::FacetCounts;
 use serde::{Deserialize, Serialize};
 
 /// Standard success response wrapper
@@ -97,6 +98,10 @@ pub struct PaginatedResponse<T> {
 
     /// Pagination metadata
     pub pagination: PaginationMeta,
+
+    /// Value counts of the requested facets, if the endpoint supports them
+    #[serde(default, skip_serializing_if = "FacetCounts::is_empty")]
+    pub facets: FacetCounts,
 }
 
 /// Pagination metadata
@@ -113,6 +118,10 @@ pub struct PaginationMeta {
 
     /// Whether there are more results
     pub has_more: bool,
+
+    /// Cursor for the next page, if the endpoint supports cursors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl<T> PaginatedResponse<T> {
@@ -127,9 +136,24 @@ impl<T> PaginatedResponse<T> {
                 offset,
                 limit,
                 has_more,
+                next_cursor: None,
             },
+            facets: FacetCounts::new(),
         }
     }
+
+    /// Set the cursor for the next page, which also decides `has_more`
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.pagination.has_more = next_cursor.is_some();
+        self.pagination.next_cursor = next_cursor;
+        self
+    }
+
+    /// Attach facet counts
+    pub fn with_facets(mut self, facets: FacetCounts) -> Self {
+        self.facets = facets;
+        self
+    }
 }
 
 impl<T> IntoResponse for PaginatedResponse<T>
@@ -351,6 +375,26 @@ mod tests {
         assert_eq!(response.pagination.total, 10);
         assert_eq!(response.pagination.offset, 0);
         assert_eq!(response.pagination.limit, 5);
+        assert!(response.pagination.next_cursor.is_none());
+
+        let response = response.with_next_cursor(Some("abc".to_string()));
+        assert!(response.pagination.has_more);
+        assert_eq!(response.pagination.next_cursor.as_deref(), Some("abc"));
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("facets").is_none());
+        let facets = FacetCounts::from([(
+            llm_registry_service::Facet::Tag,
+            vec![llm_registry_service::FacetCount {
+                value: "nlp".to_string(),
+                count: 2,
+            }],
+        )]);
+        let json = serde_json::to_value(response.with_facets(facets)).unwrap();
+        assert_eq!(
+            json["facets"],
+            serde_json::json!({ "tag": [{ "value": "nlp", "count": 2 }] })
+        );
     }
 
     #[test]
@@ -3,19 +3,30 @@
 //! This module defines all API routes and builds the router.
 
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
-    routing::{delete, get, patch, post},
-    Router,
+    routing::{delete, get, patch, post, put},
+    Extension, Router,
 };
+use std::sync::Arc;
 
 use crate::{
     auth::{optional_auth, require_auth, AuthState},
-    auth_handlers::{generate_api_key, login, logout, me, refresh_token, AuthHandlerState},
-    graphql::{build_schema, graphql_handler, graphql_playground},
+    authorization::{authorize_request, Authorizer},
+    auth_handlers::{
+        create_api_key, jwks, list_api_keys, login, logout, logout_all, me, refresh_token,
+        revoke_api_key, revoke_user_sessions, AuthHandlerState,
+    },
+    graphql::{build_schema_with_authorizer, graphql_handler, graphql_playground, graphql_ws_handler},
     handlers::{
-        delete_asset, get_asset, get_dependencies, get_dependents, health_check, list_assets,
-        metrics, register_asset, update_asset, version_info, AppState,
+        abort_upload_session, commit_upload_session, create_acl_entry, create_upload_session,
+        create_user, delete_acl_entry, delete_asset, list_acl_entries,
+        download_content, generate_lockfile, get_asset, get_dependencies, get_dependents,
+        get_upload_session, get_user, health_check, list_assets, list_users, metrics,
+        register_asset, reset_user_password, set_user_roles, set_user_status, update_asset,
+        upload_chunk, upload_content, verify_lockfile, version_info, AppState,
     },
+    rate_limit::rate_limit,
 };
 
 /// Build the API router with all routes
@@ -37,8 +48,19 @@ pub fn build_router(state: AppState) -> Router {
 pub fn build_router_with_auth(
     state: AppState,
     auth_handler_state: AuthHandlerState,
-    auth_state: AuthState,
+    mut auth_state: AuthState,
 ) -> Router {
+    let mut auth_handler_state = auth_handler_state.with_authorizer(state.authorizer.clone());
+    let state = state.with_session_revocation(auth_handler_state.shared_jwt_manager());
+    if let Some(users) = state.services.users() {
+        auth_handler_state = auth_handler_state.with_user_service(users.clone());
+        auth_state = auth_state.with_user_service(users.clone());
+    }
+    if let Some(api_keys) = state.services.api_keys() {
+        auth_handler_state = auth_handler_state.with_api_key_service(api_keys.clone());
+        auth_state = auth_state.with_api_key_service(api_keys.clone());
+    }
+
     // Build public routes
     let public_routes = Router::new()
         .route("/health", get(health_check))
@@ -47,28 +69,46 @@ pub fn build_router_with_auth(
         .with_state(state.clone());
 
     // Build auth routes (public)
-    let auth_routes = Router::new()
-        .route("/login", post(login))
-        .route("/refresh", post(refresh_token))
+    let auth_routes = rate_limited(
+        Router::new()
+            .route("/login", post(login))
+            .route("/refresh", post(refresh_token)),
+        &state,
+    )
+    .with_state(auth_handler_state.clone());
+
+    // Public keys for verifying our tokens
+    let jwks_route = Router::new()
+        .route("/.well-known/jwks.json", get(jwks))
         .with_state(auth_handler_state.clone());
 
     // Build protected auth routes
-    let protected_auth_routes = Router::new()
-        .route("/me", get(me))
-        .route("/logout", post(logout))
-        .route("/api-keys", post(generate_api_key))
-        .layer(middleware::from_fn_with_state(
-            auth_state.clone(),
-            require_auth,
-        ))
-        .with_state(auth_handler_state);
+    let protected_auth_routes = rate_limited(
+        Router::new()
+            .route("/me", get(me))
+            .route("/logout", post(logout))
+            .route("/logout-all", post(logout_all))
+            .route(
+                "/users/{user_id}/revoke-sessions",
+                post(revoke_user_sessions),
+            )
+            .route("/api-keys", get(list_api_keys).post(create_api_key))
+            .route("/api-keys/{key_id}", delete(revoke_api_key)),
+        &state,
+    )
+    .layer(middleware::from_fn_with_state(
+        auth_state.clone(),
+        require_auth,
+    ))
+    .with_state(auth_handler_state);
 
-    // Build v1 routes (with optional authentication on some endpoints)
-    let v1_routes = build_v1_routes().with_state(state);
+    // Build v1 routes (permissions are enforced per route)
+    let v1_routes = build_authorized_v1_routes(state, auth_state);
 
     // Combine all routes
     Router::new()
         .merge(public_routes)
+        .merge(jwks_route)
         .nest("/v1/auth", auth_routes)
         .nest("/v1/auth", protected_auth_routes)
         .nest("/v1", v1_routes)
@@ -81,10 +121,21 @@ pub fn build_router_with_auth(
 pub fn build_router_with_graphql(
     state: AppState,
     auth_handler_state: AuthHandlerState,
-    auth_state: AuthState,
+    mut auth_state: AuthState,
 ) -> Router {
+    let mut auth_handler_state = auth_handler_state.with_authorizer(state.authorizer.clone());
+    let state = state.with_session_revocation(auth_handler_state.shared_jwt_manager());
+    if let Some(users) = state.services.users() {
+        auth_handler_state = auth_handler_state.with_user_service(users.clone());
+        auth_state = auth_state.with_user_service(users.clone());
+    }
+    if let Some(api_keys) = state.services.api_keys() {
+        auth_handler_state = auth_handler_state.with_api_key_service(api_keys.clone());
+        auth_state = auth_state.with_api_key_service(api_keys.clone());
+    }
+
     // Build GraphQL schema
-    let schema = build_schema(state.services.clone());
+    let schema = build_schema_with_authorizer(state.services.clone(), state.authorizer.clone());
 
     // Build public routes
     let public_routes = Router::new()
@@ -94,56 +145,160 @@ pub fn build_router_with_graphql(
         .route("/graphql/playground", get(graphql_playground))
         .with_state(state.clone());
 
-    // Build GraphQL route with optional authentication
-    let graphql_route = Router::new()
-        .route("/graphql", post(graphql_handler))
-        .layer(middleware::from_fn_with_state(
-            auth_state.clone(),
-            optional_auth,
-        ))
-        .with_state(schema);
+    // Build GraphQL route with optional authentication. Subscriptions may
+    // also authenticate once connected, so they need the auth state.
+    let graphql_route = rate_limited(
+        Router::new()
+            .route("/graphql", post(graphql_handler))
+            .route("/graphql/ws", get(graphql_ws_handler)),
+        &state,
+    )
+    .layer(middleware::from_fn_with_state(
+        auth_state.clone(),
+        optional_auth,
+    ))
+    .layer(Extension(auth_state.clone()))
+    .with_state(schema);
 
     // Build auth routes (public)
-    let auth_routes = Router::new()
-        .route("/login", post(login))
-        .route("/refresh", post(refresh_token))
+    let auth_routes = rate_limited(
+        Router::new()
+            .route("/login", post(login))
+            .route("/refresh", post(refresh_token)),
+        &state,
+    )
+    .with_state(auth_handler_state.clone());
+
+    // Public keys for verifying our tokens
+    let jwks_route = Router::new()
+        .route("/.well-known/jwks.json", get(jwks))
         .with_state(auth_handler_state.clone());
 
     // Build protected auth routes
-    let protected_auth_routes = Router::new()
-        .route("/me", get(me))
-        .route("/logout", post(logout))
-        .route("/api-keys", post(generate_api_key))
-        .layer(middleware::from_fn_with_state(
-            auth_state.clone(),
-            require_auth,
-        ))
-        .with_state(auth_handler_state);
+    let protected_auth_routes = rate_limited(
+        Router::new()
+            .route("/me", get(me))
+            .route("/logout", post(logout))
+            .route("/logout-all", post(logout_all))
+            .route(
+                "/users/{user_id}/revoke-sessions",
+                post(revoke_user_sessions),
+            )
+            .route("/api-keys", get(list_api_keys).post(create_api_key))
+            .route("/api-keys/{key_id}", delete(revoke_api_key)),
+        &state,
+    )
+    .layer(middleware::from_fn_with_state(
+        auth_state.clone(),
+        require_auth,
+    ))
+    .with_state(auth_handler_state);
 
-    // Build v1 routes
-    let v1_routes = build_v1_routes().with_state(state);
+    // Build v1 routes (permissions are enforced per route)
+    let v1_routes = build_authorized_v1_routes(state, auth_state);
 
     // Combine all routes
     Router::new()
         .merge(public_routes)
+        .merge(jwks_route)
         .merge(graphql_route)
         .nest("/v1/auth", auth_routes)
         .nest("/v1/auth", protected_auth_routes)
         .nest("/v1", v1_routes)
 }
 
+/// Build v1 API routes with authentication and permission checks
+///
+/// Each route requires the permission mapped to it in
+/// [`Operation::for_route`](crate::authorization::Operation::for_route).
+///
+/// Administrative routes are only served to authenticated callers, even when
+/// anonymous callers are granted roles.
+fn build_authorized_v1_routes(state: AppState, auth_state: AuthState) -> Router {
+    let routes = build_v1_routes().route_layer(middleware::from_fn_with_state(
+        state.authorizer.clone(),
+        authorize_request,
+    ));
+    let routes = rate_limited(routes, &state).layer(middleware::from_fn_with_state(
+        auth_state.clone(),
+        optional_auth,
+    ));
+
+    let admin_routes = build_admin_routes(state.authorizer.clone());
+    let admin_routes = rate_limited(admin_routes, &state)
+        .layer(middleware::from_fn_with_state(auth_state, require_auth));
+
+    routes.merge(admin_routes).with_state(state)
+}
+
+/// Apply the rate limiter of the application state, if any
+///
+/// Must be applied inside the authentication layer, so limits are resolved
+/// for the caller's principal tier.
+fn rate_limited<S>(router: Router<S>, state: &AppState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    match &state.rate_limiter {
+        Some(limiter) => router.layer(middleware::from_fn_with_state(limiter.clone(), rate_limit)),
+        None => router,
+    }
+}
+
 /// Build v1 API routes
 fn build_v1_routes() -> Router<AppState> {
     Router::new()
         // Asset management
         .route("/assets", post(register_asset))
         .route("/assets", get(list_assets))
-        .route("/assets/:id", get(get_asset))
-        .route("/assets/:id", patch(update_asset))
-        .route("/assets/:id", delete(delete_asset))
+        .route("/assets/{id}", get(get_asset))
+        .route("/assets/{id}", patch(update_asset))
+        .route("/assets/{id}", delete(delete_asset))
         // Dependencies
-        .route("/assets/:id/dependencies", get(get_dependencies))
-        .route("/assets/:id/dependents", get(get_dependents))
+        .route("/assets/{id}/dependencies", get(get_dependencies))
+        .route("/assets/{id}/dependents", get(get_dependents))
+        // Lockfiles
+        .route("/assets/{id}/lockfile", get(generate_lockfile))
+        .route("/lockfiles/verify", post(verify_lockfile))
+        // Content (uploads are streamed, so the default body limit does not apply)
+        .route(
+            "/assets/{id}/content",
+            put(upload_content)
+                .get(download_content)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        // Resumable chunked uploads
+        .route("/assets/{id}/uploads", post(create_upload_session))
+        .route("/uploads/{session_id}", get(get_upload_session))
+        .route("/uploads/{session_id}", delete(abort_upload_session))
+        .route(
+            "/uploads/{session_id}/chunks/{index}",
+            put(upload_chunk).layer(DefaultBodyLimit::disable()),
+        )
+        .route("/uploads/{session_id}/commit", post(commit_upload_session))
+}
+
+/// Build v1 administrative routes
+///
+/// Creating and deleting ACL entries is authorized by the handlers, which
+/// know the asset an entry targets.
+fn build_admin_routes(authorizer: Arc<Authorizer>) -> Router<AppState> {
+    Router::new()
+        // Access control lists
+        .route("/acls", get(list_acl_entries))
+        // User management
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/{user_id}", get(get_user))
+        .route("/users/{user_id}/password", put(reset_user_password))
+        .route("/users/{user_id}/roles", put(set_user_roles))
+        .route("/users/{user_id}/status", put(set_user_status))
+        .route_layer(middleware::from_fn_with_state(
+            authorizer,
+            authorize_request,
+        ))
+        // Routes below are not covered by the permission check
+        .route("/acls", post(create_acl_entry))
+        .route("/acls/{acl_id}", delete(delete_acl_entry))
 }
 
 /// Route configuration
@@ -187,6 +342,148 @@ impl RouteConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::jwt::{Claims, JwtConfig, JwtManager};
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+    };
+    use llm_registry_db::{InMemoryAssetRepository, InMemoryEventStore};
+    use llm_registry_service::{
+        InMemoryTokenRevocationStore, InMemoryUserStore, ServiceRegistryBuilder,
+    };
+    use tower::ServiceExt;
+
+    fn jwt_manager(revocations: &Arc<InMemoryTokenRevocationStore>) -> JwtManager {
+        let config = JwtConfig::new("test-secret")
+            .with_issuer("test")
+            .with_audience("test");
+        JwtManager::new(config)
+            .unwrap()
+            .with_revocation_store(revocations.clone())
+    }
+
+    struct TestApp {
+        app: Router,
+        tokens: JwtManager,
+    }
+
+    impl TestApp {
+        /// Router whose anonymous callers hold the admin role
+        fn new() -> Self {
+            let services = ServiceRegistryBuilder::new()
+                .repository(Arc::new(InMemoryAssetRepository::new()))
+                .event_store(Arc::new(InMemoryEventStore::new()))
+                .user_store(Arc::new(InMemoryUserStore::new()))
+                .build()
+                .unwrap();
+            let revocations = Arc::new(InMemoryTokenRevocationStore::new());
+            let authorizer =
+                Arc::new(Authorizer::default().with_anonymous_roles(vec!["admin".into()]));
+            let app = build_router_with_auth(
+                AppState::new(services).with_authorizer(authorizer),
+                AuthHandlerState::new(jwt_manager(&revocations)),
+                AuthState::new(jwt_manager(&revocations)),
+            );
+            Self {
+                app,
+                tokens: jwt_manager(&revocations),
+            }
+        }
+
+        fn token(&self, subject: &str, role: &str) -> String {
+            let claims = Claims::new(subject, "test", "test", 3600).with_role(role);
+            self.tokens.generate_token_with_claims(claims).unwrap()
+        }
+
+        async fn send(
+            &self,
+            method: Method,
+            uri: &str,
+            token: Option<&str>,
+            body: &str,
+        ) -> axum::response::Response {
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            let request = request.body(Body::from(body.to_string())).unwrap();
+            self.app.clone().oneshot(request).await.unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_require_authentication() {
+        let app = TestApp::new();
+        let developer = app.token("dev", "developer");
+
+        // Anonymous roles do not apply to administrative routes
+        let response = app.send(Method::GET, "/v1/users", None, "").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .send(Method::GET, "/v1/users", Some(&developer), "")
+            .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app
+            .send(Method::GET, "/v1/acls", Some(&developer), "")
+            .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_acl_entry_creation_is_authorized_by_handler() {
+        let app = TestApp::new();
+        let developer = app.token("dev", "developer");
+
+        let response = app.send(Method::POST, "/v1/acls", None, "{}").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Developers pass the route check; the handler finds no ACL store configured
+        let body = r#"{"target":{"type":"name_prefix","value":"ft/"},"subject":{"type":"everyone"},"permission":"asset:read","effect":"allow"}"#;
+        let response = app
+            .send(Method::POST, "/v1/acls", Some(&developer), body)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[tokio::test]
+    async fn test_disabling_user_revokes_tokens() {
+        let app = TestApp::new();
+        let admin = app.token("admin", "admin");
+
+        let body = r#"{"username":"alice","password":"correct horse","roles":["developer"]}"#;
+        let response = app
+            .send(Method::POST, "/v1/users", Some(&admin), body)
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let user: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let user_id = user["data"]["id"].as_str().unwrap();
+
+        let alice = app.tokens.generate_token(user_id).unwrap();
+        assert!(app.tokens.verify_token(&alice).await.is_ok());
+        let response = app
+            .send(
+                Method::PUT,
+                &format!("/v1/users/{}/status", user_id),
+                Some(&admin),
+                r#"{"disabled":true}"#,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(app.tokens.verify_token(&alice).await.is_err());
+    }
+
+    #[test]
+    fn test_v1_routes_build() {
+        // Registering a route with an invalid path template panics
+        let _ = build_v1_routes();
+        let _ = build_admin_routes(Arc::new(Authorizer::default()));
+    }
 
     #[test]
     fn test_route_config_default() {
@@ -309,6 +309,17 @@ pub struct Asset {
     /// Optional timestamp when the asset was deprecated
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated_at: Option<DateTime<Utc>>,
+
+    /// Monotonically increasing revision, bumped on every stored update
+    #[serde(default = "initial_revision")]
+    pub revision: u64,
+}
+
+/// Revision assigned to a newly created asset
+pub const INITIAL_REVISION: u64 = 1;
+
+fn initial_revision() -> u64 {
+    INITIAL_REVISION
 }
 
 impl Asset {
@@ -339,6 +350,7 @@ impl Asset {
             created_at: now,
             updated_at: now,
             deprecated_at: None,
+            revision: INITIAL_REVISION,
         })
     }
 
@@ -528,6 +540,7 @@ impl AssetBuilder {
             created_at: self.created_at,
             updated_at: self.created_at,
             deprecated_at,
+            revision: INITIAL_REVISION,
         })
     }
 
@@ -551,6 +564,7 @@ impl AssetBuilder {
             created_at: self.created_at,
             updated_at: self.created_at,
             deprecated_at,
+            revision: INITIAL_REVISION,
         }
     }
 }
@@ -669,6 +683,26 @@ mod tests {
         assert_eq!(asset.asset_type, AssetType::Model);
         assert!(asset.provenance.is_none());
         assert!(asset.dependencies.is_empty());
+        assert_eq!(asset.revision, INITIAL_REVISION);
+    }
+
+    #[test]
+    fn test_asset_revision_defaults_when_missing() {
+        let version = Version::parse("1.0.0").unwrap();
+        let metadata = AssetMetadata::new("gpt-2", version);
+        let asset = Asset::builder(
+            AssetType::Model,
+            metadata,
+            create_test_storage(),
+            create_test_checksum(),
+        )
+        .build()
+        .unwrap();
+
+        let mut value = serde_json::to_value(&asset).unwrap();
+        value.as_object_mut().unwrap().remove("revision");
+        let decoded: Asset = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.revision, INITIAL_REVISION);
     }
 
     #[test]
@@ -3,16 +3,92 @@
 //! This module provides types for representing and managing dependencies between assets,
 //! including circular dependency detection and dependency graph analysis.
 
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::str::FromStr;
 
 use crate::error::{RegistryError, Result};
 use crate::types::AssetId;
 
+/// Role a dependency plays for the asset that declares it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// Loaded whenever the asset is used
+    #[default]
+    Runtime,
+    /// Needed to build the asset, such as a base model
+    Build,
+    /// Evaluates the asset, such as a test suite
+    Evaluation,
+    /// Data the asset was trained on
+    TrainingData,
+    /// Used when available, such as an alternative tokenizer
+    Optional,
+}
+
+impl DependencyKind {
+    /// Every dependency kind
+    pub const ALL: [DependencyKind; 5] = [
+        DependencyKind::Runtime,
+        DependencyKind::Build,
+        DependencyKind::Evaluation,
+        DependencyKind::TrainingData,
+        DependencyKind::Optional,
+    ];
+
+    /// Name of the kind, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Runtime => "runtime",
+            DependencyKind::Build => "build",
+            DependencyKind::Evaluation => "evaluation",
+            DependencyKind::TrainingData => "training_data",
+            DependencyKind::Optional => "optional",
+        }
+    }
+
+    /// Whether a dependency of this kind keeps its target from being deleted
+    ///
+    /// An asset can do without its evaluations and optional dependencies,
+    /// but not without what it runs on, was built from or was trained on.
+    pub fn blocks_deletion(&self) -> bool {
+        match self {
+            DependencyKind::Runtime | DependencyKind::Build | DependencyKind::TrainingData => true,
+            DependencyKind::Evaluation | DependencyKind::Optional => false,
+        }
+    }
+
+    fn is_runtime(&self) -> bool {
+        *self == DependencyKind::Runtime
+    }
+}
+
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DependencyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        DependencyKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Invalid dependency kind: {}", s))
+    }
+}
+
 /// A reference to an asset as a dependency
 ///
-/// This can reference an asset either by its unique ID or by name and version.
+/// This can reference an asset by its unique ID, by name and exact version,
+/// or by name and a version requirement. Name-based references are resolved
+/// to IDs when the depending asset is registered. Every reference has a
+/// [`DependencyKind`], `runtime` unless given otherwise.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum AssetReference {
@@ -20,6 +96,9 @@ pub enum AssetReference {
     ById {
         /// The unique asset identifier
         id: AssetId,
+        /// Role of the dependency
+        #[serde(default, skip_serializing_if = "DependencyKind::is_runtime")]
+        kind: DependencyKind,
     },
     /// Reference by name and version
     ByNameVersion {
@@ -27,13 +106,29 @@ pub enum AssetReference {
         name: String,
         /// Semantic version or version constraint
         version: String,
+        /// Role of the dependency
+        #[serde(default, skip_serializing_if = "DependencyKind::is_runtime")]
+        kind: DependencyKind,
+    },
+    /// Reference by name and version requirement, such as `^1.2`
+    ByRequirement {
+        /// Asset name
+        name: String,
+        /// Versions the dependency may resolve to
+        requirement: VersionReq,
+        /// Role of the dependency
+        #[serde(default, skip_serializing_if = "DependencyKind::is_runtime")]
+        kind: DependencyKind,
     },
 }
 
 impl AssetReference {
     /// Create a reference by ID
     pub fn by_id(id: AssetId) -> Self {
-        AssetReference::ById { id }
+        AssetReference::ById {
+            id,
+            kind: DependencyKind::default(),
+        }
     }
 
     /// Create a reference by name and version
@@ -53,13 +148,53 @@ impl AssetReference {
             ));
         }
 
-        Ok(AssetReference::ByNameVersion { name, version })
+        Ok(AssetReference::ByNameVersion {
+            name,
+            version,
+            kind: DependencyKind::default(),
+        })
+    }
+
+    /// Create a reference by name and version requirement
+    pub fn by_requirement(name: impl Into<String>, requirement: VersionReq) -> Result<Self> {
+        let name = name.into();
+
+        if name.is_empty() {
+            return Err(RegistryError::ValidationError(
+                "Asset name cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(AssetReference::ByRequirement {
+            name,
+            requirement,
+            kind: DependencyKind::default(),
+        })
+    }
+
+    /// Set the role of the dependency
+    pub fn with_kind(mut self, kind: DependencyKind) -> Self {
+        match &mut self {
+            AssetReference::ById { kind: own, .. }
+            | AssetReference::ByNameVersion { kind: own, .. }
+            | AssetReference::ByRequirement { kind: own, .. } => *own = kind,
+        }
+        self
+    }
+
+    /// Get the role of the dependency
+    pub fn kind(&self) -> DependencyKind {
+        match self {
+            AssetReference::ById { kind, .. }
+            | AssetReference::ByNameVersion { kind, .. }
+            | AssetReference::ByRequirement { kind, .. } => *kind,
+        }
     }
 
     /// Get the asset ID if this is an ID reference
     pub fn as_id(&self) -> Option<&AssetId> {
         match self {
-            AssetReference::ById { id } => Some(id),
+            AssetReference::ById { id, .. } => Some(id),
             _ => None,
         }
     }
@@ -67,16 +202,40 @@ impl AssetReference {
     /// Get the name and version if this is a name/version reference
     pub fn as_name_version(&self) -> Option<(&str, &str)> {
         match self {
-            AssetReference::ByNameVersion { name, version } => Some((name.as_str(), version.as_str())),
+            AssetReference::ByNameVersion { name, version, .. } => {
+                Some((name.as_str(), version.as_str()))
+            }
             _ => None,
         }
     }
 
+    /// Get the name and version requirement if this is a name-based reference
+    ///
+    /// An exact version requires that version only. A `ByNameVersion` string
+    /// that is not a version is read as a requirement.
+    pub fn requirement(&self) -> Option<(&str, VersionReq)> {
+        match self {
+            AssetReference::ById { .. } => None,
+            AssetReference::ByNameVersion { name, version, .. } => {
+                let requirement = match Version::parse(version) {
+                    Ok(exact) => VersionReq::parse(&format!("={}", exact)),
+                    Err(_) => VersionReq::parse(version),
+                };
+                requirement
+                    .ok()
+                    .map(|requirement| (name.as_str(), requirement))
+            }
+            AssetReference::ByRequirement {
+                name, requirement, ..
+            } => Some((name.as_str(), requirement.clone())),
+        }
+    }
+
     /// Validate the reference
     pub fn validate(&self) -> Result<()> {
         match self {
             AssetReference::ById { .. } => Ok(()),
-            AssetReference::ByNameVersion { name, version } => {
+            AssetReference::ByNameVersion { name, version, .. } => {
                 if name.is_empty() {
                     return Err(RegistryError::ValidationError(
                         "Asset name cannot be empty".to_string(),
@@ -87,6 +246,20 @@ impl AssetReference {
                         "Asset version cannot be empty".to_string(),
                     ));
                 }
+                if self.requirement().is_none() {
+                    return Err(RegistryError::ValidationError(format!(
+                        "Invalid version or version requirement: {}",
+                        version
+                    )));
+                }
+                Ok(())
+            }
+            AssetReference::ByRequirement { name, .. } => {
+                if name.is_empty() {
+                    return Err(RegistryError::ValidationError(
+                        "Asset name cannot be empty".to_string(),
+                    ));
+                }
                 Ok(())
             }
         }
@@ -96,8 +269,13 @@ impl AssetReference {
 impl fmt::Display for AssetReference {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            AssetReference::ById { id } => write!(f, "id:{}", id),
-            AssetReference::ByNameVersion { name, version } => write!(f, "{}@{}", name, version),
+            AssetReference::ById { id, .. } => write!(f, "id:{}", id),
+            AssetReference::ByNameVersion { name, version, .. } => {
+                write!(f, "{}@{}", name, version)
+            }
+            AssetReference::ByRequirement {
+                name, requirement, ..
+            } => write!(f, "{}@{}", name, requirement),
         }
     }
 }
@@ -360,6 +538,76 @@ mod tests {
         assert!(reference.as_id().is_none());
     }
 
+    #[test]
+    fn test_asset_reference_requirement() {
+        let exact = AssetReference::by_name_version("gpt-2", "1.2.0").unwrap();
+        let (name, requirement) = exact.requirement().unwrap();
+        assert_eq!(name, "gpt-2");
+        assert!(requirement.matches(&Version::new(1, 2, 0)));
+        assert!(!requirement.matches(&Version::new(1, 2, 1)));
+
+        let range =
+            AssetReference::by_requirement("gpt-2", VersionReq::parse("^1.2").unwrap()).unwrap();
+        let (_, requirement) = range.requirement().unwrap();
+        assert!(requirement.matches(&Version::new(1, 9, 0)));
+        assert_eq!(range.to_string(), "gpt-2@^1.2");
+        assert!(AssetReference::by_requirement("", VersionReq::STAR).is_err());
+
+        let json = serde_json::to_value(&range).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"name": "gpt-2", "requirement": "^1.2"})
+        );
+        let parsed: AssetReference = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, range);
+
+        let invalid = AssetReference::ByNameVersion {
+            name: "gpt-2".to_string(),
+            version: "latest".to_string(),
+            kind: DependencyKind::Runtime,
+        };
+        assert!(invalid.requirement().is_none());
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_asset_reference_kind() {
+        let id = AssetId::new();
+        let reference = AssetReference::by_id(id);
+        assert_eq!(reference.kind(), DependencyKind::Runtime);
+        assert_eq!(
+            serde_json::to_value(&reference).unwrap(),
+            serde_json::json!({ "id": id.to_string() })
+        );
+
+        let trained_on = reference.with_kind(DependencyKind::TrainingData);
+        assert_eq!(trained_on.kind(), DependencyKind::TrainingData);
+        assert_eq!(trained_on.as_id(), Some(&id));
+        let json = serde_json::to_value(&trained_on).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "id": id.to_string(), "kind": "training_data" })
+        );
+        assert_eq!(serde_json::from_value::<AssetReference>(json).unwrap(), trained_on);
+
+        let parsed: AssetReference = serde_json::from_value(serde_json::json!({
+            "name": "test-suite",
+            "requirement": "^2",
+            "kind": "evaluation"
+        }))
+        .unwrap();
+        assert_eq!(parsed.kind(), DependencyKind::Evaluation);
+        assert_eq!(parsed.requirement().unwrap().0, "test-suite");
+
+        for kind in DependencyKind::ALL {
+            assert_eq!(kind.as_str().parse::<DependencyKind>(), Ok(kind));
+        }
+        assert!("tokenizer".parse::<DependencyKind>().is_err());
+        assert!(DependencyKind::TrainingData.blocks_deletion());
+        assert!(!DependencyKind::Evaluation.blocks_deletion());
+        assert!(!DependencyKind::Optional.blocks_deletion());
+    }
+
     #[test]
     fn test_asset_reference_validation_empty_name() {
         assert!(AssetReference::by_name_version("", "1.0.0").is_err());
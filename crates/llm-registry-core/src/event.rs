@@ -166,6 +166,18 @@ impl EventType {
         }
     }
 
+    /// Get the name of the asset associated with this event, if recorded
+    pub fn asset_name(&self) -> Option<&str> {
+        match self {
+            EventType::AssetRegistered { asset_name, .. }
+            | EventType::AssetUpdated { asset_name, .. }
+            | EventType::AssetDeleted { asset_name, .. }
+            | EventType::AssetStatusChanged { asset_name, .. }
+            | EventType::AssetDownloaded { asset_name, .. } => Some(asset_name),
+            _ => None,
+        }
+    }
+
     /// Check if this is a critical/error event
     pub fn is_critical(&self) -> bool {
         matches!(
@@ -16,7 +16,7 @@ pub mod types;
 // Re-exports for convenience
 pub use asset::{Asset, AssetMetadata, AssetType};
 pub use checksum::{Checksum, HashAlgorithm};
-pub use dependency::{AssetReference, DependencyGraph};
+pub use dependency::{AssetReference, DependencyGraph, DependencyKind};
 pub use error::{RegistryError, Result};
 pub use event::{EventType, RegistryEvent};
 pub use provenance::Provenance;
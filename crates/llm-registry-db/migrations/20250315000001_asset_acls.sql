@@ -0,0 +1,37 @@
+-- Per-asset and per-namespace access control lists
+-- Migration: 20250315000001_asset_acls
+
+-- ACL entries table: One allow/deny rule per row
+CREATE TABLE asset_acl_entries (
+    -- Entry identifier (ULID format)
+    id VARCHAR(26) PRIMARY KEY,
+
+    -- Target: exactly one of a specific asset or an asset name prefix
+    asset_id VARCHAR(26) REFERENCES assets(id) ON DELETE CASCADE,
+    name_prefix VARCHAR(255),
+
+    -- Subject: principal, role or everyone
+    subject_type VARCHAR(20) NOT NULL,
+    subject VARCHAR(255),
+
+    -- Permission in resource:action form, wildcards allowed
+    permission VARCHAR(100) NOT NULL,
+
+    -- allow or deny
+    effect VARCHAR(10) NOT NULL,
+
+    -- Who created the entry
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    CHECK ((asset_id IS NULL) <> (name_prefix IS NULL)),
+    CHECK (subject_type IN ('principal', 'role', 'everyone')),
+    CHECK (subject_type = 'everyone' OR subject IS NOT NULL),
+    CHECK (effect IN ('allow', 'deny'))
+);
+
+CREATE INDEX idx_asset_acl_entries_asset ON asset_acl_entries(asset_id);
+CREATE INDEX idx_asset_acl_entries_name_prefix ON asset_acl_entries(name_prefix);
+
+COMMENT ON TABLE asset_acl_entries IS 'Allow/deny rules attached to an asset or an asset name prefix';
@@ -0,0 +1,33 @@
+-- User accounts for password login
+-- Migration: 20250401000001_users
+
+-- Users table: One row per account
+CREATE TABLE users (
+    -- User identifier (ULID format)
+    id VARCHAR(26) PRIMARY KEY,
+
+    -- Login name and contact email
+    username VARCHAR(255) NOT NULL UNIQUE,
+    email VARCHAR(255),
+
+    -- Argon2 password hash in PHC string format
+    password_hash TEXT NOT NULL,
+
+    -- Roles granted to the user, embedded in issued tokens
+    roles TEXT[] NOT NULL DEFAULT '{}',
+
+    -- Account state
+    disabled BOOLEAN NOT NULL DEFAULT FALSE,
+    failed_login_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TIMESTAMPTZ,
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ,
+
+    -- Constraints
+    CHECK (failed_login_attempts >= 0)
+);
+
+COMMENT ON TABLE users IS 'Registry user accounts with hashed passwords, roles and lockout state';
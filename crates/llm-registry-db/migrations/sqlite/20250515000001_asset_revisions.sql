@@ -0,0 +1,6 @@
+-- Asset revisions for optimistic concurrency control
+-- Migration: 20250515000001_asset_revisions
+
+-- Revision counter: starts at 1 and is bumped by every update, so writers
+-- can detect that the row changed since they read it
+ALTER TABLE assets ADD COLUMN revision INTEGER NOT NULL DEFAULT 1 CHECK (revision >= 1);
@@ -0,0 +1,49 @@
+-- Resumable chunked upload sessions
+-- Migration: 20250620000001_upload_sessions
+
+-- Upload sessions table: One row per in-progress or finished chunked upload
+CREATE TABLE upload_sessions (
+    -- Session identifier (ULID format)
+    id TEXT PRIMARY KEY NOT NULL,
+
+    -- Asset receiving the content
+    asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+
+    -- Declared layout of the upload
+    total_size INTEGER NOT NULL,
+    chunk_size INTEGER NOT NULL,
+
+    -- Session lifecycle: active, committing, committed, aborted
+    status TEXT NOT NULL DEFAULT 'active',
+
+    -- Who created the session
+    created_by TEXT,
+
+    -- Timestamps
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+
+    -- Constraints
+    CHECK (total_size > 0),
+    CHECK (chunk_size > 0)
+);
+
+CREATE INDEX idx_upload_sessions_asset ON upload_sessions(asset_id);
+CREATE INDEX idx_upload_sessions_expires_at ON upload_sessions(expires_at);
+
+-- Upload session chunks table: Chunks received so far
+CREATE TABLE upload_session_chunks (
+    session_id TEXT NOT NULL REFERENCES upload_sessions(id) ON DELETE CASCADE,
+    chunk_index INTEGER NOT NULL,
+    size_bytes INTEGER NOT NULL,
+
+    -- Per-chunk integrity
+    checksum_algorithm TEXT NOT NULL,
+    checksum_value TEXT NOT NULL,
+
+    received_at TEXT NOT NULL,
+
+    PRIMARY KEY (session_id, chunk_index),
+    CHECK (chunk_index >= 0),
+    CHECK (size_bytes >= 0)
+);
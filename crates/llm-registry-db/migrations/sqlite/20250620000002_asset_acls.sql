@@ -0,0 +1,35 @@
+-- Per-asset and per-namespace access control lists
+-- Migration: 20250620000002_asset_acls
+
+-- ACL entries table: One allow/deny rule per row
+CREATE TABLE asset_acl_entries (
+    -- Entry identifier (ULID format)
+    id TEXT PRIMARY KEY NOT NULL,
+
+    -- Target: exactly one of a specific asset or an asset name prefix
+    asset_id TEXT REFERENCES assets(id) ON DELETE CASCADE,
+    name_prefix TEXT,
+
+    -- Subject: principal, role or everyone
+    subject_type TEXT NOT NULL,
+    subject TEXT,
+
+    -- Permission in resource:action form, wildcards allowed
+    permission TEXT NOT NULL,
+
+    -- allow or deny
+    effect TEXT NOT NULL,
+
+    -- Who created the entry
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+
+    -- Constraints
+    CHECK ((asset_id IS NULL) <> (name_prefix IS NULL)),
+    CHECK (subject_type IN ('principal', 'role', 'everyone')),
+    CHECK (subject_type = 'everyone' OR subject IS NOT NULL),
+    CHECK (effect IN ('allow', 'deny'))
+);
+
+CREATE INDEX idx_asset_acl_entries_asset ON asset_acl_entries(asset_id);
+CREATE INDEX idx_asset_acl_entries_name_prefix ON asset_acl_entries(name_prefix);
@@ -0,0 +1,32 @@
+-- User accounts for password login
+-- Migration: 20250620000003_users
+
+-- Users table: One row per account
+CREATE TABLE users (
+    -- User identifier (ULID format)
+    id TEXT PRIMARY KEY NOT NULL,
+
+    -- Login name and contact email
+    username TEXT NOT NULL UNIQUE,
+    email TEXT,
+
+    -- Argon2 password hash in PHC string format
+    password_hash TEXT NOT NULL,
+
+    -- Roles granted to the user as a JSON array, embedded in issued tokens
+    roles TEXT NOT NULL DEFAULT '[]',
+
+    -- Account state
+    disabled INTEGER NOT NULL DEFAULT 0,
+    failed_login_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TEXT,
+
+    -- Timestamps
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    last_login_at TEXT,
+
+    -- Constraints
+    CHECK (failed_login_attempts >= 0),
+    CHECK (json_valid(roles))
+);
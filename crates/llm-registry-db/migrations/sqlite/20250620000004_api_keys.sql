@@ -0,0 +1,32 @@
+-- Managed API keys
+-- Migration: 20250620000004_api_keys
+
+-- API keys table: One row per key, secrets are stored hashed
+CREATE TABLE api_keys (
+    -- Key identifier (ULID format), embedded in the key itself
+    id TEXT PRIMARY KEY NOT NULL,
+
+    -- Display name and owning principal
+    name TEXT NOT NULL,
+    owner TEXT NOT NULL,
+
+    -- Owner's roles at creation and the permissions the key is restricted
+    -- to, as JSON arrays
+    roles TEXT NOT NULL DEFAULT '[]',
+    permissions TEXT NOT NULL,
+
+    -- SHA-256 hash of the key secret
+    secret_hash TEXT NOT NULL,
+
+    -- Lifecycle
+    expires_at TEXT,
+    last_used_at TEXT,
+    revoked_at TEXT,
+    created_at TEXT NOT NULL,
+
+    -- Constraints
+    CHECK (json_valid(roles)),
+    CHECK (json_valid(permissions) AND json_array_length(permissions) > 0)
+);
+
+CREATE INDEX idx_api_keys_owner ON api_keys(owner);
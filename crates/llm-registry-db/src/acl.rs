@@ -0,0 +1,547 @@
+//! Access control list persistence
+//!
+//! ACL entries refine role-wide permissions for individual assets or for all
+//! assets whose name starts with a prefix. Each entry allows or denies one
+//! permission (`resource:action`, wildcards allowed) to a principal, a role or
+//! everyone. Evaluating entries against a caller is left to the API layer,
+//! which owns the role policy. Searches apply the entries within the query,
+//! through an [`AclReadFilter`] the API layer builds for the caller.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use llm_registry_core::AssetId;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use tracing::{debug, instrument};
+
+use crate::error::{DbError, DbResult};
+
+/// Whether an ACL entry grants or revokes a permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AclEffect {
+    /// Grant the permission
+    Allow,
+    /// Revoke the permission, overriding any allow
+    Deny,
+}
+
+impl AclEffect {
+    /// Database representation of the effect
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AclEffect::Allow => "allow",
+            AclEffect::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for AclEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AclEffect {
+    type Err = DbError;
+
+    fn from_str(s: &str) -> DbResult<Self> {
+        match s {
+            "allow" => Ok(AclEffect::Allow),
+            "deny" => Ok(AclEffect::Deny),
+            other => Err(DbError::InvalidData(format!("Invalid ACL effect: {}", other))),
+        }
+    }
+}
+
+/// Who an ACL entry applies to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "name", rename_all = "snake_case")]
+pub enum AclSubject {
+    /// A single user or service, by ID
+    Principal(String),
+    /// Every holder of a role
+    Role(String),
+    /// Every caller, authenticated or not
+    Everyone,
+}
+
+impl AclSubject {
+    /// Whether the subject covers a caller with the given ID and roles
+    pub fn matches(&self, principal: &str, roles: &[String]) -> bool {
+        match self {
+            AclSubject::Principal(p) => p == principal,
+            AclSubject::Role(role) => roles.iter().any(|r| r == role),
+            AclSubject::Everyone => true,
+        }
+    }
+
+    /// Database representation as (subject_type, subject)
+    pub(crate) fn to_columns(&self) -> (&'static str, Option<&str>) {
+        match self {
+            AclSubject::Principal(p) => ("principal", Some(p)),
+            AclSubject::Role(role) => ("role", Some(role)),
+            AclSubject::Everyone => ("everyone", None),
+        }
+    }
+
+    /// Parse from database columns
+    pub(crate) fn from_columns(subject_type: &str, subject: Option<String>) -> DbResult<Self> {
+        match (subject_type, subject) {
+            ("principal", Some(p)) => Ok(AclSubject::Principal(p)),
+            ("role", Some(role)) => Ok(AclSubject::Role(role)),
+            ("everyone", _) => Ok(AclSubject::Everyone),
+            (other, _) => Err(DbError::InvalidData(format!(
+                "Invalid ACL subject type: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// What an ACL entry is attached to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum AclTarget {
+    /// A specific asset
+    Asset(AssetId),
+    /// Every asset whose name starts with the prefix
+    NamePrefix(String),
+}
+
+impl AclTarget {
+    /// Whether the target covers an asset
+    pub fn covers(&self, asset_id: &AssetId, asset_name: &str) -> bool {
+        match self {
+            AclTarget::Asset(id) => id == asset_id,
+            AclTarget::NamePrefix(prefix) => asset_name.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+/// A single access control list entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AclEntry {
+    /// Entry identifier (ULID)
+    pub id: String,
+
+    /// Asset or name prefix the entry applies to
+    pub target: AclTarget,
+
+    /// Principal, role or everyone
+    pub subject: AclSubject,
+
+    /// Permission in `resource:action` form
+    pub permission: String,
+
+    /// Allow or deny
+    pub effect: AclEffect,
+
+    /// Who created the entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+
+    /// When the entry was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl AclEntry {
+    /// Create a new entry
+    pub fn new(
+        target: AclTarget,
+        subject: AclSubject,
+        permission: impl Into<String>,
+        effect: AclEffect,
+    ) -> Self {
+        Self {
+            id: ulid::Ulid::new().to_string(),
+            target,
+            subject,
+            permission: permission.into(),
+            effect,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the creator of the entry
+    pub fn with_created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = Some(created_by.into());
+        self
+    }
+}
+
+/// Restricts an asset search to the assets a caller may read
+///
+/// The API layer builds the filter from the caller and its role policy, and
+/// repositories evaluate it against the stored ACL entries within the search
+/// query, so totals, facets and pages only count readable assets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AclReadFilter {
+    /// Caller's principal ID
+    pub principal: String,
+
+    /// Caller's roles
+    pub roles: Vec<String>,
+
+    /// Entry permissions that grant reading, including wildcards such as
+    /// `asset:*`
+    pub permissions: Vec<String>,
+
+    /// Whether the caller's roles grant reading assets that no allow entry
+    /// restricts
+    pub role_allows: bool,
+}
+
+impl AclReadFilter {
+    /// Whether the caller may read an asset, given ACL entries that include
+    /// all those covering it
+    ///
+    /// A matching deny entry hides the asset. Allow entries restrict it to
+    /// the subjects they match. Otherwise the caller's roles decide.
+    pub fn allows(&self, entries: &[AclEntry], asset_id: &AssetId, asset_name: &str) -> bool {
+        let applicable: Vec<&AclEntry> = entries
+            .iter()
+            .filter(|entry| {
+                entry.target.covers(asset_id, asset_name)
+                    && self.permissions.contains(&entry.permission)
+            })
+            .collect();
+        let matches_caller = |entry: &AclEntry| entry.subject.matches(&self.principal, &self.roles);
+
+        if applicable
+            .iter()
+            .filter(|entry| entry.effect == AclEffect::Deny)
+            .any(|entry| matches_caller(entry))
+        {
+            return false;
+        }
+
+        let mut allows = applicable
+            .iter()
+            .filter(|entry| entry.effect == AclEffect::Allow)
+            .peekable();
+        if allows.peek().is_some() {
+            return allows.any(|entry| matches_caller(entry));
+        }
+
+        self.role_allows
+    }
+}
+
+/// Store for ACL entries
+#[async_trait]
+pub trait AclStore: Send + Sync {
+    /// Persist a new entry
+    async fn create(&self, entry: AclEntry) -> DbResult<AclEntry>;
+
+    /// Delete an entry
+    async fn delete(&self, id: &str) -> DbResult<()>;
+
+    /// Get an entry by ID
+    async fn get(&self, id: &str) -> DbResult<AclEntry>;
+
+    /// List every entry
+    async fn list(&self) -> DbResult<Vec<AclEntry>>;
+
+    /// List the entries that apply to an asset, by ID or name prefix
+    async fn list_for_asset(&self, asset_id: &AssetId, asset_name: &str)
+        -> DbResult<Vec<AclEntry>>;
+}
+
+/// PostgreSQL implementation of AclStore
+#[derive(Debug, Clone)]
+pub struct PostgresAclStore {
+    pool: PgPool,
+}
+
+impl PostgresAclStore {
+    /// Create a new PostgreSQL ACL store
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AclStore for PostgresAclStore {
+    #[instrument(skip(self, entry), fields(acl_id = %entry.id))]
+    async fn create(&self, entry: AclEntry) -> DbResult<AclEntry> {
+        debug!("Creating ACL entry");
+
+        let (asset_id, name_prefix) = match &entry.target {
+            AclTarget::Asset(id) => (Some(id.to_string()), None),
+            AclTarget::NamePrefix(prefix) => (None, Some(prefix.as_str())),
+        };
+        let (subject_type, subject) = entry.subject.to_columns();
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_acl_entries (
+                id, asset_id, name_prefix, subject_type, subject,
+                permission, effect, created_by, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&entry.id)
+        .bind(asset_id)
+        .bind(name_prefix)
+        .bind(subject_type)
+        .bind(subject)
+        .bind(&entry.permission)
+        .bind(entry.effect.as_str())
+        .bind(&entry.created_by)
+        .bind(entry.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM asset_acl_entries WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("ACL entry not found: {}", id)));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get(&self, id: &str) -> DbResult<AclEntry> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, asset_id, name_prefix, subject_type, subject,
+                   permission, effect, created_by, created_at
+            FROM asset_acl_entries
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("ACL entry not found: {}", id)))?;
+
+        row_to_entry(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<AclEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, asset_id, name_prefix, subject_type, subject,
+                   permission, effect, created_by, created_at
+            FROM asset_acl_entries
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_entry).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_for_asset(
+        &self,
+        asset_id: &AssetId,
+        asset_name: &str,
+    ) -> DbResult<Vec<AclEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, asset_id, name_prefix, subject_type, subject,
+                   permission, effect, created_by, created_at
+            FROM asset_acl_entries
+            WHERE asset_id = $1 OR starts_with($2, name_prefix)
+            ORDER BY id
+            "#,
+        )
+        .bind(asset_id.to_string())
+        .bind(asset_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_entry).collect()
+    }
+}
+
+/// In-memory implementation of AclStore
+///
+/// Entries are lost on restart, so this is intended for tests and
+/// single-node development setups.
+#[derive(Debug, Default)]
+pub struct InMemoryAclStore {
+    entries: RwLock<HashMap<String, AclEntry>>,
+}
+
+impl InMemoryAclStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every entry, in no particular order
+    pub(crate) fn entries(&self) -> Vec<AclEntry> {
+        self.entries.read().unwrap().values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl AclStore for InMemoryAclStore {
+    async fn create(&self, entry: AclEntry) -> DbResult<AclEntry> {
+        let mut entries = self.entries.write().unwrap();
+        if entries.contains_key(&entry.id) {
+            return Err(DbError::AlreadyExists(format!(
+                "ACL entry already exists: {}",
+                entry.id
+            )));
+        }
+        entries.insert(entry.id.clone(), entry.clone());
+        Ok(entry)
+    }
+
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        self.entries
+            .write()
+            .unwrap()
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| DbError::NotFound(format!("ACL entry not found: {}", id)))
+    }
+
+    async fn get(&self, id: &str) -> DbResult<AclEntry> {
+        self.entries
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| DbError::NotFound(format!("ACL entry not found: {}", id)))
+    }
+
+    async fn list(&self) -> DbResult<Vec<AclEntry>> {
+        let mut entries: Vec<AclEntry> = self.entries.read().unwrap().values().cloned().collect();
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(entries)
+    }
+
+    async fn list_for_asset(
+        &self,
+        asset_id: &AssetId,
+        asset_name: &str,
+    ) -> DbResult<Vec<AclEntry>> {
+        let mut entries = self.list().await?;
+        entries.retain(|entry| entry.target.covers(asset_id, asset_name));
+        Ok(entries)
+    }
+}
+
+/// Convert database row to AclEntry
+fn row_to_entry(row: PgRow) -> DbResult<AclEntry> {
+    let asset_id: Option<String> = row.get("asset_id");
+    let name_prefix: Option<String> = row.get("name_prefix");
+    let target = match (asset_id, name_prefix) {
+        (Some(id), _) => AclTarget::Asset(
+            AssetId::from_str(&id)
+                .map_err(|e| DbError::InvalidData(format!("Invalid asset ID: {}", e)))?,
+        ),
+        (None, Some(prefix)) => AclTarget::NamePrefix(prefix),
+        (None, None) => {
+            return Err(DbError::InvalidData("ACL entry has no target".to_string()));
+        }
+    };
+    let subject_type: String = row.get("subject_type");
+    let effect: String = row.get("effect");
+
+    Ok(AclEntry {
+        id: row.get("id"),
+        target,
+        subject: AclSubject::from_columns(&subject_type, row.get("subject"))?,
+        permission: row.get("permission"),
+        effect: effect.parse()?,
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use crate::memory::InMemoryAssetRepository;
+    use crate::postgres::PostgresAssetRepository;
+
+    #[test]
+    fn test_target_and_subject_matching() {
+        let id = AssetId::new();
+        assert!(AclTarget::Asset(id).covers(&id, "anything"));
+        assert!(!AclTarget::Asset(AssetId::new()).covers(&id, "anything"));
+        assert!(AclTarget::NamePrefix("ft/".to_string()).covers(&id, "ft/llama"));
+        assert!(!AclTarget::NamePrefix("ft/".to_string()).covers(&id, "base/llama"));
+
+        let roles = vec!["fine-tuning".to_string()];
+        assert!(AclSubject::Role("fine-tuning".to_string()).matches("alice", &roles));
+        assert!(!AclSubject::Role("legal".to_string()).matches("alice", &roles));
+        assert!(AclSubject::Principal("alice".to_string()).matches("alice", &[]));
+        assert!(AclSubject::Everyone.matches("anonymous", &[]));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryAclStore::new();
+        let id = AssetId::new();
+
+        let by_prefix = store
+            .create(AclEntry::new(
+                AclTarget::NamePrefix("ft/".to_string()),
+                AclSubject::Role("fine-tuning".to_string()),
+                "asset:read",
+                AclEffect::Allow,
+            ))
+            .await
+            .unwrap();
+        store
+            .create(AclEntry::new(
+                AclTarget::Asset(id),
+                AclSubject::Everyone,
+                "asset:write",
+                AclEffect::Deny,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(store.list().await.unwrap().len(), 2);
+        assert_eq!(store.list_for_asset(&id, "ft/model").await.unwrap().len(), 2);
+        assert_eq!(store.list_for_asset(&id, "other").await.unwrap().len(), 1);
+        assert!(store
+            .list_for_asset(&AssetId::new(), "other")
+            .await
+            .unwrap()
+            .is_empty());
+
+        store.delete(&by_prefix.id).await.unwrap();
+        assert!(store.delete(&by_prefix.id).await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_acl_store_conformance() {
+        let asset = conformance::owning_asset(&InMemoryAssetRepository::new()).await;
+        conformance::acl_store(&InMemoryAclStore::new(), &asset).await;
+    }
+
+    #[tokio::test]
+    async fn test_postgres_acl_store_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            let asset =
+                conformance::owning_asset(&PostgresAssetRepository::new(pool.clone())).await;
+            conformance::acl_store(&PostgresAclStore::new(pool), &asset).await;
+        }
+    }
+}
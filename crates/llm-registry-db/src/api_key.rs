@@ -0,0 +1,358 @@
+//! API key persistence
+//!
+//! API keys are long-lived credentials for scripts and services. Only a hash
+//! of each key's secret is stored; the secret itself is shown once when the
+//! key is created. Every key belongs to an owner, is restricted to a set of
+//! permissions, and can expire or be revoked individually. Generating and
+//! verifying secrets is left to the service layer.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::{debug, instrument};
+
+use crate::error::{DbError, DbResult};
+
+/// A managed API key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Key identifier (ULID), also embedded in the key itself
+    pub id: String,
+
+    /// Human readable name
+    pub name: String,
+
+    /// Principal the key acts on behalf of
+    pub owner: String,
+
+    /// Owner's roles when the key was created
+    pub roles: Vec<String>,
+
+    /// Permissions (`resource:action`) the key is restricted to
+    pub permissions: Vec<String>,
+
+    /// Hash of the key's secret
+    #[serde(skip_serializing)]
+    pub secret_hash: String,
+
+    /// When the key stops working
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// When the key was last used to authenticate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// When the key was revoked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+
+    /// When the key was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// Whether the key can be used at the given time
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.map_or(true, |expires| expires > now)
+    }
+}
+
+/// Store for API keys
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Persist a new key
+    async fn create(&self, key: ApiKey) -> DbResult<ApiKey>;
+
+    /// Find a key by ID
+    async fn find(&self, id: &str) -> DbResult<Option<ApiKey>>;
+
+    /// List the keys of an owner, newest first
+    async fn list_for_owner(&self, owner: &str) -> DbResult<Vec<ApiKey>>;
+
+    /// List every key, newest first
+    async fn list(&self) -> DbResult<Vec<ApiKey>>;
+
+    /// Mark a key as revoked
+    async fn revoke(&self, id: &str, at: DateTime<Utc>) -> DbResult<ApiKey>;
+
+    /// Record that a key was used
+    async fn touch(&self, id: &str, at: DateTime<Utc>) -> DbResult<()>;
+}
+
+/// PostgreSQL implementation of ApiKeyStore
+#[derive(Debug, Clone)]
+pub struct PostgresApiKeyStore {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyStore {
+    /// Create a new PostgreSQL API key store
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const API_KEY_COLUMNS: &str = "id, name, owner, roles, permissions, secret_hash, expires_at, \
+     last_used_at, revoked_at, created_at";
+
+#[async_trait]
+impl ApiKeyStore for PostgresApiKeyStore {
+    #[instrument(skip(self, key), fields(api_key_id = %key.id))]
+    async fn create(&self, key: ApiKey) -> DbResult<ApiKey> {
+        debug!("Creating API key");
+
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys (
+                id, name, owner, roles, permissions, secret_hash,
+                expires_at, last_used_at, revoked_at, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(&key.id)
+        .bind(&key.name)
+        .bind(&key.owner)
+        .bind(&key.roles)
+        .bind(&key.permissions)
+        .bind(&key.secret_hash)
+        .bind(key.expires_at)
+        .bind(key.last_used_at)
+        .bind(key.revoked_at)
+        .bind(key.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self))]
+    async fn find(&self, id: &str) -> DbResult<Option<ApiKey>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE id = $1",
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_api_key).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_for_owner(&self, owner: &str) -> DbResult<Vec<ApiKey>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE owner = $1 ORDER BY id DESC",
+            API_KEY_COLUMNS
+        ))
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<ApiKey>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys ORDER BY id DESC",
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke(&self, id: &str, at: DateTime<Utc>) -> DbResult<ApiKey> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, $2)
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(at)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("API key not found: {}", id)))?;
+
+        row_to_api_key(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn touch(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> {
+        sqlx::query("UPDATE api_keys SET last_used_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// In-memory implementation of ApiKeyStore
+///
+/// Keys are lost on restart, so this is intended for tests and single-node
+/// development setups.
+#[derive(Debug, Default)]
+pub struct InMemoryApiKeyStore {
+    keys: RwLock<HashMap<String, ApiKey>>,
+}
+
+impl InMemoryApiKeyStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sorted(&self, filter: impl Fn(&ApiKey) -> bool) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> = self
+            .keys
+            .read()
+            .unwrap()
+            .values()
+            .filter(|key| filter(key))
+            .cloned()
+            .collect();
+        keys.sort_by(|a, b| b.id.cmp(&a.id));
+        keys
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn create(&self, key: ApiKey) -> DbResult<ApiKey> {
+        let mut keys = self.keys.write().unwrap();
+        if keys.contains_key(&key.id) {
+            return Err(DbError::AlreadyExists(format!(
+                "API key already exists: {}",
+                key.id
+            )));
+        }
+        keys.insert(key.id.clone(), key.clone());
+        Ok(key)
+    }
+
+    async fn find(&self, id: &str) -> DbResult<Option<ApiKey>> {
+        Ok(self.keys.read().unwrap().get(id).cloned())
+    }
+
+    async fn list_for_owner(&self, owner: &str) -> DbResult<Vec<ApiKey>> {
+        Ok(self.sorted(|key| key.owner == owner))
+    }
+
+    async fn list(&self) -> DbResult<Vec<ApiKey>> {
+        Ok(self.sorted(|_| true))
+    }
+
+    async fn revoke(&self, id: &str, at: DateTime<Utc>) -> DbResult<ApiKey> {
+        let mut keys = self.keys.write().unwrap();
+        let key = keys
+            .get_mut(id)
+            .ok_or_else(|| DbError::NotFound(format!("API key not found: {}", id)))?;
+        key.revoked_at.get_or_insert(at);
+        Ok(key.clone())
+    }
+
+    async fn touch(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> {
+        if let Some(key) = self.keys.write().unwrap().get_mut(id) {
+            key.last_used_at = Some(at);
+        }
+        Ok(())
+    }
+}
+
+/// Convert database row to ApiKey
+fn row_to_api_key(row: PgRow) -> DbResult<ApiKey> {
+    Ok(ApiKey {
+        id: row.get("id"),
+        name: row.get("name"),
+        owner: row.get("owner"),
+        roles: row.get("roles"),
+        permissions: row.get("permissions"),
+        secret_hash: row.get("secret_hash"),
+        expires_at: row.get("expires_at"),
+        last_used_at: row.get("last_used_at"),
+        revoked_at: row.get("revoked_at"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use chrono::Duration;
+
+    fn key(id: &str, owner: &str) -> ApiKey {
+        ApiKey {
+            id: id.to_string(),
+            name: "ci".to_string(),
+            owner: owner.to_string(),
+            roles: vec!["developer".to_string()],
+            permissions: vec!["asset:read".to_string()],
+            secret_hash: "hash".to_string(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryApiKeyStore::new();
+        store.create(key("01A", "alice")).await.unwrap();
+        store.create(key("01B", "alice")).await.unwrap();
+        store.create(key("01C", "bob")).await.unwrap();
+        assert!(store.create(key("01C", "bob")).await.is_err());
+
+        let alice: Vec<String> = store
+            .list_for_owner("alice")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|k| k.id)
+            .collect();
+        assert_eq!(alice, vec!["01B", "01A"]);
+        assert_eq!(store.list().await.unwrap().len(), 3);
+
+        let now = Utc::now();
+        store.touch("01A", now).await.unwrap();
+        assert_eq!(store.find("01A").await.unwrap().unwrap().last_used_at, Some(now));
+
+        let revoked = store.revoke("01A", now).await.unwrap();
+        assert!(!revoked.is_active(now));
+        assert!(store.revoke("missing", now).await.unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_key_expiry() {
+        let now = Utc::now();
+        let mut key = key("01A", "alice");
+        assert!(key.is_active(now));
+        key.expires_at = Some(now - Duration::seconds(1));
+        assert!(!key.is_active(now));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_api_key_store_conformance() {
+        conformance::api_key_store(&InMemoryApiKeyStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_postgres_api_key_store_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            conformance::api_key_store(&PostgresApiKeyStore::new(pool)).await;
+        }
+    }
+}
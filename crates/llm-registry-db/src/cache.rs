@@ -261,6 +261,11 @@ impl RedisCache {
     pub fn custom_key(&self, namespace: &str, key: &str) -> String {
         format!("{}:{}:{}", self.config.key_prefix, namespace, key)
     }
+
+    /// Connection for commands not covered by the cache API
+    pub(crate) fn connection(&self) -> ConnectionManager {
+        self.connection.clone()
+    }
 }
 
 /// Cache statistics
@@ -0,0 +1,1622 @@
+//! Conformance tests shared by all repository and store implementations
+//!
+//! Each implementation runs the same checks, so the in-memory stores keep
+//! behaving like the PostgreSQL ones. The checks only touch assets and events
+//! they create themselves, so they can run against a shared database.
+//! PostgreSQL is tested when `DATABASE_URL` points to a database, and Redis
+//! when `REDIS_URL` points to a server.
+
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use llm_registry_core::{
+    Asset, AssetId, AssetMetadata, AssetReference, AssetType, Checksum, DependencyKind, EventType,
+    HashAlgorithm, Provenance, RegistryEvent, StorageBackend, StorageLocation,
+};
+use semver::Version;
+use std::collections::HashSet;
+
+use crate::acl::{AclEffect, AclEntry, AclReadFilter, AclStore, AclSubject, AclTarget};
+use crate::api_key::{ApiKey, ApiKeyStore};
+use crate::cache::{CacheConfig, RedisCache};
+use crate::dependency_graph::DependencyDirection;
+use crate::error::DbError;
+use crate::event_store::{EventQuery, EventStore};
+use crate::filter::{AnnotationFilter, Comparison, Range};
+use crate::pool::{create_pool, run_migrations, PoolConfig};
+use crate::rate_limit::RateLimitStore;
+use crate::repository::{
+    AssetRepository, Facet, FacetCount, FacetCounts, SearchQuery, SortField, SortOrder,
+};
+use crate::token_revocation::TokenRevocationStore;
+use crate::upload_session::{UploadChunk, UploadSession, UploadSessionStatus, UploadSessionStore};
+use crate::user_store::{User, UserStore};
+use crate::DEFAULT_DATABASE_URL_ENV;
+
+/// Connect to the test database, if one is configured
+pub(crate) async fn postgres_pool() -> Option<sqlx::PgPool> {
+    let url = std::env::var(DEFAULT_DATABASE_URL_ENV).ok()?;
+    let pool = create_pool(&PoolConfig::new(url)).await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    Some(pool)
+}
+
+/// Environment variable with the URL of the test Redis server
+const REDIS_URL_ENV: &str = "REDIS_URL";
+
+/// Connect to the test Redis server, if one is configured
+///
+/// Each client gets its own key prefix, so runs do not see each other's keys.
+pub(crate) async fn redis_cache() -> Option<RedisCache> {
+    let url = std::env::var(REDIS_URL_ENV).ok()?;
+    let prefix = format!("llm_registry_test_{}", AssetId::new());
+    Some(
+        RedisCache::new(CacheConfig::new(url).with_key_prefix(prefix))
+            .await
+            .unwrap(),
+    )
+}
+
+/// Timestamps with whole seconds, which every store keeps exactly
+fn base_time() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(0)
+}
+
+struct TestAsset<'a> {
+    name: String,
+    version: &'a str,
+    asset_type: AssetType,
+    tags: &'a [&'a str],
+    description: Option<&'a str>,
+    annotations: &'a [(&'a str, &'a str)],
+    author: Option<&'a str>,
+    size_bytes: Option<u64>,
+    backend: StorageBackend,
+    created_at: DateTime<Utc>,
+}
+
+impl TestAsset<'_> {
+    fn build(self) -> Asset {
+        let mut metadata =
+            AssetMetadata::new(self.name.clone(), Version::parse(self.version).unwrap());
+        metadata.tags = self.tags.iter().map(|t| t.to_string()).collect();
+        metadata.description = self.description.map(str::to_string);
+        for (key, value) in self.annotations {
+            metadata.add_annotation(*key, *value);
+        }
+        metadata.size_bytes = self.size_bytes;
+
+        let path = format!("{}/{}", self.name, self.version);
+        let storage = StorageLocation::new(self.backend, path, None).unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+
+        let mut asset =
+            Asset::new(AssetId::new(), self.asset_type, metadata, storage, checksum).unwrap();
+        asset.created_at = self.created_at;
+        asset.updated_at = self.created_at;
+        if let Some(author) = self.author {
+            asset.provenance = Some(Provenance {
+                author: Some(author.to_string()),
+                ..Provenance::new()
+            });
+        }
+        asset
+    }
+}
+
+fn ids(assets: &[Asset]) -> Vec<AssetId> {
+    assets.iter().map(|asset| asset.id).collect()
+}
+
+fn id_set(assets: &[Asset]) -> HashSet<AssetId> {
+    assets.iter().map(|asset| asset.id).collect()
+}
+
+/// Create an asset for stores whose records must reference one
+pub(crate) async fn owning_asset(repo: &dyn AssetRepository) -> Asset {
+    let name = format!("conformance-{}", AssetId::new()).to_lowercase();
+    let asset = TestAsset {
+        name,
+        version: "1.0.0",
+        asset_type: AssetType::Model,
+        tags: &[],
+        description: None,
+        annotations: &[],
+        author: None,
+        size_bytes: None,
+        backend: StorageBackend::FileSystem {
+            base_path: "/data".to_string(),
+        },
+        created_at: base_time(),
+    };
+    repo.create(asset.build()).await.unwrap()
+}
+
+/// Check an AssetRepository implementation
+pub(crate) async fn asset_repository(repo: &dyn AssetRepository) {
+    repo.health_check().await.unwrap();
+
+    let run = format!("conformance-{}", AssetId::new()).to_lowercase();
+    let t0 = base_time();
+    let s3 = || StorageBackend::S3 {
+        bucket: "models".to_string(),
+        region: "us-east-1".to_string(),
+        endpoint: None,
+    };
+
+    let count_before = repo.count_assets().await.unwrap();
+    let models_before = repo.count_by_type(&AssetType::Model).await.unwrap();
+
+    let alpha = repo
+        .create(
+            TestAsset {
+                name: format!("{}-alpha", run),
+                version: "1.0.0",
+                asset_type: AssetType::Model,
+                tags: &["prod", "nlp", "prod"],
+                description: Some("Summarises long documents"),
+                annotations: &[("framework", "pytorch"), ("params_b", "7")],
+                author: Some("alice"),
+                size_bytes: Some(10),
+                backend: s3(),
+                created_at: t0,
+            }
+            .build(),
+        )
+        .await
+        .unwrap();
+    let beta = repo
+        .create(
+            TestAsset {
+                name: format!("{}-beta", run),
+                version: "1.0.0",
+                asset_type: AssetType::Dataset,
+                tags: &["prod"],
+                description: Some("Evaluation data for alpha"),
+                annotations: &[("framework", "jax"), ("params_b", "13")],
+                author: Some("bob"),
+                size_bytes: None,
+                backend: StorageBackend::FileSystem {
+                    base_path: "/data".to_string(),
+                },
+                created_at: t0 + Duration::seconds(1),
+            }
+            .build(),
+        )
+        .await
+        .unwrap();
+    let mut alpha_v2 = TestAsset {
+        name: format!("{}-alpha", run),
+        version: "2.0.0",
+        asset_type: AssetType::Model,
+        tags: &[],
+        description: None,
+        annotations: &[
+            ("summary", "Quantized checkpoint"),
+            ("notes", "Unsearchable remark"),
+            ("framework", "pytorch"),
+            ("params_b", "7b"),
+        ],
+        author: None,
+        size_bytes: Some(30),
+        backend: s3(),
+        created_at: t0 + Duration::seconds(2),
+    }
+    .build();
+    alpha_v2.deprecated_at = Some(t0 + Duration::seconds(3));
+    alpha_v2.metadata.license = Some("MIT".to_string());
+    let alpha_v2 = repo.create(alpha_v2).await.unwrap();
+
+    assert_eq!(repo.count_assets().await.unwrap(), count_before + 3);
+    assert_eq!(
+        repo.count_by_type(&AssetType::Model).await.unwrap(),
+        models_before + 2
+    );
+
+    // Name and version are unique
+    let duplicate = TestAsset {
+        name: format!("{}-alpha", run),
+        version: "1.0.0",
+        asset_type: AssetType::Model,
+        tags: &[],
+        description: None,
+        annotations: &[],
+        author: None,
+        size_bytes: None,
+        backend: s3(),
+        created_at: t0,
+    }
+    .build();
+    assert!(repo
+        .create(duplicate)
+        .await
+        .unwrap_err()
+        .is_already_exists());
+
+    // Lookups
+    let found = repo.find_by_id(&alpha.id).await.unwrap().unwrap();
+    assert_eq!(found.metadata.name, alpha.metadata.name);
+    assert_eq!(found.metadata.version, Version::new(1, 0, 0));
+    assert_eq!(found.metadata.tags, vec!["nlp", "prod"]);
+    assert_eq!(
+        found.metadata.description.as_deref(),
+        Some("Summarises long documents")
+    );
+    assert_eq!(found.metadata.size_bytes, Some(10));
+    assert_eq!(found.created_at, t0);
+    assert!(repo.find_by_id(&AssetId::new()).await.unwrap().is_none());
+
+    let found = repo
+        .find_by_name_and_version(&alpha.metadata.name, &Version::new(2, 0, 0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, alpha_v2.id);
+    assert!(repo
+        .find_by_name_and_version(&alpha.metadata.name, &Version::new(3, 0, 0))
+        .await
+        .unwrap()
+        .is_none());
+
+    let found = repo
+        .find_by_ids(&[alpha.id, AssetId::new(), beta.id])
+        .await
+        .unwrap();
+    assert_eq!(id_set(&found), HashSet::from([alpha.id, beta.id]));
+    assert!(repo.find_by_ids(&[]).await.unwrap().is_empty());
+
+    let versions = repo.list_versions(&alpha.metadata.name).await.unwrap();
+    assert_eq!(ids(&versions), vec![alpha_v2.id, alpha.id]);
+
+    // Search filters; the run prefix keeps other data out of the results
+    let search = |query: SearchQuery| {
+        let query = query.text(run.clone());
+        async move { repo.search(&query).await.unwrap() }
+    };
+
+    let results = search(SearchQuery::new()).await;
+    assert_eq!(id_set(&results.assets), HashSet::from([alpha.id, beta.id]));
+    assert_eq!(results.total, 2);
+
+    let results = search(SearchQuery::new().exclude_deprecated(false)).await;
+    assert_eq!(results.total, 3);
+
+    let results = search(
+        SearchQuery::new()
+            .exclude_deprecated(false)
+            .asset_type(AssetType::Model),
+    )
+    .await;
+    assert_eq!(
+        id_set(&results.assets),
+        HashSet::from([alpha.id, alpha_v2.id])
+    );
+    assert_eq!(results.total, 2);
+
+    let results = search(SearchQuery::new().tag("prod").tag("nlp")).await;
+    assert_eq!(ids(&results.assets), vec![alpha.id]);
+    assert_eq!(results.total, 1);
+
+    let results = search(SearchQuery::new().tag("prod")).await;
+    assert_eq!(results.total, 2);
+
+    let results = search(SearchQuery::new().author("alice").exclude_deprecated(false)).await;
+    assert_eq!(ids(&results.assets), vec![alpha.id]);
+
+    let results = search(SearchQuery::new().storage_backend("FileSystem")).await;
+    assert_eq!(ids(&results.assets), vec![beta.id]);
+    assert_eq!(results.total, 1);
+
+    let results = search(
+        SearchQuery::new()
+            .exclude_deprecated(false)
+            .storage_path(alpha_v2.storage.path.clone()),
+    )
+    .await;
+    assert_eq!(ids(&results.assets), vec![alpha_v2.id]);
+
+    // Facets count every matching asset rather than the page, and skip
+    // missing values
+    let counts = |pairs: &[(&str, i64)]| -> Vec<FacetCount> {
+        pairs
+            .iter()
+            .map(|(value, count)| FacetCount {
+                value: value.to_string(),
+                count: *count,
+            })
+            .collect()
+    };
+    let mut query = SearchQuery::new().exclude_deprecated(false).limit(1);
+    for facet in Facet::ALL {
+        query = query.facet(facet);
+    }
+    let results = search(query).await;
+    let status = alpha.status.to_string();
+    let model = AssetType::Model.to_string();
+    let dataset = AssetType::Dataset.to_string();
+    let s3_name = s3().to_string();
+    let expected = FacetCounts::from([
+        (Facet::AssetType, counts(&[(&model, 2), (&dataset, 1)])),
+        (Facet::Tag, counts(&[("prod", 2), ("nlp", 1)])),
+        (Facet::License, counts(&[("MIT", 1)])),
+        (Facet::Status, counts(&[(&status, 3)])),
+        (Facet::Author, counts(&[("alice", 1), ("bob", 1)])),
+        (
+            Facet::StorageBackend,
+            counts(&[(&s3_name, 2), ("FileSystem", 1)]),
+        ),
+    ]);
+    assert_eq!(results.facets, expected);
+    assert_eq!(results.assets.len(), 1);
+
+    let results = search(
+        SearchQuery::new()
+            .asset_type(AssetType::Dataset)
+            .facet(Facet::Tag),
+    )
+    .await;
+    assert_eq!(
+        results.facets,
+        FacetCounts::from([(Facet::Tag, counts(&[("prod", 1)]))])
+    );
+    assert!(search(SearchQuery::new()).await.facets.is_empty());
+
+    // Excluded assets count towards neither the total nor the facets
+    let results = search(
+        SearchQuery::new()
+            .tag("prod")
+            .exclude_ids([alpha.id])
+            .facet(Facet::Tag),
+    )
+    .await;
+    assert_eq!(results.total, 1);
+    assert!(!id_set(&results.assets).contains(&alpha.id));
+    assert_eq!(
+        results.facets,
+        FacetCounts::from([(Facet::Tag, counts(&[("prod", 1)]))])
+    );
+
+    // Annotation predicates; only values written as numbers compare
+    let filtered = |query: SearchQuery| {
+        let query = query.exclude_deprecated(false);
+        async move { id_set(&search(query).await.assets) }
+    };
+    let annotated = |filter: AnnotationFilter| filtered(SearchQuery::new().annotation(filter));
+    assert_eq!(
+        annotated(AnnotationFilter::exists("framework")).await,
+        HashSet::from([alpha.id, beta.id, alpha_v2.id])
+    );
+    assert!(annotated(AnnotationFilter::exists("missing"))
+        .await
+        .is_empty());
+    assert_eq!(
+        annotated(AnnotationFilter::equals("framework", "pytorch")).await,
+        HashSet::from([alpha.id, alpha_v2.id])
+    );
+    assert_eq!(
+        annotated(AnnotationFilter::one_of("framework", ["jax", "flax"])).await,
+        HashSet::from([beta.id])
+    );
+    assert!(
+        annotated(AnnotationFilter::one_of("framework", Vec::<String>::new()))
+            .await
+            .is_empty()
+    );
+    assert_eq!(
+        annotated(AnnotationFilter::compare("params_b", Comparison::Ge, 7.0)).await,
+        HashSet::from([alpha.id, beta.id])
+    );
+    assert_eq!(
+        annotated(AnnotationFilter::compare("params_b", Comparison::Gt, 7.0)).await,
+        HashSet::from([beta.id])
+    );
+    let results = filtered(
+        SearchQuery::new()
+            .annotation(AnnotationFilter::equals("framework", "pytorch"))
+            .annotation(AnnotationFilter::compare("params_b", Comparison::Lt, 100.0)),
+    )
+    .await;
+    assert_eq!(results, HashSet::from([alpha.id]));
+
+    // Range filters are inclusive, and missing sizes fail bounded ranges
+    let sized = |range: Range<u64>| filtered(SearchQuery::new().size_bytes(range));
+    assert_eq!(
+        sized(Range::at_least(20)).await,
+        HashSet::from([alpha_v2.id])
+    );
+    assert_eq!(sized(Range::at_most(20)).await, HashSet::from([alpha.id]));
+    assert_eq!(
+        sized(Range::new(Some(10), Some(30))).await,
+        HashSet::from([alpha.id, alpha_v2.id])
+    );
+    assert_eq!(sized(Range::default()).await.len(), 3);
+    let created = |range| filtered(SearchQuery::new().created_at(range));
+    assert_eq!(
+        created(Range::at_least(t0 + Duration::seconds(1))).await,
+        HashSet::from([beta.id, alpha_v2.id])
+    );
+    assert_eq!(
+        created(Range::new(Some(t0), Some(t0))).await,
+        HashSet::from([alpha.id])
+    );
+    let results = filtered(SearchQuery::new().updated_at(Range::at_most(t0))).await;
+    assert_eq!(results, HashSet::from([alpha.id]));
+
+    // Text search is case-insensitive and covers the description
+    let results = repo
+        .search(&SearchQuery::new().text("SUMMARISES LONG").author("alice"))
+        .await
+        .unwrap();
+    assert!(ids(&results.assets).contains(&alpha.id));
+
+    // Query syntax: phrases, exclusions, fields and searchable annotations
+    let matching = |text: &str| {
+        let query = SearchQuery::new()
+            .exclude_deprecated(false)
+            .text(format!("{} {}", run, text));
+        async move { id_set(&repo.search(&query).await.unwrap().assets) }
+    };
+    assert_eq!(
+        matching(r#""long documents""#).await,
+        HashSet::from([alpha.id])
+    );
+    assert!(matching(r#""documents long""#).await.is_empty());
+    assert_eq!(
+        matching("-nlp").await,
+        HashSet::from([beta.id, alpha_v2.id])
+    );
+    assert_eq!(
+        matching("tag:prod").await,
+        HashSet::from([alpha.id, beta.id])
+    );
+    assert!(matching("name:summarises").await.is_empty());
+    assert_eq!(
+        matching("description:summarises").await,
+        HashSet::from([alpha.id])
+    );
+    assert_eq!(matching("quantized").await, HashSet::from([alpha_v2.id]));
+    assert!(matching("unsearchable").await.is_empty());
+
+    // Name matches rank above description matches
+    let ranked = |sort_order| {
+        let query = SearchQuery::new()
+            .exclude_deprecated(false)
+            .text(format!("{} alpha", run))
+            .sort_by(SortField::Relevance)
+            .sort_order(sort_order);
+        async move { ids(&repo.search(&query).await.unwrap().assets) }
+    };
+    let results = ranked(SortOrder::Descending).await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2], beta.id);
+    assert_eq!(ranked(SortOrder::Ascending).await[0], beta.id);
+
+    // Sorting and pagination
+    let sorted = |sort_by, sort_order| {
+        search(
+            SearchQuery::new()
+                .exclude_deprecated(false)
+                .sort_by(sort_by)
+                .sort_order(sort_order),
+        )
+    };
+    let results = sorted(SortField::CreatedAt, SortOrder::Ascending).await;
+    assert_eq!(ids(&results.assets), vec![alpha.id, beta.id, alpha_v2.id]);
+    let results = sorted(SortField::CreatedAt, SortOrder::Descending).await;
+    assert_eq!(ids(&results.assets), vec![alpha_v2.id, beta.id, alpha.id]);
+
+    // Missing sizes sort last in ascending order and first in descending order
+    let results = sorted(SortField::SizeBytes, SortOrder::Ascending).await;
+    assert_eq!(ids(&results.assets), vec![alpha.id, alpha_v2.id, beta.id]);
+    let results = sorted(SortField::SizeBytes, SortOrder::Descending).await;
+    assert_eq!(ids(&results.assets), vec![beta.id, alpha_v2.id, alpha.id]);
+
+    let results = sorted(SortField::Name, SortOrder::Descending).await;
+    assert_eq!(results.assets[0].id, beta.id);
+
+    let results = search(
+        SearchQuery::new()
+            .exclude_deprecated(false)
+            .sort_by(SortField::CreatedAt)
+            .sort_order(SortOrder::Ascending)
+            .limit(1)
+            .offset(1),
+    )
+    .await;
+    assert_eq!(ids(&results.assets), vec![beta.id]);
+    assert_eq!(results.total, 3);
+    assert_eq!((results.limit, results.offset), (1, 1));
+
+    // Following cursors one asset at a time visits every asset once, in order
+    for sort_by in [
+        SortField::CreatedAt,
+        SortField::UpdatedAt,
+        SortField::Name,
+        SortField::Version,
+        SortField::SizeBytes,
+        SortField::Relevance,
+    ] {
+        for sort_order in [SortOrder::Ascending, SortOrder::Descending] {
+            let query = SearchQuery::new()
+                .exclude_deprecated(false)
+                .sort_by(sort_by)
+                .sort_order(sort_order);
+            let expected = ids(&search(query.clone()).await.assets);
+
+            // Offsets are ignored once a cursor is given
+            let mut paged = Vec::new();
+            let mut page = search(query.clone().limit(1)).await;
+            loop {
+                paged.extend(ids(&page.assets));
+                match page.next_cursor.take() {
+                    Some(cursor) => {
+                        page = search(query.clone().limit(1).offset(2).cursor(cursor)).await;
+                    }
+                    None => break,
+                }
+            }
+            assert_eq!(paged, expected, "{:?} {:?}", sort_by, sort_order);
+        }
+    }
+
+    // The last page has no cursor, and cursors only fit queries sorted the same way
+    let results = search(SearchQuery::new().exclude_deprecated(false).limit(3)).await;
+    assert!(results.next_cursor.is_none());
+    let results = search(SearchQuery::new().exclude_deprecated(false).limit(1)).await;
+    let cursor = results.next_cursor.unwrap();
+    let err = repo
+        .search(
+            &SearchQuery::new()
+                .sort_order(SortOrder::Ascending)
+                .cursor(cursor),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidQuery(_)));
+    let err = repo
+        .search(&SearchQuery::new().cursor("garbage"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidQuery(_)));
+
+    // Tags
+    repo.add_tag(&alpha.id, "zeta").await.unwrap();
+    repo.add_tag(&alpha.id, "zeta").await.unwrap();
+    assert_eq!(
+        repo.get_tags(&alpha.id).await.unwrap(),
+        vec!["nlp", "prod", "zeta"]
+    );
+    repo.remove_tag(&alpha.id, "nlp").await.unwrap();
+    repo.remove_tag(&alpha.id, "missing").await.unwrap();
+    let found = repo.find_by_id(&alpha.id).await.unwrap().unwrap();
+    assert_eq!(found.metadata.tags, vec!["prod", "zeta"]);
+    assert!(repo.get_tags(&AssetId::new()).await.unwrap().is_empty());
+    assert!(repo.add_tag(&AssetId::new(), "orphan").await.is_err());
+    assert!(repo.add_tag(&alpha.id, "").await.is_err());
+
+    let all_tags = repo.list_all_tags().await.unwrap();
+    assert!(all_tags.contains(&"zeta".to_string()));
+
+    // Tag changes are searchable
+    assert_eq!(matching("tag:zeta").await, HashSet::from([alpha.id]));
+    assert!(matching("tag:nlp").await.is_empty());
+
+    // Dependencies
+    repo.add_dependency(&alpha.id, &beta.id, Some("^1.0"), DependencyKind::Runtime)
+        .await
+        .unwrap();
+    repo.add_dependency(&alpha.id, &beta.id, Some("^1.1"), DependencyKind::Build)
+        .await
+        .unwrap();
+    assert_eq!(
+        ids(&repo.list_dependencies(&alpha.id).await.unwrap()),
+        vec![beta.id]
+    );
+    assert_eq!(
+        ids(&repo.list_reverse_dependencies(&beta.id).await.unwrap()),
+        vec![alpha.id]
+    );
+    let found = repo.find_by_id(&alpha.id).await.unwrap().unwrap();
+    assert_eq!(
+        found.dependencies,
+        vec![AssetReference::by_id(beta.id).with_kind(DependencyKind::Build)]
+    );
+
+    let err = repo
+        .add_dependency(&beta.id, &alpha.id, None, DependencyKind::Optional)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::CircularDependency(_)));
+    assert!(repo
+        .add_dependency(&beta.id, &beta.id, None, DependencyKind::Runtime)
+        .await
+        .is_err());
+    assert!(repo
+        .add_dependency(&beta.id, &AssetId::new(), None, DependencyKind::Runtime)
+        .await
+        .is_err());
+
+    // Dependencies given at creation are stored as edges
+    let mut gamma = TestAsset {
+        name: format!("{}-gamma", run),
+        version: "0.1.0",
+        asset_type: AssetType::Pipeline,
+        tags: &[],
+        description: None,
+        annotations: &[],
+        author: None,
+        size_bytes: None,
+        backend: s3(),
+        created_at: t0 + Duration::seconds(4),
+    }
+    .build();
+    gamma.dependencies = vec![AssetReference::by_id(beta.id)];
+    let gamma = repo.create(gamma).await.unwrap();
+    assert_eq!(
+        id_set(&repo.list_reverse_dependencies(&beta.id).await.unwrap()),
+        HashSet::from([alpha.id, gamma.id])
+    );
+
+    // Closures follow edges in one direction, reaching each asset once
+    repo.add_dependency(&gamma.id, &alpha.id, None, DependencyKind::Evaluation)
+        .await
+        .unwrap();
+    let closure = repo
+        .dependency_closure(&gamma.id, DependencyDirection::Dependencies, None, &[])
+        .await
+        .unwrap();
+    let edges: Vec<(AssetId, AssetId, u32)> = closure
+        .edges
+        .iter()
+        .map(|edge| (edge.asset_id, edge.dependency_id, edge.depth))
+        .collect();
+    assert_eq!(edges.len(), 3);
+    assert!(edges.contains(&(gamma.id, alpha.id, 1)));
+    assert!(edges.contains(&(gamma.id, beta.id, 1)));
+    assert!(edges.contains(&(alpha.id, beta.id, 2)));
+    let nested = closure.edges.iter().find(|edge| edge.depth == 2).unwrap();
+    assert_eq!(nested.dependency_type, DependencyKind::Build);
+    assert_eq!(nested.version_constraint.as_deref(), Some("^1.1"));
+    assert_eq!(closure.nodes.len(), 2);
+    assert!(closure.nodes.iter().all(|node| node.depth == 1));
+    assert!(!closure.truncated && !closure.has_cycles());
+
+    // Kind filters leave out other edges and whatever only they reach
+    let closure = repo
+        .dependency_closure(
+            &gamma.id,
+            DependencyDirection::Dependencies,
+            None,
+            &[DependencyKind::Runtime, DependencyKind::Build],
+        )
+        .await
+        .unwrap();
+    let edges: Vec<(AssetId, AssetId, DependencyKind)> = closure
+        .edges
+        .iter()
+        .map(|edge| (edge.asset_id, edge.dependency_id, edge.dependency_type))
+        .collect();
+    assert_eq!(edges, vec![(gamma.id, beta.id, DependencyKind::Runtime)]);
+    assert_eq!(closure.nodes.len(), 1);
+
+    let closure = repo
+        .dependency_closure(&gamma.id, DependencyDirection::Dependencies, Some(1), &[])
+        .await
+        .unwrap();
+    assert_eq!(closure.edges.len(), 2);
+    assert!(closure.truncated);
+
+    let closure = repo
+        .dependency_closure(&beta.id, DependencyDirection::Dependents, None, &[])
+        .await
+        .unwrap();
+    let nodes: Vec<(AssetId, u32)> = closure
+        .nodes
+        .iter()
+        .map(|node| (node.asset_id, node.depth))
+        .collect();
+    assert_eq!(nodes.len(), 2);
+    assert!(nodes.contains(&(alpha.id, 1)) && nodes.contains(&(gamma.id, 1)));
+    assert_eq!(closure.edges.len(), 3);
+    assert!(closure
+        .edges
+        .iter()
+        .any(|edge| (edge.asset_id, edge.dependency_id, edge.depth) == (gamma.id, alpha.id, 2)));
+
+    let closure = repo
+        .dependency_closure(&AssetId::new(), DependencyDirection::Dependencies, None, &[])
+        .await
+        .unwrap();
+    assert!(closure.edges.is_empty() && closure.nodes.is_empty());
+    repo.remove_dependency(&gamma.id, &alpha.id).await.unwrap();
+
+    repo.remove_dependency(&alpha.id, &beta.id).await.unwrap();
+    repo.remove_dependency(&alpha.id, &beta.id).await.unwrap();
+    assert!(repo.list_dependencies(&alpha.id).await.unwrap().is_empty());
+
+    // Updates replace fields and tags but keep dependency edges
+    let mut updated = repo.find_by_id(&gamma.id).await.unwrap().unwrap();
+    updated.metadata.description = Some("Updated".to_string());
+    updated.metadata.tags = vec!["beta".to_string(), "alpha".to_string()];
+    updated.dependencies.clear();
+    assert_eq!(updated.revision, 1);
+    let returned = repo.update(updated, None).await.unwrap();
+    assert_eq!(returned.revision, 2);
+    let found = repo.find_by_id(&gamma.id).await.unwrap().unwrap();
+    assert_eq!(found.metadata.description.as_deref(), Some("Updated"));
+    assert_eq!(found.metadata.tags, vec!["alpha", "beta"]);
+    assert_eq!(found.dependencies, vec![AssetReference::by_id(beta.id)]);
+    assert_eq!(found.revision, 2);
+
+    // Updates with an expected revision only apply while it is current
+    let mut stale = found.clone();
+    stale.metadata.description = Some("Stale".to_string());
+    let err = repo.update(stale.clone(), Some(1)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        DbError::RevisionConflict {
+            expected: 1,
+            actual: 2
+        }
+    ));
+    assert_eq!(repo.update(stale, Some(2)).await.unwrap().revision, 3);
+    let found = repo.find_by_id(&gamma.id).await.unwrap().unwrap();
+    assert_eq!(found.metadata.description.as_deref(), Some("Stale"));
+    assert_eq!(found.revision, 3);
+
+    let mut missing = found.clone();
+    missing.id = AssetId::new();
+    let err = repo.update(missing.clone(), None).await.unwrap_err();
+    assert!(err.is_not_found());
+    let err = repo.update(missing, Some(3)).await.unwrap_err();
+    assert!(err.is_not_found());
+
+    let mut clash = found;
+    clash.metadata.name = alpha.metadata.name.clone();
+    clash.metadata.version = alpha.metadata.version.clone();
+    let err = repo.update(clash, None).await.unwrap_err();
+    assert!(err.is_already_exists());
+
+    // Deletes with an expected revision only apply while it is current
+    let beta_revision = repo.find_by_id(&beta.id).await.unwrap().unwrap().revision;
+    let err = repo
+        .delete(&beta.id, Some(beta_revision + 1), &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DbError::RevisionConflict { expected, actual }
+            if expected == beta_revision + 1 && actual == beta_revision
+    ));
+    assert!(repo.find_by_id(&beta.id).await.unwrap().is_some());
+
+    // Dependencies of a blocking kind keep an asset from being deleted
+    let err = repo
+        .delete(&beta.id, Some(beta_revision), &[DependencyKind::Runtime])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::HasDependents { count: 1, .. }));
+    assert!(repo.find_by_id(&beta.id).await.unwrap().is_some());
+
+    // Deleting an asset removes its edges
+    repo.delete(&beta.id, Some(beta_revision), &[DependencyKind::Optional])
+        .await
+        .unwrap();
+    assert!(repo.find_by_id(&beta.id).await.unwrap().is_none());
+    assert!(repo.list_dependencies(&gamma.id).await.unwrap().is_empty());
+    assert!(repo
+        .delete(&beta.id, None, &[])
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert!(repo
+        .delete(&beta.id, Some(beta_revision), &[])
+        .await
+        .unwrap_err()
+        .is_not_found());
+
+    for id in [alpha.id, alpha_v2.id, gamma.id] {
+        repo.delete(&id, None, &[]).await.unwrap();
+    }
+    assert_eq!(repo.count_assets().await.unwrap(), count_before);
+}
+
+fn registered(asset_id: AssetId, timestamp: DateTime<Utc>, actor: Option<&str>) -> RegistryEvent {
+    let mut event = RegistryEvent::new(EventType::AssetRegistered {
+        asset_id,
+        asset_name: "events".to_string(),
+        asset_version: "1.0.0".to_string(),
+        asset_type: "model".to_string(),
+    });
+    event.timestamp = timestamp;
+    event.actor = actor.map(str::to_string);
+    event
+}
+
+fn updated(asset_id: AssetId, timestamp: DateTime<Utc>, actor: Option<&str>) -> RegistryEvent {
+    let mut event = RegistryEvent::new(EventType::AssetUpdated {
+        asset_id,
+        asset_name: "events".to_string(),
+        updated_fields: vec!["description".to_string()],
+    });
+    event.timestamp = timestamp;
+    event.actor = actor.map(str::to_string);
+    event
+}
+
+/// Check an EventStore implementation
+pub(crate) async fn event_store(store: &dyn EventStore) {
+    store.health_check().await.unwrap();
+
+    let t0 = base_time();
+    let x = AssetId::new();
+    let y = AssetId::new();
+
+    let cursor = store.latest_sequence().await.unwrap();
+    let count_before = store.count_events().await.unwrap();
+    let updates_before = store.count_by_type("asset_updated").await.unwrap();
+
+    store
+        .append(registered(x, t0, Some("alice")))
+        .await
+        .unwrap();
+    store
+        .append(updated(x, t0 + Duration::seconds(1), None))
+        .await
+        .unwrap();
+    let batch = store
+        .append_batch(vec![
+            registered(y, t0 + Duration::seconds(2), Some("bob")),
+            updated(x, t0 + Duration::seconds(3), Some("bob")),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), 2);
+    assert!(store.append_batch(Vec::new()).await.unwrap().is_empty());
+
+    assert_eq!(store.count_events().await.unwrap(), count_before + 4);
+    assert_eq!(
+        store.count_by_type("asset_updated").await.unwrap(),
+        updates_before + 2
+    );
+
+    // Reading resumes after a cursor, in append order
+    let read = store.read_after(cursor, 100).await.unwrap();
+    assert_eq!(read.len(), 4);
+    assert!(read
+        .windows(2)
+        .all(|pair| pair[0].sequence < pair[1].sequence));
+    assert_eq!(read[0].event.asset_id(), Some(x));
+    assert_eq!(read[2].event.asset_id(), Some(y));
+    assert_eq!(
+        store.latest_sequence().await.unwrap(),
+        Some(read[3].sequence)
+    );
+    assert_eq!(
+        store.find_sequence(&read[1].event_id).await.unwrap(),
+        Some(read[1].sequence)
+    );
+    assert!(store
+        .find_sequence(&AssetId::new().to_string())
+        .await
+        .unwrap()
+        .is_none());
+    let read = store.read_after(Some(read[1].sequence), 1).await.unwrap();
+    assert_eq!(read.len(), 1);
+    assert_eq!(read[0].event.asset_id(), Some(y));
+
+    // Queries return the most recent events first
+    let results = store.query(&EventQuery::new().asset_id(x)).await.unwrap();
+    assert_eq!(results.total, 3);
+    let timestamps: Vec<_> = results.events.iter().map(|e| e.timestamp).collect();
+    assert_eq!(
+        timestamps,
+        vec![t0 + Duration::seconds(3), t0 + Duration::seconds(1), t0]
+    );
+
+    let results = store
+        .query(&EventQuery::new().asset_id(x).event_type("asset_updated"))
+        .await
+        .unwrap();
+    assert_eq!(results.total, 2);
+
+    // Events without an actor are attributed to the system
+    let results = store
+        .query(&EventQuery::new().asset_id(x).actor("system"))
+        .await
+        .unwrap();
+    assert_eq!(results.total, 1);
+    assert_eq!(results.events[0].actor.as_deref(), Some("system"));
+
+    let results = store
+        .query(
+            &EventQuery::new()
+                .asset_id(x)
+                .after(t0)
+                .before(t0 + Duration::seconds(3)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.total, 1);
+    assert_eq!(results.events[0].event_name(), "asset_updated");
+
+    let results = store
+        .query(&EventQuery::new().asset_id(x).limit(1).offset(1))
+        .await
+        .unwrap();
+    assert_eq!(results.events.len(), 1);
+    assert_eq!(results.events[0].timestamp, t0 + Duration::seconds(1));
+    assert_eq!(results.total, 3);
+    assert!(results.has_more());
+
+    // Cursors continue after the last event returned and ignore the offset
+    let first = store
+        .query(&EventQuery::new().asset_id(x).limit(2))
+        .await
+        .unwrap();
+    assert_eq!(first.events.len(), 2);
+    let cursor = first.next_cursor.unwrap();
+    let rest = store
+        .query(
+            &EventQuery::new()
+                .asset_id(x)
+                .limit(2)
+                .offset(1)
+                .cursor(cursor),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rest.events.len(), 1);
+    assert_eq!(rest.events[0].timestamp, t0);
+    assert!(rest.next_cursor.is_none());
+    let err = store
+        .query(&EventQuery::new().cursor("garbage"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidQuery(_)));
+
+    // Events with the same timestamp are ordered by ID
+    let z = AssetId::new();
+    store
+        .append_batch(vec![
+            registered(z, t0, None),
+            updated(z, t0, None),
+            updated(z, t0, None),
+        ])
+        .await
+        .unwrap();
+    let mut paged = 0;
+    let mut cursor = None;
+    loop {
+        let mut query = EventQuery::new().asset_id(z).limit(1);
+        if let Some(cursor) = cursor.take() {
+            query = query.cursor(cursor);
+        }
+        let page = store.query(&query).await.unwrap();
+        paged += page.events.len();
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(paged, 3);
+
+    let events = store.get_asset_events(&x, 2).await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].timestamp, t0 + Duration::seconds(3));
+
+    let latest = store.get_latest_event(&x).await.unwrap().unwrap();
+    assert_eq!(latest.timestamp, t0 + Duration::seconds(3));
+    assert_eq!(latest.actor.as_deref(), Some("bob"));
+    assert!(store
+        .get_latest_event(&AssetId::new())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Check an UploadSessionStore implementation
+///
+/// `asset_id` must refer to an existing asset where the store requires one.
+pub(crate) async fn upload_session_store(store: &dyn UploadSessionStore, asset_id: AssetId) {
+    let t0 = base_time();
+    let chunk = |index: u32, size_bytes: u64, digest: char| UploadChunk {
+        index,
+        size_bytes,
+        checksum: Checksum::new(HashAlgorithm::SHA256, digest.to_string().repeat(64)).unwrap(),
+        received_at: t0,
+    };
+
+    let session = store
+        .create(UploadSession::new(asset_id, 25, 10, Duration::hours(1)).with_created_by("alice"))
+        .await
+        .unwrap();
+    assert!(store
+        .create(session.clone())
+        .await
+        .unwrap_err()
+        .is_already_exists());
+
+    // Chunks may arrive in any order, and a re-upload replaces the record
+    store
+        .record_chunk(&session.id, chunk(2, 5, 'a'))
+        .await
+        .unwrap();
+    store
+        .record_chunk(&session.id, chunk(0, 10, 'b'))
+        .await
+        .unwrap();
+    store
+        .record_chunk(&session.id, chunk(0, 10, 'c'))
+        .await
+        .unwrap();
+
+    let found = store.find(&session.id).await.unwrap().unwrap();
+    assert_eq!(found.asset_id, asset_id);
+    assert_eq!((found.total_size, found.chunk_size), (25, 10));
+    assert_eq!(found.status, UploadSessionStatus::Active);
+    assert_eq!(found.created_by.as_deref(), Some("alice"));
+    assert_eq!(found.missing_chunks(), vec![1]);
+    assert_eq!(found.chunks[&0].checksum.value, "c".repeat(64));
+    assert_eq!(found.chunks[&2].size_bytes, 5);
+    assert_eq!(found.chunks[&2].received_at, t0);
+    assert!(store
+        .find("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Only one caller wins a status transition
+    let transition = |from, to| store.transition_status(&session.id, from, to);
+    assert!(
+        transition(UploadSessionStatus::Active, UploadSessionStatus::Committing)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !transition(UploadSessionStatus::Active, UploadSessionStatus::Committing)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !transition(UploadSessionStatus::Active, UploadSessionStatus::Aborted)
+            .await
+            .unwrap()
+    );
+    store
+        .set_status(&session.id, UploadSessionStatus::Committed)
+        .await
+        .unwrap();
+    let found = store.find(&session.id).await.unwrap().unwrap();
+    assert_eq!(found.status, UploadSessionStatus::Committed);
+
+    let unknown = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+    assert!(store
+        .set_status(unknown, UploadSessionStatus::Aborted)
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert!(store
+        .transition_status(
+            unknown,
+            UploadSessionStatus::Active,
+            UploadSessionStatus::Aborted
+        )
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert!(store
+        .record_chunk(unknown, chunk(0, 10, 'a'))
+        .await
+        .is_err());
+
+    // Expired sessions are listed whatever their status
+    let expired = store
+        .create(UploadSession::new(asset_id, 10, 10, Duration::zero()))
+        .await
+        .unwrap();
+    store
+        .record_chunk(&expired.id, chunk(0, 10, 'd'))
+        .await
+        .unwrap();
+    let listed = store.list_expired(Utc::now()).await.unwrap();
+    let listed: Vec<&UploadSession> = listed.iter().filter(|s| s.asset_id == asset_id).collect();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, expired.id);
+    assert!(listed[0].is_complete());
+
+    for id in [&session.id, &expired.id] {
+        store.delete(id).await.unwrap();
+        assert!(store.find(id).await.unwrap().is_none());
+    }
+    store.delete(&session.id).await.unwrap();
+}
+
+/// Check an AclStore implementation
+///
+/// `asset` must exist where the store requires entries to reference one.
+pub(crate) async fn acl_store(store: &dyn AclStore, asset: &Asset) {
+    let name = asset.metadata.name.as_str();
+    let entry = |target, subject, permission: &str, effect| AclEntry {
+        created_at: base_time(),
+        ..AclEntry::new(target, subject, permission, effect)
+    };
+
+    let by_id = store
+        .create(
+            entry(
+                AclTarget::Asset(asset.id),
+                AclSubject::Everyone,
+                "asset:write",
+                AclEffect::Deny,
+            )
+            .with_created_by("alice"),
+        )
+        .await
+        .unwrap();
+    let by_prefix = store
+        .create(entry(
+            AclTarget::NamePrefix(name[..name.len() - 1].to_string()),
+            AclSubject::Role("fine-tuning".to_string()),
+            "asset:read",
+            AclEffect::Allow,
+        ))
+        .await
+        .unwrap();
+    let unrelated = store
+        .create(entry(
+            AclTarget::NamePrefix(format!("{}-other", name)),
+            AclSubject::Principal("bob".to_string()),
+            "acl:write",
+            AclEffect::Allow,
+        ))
+        .await
+        .unwrap();
+    assert!(store
+        .create(by_id.clone())
+        .await
+        .unwrap_err()
+        .is_already_exists());
+
+    // Entries round-trip unchanged
+    assert_eq!(store.get(&by_id.id).await.unwrap(), by_id);
+    assert_eq!(store.get(&unrelated.id).await.unwrap(), unrelated);
+    assert!(store
+        .get("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+        .await
+        .unwrap_err()
+        .is_not_found());
+
+    let listed: Vec<String> = store
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.id)
+        .filter(|id| [&by_id.id, &by_prefix.id, &unrelated.id].contains(&id))
+        .collect();
+    let mut expected = vec![by_id.id.clone(), by_prefix.id.clone(), unrelated.id.clone()];
+    expected.sort();
+    assert_eq!(listed, expected);
+
+    // Entries apply by asset ID or by name prefix, ordered by ID
+    let applicable = store.list_for_asset(&asset.id, name).await.unwrap();
+    let mut expected = vec![by_id.clone(), by_prefix.clone()];
+    expected.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(applicable, expected);
+    let applicable = store.list_for_asset(&AssetId::new(), name).await.unwrap();
+    assert_eq!(applicable, vec![by_prefix.clone()]);
+
+    for entry in [&by_id, &by_prefix, &unrelated] {
+        store.delete(&entry.id).await.unwrap();
+        assert!(store.get(&entry.id).await.unwrap_err().is_not_found());
+    }
+    assert!(store.delete(&by_id.id).await.unwrap_err().is_not_found());
+}
+
+/// Check that an AssetRepository restricts searches to readable assets under
+/// the entries of an AclStore sharing its database
+pub(crate) async fn acl_search(repo: &dyn AssetRepository, store: &dyn AclStore) {
+    let run = format!("conformance-{}", AssetId::new()).to_lowercase();
+    let tags = [run.as_str()];
+    let create = |suffix: &str| {
+        let asset = TestAsset {
+            name: format!("{}/{}", run, suffix),
+            version: "1.0.0",
+            asset_type: AssetType::Model,
+            tags: &tags,
+            description: None,
+            annotations: &[],
+            author: None,
+            size_bytes: None,
+            backend: StorageBackend::FileSystem {
+                base_path: "/data".to_string(),
+            },
+            created_at: base_time(),
+        }
+        .build();
+        async move { repo.create(asset).await.unwrap() }
+    };
+    let open = create("open").await;
+    let tuned = create("ft/tuned").await;
+    let held = create("held").await;
+    let shared = create("shared").await;
+
+    let entries = [
+        AclEntry::new(
+            AclTarget::NamePrefix(format!("{}/ft/", run)),
+            AclSubject::Role("fine-tuning".to_string()),
+            "asset:read",
+            AclEffect::Allow,
+        ),
+        AclEntry::new(
+            AclTarget::Asset(held.id),
+            AclSubject::Everyone,
+            "*:*",
+            AclEffect::Deny,
+        ),
+        AclEntry::new(
+            AclTarget::Asset(shared.id),
+            AclSubject::Principal("alice".to_string()),
+            "asset:*",
+            AclEffect::Allow,
+        ),
+        // Other permissions do not affect reading
+        AclEntry::new(
+            AclTarget::Asset(open.id),
+            AclSubject::Everyone,
+            "asset:write",
+            AclEffect::Deny,
+        ),
+    ];
+    for entry in &entries {
+        store.create(entry.clone()).await.unwrap();
+    }
+
+    let readable = |principal: &str, roles: &[&str], role_allows: bool| {
+        let query = SearchQuery::new()
+            .tag(run.clone())
+            .facet(Facet::Tag)
+            .readable_by(AclReadFilter {
+                principal: principal.to_string(),
+                roles: roles.iter().map(|role| role.to_string()).collect(),
+                permissions: ["asset:read", "asset:*", "*:read", "*:*"]
+                    .map(str::to_string)
+                    .to_vec(),
+                role_allows,
+            });
+        let run = run.clone();
+        async move {
+            // Hidden assets count towards neither the total nor the facets
+            let results = repo.search(&query).await.unwrap();
+            assert_eq!(results.total, results.assets.len() as i64);
+            let tag_counts = results.facets.get(&Facet::Tag).cloned().unwrap_or_default();
+            let expected: Vec<FacetCount> = (results.total > 0)
+                .then(|| FacetCount {
+                    value: run.clone(),
+                    count: results.total,
+                })
+                .into_iter()
+                .collect();
+            assert_eq!(tag_counts, expected);
+            id_set(&results.assets)
+        }
+    };
+
+    // Allow entries restrict assets to their subjects, and deny entries hide
+    // them from everyone
+    assert_eq!(
+        readable("bob", &["viewer"], true).await,
+        id_set(std::slice::from_ref(&open))
+    );
+    assert_eq!(
+        readable("carol", &["fine-tuning"], true).await,
+        id_set(&[open.clone(), tuned.clone()])
+    );
+    assert_eq!(
+        readable("alice", &["viewer"], true).await,
+        id_set(&[open.clone(), shared.clone()])
+    );
+
+    // Without a role granting reading, only allow entries do
+    assert_eq!(
+        readable("carol", &["fine-tuning"], false).await,
+        id_set(std::slice::from_ref(&tuned))
+    );
+    assert!(readable("bob", &[], false).await.is_empty());
+
+    // Paging through readable assets skips the others
+    let query = SearchQuery::new()
+        .tag(run.clone())
+        .readable_by(AclReadFilter {
+            principal: "alice".to_string(),
+            roles: Vec::new(),
+            permissions: ["asset:read", "asset:*", "*:read", "*:*"]
+                .map(str::to_string)
+                .to_vec(),
+            role_allows: true,
+        })
+        .sort_by(SortField::Name)
+        .sort_order(SortOrder::Ascending)
+        .limit(1);
+    let first = repo.search(&query).await.unwrap();
+    assert_eq!(first.total, 2);
+    assert_eq!(ids(&first.assets), vec![open.id]);
+    let cursor = first.next_cursor.unwrap();
+    let second = repo.search(&query.clone().cursor(cursor)).await.unwrap();
+    assert_eq!(ids(&second.assets), vec![shared.id]);
+    assert!(second.next_cursor.is_none());
+
+    for entry in &entries {
+        store.delete(&entry.id).await.unwrap();
+    }
+    for asset in [&open, &tuned, &held, &shared] {
+        repo.delete(&asset.id, None, &[]).await.unwrap();
+    }
+}
+
+/// Check a UserStore implementation
+pub(crate) async fn user_store(store: &dyn UserStore) {
+    let t0 = base_time();
+    let suffix = AssetId::new().to_string().to_lowercase();
+    let new_user = |name: &str| User {
+        created_at: t0,
+        updated_at: t0,
+        ..User::new(format!("{}-{}", name, suffix), "$argon2id$hash")
+    };
+
+    let alice = store
+        .create(
+            new_user("alice")
+                .with_email("alice@example.org")
+                .with_roles(vec!["developer".to_string(), "viewer".to_string()]),
+        )
+        .await
+        .unwrap();
+    let bob = store.create(new_user("bob")).await.unwrap();
+
+    // Usernames are unique
+    let taken = User::new(alice.username.clone(), "other");
+    assert!(store.create(taken).await.unwrap_err().is_already_exists());
+
+    // Users round-trip unchanged
+    assert_eq!(store.find_by_id(&alice.id).await.unwrap().unwrap(), alice);
+    assert_eq!(
+        store
+            .find_by_username(&bob.username)
+            .await
+            .unwrap()
+            .unwrap(),
+        bob
+    );
+    assert!(store
+        .find_by_id("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(store
+        .find_by_username(&format!("nobody-{}", suffix))
+        .await
+        .unwrap()
+        .is_none());
+
+    let listed: Vec<String> = store
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|user| user.username.ends_with(&suffix))
+        .map(|user| user.username)
+        .collect();
+    assert_eq!(listed, vec![alice.username.clone(), bob.username.clone()]);
+
+    // Updates replace the mutable fields
+    let mut changed = alice.clone();
+    changed.email = None;
+    changed.password_hash = "$argon2id$new".to_string();
+    changed.roles = vec!["admin".to_string()];
+    changed.disabled = true;
+    let updated = store.update(changed).await.unwrap();
+    assert_eq!(updated.email, None);
+    assert_eq!(updated.password_hash, "$argon2id$new");
+    assert_eq!(updated.roles, vec!["admin"]);
+    assert!(updated.disabled);
+    assert_eq!(updated.created_at, t0);
+    assert_eq!(store.find_by_id(&alice.id).await.unwrap().unwrap(), updated);
+    let mut missing = new_user("missing");
+    missing.id = "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string();
+    assert!(store.update(missing).await.unwrap_err().is_not_found());
+
+    // Failed logins lock the account once the limit is reached
+    let until = t0 + Duration::minutes(15);
+    let after_one = store.record_login_failure(&bob.id, 2, until).await.unwrap();
+    assert_eq!(after_one.failed_login_attempts, 1);
+    assert!(!after_one.is_locked(t0));
+    let after_two = store.record_login_failure(&bob.id, 2, until).await.unwrap();
+    assert_eq!(after_two.failed_login_attempts, 0);
+    assert_eq!(after_two.locked_until, Some(until));
+
+    let success = store.record_login_success(&bob.id, t0).await.unwrap();
+    assert_eq!(success.failed_login_attempts, 0);
+    assert_eq!(success.locked_until, None);
+    assert_eq!(success.last_login_at, Some(t0));
+
+    let unknown = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+    assert!(store
+        .record_login_failure(unknown, 2, until)
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert!(store
+        .record_login_success(unknown, t0)
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+/// Check a TokenRevocationStore implementation
+pub(crate) async fn token_revocation_store(store: &dyn TokenRevocationStore) {
+    let now = Utc::now();
+    let expires = now + Duration::hours(1);
+    let before = now - Duration::microseconds(1);
+    let jti = || AssetId::new().to_string();
+    let (alice, bob) = (jti(), jti());
+
+    // Single tokens
+    let (revoked, other) = (jti(), jti());
+    assert!(!store.is_revoked(&revoked, &alice, now).await.unwrap());
+    store.revoke(&revoked, expires).await.unwrap();
+    assert!(store.is_revoked(&revoked, &alice, now).await.unwrap());
+    assert!(store.is_revoked(&revoked, &bob, now).await.unwrap());
+    assert!(!store.is_revoked(&other, &alice, now).await.unwrap());
+
+    // Subjects: tokens issued before the cutoff, to the microsecond
+    store.revoke_subject(&alice, now, expires).await.unwrap();
+    assert!(store.is_revoked(&other, &alice, before).await.unwrap());
+    assert!(!store.is_revoked(&other, &alice, now).await.unwrap());
+    assert!(!store
+        .is_revoked(&other, &alice, now + Duration::milliseconds(1))
+        .await
+        .unwrap());
+    assert!(!store.is_revoked(&other, &bob, before).await.unwrap());
+
+    // Cutoffs only move forward
+    store
+        .revoke_subject(&alice, now - Duration::hours(1), expires)
+        .await
+        .unwrap();
+    assert!(store.is_revoked(&other, &alice, before).await.unwrap());
+    let later = now + Duration::seconds(1);
+    store.revoke_subject(&alice, later, expires).await.unwrap();
+    assert!(store.is_revoked(&other, &alice, now).await.unwrap());
+    assert!(!store.is_revoked(&other, &alice, later).await.unwrap());
+}
+
+/// Check an ApiKeyStore implementation
+pub(crate) async fn api_key_store(store: &dyn ApiKeyStore) {
+    let t0 = base_time();
+    let suffix = AssetId::new().to_string().to_lowercase();
+    let (alice, bob) = (format!("alice-{}", suffix), format!("bob-{}", suffix));
+    let key = |owner: &str| ApiKey {
+        id: AssetId::new().to_string(),
+        name: "ci".to_string(),
+        owner: owner.to_string(),
+        roles: vec!["developer".to_string()],
+        permissions: vec!["asset:read".to_string(), "asset:write".to_string()],
+        secret_hash: "a".repeat(64),
+        expires_at: Some(t0 + Duration::days(30)),
+        last_used_at: None,
+        revoked_at: None,
+        created_at: t0,
+    };
+
+    let first = store.create(key(&alice)).await.unwrap();
+    let second = store.create(key(&alice)).await.unwrap();
+    let other = store.create(key(&bob)).await.unwrap();
+    assert!(store
+        .create(first.clone())
+        .await
+        .unwrap_err()
+        .is_already_exists());
+
+    // Keys round-trip unchanged
+    assert_eq!(store.find(&first.id).await.unwrap().unwrap(), first);
+    assert!(store
+        .find("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Listings are newest (highest ID) first
+    let mut expected = vec![first.id.clone(), second.id.clone()];
+    expected.sort_by(|a, b| b.cmp(a));
+    let listed: Vec<String> = store
+        .list_for_owner(&alice)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|key| key.id)
+        .collect();
+    assert_eq!(listed, expected);
+    let listed: Vec<String> = store
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|key| key.owner.ends_with(&suffix))
+        .map(|key| key.id)
+        .collect();
+    expected.push(other.id.clone());
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(listed, expected);
+
+    // Usage is recorded, and touching an unknown key is not an error
+    let used = t0 + Duration::minutes(1);
+    store.touch(&first.id, used).await.unwrap();
+    store
+        .touch("01ARZ3NDEKTSV4RRFFQ69G5FAV", used)
+        .await
+        .unwrap();
+    let found = store.find(&first.id).await.unwrap().unwrap();
+    assert_eq!(found.last_used_at, Some(used));
+
+    // Revoking keeps the first revocation time
+    let revoked = store.revoke(&first.id, used).await.unwrap();
+    assert_eq!(revoked.revoked_at, Some(used));
+    assert!(!revoked.is_active(used));
+    let again = store
+        .revoke(&first.id, used + Duration::minutes(1))
+        .await
+        .unwrap();
+    assert_eq!(again.revoked_at, Some(used));
+    assert!(store
+        .find(&second.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_active(used));
+    assert!(store
+        .revoke("01ARZ3NDEKTSV4RRFFQ69G5FAV", used)
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+/// Check a RateLimitStore implementation
+pub(crate) async fn rate_limit_store(store: &dyn RateLimitStore) {
+    use std::time::Duration;
+
+    let key = |name: &str| format!("{}-{}", name, AssetId::new());
+    let window = Duration::from_secs(60);
+
+    // A new bucket starts full and is drained one token per request
+    let user = key("user");
+    for remaining in (0..3).rev() {
+        let decision = store.acquire(&user, 3, window).await.unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, 3);
+        assert_eq!(decision.remaining, remaining);
+        assert!(decision.retry_after.is_none());
+        assert!(decision.reset_after <= window);
+    }
+
+    // An empty bucket denies, and tells when the next token is due
+    let denied = store.acquire(&user, 3, window).await.unwrap();
+    assert!(!denied.allowed);
+    assert_eq!(denied.remaining, 0);
+    let retry_after = denied.retry_after.unwrap();
+    assert!(retry_after > Duration::ZERO);
+    assert!(retry_after <= Duration::from_secs(20));
+
+    // Other keys have their own bucket
+    let other = store.acquire(&key("other"), 3, window).await.unwrap();
+    assert!(other.allowed);
+
+    // Buckets refill over the window
+    let short = Duration::from_millis(200);
+    let bursty = key("bursty");
+    for _ in 0..2 {
+        assert!(store.acquire(&bursty, 2, short).await.unwrap().allowed);
+    }
+    assert!(!store.acquire(&bursty, 2, short).await.unwrap().allowed);
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(store.acquire(&bursty, 2, short).await.unwrap().allowed);
+}
@@ -0,0 +1,226 @@
+//! Opaque cursors for keyset pagination
+//!
+//! A cursor records the sort key and ID of the last row of a page. The next
+//! page starts strictly after that position, so rows inserted or deleted in
+//! the meantime do not shift pages the way an offset does, and the database
+//! can seek through an index instead of skipping rows.
+//!
+//! Cursors are URL-safe base64 encoded JSON. Clients should treat them as
+//! opaque strings.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use llm_registry_core::{Asset, AssetId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::{DbError, DbResult};
+use crate::repository::{SearchQuery, SortField, SortOrder};
+
+/// Value of the sort column at a cursor position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// `created_at` or `updated_at`
+    Timestamp(DateTime<Utc>),
+    /// Name or version
+    Text(String),
+    /// Size in bytes, which may be missing
+    Size(Option<i64>),
+    /// Relevance to the search text
+    Rank(f64),
+}
+
+impl SortKey {
+    /// Sort key of an asset for the given field
+    ///
+    /// `rank` is the relevance of the asset to the search text, which only
+    /// the store can compute.
+    pub fn of(field: SortField, asset: &Asset, rank: f64) -> Self {
+        match field {
+            SortField::CreatedAt => SortKey::Timestamp(asset.created_at),
+            SortField::UpdatedAt => SortKey::Timestamp(asset.updated_at),
+            SortField::Name => SortKey::Text(asset.metadata.name.clone()),
+            SortField::Version => SortKey::Text(asset.metadata.version.to_string()),
+            SortField::SizeBytes => SortKey::Size(asset.metadata.size_bytes.map(|s| s as i64)),
+            SortField::Relevance => SortKey::Rank(rank),
+        }
+    }
+
+    fn matches(&self, field: SortField) -> bool {
+        matches!(
+            (self, field),
+            (
+                SortKey::Timestamp(_),
+                SortField::CreatedAt | SortField::UpdatedAt
+            ) | (SortKey::Text(_), SortField::Name | SortField::Version)
+                | (SortKey::Size(_), SortField::SizeBytes)
+                | (SortKey::Rank(_), SortField::Relevance)
+        )
+    }
+}
+
+/// Position in an asset search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetCursor {
+    /// Sort field of the query the cursor belongs to
+    pub sort_by: SortField,
+
+    /// Sort order of the query the cursor belongs to
+    pub sort_order: SortOrder,
+
+    /// Sort key of the last asset returned
+    pub key: SortKey,
+
+    /// ID of the last asset returned, which breaks ties between equal keys
+    pub id: AssetId,
+}
+
+impl AssetCursor {
+    /// Cursor pointing just after `asset`, ranked `rank`, in the results of `query`
+    pub fn after(query: &SearchQuery, asset: &Asset, rank: f64) -> Self {
+        Self {
+            sort_by: query.sort_by,
+            sort_order: query.sort_order,
+            key: SortKey::of(query.sort_by, asset, rank),
+            id: asset.id,
+        }
+    }
+
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        encode(self)
+    }
+
+    /// Decode a cursor and check that it belongs to a query sorted like `query`
+    pub fn decode(cursor: &str, query: &SearchQuery) -> DbResult<Self> {
+        let decoded: Self = decode(cursor)?;
+        if decoded.sort_by != query.sort_by || decoded.sort_order != query.sort_order {
+            return Err(DbError::InvalidQuery(
+                "Cursor was created for a query with different sorting".to_string(),
+            ));
+        }
+        if !decoded.key.matches(decoded.sort_by) {
+            return Err(DbError::InvalidQuery("Malformed cursor".to_string()));
+        }
+        Ok(decoded)
+    }
+
+    /// Encoded cursors of each asset in a page, and of the next page if more
+    /// results follow
+    ///
+    /// `ranks` holds the relevance of each asset, in the same order.
+    pub fn page(
+        query: &SearchQuery,
+        page: &[Asset],
+        ranks: &[f64],
+        has_more: bool,
+    ) -> (Vec<String>, Option<String>) {
+        let cursors: Vec<String> = page
+            .iter()
+            .zip(ranks)
+            .map(|(asset, rank)| Self::after(query, asset, *rank).encode())
+            .collect();
+        let next = cursors.last().filter(|_| has_more).cloned();
+        (cursors, next)
+    }
+
+    /// Decode the cursor of a search query, if it has one
+    pub fn from_query(query: &SearchQuery) -> DbResult<Option<Self>> {
+        query
+            .cursor
+            .as_deref()
+            .map(|cursor| Self::decode(cursor, query))
+            .transpose()
+    }
+}
+
+/// Position in an event query, which is ordered newest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    /// Timestamp of the last event returned
+    pub timestamp: DateTime<Utc>,
+
+    /// Store-assigned ID of the last event returned
+    pub event_id: String,
+}
+
+impl EventCursor {
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        encode(self)
+    }
+
+    /// Decode a cursor produced by [`EventCursor::encode`]
+    pub fn decode(cursor: &str) -> DbResult<Self> {
+        decode(cursor)
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> String {
+    // Serializing these plain structs cannot fail
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap_or_default())
+}
+
+fn decode<T: DeserializeOwned>(cursor: &str) -> DbResult<T> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| DbError::InvalidQuery("Malformed cursor".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_cursor_round_trip() {
+        let query = SearchQuery::new().sort_by(SortField::SizeBytes);
+        let cursor = AssetCursor {
+            sort_by: SortField::SizeBytes,
+            sort_order: SortOrder::Descending,
+            key: SortKey::Size(None),
+            id: AssetId::new(),
+        };
+
+        let encoded = cursor.encode();
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(AssetCursor::decode(&encoded, &query).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_asset_cursor_rejects_other_sorting() {
+        let cursor = AssetCursor {
+            sort_by: SortField::Name,
+            sort_order: SortOrder::Ascending,
+            key: SortKey::Text("gpt".to_string()),
+            id: AssetId::new(),
+        }
+        .encode();
+
+        let query = SearchQuery::new().sort_by(SortField::Name);
+        assert!(AssetCursor::decode(&cursor, &query).is_err());
+        let query = query.sort_order(SortOrder::Ascending);
+        assert!(AssetCursor::decode(&cursor, &query).is_ok());
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage() {
+        let query = SearchQuery::new();
+        assert!(AssetCursor::decode("not a cursor", &query).is_err());
+        assert!(EventCursor::decode("e30").is_err());
+
+        // Key type must fit the sort field
+        let mismatched = AssetCursor {
+            sort_by: SortField::CreatedAt,
+            sort_order: SortOrder::Descending,
+            key: SortKey::Text("x".to_string()),
+            id: AssetId::new(),
+        }
+        .encode();
+        assert!(AssetCursor::decode(&mismatched, &query).is_err());
+    }
+}
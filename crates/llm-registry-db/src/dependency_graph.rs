@@ -0,0 +1,247 @@
+//! Transitive dependency closures
+//!
+//! A closure holds every dependency edge reachable from a root asset,
+//! following edges either towards the assets it depends on or towards the
+//! assets that depend on it. Each asset is reached at its shortest distance
+//! from the root, so an edge is reported once, at the depth of its source
+//! plus one.
+//!
+//! The stores refuse edges that would close a cycle, but rows written around
+//! them can still form one. Edges on a cycle are flagged rather than followed
+//! again.
+
+use llm_registry_core::{AssetId, DependencyKind};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Deepest a traversal goes
+///
+/// Larger limits are capped at it, and a closure that reaches it is reported
+/// as truncated.
+pub const MAX_DEPENDENCY_DEPTH: u32 = 100;
+
+/// Which way to follow dependency edges
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyDirection {
+    /// Towards the assets the root depends on
+    #[default]
+    Dependencies,
+    /// Towards the assets that depend on the root
+    Dependents,
+}
+
+impl DependencyDirection {
+    /// Asset an edge leads to when followed in this direction
+    pub fn target(self, edge: &DependencyEdge) -> AssetId {
+        match self {
+            DependencyDirection::Dependencies => edge.dependency_id,
+            DependencyDirection::Dependents => edge.asset_id,
+        }
+    }
+
+    /// Asset an edge is followed from in this direction
+    pub fn source(self, edge: &DependencyEdge) -> AssetId {
+        match self {
+            DependencyDirection::Dependencies => edge.asset_id,
+            DependencyDirection::Dependents => edge.dependency_id,
+        }
+    }
+}
+
+/// Dependency relationship between two assets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Asset that has the dependency
+    pub asset_id: AssetId,
+
+    /// Asset being depended upon
+    pub dependency_id: AssetId,
+
+    /// Kind of dependency, such as `runtime`
+    pub dependency_type: DependencyKind,
+
+    /// Version requirement recorded for the dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_constraint: Option<String>,
+
+    /// Distance of the edge from the root; direct edges are at depth 1
+    pub depth: u32,
+
+    /// Whether the edge lies on a dependency cycle
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+}
+
+/// Asset reached by a closure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClosureNode {
+    /// Asset ID
+    pub asset_id: AssetId,
+
+    /// Asset name
+    pub name: String,
+
+    /// Asset version
+    pub version: Version,
+
+    /// Shortest distance from the root
+    pub depth: u32,
+}
+
+/// Every edge reachable from an asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyClosure {
+    /// Asset the traversal started from
+    pub root: AssetId,
+
+    /// Direction edges were followed in
+    pub direction: DependencyDirection,
+
+    /// Assets reached, nearest first, not including the root
+    pub nodes: Vec<ClosureNode>,
+
+    /// Edges between the root and reached assets, nearest first
+    pub edges: Vec<DependencyEdge>,
+
+    /// Whether edges beyond the depth limit were left out
+    pub truncated: bool,
+}
+
+impl DependencyClosure {
+    /// Whether any edge lies on a cycle
+    pub fn has_cycles(&self) -> bool {
+        self.edges.iter().any(|edge| edge.cycle)
+    }
+
+    /// Build a closure from the edges a store found
+    ///
+    /// Each entry pairs an edge with the name and version of the asset it
+    /// leads to. Stores look one level past `depth_limit`, so that edges
+    /// beyond it show the closure was truncated.
+    pub(crate) fn assemble(
+        root: AssetId,
+        direction: DependencyDirection,
+        depth_limit: u32,
+        found: Vec<(DependencyEdge, String, Version)>,
+    ) -> Self {
+        let mut truncated = false;
+        let mut nodes: HashMap<AssetId, ClosureNode> = HashMap::new();
+        let mut edges = Vec::new();
+
+        for (edge, name, version) in found {
+            if edge.depth > depth_limit {
+                truncated = true;
+                continue;
+            }
+            let target = direction.target(&edge);
+            if target != root {
+                let node = nodes.entry(target).or_insert_with(|| ClosureNode {
+                    asset_id: target,
+                    name,
+                    version,
+                    depth: edge.depth,
+                });
+                node.depth = node.depth.min(edge.depth);
+            }
+            edges.push(edge);
+        }
+
+        mark_cycles(&mut edges);
+
+        let mut nodes: Vec<ClosureNode> = nodes.into_values().collect();
+        nodes.sort_by(|a, b| (a.depth, &a.name, &a.version).cmp(&(b.depth, &b.name, &b.version)));
+        edges.sort_by_cached_key(|edge| {
+            (
+                edge.depth,
+                edge.asset_id.to_string(),
+                edge.dependency_id.to_string(),
+            )
+        });
+
+        Self {
+            root,
+            direction,
+            nodes,
+            edges,
+            truncated,
+        }
+    }
+}
+
+/// Flag every edge whose target leads back to its source
+fn mark_cycles(edges: &mut [DependencyEdge]) {
+    let mut adjacency: HashMap<AssetId, Vec<AssetId>> = HashMap::new();
+    for edge in edges.iter() {
+        adjacency
+            .entry(edge.asset_id)
+            .or_default()
+            .push(edge.dependency_id);
+    }
+
+    for edge in edges.iter_mut() {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([edge.dependency_id]);
+        while let Some(id) = queue.pop_front() {
+            if id == edge.asset_id {
+                edge.cycle = true;
+                break;
+            }
+            if visited.insert(id) {
+                queue.extend(adjacency.get(&id).into_iter().flatten());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(
+        asset_id: AssetId,
+        dependency_id: AssetId,
+        depth: u32,
+    ) -> (DependencyEdge, String, Version) {
+        let edge = DependencyEdge {
+            asset_id,
+            dependency_id,
+            dependency_type: DependencyKind::Runtime,
+            version_constraint: None,
+            depth,
+            cycle: false,
+        };
+        (edge, dependency_id.to_string(), Version::new(1, 0, 0))
+    }
+
+    #[test]
+    fn test_assemble_flags_cycles_and_truncation() {
+        let [root, a, b, c] = [(); 4].map(|_| AssetId::new());
+        let found = vec![
+            edge(root, a, 1),
+            edge(a, b, 2),
+            edge(b, a, 3),
+            edge(b, c, 3),
+            edge(c, root, 4),
+        ];
+
+        let closure =
+            DependencyClosure::assemble(root, DependencyDirection::Dependencies, 3, found);
+
+        assert!(closure.truncated);
+        assert_eq!(closure.edges.len(), 4);
+        let cycles: Vec<bool> = closure.edges.iter().map(|edge| edge.cycle).collect();
+        assert_eq!(cycles.iter().filter(|cycle| **cycle).count(), 2);
+        assert!(!closure.edges[0].cycle);
+        assert!(closure.has_cycles());
+
+        let depths: Vec<(AssetId, u32)> = closure
+            .nodes
+            .iter()
+            .map(|node| (node.asset_id, node.depth))
+            .collect();
+        assert_eq!(depths.len(), 3);
+        assert!(depths.contains(&(a, 1)) && depths.contains(&(b, 2)) && depths.contains(&(c, 3)));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides error types for database operations, including
 //! connection errors, query errors, and data validation errors.
 
+use sqlx::error::ErrorKind;
 use thiserror::Error;
 
 /// Result type alias for database operations
@@ -39,6 +40,14 @@ pub enum DbError {
     #[error("Asset already exists: {0}")]
     AlreadyExists(String),
 
+    /// Stored revision does not match the expected one
+    #[error("Revision conflict: expected revision {expected}, found {actual}")]
+    RevisionConflict { expected: u64, actual: u64 },
+
+    /// Asset is still required by other assets
+    #[error("Asset {asset} is required by {count} other assets")]
+    HasDependents { asset: String, count: usize },
+
     /// Constraint violation
     #[error("Constraint violation: {0}")]
     ConstraintViolation(String),
@@ -75,6 +84,10 @@ pub enum DbError {
     #[error("Cache error: {0}")]
     Cache(String),
 
+    /// Blob storage error
+    #[error("Blob storage error: {0}")]
+    Storage(String),
+
     /// Internal database error
     #[error("Internal database error: {0}")]
     Internal(String),
@@ -109,6 +122,11 @@ impl DbError {
         matches!(self, DbError::AlreadyExists(_) | DbError::UniqueViolation(_))
     }
 
+    /// Check if this error is an optimistic concurrency conflict
+    pub fn is_revision_conflict(&self) -> bool {
+        matches!(self, DbError::RevisionConflict { .. })
+    }
+
     /// Check if this is a transient error that could be retried
     pub fn is_transient(&self) -> bool {
         matches!(
@@ -146,7 +164,17 @@ impl From<sqlx::Error> for DbError {
                         // Various integrity constraint violations
                         DbError::ConstraintViolation(message.to_string())
                     }
-                    _ => DbError::Query(message.to_string()),
+                    // Other backends (SQLite) use their own codes
+                    _ => match db_err.kind() {
+                        ErrorKind::UniqueViolation => DbError::UniqueViolation(message.to_string()),
+                        ErrorKind::ForeignKeyViolation => {
+                            DbError::ForeignKeyViolation(message.to_string())
+                        }
+                        ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+                            DbError::ConstraintViolation(message.to_string())
+                        }
+                        _ => DbError::Query(message.to_string()),
+                    },
                 }
             }
 
@@ -225,6 +253,13 @@ mod tests {
 
         let connection = DbError::Connection("test".to_string());
         assert!(connection.is_transient());
+
+        let conflict = DbError::RevisionConflict {
+            expected: 1,
+            actual: 2,
+        };
+        assert!(conflict.is_revision_conflict());
+        assert!(!conflict.is_transient());
     }
 
     #[test]
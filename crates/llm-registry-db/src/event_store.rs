@@ -8,9 +8,11 @@ use chrono::{DateTime, Utc};
 use llm_registry_core::{AssetId, EventType, RegistryEvent};
 use serde_json::Value as JsonValue;
 use sqlx::postgres::PgRow;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use std::sync::{Mutex, RwLock};
 use tracing::{debug, instrument};
 
+use crate::cursor::EventCursor;
 use crate::error::{DbError, DbResult};
 
 /// Query parameters for searching events
@@ -36,6 +38,12 @@ pub struct EventQuery {
 
     /// Number of events to skip
     pub offset: i64,
+
+    /// Opaque cursor from a previous page's `next_cursor`
+    ///
+    /// When set, results start right after the cursor position and `offset`
+    /// is ignored.
+    pub cursor: Option<String>,
 }
 
 impl EventQuery {
@@ -89,6 +97,12 @@ impl EventQuery {
         self.offset = offset;
         self
     }
+
+    /// Continue after the given cursor instead of an offset
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
 }
 
 /// Results from an event query
@@ -105,6 +119,9 @@ pub struct EventQueryResults {
 
     /// Current limit
     pub limit: i64,
+
+    /// Cursor for the next page, if there are more events
+    pub next_cursor: Option<String>,
 }
 
 impl EventQueryResults {
@@ -119,6 +136,25 @@ impl EventQueryResults {
     }
 }
 
+/// A persisted event together with its store-assigned ID and sequence number
+///
+/// Sequence numbers are assigned by the store and become visible in
+/// increasing order, so the sequence number of the last event a consumer has
+/// seen can be used as a cursor to resume reading without skipping events.
+/// Event IDs are generated by each registry instance and are not ordered
+/// across instances.
+#[derive(Debug, Clone)]
+pub struct StoredEvent {
+    /// Event identifier assigned by the store
+    pub event_id: String,
+
+    /// Position of the event in the store's append order
+    pub sequence: i64,
+
+    /// The event itself
+    pub event: RegistryEvent,
+}
+
 /// Event store trait for persisting and querying registry events
 #[async_trait]
 pub trait EventStore: Send + Sync {
@@ -168,6 +204,22 @@ pub trait EventStore: Send + Sync {
     /// * The most recent event for the asset, if any
     async fn get_latest_event(&self, asset_id: &AssetId) -> DbResult<Option<RegistryEvent>>;
 
+    /// Read events in append order, starting after the given sequence number
+    ///
+    /// # Arguments
+    /// * `after` - Sequence number to resume after, or `None` to read from the beginning
+    /// * `limit` - Maximum number of events to return
+    ///
+    /// # Returns
+    /// * Events with sequence numbers greater than `after`, in sequence order
+    async fn read_after(&self, after: Option<i64>, limit: i64) -> DbResult<Vec<StoredEvent>>;
+
+    /// Get the sequence number of the most recently appended event, if any
+    async fn latest_sequence(&self) -> DbResult<Option<i64>>;
+
+    /// Get the sequence number of an event, to resume reading after it
+    async fn find_sequence(&self, event_id: &str) -> DbResult<Option<i64>>;
+
     /// Count total events in the store
     async fn count_events(&self) -> DbResult<i64>;
 
@@ -202,26 +254,10 @@ impl EventStore for PostgresEventStore {
     async fn append(&self, event: RegistryEvent) -> DbResult<RegistryEvent> {
         debug!("Appending event to store");
 
-        let event_type_str = event.event_type.event_name();
-        let asset_id = event.event_type.asset_id();
-
-        sqlx::query(
-            r#"
-            INSERT INTO registry_events (
-                event_id, event_type, asset_id, timestamp,
-                actor, payload, metadata
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
-            "#,
-        )
-        .bind(ulid::Ulid::new().to_string())
-        .bind(event_type_str)
-        .bind(asset_id.map(|id| id.to_string()))
-        .bind(&event.timestamp)
-        .bind(&event.actor.as_deref().unwrap_or("system"))
-        .bind(serde_json::to_value(&event.event_type)?)
-        .bind(serde_json::to_value(&event.context)?)
-        .execute(&self.pool)
-        .await?;
+        let mut tx = self.pool.begin().await?;
+        lock_appends(&mut tx).await?;
+        insert_event(&mut tx, &event).await?;
+        tx.commit().await?;
 
         debug!("Event appended successfully");
         Ok(event)
@@ -236,28 +272,10 @@ impl EventStore for PostgresEventStore {
         }
 
         let mut tx = self.pool.begin().await?;
+        lock_appends(&mut tx).await?;
 
         for event in &events {
-            let event_type_str = event.event_type.event_name();
-            let asset_id = event.event_type.asset_id();
-
-            sqlx::query(
-                r#"
-                INSERT INTO registry_events (
-                    event_id, event_type, asset_id, timestamp,
-                    actor, payload, metadata
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7)
-                "#,
-            )
-            .bind(ulid::Ulid::new().to_string())
-            .bind(event_type_str)
-            .bind(asset_id.map(|id| id.to_string()))
-            .bind(&event.timestamp)
-            .bind(&event.actor.as_deref().unwrap_or("system"))
-            .bind(serde_json::to_value(&event.event_type)?)
-            .bind(serde_json::to_value(&event.context)?)
-            .execute(&mut *tx)
-            .await?;
+            insert_event(&mut tx, event).await?;
         }
 
         tx.commit().await?;
@@ -270,7 +288,7 @@ impl EventStore for PostgresEventStore {
     async fn query(&self, query: &EventQuery) -> DbResult<EventQueryResults> {
         debug!("Querying events");
 
-        let mut sql = String::from(
+        let mut builder = QueryBuilder::<Postgres>::new(
             r#"
             SELECT
                 event_id, event_type, asset_id, timestamp,
@@ -279,64 +297,39 @@ impl EventStore for PostgresEventStore {
             WHERE 1=1
             "#,
         );
-
-        let mut conditions = Vec::new();
-
-        if query.asset_id.is_some() {
-            conditions.push("asset_id = $ASSET_ID");
-        }
-
-        if !query.event_types.is_empty() {
-            conditions.push("event_type = ANY($EVENT_TYPES)");
-        }
-
-        if query.actor.is_some() {
-            conditions.push("actor = $ACTOR");
-        }
-
-        if query.after.is_some() {
-            conditions.push("timestamp > $AFTER");
-        }
-
-        if query.before.is_some() {
-            conditions.push("timestamp < $BEFORE");
-        }
-
-        if !conditions.is_empty() {
-            sql.push_str(" AND ");
-            sql.push_str(&conditions.join(" AND "));
-        }
-
-        sql.push_str(" ORDER BY timestamp DESC");
-        sql.push_str(&format!(" LIMIT {} OFFSET {}", query.limit, query.offset));
-
-        // Create string bindings before building query to ensure proper lifetimes
-        let asset_id_str = query.asset_id.as_ref().map(|id| id.to_string());
-
-        // Build query dynamically
-        let mut db_query = sqlx::query(&sql);
-
-        if let Some(ref id_str) = asset_id_str {
-            db_query = db_query.bind(id_str);
-        }
-
-        if !query.event_types.is_empty() {
-            db_query = db_query.bind(&query.event_types);
-        }
-
-        if let Some(ref actor) = query.actor {
-            db_query = db_query.bind(actor);
+        push_event_filters(&mut builder, query);
+
+        let cursor = query
+            .cursor
+            .as_deref()
+            .map(EventCursor::decode)
+            .transpose()?;
+        if let Some(cursor) = cursor.as_ref() {
+            builder.push(" AND (timestamp, event_id) < (");
+            builder.push_bind(cursor.timestamp);
+            builder.push(", ");
+            builder.push_bind(cursor.event_id.clone());
+            builder.push(")");
         }
 
-        if let Some(after) = query.after {
-            db_query = db_query.bind(after);
-        }
-
-        if let Some(before) = query.before {
-            db_query = db_query.bind(before);
-        }
-
-        let rows = db_query.fetch_all(&self.pool).await?;
+        // Fetch one extra row to learn whether another page follows
+        let limit = query.limit.max(0);
+        builder.push(" ORDER BY timestamp DESC, event_id DESC LIMIT ");
+        builder.push_bind(limit + 1);
+        builder.push(" OFFSET ");
+        builder.push_bind(if cursor.is_some() { 0 } else { query.offset });
+
+        let mut rows = builder.build().fetch_all(&self.pool).await?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = rows.last().filter(|_| has_more).map(|row| {
+            EventCursor {
+                timestamp: row.get("timestamp"),
+                event_id: row.get("event_id"),
+            }
+            .encode()
+        });
 
         let events: Result<Vec<RegistryEvent>, DbError> =
             rows.into_iter().map(row_to_event).collect();
@@ -350,6 +343,7 @@ impl EventStore for PostgresEventStore {
             total,
             offset: query.offset,
             limit: query.limit,
+            next_cursor,
         })
     }
 
@@ -364,7 +358,7 @@ impl EventStore for PostgresEventStore {
                 actor, payload, metadata
             FROM registry_events
             WHERE asset_id = $1
-            ORDER BY timestamp DESC
+            ORDER BY timestamp DESC, event_id DESC
             LIMIT $2
             "#,
         )
@@ -390,7 +384,7 @@ impl EventStore for PostgresEventStore {
                 actor, payload, metadata
             FROM registry_events
             WHERE asset_id = $1
-            ORDER BY timestamp DESC
+            ORDER BY timestamp DESC, event_id DESC
             LIMIT 1
             "#,
         )
@@ -404,6 +398,56 @@ impl EventStore for PostgresEventStore {
         }
     }
 
+    #[instrument(skip(self))]
+    async fn read_after(&self, after: Option<i64>, limit: i64) -> DbResult<Vec<StoredEvent>> {
+        debug!("Reading events after cursor");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                event_id, sequence, event_type, asset_id, timestamp,
+                actor, payload, metadata
+            FROM registry_events
+            WHERE $1::BIGINT IS NULL OR sequence > $1
+            ORDER BY sequence ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let event_id: String = row.get("event_id");
+                let sequence: i64 = row.get("sequence");
+                Ok(StoredEvent {
+                    event_id,
+                    sequence,
+                    event: row_to_event(row)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn latest_sequence(&self) -> DbResult<Option<i64>> {
+        let row = sqlx::query("SELECT MAX(sequence) as sequence FROM registry_events")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("sequence"))
+    }
+
+    async fn find_sequence(&self, event_id: &str) -> DbResult<Option<i64>> {
+        let row = sqlx::query("SELECT sequence FROM registry_events WHERE event_id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|row| row.get("sequence")))
+    }
+
     async fn count_events(&self) -> DbResult<i64> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM registry_events")
             .fetch_one(&self.pool)
@@ -433,35 +477,311 @@ impl EventStore for PostgresEventStore {
 impl PostgresEventStore {
     /// Count query results without pagination
     async fn count_query_results(&self, query: &EventQuery) -> DbResult<i64> {
-        let mut sql = String::from("SELECT COUNT(*) as count FROM registry_events WHERE 1=1");
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT COUNT(*) as count FROM registry_events WHERE 1=1",
+        );
+        push_event_filters(&mut builder, query);
 
-        if query.asset_id.is_some() {
-            sql.push_str(" AND asset_id = $1");
-        }
+        let row = builder.build().fetch_one(&self.pool).await?;
 
-        if query.actor.is_some() {
-            sql.push_str(" AND actor = $2");
-        }
+        Ok(row.get("count"))
+    }
+}
+
+/// Append the filter conditions of an event query
+fn push_event_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &EventQuery) {
+    if let Some(asset_id) = query.asset_id {
+        builder.push(" AND asset_id = ");
+        builder.push_bind(asset_id.to_string());
+    }
 
-        // Create string bindings before building query to ensure proper lifetimes
-        let asset_id_str = query.asset_id.as_ref().map(|id| id.to_string());
+    if !query.event_types.is_empty() {
+        builder.push(" AND event_type = ANY(");
+        builder.push_bind(query.event_types.clone());
+        builder.push(")");
+    }
 
-        let mut db_query = sqlx::query(&sql);
+    if let Some(ref actor) = query.actor {
+        builder.push(" AND actor = ");
+        builder.push_bind(actor.clone());
+    }
 
-        if let Some(ref id_str) = asset_id_str {
-            db_query = db_query.bind(id_str);
-        }
+    if let Some(after) = query.after {
+        builder.push(" AND timestamp > ");
+        builder.push_bind(after);
+    }
+
+    if let Some(before) = query.before {
+        builder.push(" AND timestamp < ");
+        builder.push_bind(before);
+    }
+}
+
+/// In-memory implementation of EventStore
+///
+/// Events are read back the way PostgreSQL returns them: the actor defaults
+/// to `"system"`, and the correlation ID and source are not kept.
+#[derive(Debug, Default)]
+pub struct InMemoryEventStore {
+    events: RwLock<Vec<StoredEvent>>,
+}
+
+impl InMemoryEventStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events matching a query, most recent first
+    fn matching(&self, query: &EventQuery) -> Vec<StoredEvent> {
+        let events = self.events.read().unwrap();
+        let mut matches: Vec<&StoredEvent> = events
+            .iter()
+            .filter(|stored| {
+                let event = &stored.event;
+                query
+                    .asset_id
+                    .map_or(true, |id| event.event_type.asset_id() == Some(id))
+                    && (query.event_types.is_empty()
+                        || query
+                            .event_types
+                            .iter()
+                            .any(|t| t == event.event_type.event_name()))
+                    && query
+                        .actor
+                        .as_ref()
+                        .map_or(true, |actor| event.actor.as_ref() == Some(actor))
+                    && query.after.map_or(true, |after| event.timestamp > after)
+                    && query.before.map_or(true, |before| event.timestamp < before)
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.event
+                .timestamp
+                .cmp(&a.event.timestamp)
+                .then_with(|| b.event_id.cmp(&a.event_id))
+        });
+        matches.into_iter().cloned().collect()
+    }
+}
+
+/// Strip an event down to what the store keeps
+fn stored_form(event: &RegistryEvent) -> RegistryEvent {
+    RegistryEvent {
+        event_type: event.event_type.clone(),
+        timestamp: event.timestamp,
+        correlation_id: None,
+        actor: Some(event.actor.clone().unwrap_or_else(|| "system".to_string())),
+        source: None,
+        context: event.context.clone(),
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(&self, event: RegistryEvent) -> DbResult<RegistryEvent> {
+        let mut stored = self.events.write().unwrap();
+        let sequence = stored.len() as i64 + 1;
+        stored.push(StoredEvent {
+            event_id: next_event_id(),
+            sequence,
+            event: stored_form(&event),
+        });
+        Ok(event)
+    }
 
-        if let Some(ref actor) = query.actor {
-            db_query = db_query.bind(actor);
+    async fn append_batch(&self, events: Vec<RegistryEvent>) -> DbResult<Vec<RegistryEvent>> {
+        let mut stored = self.events.write().unwrap();
+        for event in &events {
+            let sequence = stored.len() as i64 + 1;
+            stored.push(StoredEvent {
+                event_id: next_event_id(),
+                sequence,
+                event: stored_form(event),
+            });
         }
+        Ok(events)
+    }
 
-        let row = db_query.fetch_one(&self.pool).await?;
+    async fn query(&self, query: &EventQuery) -> DbResult<EventQueryResults> {
+        let matches = self.matching(query);
+        let total = matches.len() as i64;
+        let limit = query.limit.max(0) as usize;
+
+        let mut page: Vec<StoredEvent> = match query.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor = EventCursor::decode(cursor)?;
+                matches
+                    .into_iter()
+                    .skip_while(|stored| {
+                        (stored.event.timestamp, &stored.event_id)
+                            >= (cursor.timestamp, &cursor.event_id)
+                    })
+                    .take(limit + 1)
+                    .collect()
+            }
+            None => matches
+                .into_iter()
+                .skip(query.offset.max(0) as usize)
+                .take(limit + 1)
+                .collect(),
+        };
+        let has_more = page.len() > limit;
+        page.truncate(limit);
 
-        Ok(row.get("count"))
+        let next_cursor = page.last().filter(|_| has_more).map(|stored| {
+            EventCursor {
+                timestamp: stored.event.timestamp,
+                event_id: stored.event_id.clone(),
+            }
+            .encode()
+        });
+
+        Ok(EventQueryResults {
+            events: page.into_iter().map(|stored| stored.event).collect(),
+            total,
+            offset: query.offset,
+            limit: query.limit,
+            next_cursor,
+        })
+    }
+
+    async fn get_asset_events(
+        &self,
+        asset_id: &AssetId,
+        limit: i64,
+    ) -> DbResult<Vec<RegistryEvent>> {
+        Ok(self
+            .matching(&EventQuery::new().asset_id(*asset_id))
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|stored| stored.event)
+            .collect())
+    }
+
+    async fn get_latest_event(&self, asset_id: &AssetId) -> DbResult<Option<RegistryEvent>> {
+        Ok(self
+            .matching(&EventQuery::new().asset_id(*asset_id))
+            .into_iter()
+            .next()
+            .map(|stored| stored.event))
+    }
+
+    async fn read_after(&self, after: Option<i64>, limit: i64) -> DbResult<Vec<StoredEvent>> {
+        // Sequence numbers are the 1-based positions in the log
+        let skip = after.unwrap_or(0).max(0) as usize;
+        Ok(self
+            .events
+            .read()
+            .unwrap()
+            .iter()
+            .skip(skip)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn latest_sequence(&self) -> DbResult<Option<i64>> {
+        Ok(self
+            .events
+            .read()
+            .unwrap()
+            .last()
+            .map(|stored| stored.sequence))
+    }
+
+    async fn find_sequence(&self, event_id: &str) -> DbResult<Option<i64>> {
+        Ok(self
+            .events
+            .read()
+            .unwrap()
+            .iter()
+            .find(|stored| stored.event_id == event_id)
+            .map(|stored| stored.sequence))
+    }
+
+    async fn count_events(&self) -> DbResult<i64> {
+        Ok(self.events.read().unwrap().len() as i64)
+    }
+
+    async fn count_by_type(&self, event_type: &str) -> DbResult<i64> {
+        Ok(self
+            .events
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|stored| stored.event.event_type.event_name() == event_type)
+            .count() as i64)
+    }
+
+    async fn health_check(&self) -> DbResult<()> {
+        Ok(())
+    }
+}
+
+/// Generator shared by all appends so event IDs increase monotonically
+static EVENT_IDS: Mutex<ulid::Generator> = Mutex::new(ulid::Generator::new());
+
+/// Generate the ID for a new event
+///
+/// IDs from one instance increase monotonically, even within a millisecond.
+/// They are not ordered across instances, so readers tailing the log resume
+/// by sequence number instead. Falls back to a random ULID if the monotonic
+/// generator overflows.
+pub(crate) fn next_event_id() -> String {
+    let id = EVENT_IDS
+        .lock()
+        .ok()
+        .and_then(|mut ids| ids.generate().ok());
+    match id {
+        Some(id) => id.to_string(),
+        None => ulid::Ulid::new().to_string(),
     }
 }
 
+/// Advisory lock key held by every append until it commits
+const APPEND_LOCK_KEY: i64 = 0x6c6c_6d5f_6576_656e;
+
+/// Serialize appends for the rest of the transaction
+///
+/// Sequence numbers are taken when a row is inserted, but become visible when
+/// its transaction commits. Holding one lock from insert to commit makes the
+/// two orders agree, so a reader that has seen sequence `n` never later finds
+/// a smaller one appear.
+async fn lock_appends(tx: &mut sqlx::Transaction<'_, Postgres>) -> DbResult<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(APPEND_LOCK_KEY)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Insert one event
+async fn insert_event(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    event: &RegistryEvent,
+) -> DbResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO registry_events (
+            event_id, event_type, asset_id, timestamp,
+            actor, payload, metadata
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(next_event_id())
+    .bind(event.event_type.event_name())
+    .bind(event.event_type.asset_id().map(|id| id.to_string()))
+    .bind(event.timestamp)
+    .bind(event.actor.as_deref().unwrap_or("system"))
+    .bind(serde_json::to_value(&event.event_type)?)
+    .bind(serde_json::to_value(&event.context)?)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
 /// Convert database row to RegistryEvent
 fn row_to_event(row: PgRow) -> DbResult<RegistryEvent> {
     let payload: JsonValue = row.get("payload");
@@ -488,6 +808,7 @@ fn row_to_event(row: PgRow) -> DbResult<RegistryEvent> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conformance;
 
     #[test]
     fn test_event_query_builder() {
@@ -504,6 +825,13 @@ mod tests {
         assert_eq!(query.limit, 50);
     }
 
+    #[test]
+    fn test_event_ids_are_monotonic() {
+        let ids: Vec<String> = (0..1000).map(|_| next_event_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.parse::<ulid::Ulid>().is_ok()));
+    }
+
     #[test]
     fn test_event_query_results_has_more() {
         let results = EventQueryResults {
@@ -511,10 +839,23 @@ mod tests {
             total: 100,
             offset: 0,
             limit: 50,
+            next_cursor: None,
         };
 
         assert_eq!(results.count(), 0);
         // With offset 0 and 0 events, offset + count (0) < total (100), so has_more = true
         assert!(results.has_more());
     }
+
+    #[tokio::test]
+    async fn test_in_memory_event_store_conformance() {
+        conformance::event_store(&InMemoryEventStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_postgres_event_store_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            conformance::event_store(&PostgresEventStore::new(pool)).await;
+        }
+    }
 }
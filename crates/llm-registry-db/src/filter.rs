@@ -0,0 +1,250 @@
+//! Structured search filters on annotations and value ranges
+//!
+//! Annotation filters look at the key/value `annotations` of an asset, which
+//! are stored as strings. Numeric comparisons only match values written as
+//! JSON numbers, such as `7` or `13.5`; other values never compare.
+
+use llm_registry_core::Annotations;
+use serde::{Deserialize, Serialize};
+
+/// Numeric comparison against a bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    /// Less than
+    Lt,
+    /// Less than or equal
+    Le,
+    /// Greater than
+    Gt,
+    /// Greater than or equal
+    Ge,
+}
+
+impl Comparison {
+    /// SQL operator of the comparison
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    /// Whether `value` compares to `bound` this way
+    pub fn holds(self, value: f64, bound: f64) -> bool {
+        match self {
+            Comparison::Lt => value < bound,
+            Comparison::Le => value <= bound,
+            Comparison::Gt => value > bound,
+            Comparison::Ge => value >= bound,
+        }
+    }
+}
+
+/// Condition on the value of one annotation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationCondition {
+    /// The annotation is present, with any value
+    Exists,
+    /// The value is exactly this string
+    Equals(String),
+    /// The value is exactly one of these strings
+    In(Vec<String>),
+    /// The value is a number that compares to `value` as given
+    Compare {
+        /// Comparison to apply
+        op: Comparison,
+        /// Bound to compare against
+        value: f64,
+    },
+}
+
+/// Filter on one annotation of an asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationFilter {
+    /// Annotation key
+    pub key: String,
+
+    /// Condition the value must meet
+    pub condition: AnnotationCondition,
+}
+
+impl AnnotationFilter {
+    /// Match assets that have the annotation
+    pub fn exists(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            condition: AnnotationCondition::Exists,
+        }
+    }
+
+    /// Match assets whose annotation equals `value`
+    pub fn equals(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            condition: AnnotationCondition::Equals(value.into()),
+        }
+    }
+
+    /// Match assets whose annotation equals one of `values`
+    pub fn one_of<I, S>(key: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            key: key.into(),
+            condition: AnnotationCondition::In(values.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Match assets whose annotation is a number comparing to `value` by `op`
+    pub fn compare(key: impl Into<String>, op: Comparison, value: f64) -> Self {
+        Self {
+            key: key.into(),
+            condition: AnnotationCondition::Compare { op, value },
+        }
+    }
+
+    /// Whether a set of annotations passes the filter
+    pub fn matches(&self, annotations: &Annotations) -> bool {
+        let Some(value) = annotations.get(&self.key) else {
+            return false;
+        };
+        match &self.condition {
+            AnnotationCondition::Exists => true,
+            AnnotationCondition::Equals(expected) => value == expected,
+            AnnotationCondition::In(expected) => expected.contains(value),
+            AnnotationCondition::Compare { op, value: bound } => {
+                parse_number(value).is_some_and(|number| op.holds(number, *bound))
+            }
+        }
+    }
+}
+
+/// Parse an annotation value written as a JSON number
+///
+/// Surrounding whitespace, leading zeros and forms such as `inf` are
+/// rejected, as the database stores do.
+pub fn parse_number(value: &str) -> Option<f64> {
+    if value.trim() != value {
+        return None;
+    }
+    match serde_json::from_str(value).ok()? {
+        serde_json::Value::Number(number) => number.as_f64(),
+        _ => None,
+    }
+}
+
+/// Inclusive bounds on a value; a missing bound is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range<T> {
+    /// Smallest value allowed
+    pub min: Option<T>,
+
+    /// Largest value allowed
+    pub max: Option<T>,
+}
+
+impl<T> Default for Range<T> {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl<T> Range<T> {
+    /// Range with the given bounds
+    pub fn new(min: Option<T>, max: Option<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Values of at least `min`
+    pub fn at_least(min: T) -> Self {
+        Self::new(Some(min), None)
+    }
+
+    /// Values of at most `max`
+    pub fn at_most(max: T) -> Self {
+        Self::new(None, Some(max))
+    }
+
+    /// Whether the range has no bounds, so it allows missing values too
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Convert both bounds
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Range<U> {
+        Range {
+            min: self.min.map(&f),
+            max: self.max.map(&f),
+        }
+    }
+}
+
+impl<T: PartialOrd> Range<T> {
+    /// Whether a value lies within the bounds
+    pub fn contains(&self, value: &T) -> bool {
+        self.min.as_ref().map_or(true, |min| value >= min)
+            && self.max.as_ref().map_or(true, |max| value <= max)
+    }
+
+    /// Whether an optional value passes; missing values only pass an
+    /// unbounded range
+    pub fn allows(&self, value: Option<&T>) -> bool {
+        match value {
+            Some(value) => self.contains(value),
+            None => self.is_unbounded(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("7"), Some(7.0));
+        assert_eq!(parse_number("-13.5"), Some(-13.5));
+        assert_eq!(parse_number("1e3"), Some(1000.0));
+        for invalid in ["", " 7", "007", "inf", "NaN", "7b", "\"7\"", "+1", ".5"] {
+            assert_eq!(parse_number(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_annotation_filter_matches() {
+        let annotations = Annotations::from([
+            ("framework".to_string(), "pytorch".to_string()),
+            ("params_b".to_string(), "7".to_string()),
+        ]);
+
+        assert!(AnnotationFilter::exists("framework").matches(&annotations));
+        assert!(!AnnotationFilter::exists("task").matches(&annotations));
+        assert!(AnnotationFilter::equals("framework", "pytorch").matches(&annotations));
+        assert!(!AnnotationFilter::equals("framework", "PyTorch").matches(&annotations));
+        assert!(AnnotationFilter::one_of("framework", ["jax", "pytorch"]).matches(&annotations));
+        assert!(!AnnotationFilter::one_of("framework", Vec::<String>::new()).matches(&annotations));
+        assert!(AnnotationFilter::compare("params_b", Comparison::Ge, 7.0).matches(&annotations));
+        assert!(!AnnotationFilter::compare("params_b", Comparison::Gt, 7.0).matches(&annotations));
+        assert!(!AnnotationFilter::compare("framework", Comparison::Lt, 1e9).matches(&annotations));
+    }
+
+    #[test]
+    fn test_range() {
+        let range = Range::new(Some(2), Some(4));
+        assert!(range.contains(&2) && range.contains(&4));
+        assert!(!range.contains(&1) && !range.contains(&5));
+        assert!(!range.allows(None));
+        assert!(Range::<i32>::default().allows(None));
+        assert!(Range::at_least(3).contains(&100));
+        assert_eq!(Range::at_most(3u64).map(|v| v as i64), Range::at_most(3i64));
+    }
+}
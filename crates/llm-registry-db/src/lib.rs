@@ -3,8 +3,21 @@
 //! This crate provides database persistence for the LLM Registry system, including:
 //! - Connection pool management with deadpool
 //! - Repository trait abstractions for assets
+//! - Transitive dependency closures resolved in a single query
+//! - Keyset pagination with opaque cursors
+//! - Annotation predicates and range filters for asset search
+//! - Text search query parsing, with PostgreSQL full-text ranking
 //! - PostgreSQL implementation with SQLx
+//! - SQLite implementation for single-node and edge deployments (`sqlite` feature)
+//! - In-memory repository and event store for tests and embedded use
 //! - Event store for audit trails and event sourcing
+//! - Blob storage drivers for artifact content (file system, S3-compatible)
+//! - Upload session tracking for resumable chunked uploads
+//! - Access control list entries for per-asset and per-namespace permissions
+//! - User accounts with password hashes, roles and lockout state
+//! - Revoked token tracking for server-side logout
+//! - Hashed, scoped API keys
+//! - Rate limit buckets, in memory or shared through Redis
 //! - Database migrations
 //! - Comprehensive error handling
 //!
@@ -42,18 +55,52 @@
 pub use llm_registry_core;
 
 // Public modules
+pub mod acl;
+pub mod api_key;
+pub mod blob_store;
 pub mod cache;
+#[cfg(test)]
+mod conformance;
+pub mod cursor;
+pub mod dependency_graph;
 pub mod error;
 pub mod event_store;
+pub mod filter;
+pub mod memory;
 pub mod nats_publisher;
 pub mod pool;
 pub mod postgres;
+pub mod rate_limit;
 pub mod repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod text_query;
+pub mod token_revocation;
+pub mod upload_session;
+pub mod user_store;
 
 // Re-exports for convenience
+pub use acl::{
+    AclEffect, AclEntry, AclReadFilter, AclStore, AclSubject, AclTarget, InMemoryAclStore,
+    PostgresAclStore,
+};
+pub use api_key::{ApiKey, ApiKeyStore, InMemoryApiKeyStore, PostgresApiKeyStore};
+pub use blob_store::{
+    create_blob_store, BlobMetadata, BlobStore, BlobStream, FileSystemBlobStore, S3BlobStore,
+    S3BlobStoreConfig,
+};
 pub use cache::{CacheConfig, CacheStats, RedisCache};
+pub use cursor::{AssetCursor, EventCursor, SortKey};
+pub use dependency_graph::{
+    ClosureNode, DependencyClosure, DependencyDirection, DependencyEdge, MAX_DEPENDENCY_DEPTH,
+};
 pub use error::{DbError, DbResult};
-pub use event_store::{EventQuery, EventQueryResults, EventStore, PostgresEventStore};
+pub use event_store::{
+    EventQuery, EventQueryResults, EventStore, InMemoryEventStore, PostgresEventStore,
+    StoredEvent,
+};
+pub use filter::{AnnotationCondition, AnnotationFilter, Comparison, Range};
+pub use memory::InMemoryAssetRepository;
 pub use nats_publisher::{
     EventMessage, NatsEventPublisher, NatsPublisherConfig, NatsSubscriberConfig,
 };
@@ -62,10 +109,33 @@ pub use pool::{
     PoolStats,
 };
 pub use postgres::PostgresAssetRepository;
-pub use repository::{AssetRepository, SearchQuery, SearchResults, SortField, SortOrder};
+pub use rate_limit::{
+    InMemoryRateLimitStore, RateLimitDecision, RateLimitStore, RedisRateLimitStore,
+};
+pub use repository::{
+    AssetRepository, Facet, FacetCount, FacetCounts, SearchQuery, SearchResults, SortField,
+    SortOrder,
+};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{
+    create_sqlite_pool, run_sqlite_migrations, SqliteAclStore, SqliteApiKeyStore,
+    SqliteAssetRepository, SqliteEventStore, SqliteUploadSessionStore, SqliteUserStore,
+};
+pub use text_query::{TextField, TextQuery, TextTerm, SEARCHABLE_ANNOTATIONS};
+pub use token_revocation::{
+    spawn_revocation_purge, InMemoryTokenRevocationStore, RedisTokenRevocationStore,
+    TokenRevocationStore,
+};
+pub use upload_session::{
+    InMemoryUploadSessionStore, PostgresUploadSessionStore, UploadChunk, UploadSession,
+    UploadSessionStatus, UploadSessionStore,
+};
+pub use user_store::{InMemoryUserStore, PostgresUserStore, User, UserStore};
 
 // Re-export sqlx types that users may need
 pub use sqlx::postgres::PgPool;
+#[cfg(feature = "sqlite")]
+pub use sqlx::sqlite::SqlitePool;
 
 /// Database layer version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
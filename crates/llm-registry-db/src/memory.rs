@@ -0,0 +1,745 @@
+//! In-memory implementation of AssetRepository
+//!
+//! This module provides an AssetRepository that keeps everything in process
+//! memory. It follows the semantics of the PostgreSQL implementation,
+//! including its constraints, so it can stand in for a database in tests and
+//! embedded deployments. Nothing is persisted.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use llm_registry_core::asset::INITIAL_REVISION;
+use llm_registry_core::{Asset, AssetId, AssetReference, AssetType, DependencyKind};
+use semver::Version;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+
+use crate::acl::{AclEntry, InMemoryAclStore};
+use crate::cursor::{AssetCursor, SortKey};
+use crate::dependency_graph::{self, DependencyClosure, DependencyDirection, MAX_DEPENDENCY_DEPTH};
+use crate::error::{DbError, DbResult};
+use crate::repository::{
+    AssetRepository, Facet, FacetCount, FacetCounts, SearchQuery, SearchResults, SortField,
+    SortOrder,
+};
+use crate::text_query::TextQuery;
+
+/// A dependency edge between two assets
+#[derive(Debug, Clone)]
+struct DependencyEdge {
+    asset_id: AssetId,
+    dependency_id: AssetId,
+    dependency_type: DependencyKind,
+    version_constraint: Option<String>,
+}
+
+/// Repository contents
+///
+/// Assets are stored without their dependencies, which live in `edges` like
+/// the rows of the dependencies table. Tags are kept sorted and unique.
+#[derive(Debug, Default)]
+struct State {
+    assets: HashMap<AssetId, Asset>,
+    edges: Vec<DependencyEdge>,
+}
+
+impl State {
+    /// Assemble an asset with its dependency references
+    fn load(&self, asset: &Asset) -> Asset {
+        let mut asset = asset.clone();
+        asset.dependencies = self
+            .edges
+            .iter()
+            .filter(|edge| edge.asset_id == asset.id)
+            .map(|edge| {
+                AssetReference::by_id(edge.dependency_id).with_kind(edge.dependency_type)
+            })
+            .collect();
+        asset
+    }
+
+    fn find(&self, id: &AssetId) -> Option<Asset> {
+        self.assets.get(id).map(|asset| self.load(asset))
+    }
+
+    /// Check that no other asset has the same name and version
+    fn check_unique(&self, asset: &Asset) -> DbResult<()> {
+        let taken = self.assets.values().any(|other| {
+            other.id != asset.id
+                && other.metadata.name == asset.metadata.name
+                && other.metadata.version == asset.metadata.version
+        });
+        if taken {
+            return Err(DbError::AlreadyExists(format!(
+                "Asset {}@{} already exists",
+                asset.metadata.name, asset.metadata.version
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that both ends of a dependency edge exist and differ
+    fn check_edge(&self, asset_id: &AssetId, dependency_id: &AssetId) -> DbResult<()> {
+        if asset_id == dependency_id {
+            return Err(DbError::ConstraintViolation(format!(
+                "Asset {} cannot depend on itself",
+                asset_id
+            )));
+        }
+        for id in [asset_id, dependency_id] {
+            if !self.assets.contains_key(id) {
+                return Err(DbError::ForeignKeyViolation(format!(
+                    "Asset {} does not exist",
+                    id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `to` is reachable from `from` through dependency edges
+    fn reachable(&self, from: &AssetId, to: &AssetId) -> bool {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([*from]);
+        while let Some(id) = queue.pop_front() {
+            if id == *to {
+                return true;
+            }
+            if visited.insert(id) {
+                queue.extend(
+                    self.edges
+                        .iter()
+                        .filter(|edge| edge.asset_id == id)
+                        .map(|edge| edge.dependency_id),
+                );
+            }
+        }
+        false
+    }
+
+    fn upsert_edge(&mut self, edge: DependencyEdge) {
+        match self.edges.iter_mut().find(|existing| {
+            existing.asset_id == edge.asset_id && existing.dependency_id == edge.dependency_id
+        }) {
+            Some(existing) => {
+                existing.dependency_type = edge.dependency_type;
+                existing.version_constraint = edge.version_constraint;
+            }
+            None => self.edges.push(edge),
+        }
+    }
+}
+
+/// In-memory implementation of AssetRepository
+#[derive(Debug, Default)]
+pub struct InMemoryAssetRepository {
+    state: RwLock<State>,
+    /// ACL entries applied by searches, which would share a database with
+    /// the assets in the PostgreSQL implementation
+    acl: Option<Arc<InMemoryAclStore>>,
+}
+
+impl InMemoryAssetRepository {
+    /// Create an empty repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the entries of an ACL store when searches are restricted to the
+    /// assets a caller may read
+    ///
+    /// Without a store, such searches see no entries and the caller's roles
+    /// decide.
+    pub fn with_acl_store(mut self, store: Arc<InMemoryAclStore>) -> Self {
+        self.acl = Some(store);
+        self
+    }
+}
+
+/// Sort and deduplicate tags, rejecting empty ones
+fn normalize_tags(tags: &[String]) -> DbResult<Vec<String>> {
+    if tags.iter().any(|tag| tag.is_empty()) {
+        return Err(DbError::ConstraintViolation(
+            "Tags must not be empty".to_string(),
+        ));
+    }
+    Ok(tags
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect())
+}
+
+/// Count the values of each requested facet among matching assets
+fn count_facets(facets: &[Facet], matches: &[(&Asset, f64)]) -> FacetCounts {
+    facets
+        .iter()
+        .map(|facet| {
+            let mut counts: HashMap<String, i64> = HashMap::new();
+            for (asset, _) in matches {
+                for value in facet_values(*facet, asset) {
+                    *counts.entry(value).or_default() += 1;
+                }
+            }
+            let mut counts: Vec<FacetCount> = counts
+                .into_iter()
+                .map(|(value, count)| FacetCount { value, count })
+                .collect();
+            counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+            (*facet, counts)
+        })
+        .collect()
+}
+
+/// Values of a facet for one asset, stored as the database stores them
+fn facet_values(facet: Facet, asset: &Asset) -> Vec<String> {
+    match facet {
+        Facet::AssetType => vec![asset.asset_type.to_string()],
+        Facet::Tag => asset.metadata.tags.clone(),
+        Facet::License => asset.metadata.license.iter().cloned().collect(),
+        Facet::Status => vec![asset.status.to_string()],
+        Facet::Author => asset
+            .provenance
+            .iter()
+            .filter_map(|provenance| provenance.author.clone())
+            .collect(),
+        Facet::StorageBackend => vec![asset.storage.backend.to_string()],
+    }
+}
+
+/// Whether an asset passes the filters of a search query, given the ACL
+/// entries that restrict who may read it
+fn matches_query(asset: &Asset, query: &SearchQuery, text: &TextQuery, acl: &[AclEntry]) -> bool {
+    if !text.matches(asset) {
+        return false;
+    }
+
+    if !query.asset_types.is_empty()
+        && !query
+            .asset_types
+            .iter()
+            .any(|t| t.to_string() == asset.asset_type.to_string())
+    {
+        return false;
+    }
+
+    if let Some(author) = &query.author {
+        let asset_author = asset.provenance.as_ref().and_then(|p| p.author.as_ref());
+        if asset_author != Some(author) {
+            return false;
+        }
+    }
+
+    if let Some(backend) = &query.storage_backend {
+        if asset.storage.backend.to_string() != *backend {
+            return false;
+        }
+    }
+
+    if let Some(path) = &query.storage_path {
+        if asset.storage.path != *path {
+            return false;
+        }
+    }
+
+    if query.exclude_ids.contains(&asset.id) {
+        return false;
+    }
+
+    if let Some(filter) = &query.readable_by {
+        if !filter.allows(acl, &asset.id, &asset.metadata.name) {
+            return false;
+        }
+    }
+
+    if query.exclude_deprecated && asset.deprecated_at.is_some() {
+        return false;
+    }
+
+    if !query.size_bytes.allows(asset.metadata.size_bytes.as_ref())
+        || !query.created_at.contains(&asset.created_at)
+        || !query.updated_at.contains(&asset.updated_at)
+    {
+        return false;
+    }
+
+    if !query
+        .annotations
+        .iter()
+        .all(|filter| filter.matches(&asset.metadata.annotations))
+    {
+        return false;
+    }
+
+    query
+        .tags
+        .iter()
+        .all(|tag| asset.metadata.tags.contains(tag))
+}
+
+/// Compare two assets by a sort field, in ascending order
+///
+/// Missing sizes sort after all others, as NULLs do in PostgreSQL. Each
+/// asset comes with its relevance to the search text.
+fn compare_by(
+    field: SortField,
+    (a, a_rank): (&Asset, f64),
+    (b, b_rank): (&Asset, f64),
+) -> Ordering {
+    match field {
+        SortField::CreatedAt => a.created_at.cmp(&b.created_at),
+        SortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+        SortField::Name => a.metadata.name.cmp(&b.metadata.name),
+        SortField::Version => a.metadata.version.cmp(&b.metadata.version),
+        SortField::SizeBytes => {
+            let key = |asset: &Asset| {
+                (
+                    asset.metadata.size_bytes.is_none(),
+                    asset.metadata.size_bytes,
+                )
+            };
+            key(a).cmp(&key(b))
+        }
+        SortField::Relevance => a_rank.total_cmp(&b_rank),
+    }
+}
+
+/// Compare an asset with a cursor position, in ascending order
+///
+/// Uses the same ordering as [`compare_by`], so versions compare as semver.
+fn compare_to_cursor(asset: &Asset, rank: f64, cursor: &AssetCursor) -> Ordering {
+    let ordering = match (SortKey::of(cursor.sort_by, asset, rank), &cursor.key) {
+        (SortKey::Timestamp(own), SortKey::Timestamp(key)) => own.cmp(key),
+        (SortKey::Text(own), SortKey::Text(key)) => match cursor.sort_by {
+            SortField::Version => match Version::parse(key) {
+                Ok(key) => asset.metadata.version.cmp(&key),
+                Err(_) => own.as_str().cmp(key),
+            },
+            _ => own.as_str().cmp(key),
+        },
+        (SortKey::Size(own), SortKey::Size(key)) => {
+            (own.is_none(), own).cmp(&(key.is_none(), *key))
+        }
+        (SortKey::Rank(own), SortKey::Rank(key)) => own.total_cmp(key),
+        // Decoding checks that the key fits the sort field
+        _ => Ordering::Equal,
+    };
+    ordering.then_with(|| asset.id.as_ulid().cmp(cursor.id.as_ulid()))
+}
+
+#[async_trait]
+impl AssetRepository for InMemoryAssetRepository {
+    async fn create(&self, mut asset: Asset) -> DbResult<Asset> {
+        let mut state = self.state.write().unwrap();
+        if state.assets.contains_key(&asset.id) {
+            return Err(DbError::AlreadyExists(format!(
+                "Asset {} already exists",
+                asset.id
+            )));
+        }
+        state.check_unique(&asset)?;
+
+        let mut edges = Vec::new();
+        for dep in &asset.dependencies {
+            let dep_id = dep.as_id().ok_or_else(|| {
+                DbError::InvalidData(
+                    "Dependency must be resolved to ID before persisting".to_string(),
+                )
+            })?;
+            if *dep_id == asset.id {
+                return Err(DbError::ConstraintViolation(format!(
+                    "Asset {} cannot depend on itself",
+                    asset.id
+                )));
+            }
+            if !state.assets.contains_key(dep_id) {
+                return Err(DbError::ForeignKeyViolation(format!(
+                    "Asset {} does not exist",
+                    dep_id
+                )));
+            }
+            edges.push(DependencyEdge {
+                asset_id: asset.id,
+                dependency_id: *dep_id,
+                dependency_type: dep.kind(),
+                version_constraint: None,
+            });
+        }
+
+        asset.revision = INITIAL_REVISION;
+        let mut stored = asset.clone();
+        stored.metadata.tags = normalize_tags(&asset.metadata.tags)?;
+        stored.dependencies.clear();
+        asset.metadata.tags = stored.metadata.tags.clone();
+
+        state.assets.insert(asset.id, stored);
+        for edge in edges {
+            state.upsert_edge(edge);
+        }
+        Ok(asset)
+    }
+
+    async fn find_by_id(&self, id: &AssetId) -> DbResult<Option<Asset>> {
+        Ok(self.state.read().unwrap().find(id))
+    }
+
+    async fn find_by_name_and_version(
+        &self,
+        name: &str,
+        version: &Version,
+    ) -> DbResult<Option<Asset>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .assets
+            .values()
+            .find(|asset| asset.metadata.name == name && asset.metadata.version == *version)
+            .map(|asset| state.load(asset)))
+    }
+
+    async fn find_by_ids(&self, ids: &[AssetId]) -> DbResult<Vec<Asset>> {
+        let state = self.state.read().unwrap();
+        let mut seen = HashSet::new();
+        Ok(ids
+            .iter()
+            .filter(|id| seen.insert(**id))
+            .filter_map(|id| state.find(id))
+            .collect())
+    }
+
+    async fn search(&self, query: &SearchQuery) -> DbResult<SearchResults> {
+        let acl = match (&query.readable_by, &self.acl) {
+            (Some(_), Some(store)) => store.entries(),
+            _ => Vec::new(),
+        };
+        let state = self.state.read().unwrap();
+        let text = TextQuery::parse(query.text.as_deref().unwrap_or_default());
+        let mut matches: Vec<(&Asset, f64)> = state
+            .assets
+            .values()
+            .filter(|asset| matches_query(asset, query, &text, &acl))
+            .map(|asset| (asset, text.rank(asset)))
+            .collect();
+        let facets = count_facets(&query.facets, &matches);
+
+        // Ties are broken by ID so pages are stable
+        matches.sort_by(|a, b| {
+            let ordering = compare_by(query.sort_by, *a, *b)
+                .then_with(|| a.0.id.as_ulid().cmp(b.0.id.as_ulid()));
+            match query.sort_order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+
+        let total = matches.len() as i64;
+        let limit = query.limit.max(0) as usize;
+
+        // A cursor replaces the offset: the page starts just after it
+        let start = match AssetCursor::from_query(query)? {
+            Some(cursor) => matches.partition_point(|(asset, rank)| {
+                let ordering = compare_to_cursor(asset, *rank, &cursor);
+                match query.sort_order {
+                    SortOrder::Ascending => ordering.is_le(),
+                    SortOrder::Descending => ordering.is_ge(),
+                }
+            }),
+            None => query.offset.max(0) as usize,
+        };
+
+        let mut page: Vec<(&Asset, f64)> =
+            matches.into_iter().skip(start).take(limit + 1).collect();
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+
+        let ranks: Vec<f64> = page.iter().map(|(_, rank)| *rank).collect();
+        let assets: Vec<Asset> = page
+            .into_iter()
+            .map(|(asset, _)| state.load(asset))
+            .collect();
+        let (cursors, next_cursor) = AssetCursor::page(query, &assets, &ranks, has_more);
+
+        Ok(SearchResults {
+            assets,
+            total,
+            offset: query.offset,
+            limit: query.limit,
+            next_cursor,
+            cursors,
+            facets,
+        })
+    }
+
+    async fn update(&self, mut asset: Asset, expected_revision: Option<u64>) -> DbResult<Asset> {
+        let mut state = self.state.write().unwrap();
+        let current = match state.assets.get(&asset.id) {
+            Some(current) => current.revision,
+            None => return Err(DbError::NotFound(format!("Asset {} not found", asset.id))),
+        };
+        if let Some(expected) = expected_revision {
+            if expected != current {
+                return Err(DbError::RevisionConflict {
+                    expected,
+                    actual: current,
+                });
+            }
+        }
+        state.check_unique(&asset)?;
+        asset.revision = current + 1;
+
+        // Dependencies are managed through add_dependency and remove_dependency
+        let mut stored = asset.clone();
+        stored.metadata.tags = normalize_tags(&asset.metadata.tags)?;
+        stored.dependencies.clear();
+        stored.updated_at = Utc::now();
+        state.assets.insert(asset.id, stored);
+
+        Ok(asset)
+    }
+
+    async fn delete(
+        &self,
+        id: &AssetId,
+        expected_revision: Option<u64>,
+        blocking: &[DependencyKind],
+    ) -> DbResult<()> {
+        // The write lock is held from the checks to the removal
+        let mut state = self.state.write().unwrap();
+        let current = match state.assets.get(id) {
+            Some(current) => current.revision,
+            None => return Err(DbError::NotFound(format!("Asset {} not found", id))),
+        };
+        if let Some(expected) = expected_revision {
+            if expected != current {
+                return Err(DbError::RevisionConflict {
+                    expected,
+                    actual: current,
+                });
+            }
+        }
+        let count = state
+            .edges
+            .iter()
+            .filter(|edge| edge.dependency_id == *id && blocking.contains(&edge.dependency_type))
+            .count();
+        if count > 0 {
+            return Err(DbError::HasDependents {
+                asset: id.to_string(),
+                count,
+            });
+        }
+        state.assets.remove(id);
+        state
+            .edges
+            .retain(|edge| edge.asset_id != *id && edge.dependency_id != *id);
+        Ok(())
+    }
+
+    async fn list_versions(&self, name: &str) -> DbResult<Vec<Asset>> {
+        let state = self.state.read().unwrap();
+        let mut versions: Vec<&Asset> = state
+            .assets
+            .values()
+            .filter(|asset| asset.metadata.name == name)
+            .collect();
+        versions.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| b.id.as_ulid().cmp(a.id.as_ulid()))
+        });
+        Ok(versions
+            .into_iter()
+            .map(|asset| state.load(asset))
+            .collect())
+    }
+
+    async fn list_dependencies(&self, id: &AssetId) -> DbResult<Vec<Asset>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .edges
+            .iter()
+            .filter(|edge| edge.asset_id == *id)
+            .filter_map(|edge| state.find(&edge.dependency_id))
+            .collect())
+    }
+
+    async fn list_reverse_dependencies(&self, id: &AssetId) -> DbResult<Vec<Asset>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .edges
+            .iter()
+            .filter(|edge| edge.dependency_id == *id)
+            .filter_map(|edge| state.find(&edge.asset_id))
+            .collect())
+    }
+
+    async fn dependency_closure(
+        &self,
+        id: &AssetId,
+        direction: DependencyDirection,
+        max_depth: Option<u32>,
+        kinds: &[DependencyKind],
+    ) -> DbResult<DependencyClosure> {
+        let depth_limit =
+            max_depth.map_or(MAX_DEPENDENCY_DEPTH, |depth| depth.min(MAX_DEPENDENCY_DEPTH));
+        let state = self.state.read().unwrap();
+
+        // Breadth first, so every asset is expanded once at its shortest depth
+        let mut depths = HashMap::from([(*id, 0)]);
+        let mut queue = VecDeque::from([*id]);
+        let mut found = Vec::new();
+        while let Some(source) = queue.pop_front() {
+            let depth = depths[&source] + 1;
+            for edge in &state.edges {
+                let (near, far) = match direction {
+                    DependencyDirection::Dependencies => (edge.asset_id, edge.dependency_id),
+                    DependencyDirection::Dependents => (edge.dependency_id, edge.asset_id),
+                };
+                if near != source
+                    || !(kinds.is_empty() || kinds.contains(&edge.dependency_type))
+                {
+                    continue;
+                }
+                let Some(target) = state.assets.get(&far) else {
+                    continue;
+                };
+                found.push((
+                    dependency_graph::DependencyEdge {
+                        asset_id: edge.asset_id,
+                        dependency_id: edge.dependency_id,
+                        dependency_type: edge.dependency_type,
+                        version_constraint: edge.version_constraint.clone(),
+                        depth,
+                        cycle: false,
+                    },
+                    target.metadata.name.clone(),
+                    target.metadata.version.clone(),
+                ));
+                if depth <= depth_limit && !depths.contains_key(&far) {
+                    depths.insert(far, depth);
+                    queue.push_back(far);
+                }
+            }
+        }
+
+        Ok(DependencyClosure::assemble(
+            *id,
+            direction,
+            depth_limit,
+            found,
+        ))
+    }
+
+    async fn add_tag(&self, id: &AssetId, tag: &str) -> DbResult<()> {
+        let mut state = self.state.write().unwrap();
+        let asset = state
+            .assets
+            .get_mut(id)
+            .ok_or_else(|| DbError::ForeignKeyViolation(format!("Asset {} does not exist", id)))?;
+        let mut tags = asset.metadata.tags.clone();
+        tags.push(tag.to_string());
+        asset.metadata.tags = normalize_tags(&tags)?;
+        Ok(())
+    }
+
+    async fn remove_tag(&self, id: &AssetId, tag: &str) -> DbResult<()> {
+        if let Some(asset) = self.state.write().unwrap().assets.get_mut(id) {
+            asset.metadata.tags.retain(|t| t != tag);
+        }
+        Ok(())
+    }
+
+    async fn get_tags(&self, id: &AssetId) -> DbResult<Vec<String>> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .assets
+            .get(id)
+            .map(|asset| asset.metadata.tags.clone())
+            .unwrap_or_default())
+    }
+
+    async fn list_all_tags(&self) -> DbResult<Vec<String>> {
+        let state = self.state.read().unwrap();
+        let tags: BTreeSet<&String> = state
+            .assets
+            .values()
+            .flat_map(|asset| &asset.metadata.tags)
+            .collect();
+        Ok(tags.into_iter().cloned().collect())
+    }
+
+    async fn add_dependency(
+        &self,
+        asset_id: &AssetId,
+        dependency_id: &AssetId,
+        version_constraint: Option<&str>,
+        kind: DependencyKind,
+    ) -> DbResult<()> {
+        let mut state = self.state.write().unwrap();
+
+        if asset_id != dependency_id && state.reachable(dependency_id, asset_id) {
+            return Err(DbError::CircularDependency(format!(
+                "Adding dependency from {} to {} would create a cycle",
+                asset_id, dependency_id
+            )));
+        }
+        state.check_edge(asset_id, dependency_id)?;
+
+        state.upsert_edge(DependencyEdge {
+            asset_id: *asset_id,
+            dependency_id: *dependency_id,
+            dependency_type: kind,
+            version_constraint: version_constraint.map(str::to_string),
+        });
+        Ok(())
+    }
+
+    async fn remove_dependency(&self, asset_id: &AssetId, dependency_id: &AssetId) -> DbResult<()> {
+        self.state
+            .write()
+            .unwrap()
+            .edges
+            .retain(|edge| !(edge.asset_id == *asset_id && edge.dependency_id == *dependency_id));
+        Ok(())
+    }
+
+    async fn count_assets(&self) -> DbResult<i64> {
+        Ok(self.state.read().unwrap().assets.len() as i64)
+    }
+
+    async fn count_by_type(&self, asset_type: &AssetType) -> DbResult<i64> {
+        let asset_type = asset_type.to_string();
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .assets
+            .values()
+            .filter(|asset| asset.asset_type.to_string() == asset_type)
+            .count() as i64)
+    }
+
+    async fn health_check(&self) -> DbResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+
+    #[tokio::test]
+    async fn test_asset_repository_conformance() {
+        conformance::asset_repository(&InMemoryAssetRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_acl_search_conformance() {
+        let store = Arc::new(InMemoryAclStore::new());
+        let repo = InMemoryAssetRepository::new().with_acl_store(store.clone());
+        conformance::acl_search(&repo, store.as_ref()).await;
+    }
+}
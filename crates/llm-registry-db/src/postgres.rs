@@ -5,20 +5,31 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use llm_registry_core::asset::INITIAL_REVISION;
 use llm_registry_core::{
-    Asset, AssetId, AssetMetadata, AssetStatus, AssetType, Checksum, HashAlgorithm, Provenance,
-    StorageBackend, StorageLocation,
+    Asset, AssetId, AssetMetadata, AssetReference, AssetStatus, AssetType, Checksum,
+    DependencyKind, HashAlgorithm, Provenance, StorageBackend, StorageLocation,
 };
 use semver::Version;
 use serde_json::Value as JsonValue;
 use sqlx::postgres::PgRow;
-use sqlx::{PgPool, Row};
-use std::collections::HashMap;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use std::collections::{BTreeSet, HashMap};
 use std::str::FromStr;
 use tracing::{debug, instrument};
 
+use crate::acl::{AclEffect, AclReadFilter};
+use crate::cursor::{AssetCursor, SortKey};
+use crate::dependency_graph::{
+    DependencyClosure, DependencyDirection, DependencyEdge, MAX_DEPENDENCY_DEPTH,
+};
 use crate::error::{DbError, DbResult};
-use crate::repository::{AssetRepository, SearchQuery, SearchResults, SortField, SortOrder};
+use crate::filter::{AnnotationCondition, AnnotationFilter, Range};
+use crate::repository::{
+    AssetRepository, Facet, FacetCount, FacetCounts, SearchQuery, SearchResults, SortField,
+    SortOrder,
+};
+use crate::text_query::TextQuery;
 
 /// PostgreSQL implementation of AssetRepository
 #[derive(Debug, Clone)]
@@ -41,9 +52,12 @@ impl PostgresAssetRepository {
 #[async_trait]
 impl AssetRepository for PostgresAssetRepository {
     #[instrument(skip(self, asset), fields(asset_id = %asset.id, asset_name = %asset.metadata.name))]
-    async fn create(&self, asset: Asset) -> DbResult<Asset> {
+    async fn create(&self, mut asset: Asset) -> DbResult<Asset> {
         debug!("Creating asset in database");
 
+        // New rows always start at the initial revision (column default)
+        asset.revision = INITIAL_REVISION;
+
         // Start a transaction
         let mut tx = self.pool.begin().await?;
 
@@ -95,7 +109,8 @@ impl AssetRepository for PostgresAssetRepository {
         .bind(&asset.deprecated_at)
         .bind(serde_json::to_value(&asset.metadata.annotations)?)
         .execute(&mut *tx)
-        .await?;
+        .await
+        .map_err(|e| map_unique_violation(e, &asset))?;
 
         // Insert tags
         for tag in &asset.metadata.tags {
@@ -120,13 +135,15 @@ impl AssetRepository for PostgresAssetRepository {
 
             sqlx::query(
                 r#"
-                INSERT INTO asset_dependencies (asset_id, dependency_id, version_constraint)
-                VALUES ($1, $2, $3)
+                INSERT INTO asset_dependencies
+                    (asset_id, dependency_id, dependency_type, version_constraint)
+                VALUES ($1, $2, $3, $4)
                 ON CONFLICT (asset_id, dependency_id) DO NOTHING
                 "#,
             )
             .bind(&asset.id.to_string())
             .bind(&dep_id.to_string())
+            .bind(dep.kind().as_str())
             .bind(dep.as_name_version().map(|(_, v)| v))
             .execute(&mut *tx)
             .await?;
@@ -152,7 +169,7 @@ impl AssetRepository for PostgresAssetRepository {
                 signature_algorithm, signature_value, signature_key_id,
                 description, license, content_type,
                 author, source_repo, commit_hash, build_id,
-                created_at, updated_at, deprecated_at, metadata
+                created_at, updated_at, deprecated_at, metadata, revision
             FROM assets
             WHERE id = $1
             "#,
@@ -188,7 +205,7 @@ impl AssetRepository for PostgresAssetRepository {
                 signature_algorithm, signature_value, signature_key_id,
                 description, license, content_type,
                 author, source_repo, commit_hash, build_id,
-                created_at, updated_at, deprecated_at, metadata
+                created_at, updated_at, deprecated_at, metadata, revision
             FROM assets
             WHERE name = $1 AND version = $2
             "#,
@@ -227,7 +244,7 @@ impl AssetRepository for PostgresAssetRepository {
                 signature_algorithm, signature_value, signature_key_id,
                 description, license, content_type,
                 author, source_repo, commit_hash, build_id,
-                created_at, updated_at, deprecated_at, metadata
+                created_at, updated_at, deprecated_at, metadata, revision
             FROM assets
             WHERE id = ANY($1)
             "#,
@@ -250,8 +267,7 @@ impl AssetRepository for PostgresAssetRepository {
     async fn search(&self, query: &SearchQuery) -> DbResult<SearchResults> {
         debug!("Searching assets with filters");
 
-        // Build dynamic query
-        let mut sql = String::from(
+        let mut builder = QueryBuilder::<Postgres>::new(
             r#"
             SELECT
                 a.id, a.name, a.version, a.asset_type, a.status,
@@ -260,119 +276,47 @@ impl AssetRepository for PostgresAssetRepository {
                 a.signature_algorithm, a.signature_value, a.signature_key_id,
                 a.description, a.license, a.content_type,
                 a.author, a.source_repo, a.commit_hash, a.build_id,
-                a.created_at, a.updated_at, a.deprecated_at, a.metadata
-            FROM assets a
-            WHERE 1=1
+                a.created_at, a.updated_at, a.deprecated_at, a.metadata, a.revision,
             "#,
         );
-
-        let mut conditions = Vec::new();
-        let mut bind_values: Vec<String> = Vec::new();
-        let mut param_num = 1;
-
-        // Text search
-        if let Some(ref text) = query.text {
-            conditions.push(format!(
-                "(a.name ILIKE ${} OR a.description ILIKE ${})",
-                param_num, param_num + 1
-            ));
-            bind_values.push(format!("%{}%", text));
-            bind_values.push(format!("%{}%", text));
-            param_num += 2;
-        }
-
-        // Asset type filter
-        if !query.asset_types.is_empty() {
-            conditions.push(format!("a.asset_type = ANY(${})", param_num));
-            // This is a placeholder - we'll use a different approach for ANY
-            param_num += 1;
-        }
-
-        // Author filter
-        if let Some(ref author) = query.author {
-            conditions.push(format!("a.author = ${}", param_num));
-            bind_values.push(author.clone());
-            param_num += 1;
-        }
-
-        // Storage backend filter
-        if let Some(ref backend) = query.storage_backend {
-            conditions.push(format!("a.storage_backend = ${}", param_num));
-            bind_values.push(backend.clone());
-            param_num += 1;
-        }
-
-        // Deprecated filter
-        if query.exclude_deprecated {
-            conditions.push("a.deprecated_at IS NULL".to_string());
-        }
-
-        // Tag filter - must have all specified tags
-        if !query.tags.is_empty() {
-            let tag_condition = format!(
-                "a.id IN (
-                    SELECT asset_id FROM asset_tags
-                    WHERE tag = ANY(${}::text[])
-                    GROUP BY asset_id
-                    HAVING COUNT(DISTINCT tag) = {}
-                )",
-                param_num,
-                query.tags.len()
-            );
-            conditions.push(tag_condition);
-            #[allow(unused_assignments)]
-            {
-                param_num += 1;
-            }
-        }
-
-        // Add conditions to query
-        if !conditions.is_empty() {
-            sql.push_str(" AND ");
-            sql.push_str(&conditions.join(" AND "));
+        let tsquery = search_tsquery(query);
+        push_rank(&mut builder, tsquery.as_deref());
+        builder.push(" AS rank FROM assets a WHERE 1=1");
+
+        let cursor = AssetCursor::from_query(query)?;
+        push_search_filters(&mut builder, query);
+        if let Some(ref cursor) = cursor {
+            push_cursor_filter(&mut builder, cursor, tsquery.as_deref());
         }
 
-        // Add ORDER BY
-        let sort_field = match query.sort_by {
-            SortField::CreatedAt => "a.created_at",
-            SortField::UpdatedAt => "a.updated_at",
-            SortField::Name => "a.name",
-            SortField::Version => "a.version",
-            SortField::SizeBytes => "a.size_bytes",
-        };
-
+        // Add ORDER BY, breaking ties by ID so pages are stable
         let sort_order = match query.sort_order {
             SortOrder::Ascending => "ASC",
             SortOrder::Descending => "DESC",
         };
 
-        sql.push_str(&format!(" ORDER BY {} {}", sort_field, sort_order));
+        builder.push(format!(
+            " ORDER BY {} {}, a.id {}",
+            sort_column(query.sort_by),
+            sort_order,
+            sort_order
+        ));
 
-        // Add LIMIT and OFFSET
-        sql.push_str(&format!(" LIMIT {} OFFSET {}", query.limit, query.offset));
+        // Fetch one extra row to learn whether another page follows
+        let limit = query.limit.max(0);
+        builder.push(" LIMIT ");
+        builder.push_bind(limit + 1);
+        builder.push(" OFFSET ");
+        builder.push_bind(if cursor.is_some() { 0 } else { query.offset });
 
-        // For simplicity, we'll use a simpler approach - rebuild with sqlx query builder
-        // In production, you'd want to use a query builder or macro for this
-        let mut final_query = sqlx::query(&sql);
-
-        // Bind parameters in order
-        for value in &bind_values {
-            final_query = final_query.bind(value);
-        }
-
-        let types: Vec<String> = query.asset_types.iter().map(|t| t.to_string()).collect();
-        if !query.asset_types.is_empty() {
-            final_query = final_query.bind(&types);
-        }
-
-        if !query.tags.is_empty() {
-            final_query = final_query.bind(&query.tags);
-        }
-
-        let rows = final_query.fetch_all(&self.pool).await?;
+        let mut rows = builder.build().fetch_all(&self.pool).await?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
 
         let mut assets = Vec::new();
+        let mut ranks = Vec::new();
         for row in rows {
+            ranks.push(row.get("rank"));
             let asset = row_to_asset(row)?;
             let asset = self.load_asset_relations(asset).await?;
             assets.push(asset);
@@ -380,22 +324,27 @@ impl AssetRepository for PostgresAssetRepository {
 
         // Get total count (without pagination)
         let total = self.count_search_results(query).await?;
+        let facets = self.count_facets(query).await?;
+        let (cursors, next_cursor) = AssetCursor::page(query, &assets, &ranks, has_more);
 
         Ok(SearchResults {
             assets,
             total,
             offset: query.offset,
             limit: query.limit,
+            next_cursor,
+            cursors,
+            facets,
         })
     }
 
     #[instrument(skip(self, asset), fields(asset_id = %asset.id))]
-    async fn update(&self, asset: Asset) -> DbResult<Asset> {
+    async fn update(&self, mut asset: Asset, expected_revision: Option<u64>) -> DbResult<Asset> {
         debug!("Updating asset");
 
         let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query(
+        let revision: Option<i64> = sqlx::query_scalar(
             r#"
             UPDATE assets SET
                 name = $2,
@@ -420,8 +369,10 @@ impl AssetRepository for PostgresAssetRepository {
                 build_id = $21,
                 deprecated_at = $22,
                 metadata = $23,
-                updated_at = $24
-            WHERE id = $1
+                updated_at = $24,
+                revision = revision + 1
+            WHERE id = $1 AND ($25::BIGINT IS NULL OR revision = $25)
+            RETURNING revision
             "#,
         )
         .bind(&asset.id.to_string())
@@ -448,12 +399,29 @@ impl AssetRepository for PostgresAssetRepository {
         .bind(&asset.deprecated_at)
         .bind(serde_json::to_value(&asset.metadata.annotations)?)
         .bind(Utc::now())
-        .execute(&mut *tx)
-        .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(DbError::NotFound(format!("Asset {} not found", asset.id)));
-        }
+        .bind(expected_revision.map(|r| r as i64))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| map_unique_violation(e, &asset))?;
+
+        asset.revision = match revision {
+            Some(revision) => revision as u64,
+            None => {
+                // Either the row is gone or another writer got there first
+                let actual: Option<i64> =
+                    sqlx::query_scalar("SELECT revision FROM assets WHERE id = $1")
+                        .bind(asset.id.to_string())
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                return Err(match (actual, expected_revision) {
+                    (Some(actual), Some(expected)) => DbError::RevisionConflict {
+                        expected,
+                        actual: actual as u64,
+                    },
+                    _ => DbError::NotFound(format!("Asset {} not found", asset.id)),
+                });
+            }
+        };
 
         // Update tags - delete and re-insert for simplicity
         sqlx::query("DELETE FROM asset_tags WHERE asset_id = $1")
@@ -466,6 +434,7 @@ impl AssetRepository for PostgresAssetRepository {
                 r#"
                 INSERT INTO asset_tags (asset_id, tag)
                 VALUES ($1, $2)
+                ON CONFLICT (asset_id, tag) DO NOTHING
                 "#,
             )
             .bind(&asset.id.to_string())
@@ -481,18 +450,57 @@ impl AssetRepository for PostgresAssetRepository {
     }
 
     #[instrument(skip(self), fields(asset_id = %id))]
-    async fn delete(&self, id: &AssetId) -> DbResult<()> {
+    async fn delete(
+        &self,
+        id: &AssetId,
+        expected_revision: Option<u64>,
+        blocking: &[DependencyKind],
+    ) -> DbResult<()> {
         debug!("Deleting asset");
 
-        let result = sqlx::query("DELETE FROM assets WHERE id = $1")
-            .bind(&id.to_string())
-            .execute(&self.pool)
+        // Locking the row makes concurrent inserts of dependencies on it wait,
+        // so the delete below sees every committed one
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT 1 FROM assets WHERE id = $1 FOR UPDATE")
+            .bind(id.to_string())
+            .execute(&mut *tx)
             .await?;
 
+        let result = sqlx::query(&format!(
+            "DELETE FROM assets WHERE id = $1 AND ($2::BIGINT IS NULL OR revision = $2) AND NOT {}",
+            blocked_asset_filter(blocking)
+        ))
+        .bind(id.to_string())
+        .bind(expected_revision.map(|r| r as i64))
+        .execute(&mut *tx)
+        .await?;
+
         if result.rows_affected() == 0 {
-            return Err(DbError::NotFound(format!("Asset {} not found", id)));
+            // The row is gone, another writer got there first, or other
+            // assets still need it
+            let actual: Option<i64> =
+                sqlx::query_scalar("SELECT revision FROM assets WHERE id = $1")
+                    .bind(id.to_string())
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let blocked: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM asset_dependencies d WHERE d.dependency_id = $1 AND {}",
+                closure_kind_filter(blocking)
+            ))
+            .bind(id.to_string())
+            .fetch_one(&mut *tx)
+            .await?;
+            return Err(delete_error(
+                id,
+                actual,
+                expected_revision,
+                blocking,
+                blocked,
+            ));
         }
 
+        tx.commit().await?;
+
         debug!("Asset deleted successfully");
         Ok(())
     }
@@ -510,7 +518,7 @@ impl AssetRepository for PostgresAssetRepository {
                 signature_algorithm, signature_value, signature_key_id,
                 description, license, content_type,
                 author, source_repo, commit_hash, build_id,
-                created_at, updated_at, deprecated_at, metadata
+                created_at, updated_at, deprecated_at, metadata, revision
             FROM assets
             WHERE name = $1
             ORDER BY created_at DESC
@@ -543,7 +551,7 @@ impl AssetRepository for PostgresAssetRepository {
                 a.signature_algorithm, a.signature_value, a.signature_key_id,
                 a.description, a.license, a.content_type,
                 a.author, a.source_repo, a.commit_hash, a.build_id,
-                a.created_at, a.updated_at, a.deprecated_at, a.metadata
+                a.created_at, a.updated_at, a.deprecated_at, a.metadata, a.revision
             FROM assets a
             INNER JOIN asset_dependencies d ON a.id = d.dependency_id
             WHERE d.asset_id = $1
@@ -576,7 +584,7 @@ impl AssetRepository for PostgresAssetRepository {
                 a.signature_algorithm, a.signature_value, a.signature_key_id,
                 a.description, a.license, a.content_type,
                 a.author, a.source_repo, a.commit_hash, a.build_id,
-                a.created_at, a.updated_at, a.deprecated_at, a.metadata
+                a.created_at, a.updated_at, a.deprecated_at, a.metadata, a.revision
             FROM assets a
             INNER JOIN asset_dependencies d ON a.id = d.asset_id
             WHERE d.dependency_id = $1
@@ -596,6 +604,65 @@ impl AssetRepository for PostgresAssetRepository {
         Ok(assets)
     }
 
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn dependency_closure(
+        &self,
+        id: &AssetId,
+        direction: DependencyDirection,
+        max_depth: Option<u32>,
+        kinds: &[DependencyKind],
+    ) -> DbResult<DependencyClosure> {
+        debug!("Resolving dependency closure");
+
+        let depth_limit =
+            max_depth.map_or(MAX_DEPENDENCY_DEPTH, |depth| depth.min(MAX_DEPENDENCY_DEPTH));
+        let (near, far) = closure_columns(direction);
+
+        // `reachable` holds each asset once per depth it can be reached at,
+        // which terminates on cycles because depths are bounded. Edges are
+        // then taken from the shortest depth of their source, including one
+        // level past the limit to detect truncation.
+        let kind_filter = closure_kind_filter(kinds);
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH RECURSIVE reachable(id, depth) AS (
+                SELECT $1::varchar, 0
+                UNION
+                SELECT d.{far}, r.depth + 1
+                FROM reachable r
+                INNER JOIN asset_dependencies d ON d.{near} = r.id
+                WHERE r.depth < $2 AND {kind_filter}
+            ),
+            nearest AS (
+                SELECT id, MIN(depth) AS depth FROM reachable GROUP BY id
+            )
+            SELECT
+                d.asset_id, d.dependency_id, d.dependency_type, d.version_constraint,
+                n.depth + 1 AS depth, a.name, a.version
+            FROM nearest n
+            INNER JOIN asset_dependencies d ON d.{near} = n.id
+            INNER JOIN assets a ON a.id = d.{far}
+            WHERE {kind_filter}
+            "#,
+        ))
+        .bind(id.to_string())
+        .bind(depth_limit as i32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let found = rows
+            .into_iter()
+            .map(row_to_closure_entry)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        Ok(DependencyClosure::assemble(
+            *id,
+            direction,
+            depth_limit,
+            found,
+        ))
+    }
+
     #[instrument(skip(self), fields(asset_id = %id, tag = %tag))]
     async fn add_tag(&self, id: &AssetId, tag: &str) -> DbResult<()> {
         debug!("Adding tag to asset");
@@ -667,6 +734,7 @@ impl AssetRepository for PostgresAssetRepository {
         asset_id: &AssetId,
         dependency_id: &AssetId,
         version_constraint: Option<&str>,
+        kind: DependencyKind,
     ) -> DbResult<()> {
         debug!("Adding dependency relationship");
 
@@ -680,14 +748,17 @@ impl AssetRepository for PostgresAssetRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO asset_dependencies (asset_id, dependency_id, version_constraint)
-            VALUES ($1, $2, $3)
+            INSERT INTO asset_dependencies
+                (asset_id, dependency_id, dependency_type, version_constraint)
+            VALUES ($1, $2, $3, $4)
             ON CONFLICT (asset_id, dependency_id) DO UPDATE
-            SET version_constraint = EXCLUDED.version_constraint
+            SET dependency_type = EXCLUDED.dependency_type,
+                version_constraint = EXCLUDED.version_constraint
             "#,
         )
         .bind(&asset_id.to_string())
         .bind(&dependency_id.to_string())
+        .bind(kind.as_str())
         .bind(version_constraint)
         .execute(&self.pool)
         .await?;
@@ -750,7 +821,7 @@ impl PostgresAssetRepository {
 
         // Load dependency references
         let dep_rows = sqlx::query(
-            "SELECT dependency_id FROM asset_dependencies WHERE asset_id = $1"
+            "SELECT dependency_id, dependency_type FROM asset_dependencies WHERE asset_id = $1"
         )
         .bind(&asset.id.to_string())
         .fetch_all(&self.pool)
@@ -760,9 +831,11 @@ impl PostgresAssetRepository {
             .iter()
             .filter_map(|row| {
                 let dep_id_str: String = row.get("dependency_id");
+                let kind: String = row.get("dependency_type");
+                let kind = DependencyKind::from_str(&kind).unwrap_or_default();
                 AssetId::from_str(&dep_id_str)
                     .ok()
-                    .map(|id| llm_registry_core::AssetReference::by_id(id))
+                    .map(|id| AssetReference::by_id(id).with_kind(kind))
             })
             .collect();
 
@@ -801,25 +874,433 @@ impl PostgresAssetRepository {
 
     /// Count search results without pagination
     async fn count_search_results(&self, query: &SearchQuery) -> DbResult<i64> {
-        // Simplified count query - in production, this should mirror the search logic
-        let mut sql = String::from("SELECT COUNT(*) as count FROM assets a WHERE 1=1");
+        let mut builder =
+            QueryBuilder::<Postgres>::new("SELECT COUNT(*) as count FROM assets a WHERE 1=1");
+        push_search_filters(&mut builder, query);
+
+        let row = builder.build().fetch_one(&self.pool).await?;
 
-        if query.exclude_deprecated {
-            sql.push_str(" AND a.deprecated_at IS NULL");
+        Ok(row.get("count"))
+    }
+
+    /// Count the values of a facet among assets matching the query filters
+    async fn count_facet(&self, query: &SearchQuery, facet: Facet) -> DbResult<Vec<FacetCount>> {
+        let column = facet_column(facet);
+        let mut builder = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} AS value, COUNT(*) AS count FROM assets a",
+            column
+        ));
+        if facet == Facet::Tag {
+            builder.push(" JOIN asset_tags t ON t.asset_id = a.id");
         }
+        builder.push(format!(" WHERE {} IS NOT NULL", column));
+        push_search_filters(&mut builder, query);
+        builder.push(format!(
+            " GROUP BY {} ORDER BY count DESC, {} COLLATE \"C\"",
+            column, column
+        ));
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| FacetCount {
+                value: row.get("value"),
+                count: row.get("count"),
+            })
+            .collect())
+    }
 
-        if !query.asset_types.is_empty() {
-            let types: Vec<String> = query.asset_types.iter().map(|t| t.to_string()).collect();
-            let placeholders: Vec<String> = types.iter().map(|t| format!("'{}'", t)).collect();
-            sql.push_str(&format!(" AND a.asset_type IN ({})", placeholders.join(", ")));
+    /// Count every facet the query requests
+    async fn count_facets(&self, query: &SearchQuery) -> DbResult<FacetCounts> {
+        let mut facets = FacetCounts::new();
+        for facet in &query.facets {
+            facets.insert(*facet, self.count_facet(query, *facet).await?);
         }
+        Ok(facets)
+    }
+}
 
-        let row = sqlx::query(&sql).fetch_one(&self.pool).await?;
+/// Column holding the values of a facet; tags need a join with `asset_tags t`
+fn facet_column(facet: Facet) -> &'static str {
+    match facet {
+        Facet::AssetType => "a.asset_type",
+        Facet::Tag => "t.tag",
+        Facet::License => "a.license",
+        Facet::Status => "a.status",
+        Facet::Author => "a.author",
+        Facet::StorageBackend => "a.storage_backend",
+    }
+}
 
-        Ok(row.get("count"))
+/// Column an asset search is sorted by
+///
+/// Relevance refers to the `rank` output column, which only ORDER BY can use.
+fn sort_column(field: SortField) -> &'static str {
+    match field {
+        SortField::CreatedAt => "a.created_at",
+        SortField::UpdatedAt => "a.updated_at",
+        SortField::Name => "a.name",
+        SortField::Version => "a.version",
+        SortField::SizeBytes => "a.size_bytes",
+        SortField::Relevance => "rank",
+    }
+}
+
+/// `to_tsquery` input for the search text, if there is any
+fn search_tsquery(query: &SearchQuery) -> Option<String> {
+    let text = TextQuery::parse(query.text.as_deref()?);
+    (!text.is_empty()).then(|| text.to_tsquery())
+}
+
+/// Append the relevance of an asset to the search text
+///
+/// Without search text every asset ranks zero.
+fn push_rank(builder: &mut QueryBuilder<'_, Postgres>, tsquery: Option<&str>) {
+    match tsquery {
+        Some(tsquery) => {
+            builder.push("ts_rank(a.search_vector, to_tsquery('english', ");
+            builder.push_bind(tsquery.to_string());
+            builder.push("))::float8");
+        }
+        None => {
+            builder.push("0::float8");
+        }
+    }
+}
+
+/// Restrict a search to the rows after a cursor position
+///
+/// PostgreSQL sorts NULL sizes last in ascending and first in descending
+/// order, so they need their own conditions.
+fn push_cursor_filter(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    cursor: &AssetCursor,
+    tsquery: Option<&str>,
+) {
+    let id = cursor.id.to_string();
+    match (&cursor.key, cursor.sort_order) {
+        (SortKey::Size(None), SortOrder::Ascending) => {
+            builder.push(" AND a.size_bytes IS NULL AND a.id > ");
+            builder.push_bind(id);
+        }
+        (SortKey::Size(None), SortOrder::Descending) => {
+            builder.push(" AND (a.size_bytes IS NOT NULL OR a.id < ");
+            builder.push_bind(id);
+            builder.push(")");
+        }
+        (key, order) => {
+            let comparison = match order {
+                SortOrder::Ascending => ">",
+                SortOrder::Descending => "<",
+            };
+            builder.push(" AND ((");
+            match cursor.sort_by {
+                SortField::Relevance => push_rank(builder, tsquery),
+                field => {
+                    builder.push(sort_column(field));
+                }
+            }
+            builder.push(format!(", a.id) {} (", comparison));
+            match key {
+                SortKey::Timestamp(timestamp) => builder.push_bind(*timestamp),
+                SortKey::Text(text) => builder.push_bind(text.clone()),
+                SortKey::Size(size) => builder.push_bind(*size),
+                SortKey::Rank(rank) => builder.push_bind(*rank),
+            };
+            builder.push(", ");
+            builder.push_bind(id);
+            builder.push(")");
+            if order == SortOrder::Ascending && matches!(key, SortKey::Size(Some(_))) {
+                builder.push(" OR a.size_bytes IS NULL");
+            }
+            builder.push(")");
+        }
+    }
+}
+
+/// Append the condition that a caller may read the asset under the ACL
+/// entries covering it
+///
+/// Follows [`AclReadFilter::allows`]: a matching deny entry hides the asset,
+/// allow entries restrict it to the subjects they match, and otherwise the
+/// caller's roles decide.
+fn push_acl_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &AclReadFilter) {
+    builder.push(" AND NOT");
+    push_acl_entries(builder, filter, AclEffect::Deny, true);
+    builder.push(" AND (");
+    push_acl_entries(builder, filter, AclEffect::Allow, true);
+    if filter.role_allows {
+        builder.push(" OR NOT");
+        push_acl_entries(builder, filter, AclEffect::Allow, false);
+    }
+    builder.push(")");
+}
+
+/// Append whether ACL entries with an effect cover the asset, optionally
+/// only those matching the caller
+fn push_acl_entries(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    filter: &AclReadFilter,
+    effect: AclEffect,
+    matching_caller: bool,
+) {
+    builder.push(
+        " EXISTS (
+            SELECT 1 FROM asset_acl_entries e
+            WHERE (e.asset_id = a.id OR starts_with(a.name, e.name_prefix))
+              AND e.permission = ANY(",
+    );
+    builder.push_bind(filter.permissions.clone());
+    builder.push(format!(") AND e.effect = '{}'", effect.as_str()));
+    if matching_caller {
+        builder.push(
+            " AND (e.subject_type = 'everyone'
+              OR (e.subject_type = 'principal' AND e.subject = ",
+        );
+        builder.push_bind(filter.principal.clone());
+        builder.push(") OR (e.subject_type = 'role' AND e.subject = ANY(");
+        builder.push_bind(filter.roles.clone());
+        builder.push(")))");
+    }
+    builder.push(")");
+}
+
+/// Append the filter conditions of a search query
+fn push_search_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &SearchQuery) {
+    // Full-text search through the weighted search vector
+    if let Some(tsquery) = search_tsquery(query) {
+        builder.push(" AND a.search_vector @@ to_tsquery('english', ");
+        builder.push_bind(tsquery);
+        builder.push(")");
+    }
+
+    // Asset type filter
+    if !query.asset_types.is_empty() {
+        let types: Vec<String> = query.asset_types.iter().map(|t| t.to_string()).collect();
+        builder.push(" AND a.asset_type = ANY(");
+        builder.push_bind(types);
+        builder.push(")");
+    }
+
+    // Author filter
+    if let Some(ref author) = query.author {
+        builder.push(" AND a.author = ");
+        builder.push_bind(author.clone());
+    }
+
+    // Storage backend filter
+    if let Some(ref backend) = query.storage_backend {
+        builder.push(" AND a.storage_backend = ");
+        builder.push_bind(backend.clone());
+    }
+
+    // Storage path filter
+    if let Some(ref path) = query.storage_path {
+        builder.push(" AND a.storage_path = ");
+        builder.push_bind(path.clone());
+    }
+
+    // Excluded assets
+    if !query.exclude_ids.is_empty() {
+        let ids: Vec<String> = query.exclude_ids.iter().map(|id| id.to_string()).collect();
+        builder.push(" AND NOT a.id = ANY(");
+        builder.push_bind(ids);
+        builder.push(")");
+    }
+
+    // ACL filter
+    if let Some(ref filter) = query.readable_by {
+        push_acl_filter(builder, filter);
+    }
+
+    // Deprecated filter
+    if query.exclude_deprecated {
+        builder.push(" AND a.deprecated_at IS NULL");
+    }
+
+    // Range filters
+    let size_bytes = query.size_bytes.map(|size| size as i64);
+    push_range(builder, "a.size_bytes", size_bytes);
+    push_range(builder, "a.created_at", query.created_at);
+    push_range(builder, "a.updated_at", query.updated_at);
+
+    // Annotation filters
+    for filter in &query.annotations {
+        push_annotation_filter(builder, filter);
+    }
+
+    // Tag filter - must have all specified tags
+    if !query.tags.is_empty() {
+        let tags: BTreeSet<&String> = query.tags.iter().collect();
+        builder.push(
+            " AND a.id IN (
+                SELECT asset_id FROM asset_tags
+                WHERE tag = ANY(",
+        );
+        builder.push_bind(tags.iter().map(|t| t.to_string()).collect::<Vec<_>>());
+        builder.push(format!(
+            ")
+                GROUP BY asset_id
+                HAVING COUNT(DISTINCT tag) = {}
+            )",
+            tags.len()
+        ));
+    }
+}
+
+/// Append inclusive bounds on a column; NULLs fail any bound
+fn push_range<'a, T>(builder: &mut QueryBuilder<'a, Postgres>, column: &str, range: Range<T>)
+where
+    T: 'a + Send + sqlx::Encode<'a, Postgres> + sqlx::Type<Postgres>,
+{
+    if let Some(min) = range.min {
+        builder.push(format!(" AND {} >= ", column));
+        builder.push_bind(min);
+    }
+    if let Some(max) = range.max {
+        builder.push(format!(" AND {} <= ", column));
+        builder.push_bind(max);
+    }
+}
+
+/// Append a condition on one annotation in the `metadata` column
+///
+/// Existence and equality use the GIN index on `metadata`. Numeric
+/// comparisons only cast values written as JSON numbers, so other values
+/// never match instead of failing the query.
+fn push_annotation_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &AnnotationFilter) {
+    match &filter.condition {
+        AnnotationCondition::Exists => {
+            builder.push(" AND a.metadata ? ");
+            builder.push_bind(filter.key.clone());
+        }
+        AnnotationCondition::Equals(value) => {
+            builder.push(" AND a.metadata @> jsonb_build_object(");
+            builder.push_bind(filter.key.clone());
+            builder.push("::text, ");
+            builder.push_bind(value.clone());
+            builder.push("::text)");
+        }
+        AnnotationCondition::In(values) => {
+            builder.push(" AND a.metadata ->> ");
+            builder.push_bind(filter.key.clone());
+            builder.push(" = ANY(");
+            builder.push_bind(values.clone());
+            builder.push(")");
+        }
+        AnnotationCondition::Compare { op, value } => {
+            builder.push(" AND CASE WHEN a.metadata ->> ");
+            builder.push_bind(filter.key.clone());
+            builder.push(format!(
+                " ~ '{}' THEN (a.metadata ->> ",
+                JSON_NUMBER_PATTERN
+            ));
+            builder.push_bind(filter.key.clone());
+            builder.push(format!(")::float8 END {} ", op.as_sql()));
+            builder.push_bind(*value);
+        }
+    }
+}
+
+/// Regular expression for JSON number syntax
+const JSON_NUMBER_PATTERN: &str = r"^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][-+]?[0-9]+)?$";
+
+/// Report a clash on the asset ID or on name and version as AlreadyExists
+pub(crate) fn map_unique_violation(err: sqlx::Error, asset: &Asset) -> DbError {
+    match DbError::from(err) {
+        DbError::UniqueViolation(_) => DbError::AlreadyExists(format!(
+            "Asset {}@{} already exists",
+            asset.metadata.name, asset.metadata.version
+        )),
+        other => other,
+    }
+}
+
+/// Columns of `asset_dependencies` an edge is followed from and to
+pub(crate) fn closure_columns(direction: DependencyDirection) -> (&'static str, &'static str) {
+    match direction {
+        DependencyDirection::Dependencies => ("asset_id", "dependency_id"),
+        DependencyDirection::Dependents => ("dependency_id", "asset_id"),
+    }
+}
+
+/// Condition restricting closure edges `d` to the given kinds
+///
+/// An empty list follows every edge. Kinds are a closed set of identifiers,
+/// so they are inlined rather than bound.
+pub(crate) fn closure_kind_filter(kinds: &[DependencyKind]) -> String {
+    if kinds.is_empty() {
+        return "1 = 1".to_string();
+    }
+    let kinds: Vec<String> = kinds.iter().map(|kind| format!("'{}'", kind)).collect();
+    format!("d.dependency_type IN ({})", kinds.join(", "))
+}
+
+/// Condition matching assets that other assets depend on through one of
+/// `kinds`
+///
+/// An empty list matches no asset.
+pub(crate) fn blocked_asset_filter(kinds: &[DependencyKind]) -> String {
+    if kinds.is_empty() {
+        return "1 = 0".to_string();
+    }
+    format!(
+        "EXISTS (SELECT 1 FROM asset_dependencies d WHERE d.dependency_id = assets.id AND {})",
+        closure_kind_filter(kinds)
+    )
+}
+
+/// Explain why a delete matched no row, given the asset's stored revision
+/// and the number of dependencies on it through blocking kinds
+pub(crate) fn delete_error(
+    id: &AssetId,
+    actual: Option<i64>,
+    expected_revision: Option<u64>,
+    blocking: &[DependencyKind],
+    blocked: i64,
+) -> DbError {
+    match (actual, expected_revision) {
+        (None, _) => DbError::NotFound(format!("Asset {} not found", id)),
+        (Some(actual), Some(expected)) if actual as u64 != expected => DbError::RevisionConflict {
+            expected,
+            actual: actual as u64,
+        },
+        _ if !blocking.is_empty() && blocked > 0 => DbError::HasDependents {
+            asset: id.to_string(),
+            count: blocked as usize,
+        },
+        _ => DbError::NotFound(format!("Asset {} not found", id)),
     }
 }
 
+pub(crate) fn parse_asset_id(id: &str) -> DbResult<AssetId> {
+    AssetId::from_str(id).map_err(|e| DbError::InvalidData(format!("Invalid asset ID: {}", e)))
+}
+
+/// Convert a dependency closure row to an edge and the name and version of
+/// the asset it leads to
+fn row_to_closure_entry(row: PgRow) -> DbResult<(DependencyEdge, String, Version)> {
+    let asset_id: String = row.get("asset_id");
+    let dependency_id: String = row.get("dependency_id");
+    let dependency_type: String = row.get("dependency_type");
+    let depth: i32 = row.get("depth");
+    let version: String = row.get("version");
+
+    let edge = DependencyEdge {
+        asset_id: parse_asset_id(&asset_id)?,
+        dependency_id: parse_asset_id(&dependency_id)?,
+        dependency_type: DependencyKind::from_str(&dependency_type)
+            .map_err(DbError::InvalidData)?,
+        version_constraint: row.get("version_constraint"),
+        depth: depth as u32,
+        cycle: false,
+    };
+    let version = Version::parse(&version)
+        .map_err(|e| DbError::InvalidData(format!("Invalid version: {}", e)))?;
+
+    Ok((edge, row.get("name"), version))
+}
+
 /// Convert a database row to an Asset
 fn row_to_asset(row: PgRow) -> DbResult<Asset> {
     let id_str: String = row.get("id");
@@ -853,6 +1334,7 @@ fn row_to_asset(row: PgRow) -> DbResult<Asset> {
     let created_at: DateTime<Utc> = row.get("created_at");
     let updated_at: DateTime<Utc> = row.get("updated_at");
     let deprecated_at: Option<DateTime<Utc>> = row.get("deprecated_at");
+    let revision: i64 = row.get("revision");
 
     let size_bytes: Option<i64> = row.get("size_bytes");
 
@@ -910,10 +1392,11 @@ fn row_to_asset(row: PgRow) -> DbResult<Asset> {
         created_at,
         updated_at,
         deprecated_at,
+        revision: revision as u64,
     })
 }
 
-fn parse_asset_type(s: &str) -> DbResult<AssetType> {
+pub(crate) fn parse_asset_type(s: &str) -> DbResult<AssetType> {
     match s {
         "model" => Ok(AssetType::Model),
         "pipeline" => Ok(AssetType::Pipeline),
@@ -925,12 +1408,12 @@ fn parse_asset_type(s: &str) -> DbResult<AssetType> {
     }
 }
 
-fn parse_asset_status(s: &str) -> DbResult<AssetStatus> {
+pub(crate) fn parse_asset_status(s: &str) -> DbResult<AssetStatus> {
     AssetStatus::from_str(s)
         .map_err(|e| DbError::InvalidData(format!("Invalid asset status: {}", e)))
 }
 
-fn parse_storage_backend_from_db(s: &str) -> DbResult<StorageBackend> {
+pub(crate) fn parse_storage_backend_from_db(s: &str) -> DbResult<StorageBackend> {
     // For simplicity in the database, we'll store just the backend type as a string
     // and reconstruct minimal backend config. In a real system, you'd store full JSON.
     match s {
@@ -961,7 +1444,32 @@ fn parse_storage_backend_from_db(s: &str) -> DbResult<StorageBackend> {
     }
 }
 
-fn parse_hash_algorithm(s: &str) -> DbResult<HashAlgorithm> {
+pub(crate) fn parse_hash_algorithm(s: &str) -> DbResult<HashAlgorithm> {
     HashAlgorithm::from_str(s)
         .map_err(|e| DbError::InvalidData(format!("Invalid hash algorithm: {}", e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acl::PostgresAclStore;
+    use crate::conformance;
+
+    #[tokio::test]
+    async fn test_asset_repository_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            conformance::asset_repository(&PostgresAssetRepository::new(pool)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_acl_search_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            conformance::acl_search(
+                &PostgresAssetRepository::new(pool.clone()),
+                &PostgresAclStore::new(pool),
+            )
+            .await;
+        }
+    }
+}
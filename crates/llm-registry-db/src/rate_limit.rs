@@ -0,0 +1,298 @@
+//! Rate limit buckets
+//!
+//! Request rate limits are enforced with token buckets: a bucket holds up to
+//! `capacity` tokens, refills at `capacity` tokens per window, and every
+//! request takes one token. This module stores the buckets. The in-memory
+//! store is local to one process; the Redis store shares buckets between all
+//! replicas and updates them atomically with a Lua script, so a limit holds
+//! no matter how many instances serve the requests.
+
+use async_trait::async_trait;
+use redis::Script;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+use crate::cache::RedisCache;
+use crate::error::{DbError, DbResult};
+
+/// Number of acquisitions between sweeps of idle in-memory buckets
+const SWEEP_INTERVAL: u64 = 1024;
+
+/// Outcome of taking a token from a bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    /// Whether the request may proceed
+    pub allowed: bool,
+
+    /// Bucket capacity
+    pub limit: u32,
+
+    /// Whole tokens left after this request
+    pub remaining: u32,
+
+    /// Time until the bucket is full again
+    pub reset_after: Duration,
+
+    /// Time until the next token is available, if the request was denied
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitDecision {
+    /// Build a decision from a bucket's state after a request
+    fn from_bucket(allowed: bool, tokens: f64, capacity: u32, window: Duration) -> Self {
+        let per_token = window.as_secs_f64() / f64::from(capacity.max(1));
+        let time_for = |missing: f64| Duration::from_secs_f64((missing * per_token).max(0.0));
+
+        Self {
+            allowed,
+            limit: capacity,
+            remaining: tokens.floor() as u32,
+            reset_after: time_for(f64::from(capacity) - tokens),
+            retry_after: (!allowed).then(|| time_for(1.0 - tokens)),
+        }
+    }
+}
+
+/// Store for rate limit buckets
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Take one token from the bucket `key`
+    ///
+    /// A missing bucket starts full. The bucket holds `capacity` tokens and
+    /// refills completely over `window`.
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        window: Duration,
+    ) -> DbResult<RateLimitDecision>;
+}
+
+/// Token bucket state
+#[derive(Debug, Clone)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    window: Duration,
+}
+
+/// In-memory implementation of RateLimitStore
+///
+/// Buckets are not shared between processes, so every replica enforces the
+/// limit separately. Buckets that have refilled completely are dropped
+/// periodically, since they are equivalent to missing ones.
+#[derive(Debug, Default)]
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+    acquisitions: Mutex<u64>,
+}
+
+impl InMemoryRateLimitStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of buckets currently held
+    pub fn len(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+
+    /// Whether no buckets are held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop buckets that have been idle long enough to be full again
+    pub fn sweep(&self, now: Instant) -> usize {
+        let mut buckets = self.buckets.lock().unwrap();
+        let before = buckets.len();
+        buckets.retain(|_, bucket| now.duration_since(bucket.updated_at) < bucket.window);
+        before - buckets.len()
+    }
+
+    fn acquire_at(
+        &self,
+        key: &str,
+        capacity: u32,
+        window: Duration,
+        now: Instant,
+    ) -> RateLimitDecision {
+        let capacity_f = f64::from(capacity);
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity_f,
+            updated_at: now,
+            window,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        let refill = elapsed.as_secs_f64() * capacity_f / window.as_secs_f64().max(f64::EPSILON);
+        bucket.tokens = (bucket.tokens + refill).min(capacity_f);
+        bucket.updated_at = now;
+        bucket.window = window;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        RateLimitDecision::from_bucket(allowed, bucket.tokens, capacity, window)
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        window: Duration,
+    ) -> DbResult<RateLimitDecision> {
+        let now = Instant::now();
+        let decision = self.acquire_at(key, capacity, window, now);
+
+        let sweep = {
+            let mut acquisitions = self.acquisitions.lock().unwrap();
+            *acquisitions += 1;
+            *acquisitions % SWEEP_INTERVAL == 0
+        };
+        if sweep {
+            self.sweep(now);
+        }
+
+        Ok(decision)
+    }
+}
+
+/// Token bucket update, run atomically by Redis
+///
+/// Uses the Redis server clock so replicas with skewed clocks agree. The key
+/// expires once the bucket would be full again.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local window_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1]) or capacity
+local ts = tonumber(state[2]) or now
+
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * capacity / window_ms)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', tostring(now))
+redis.call('PEXPIRE', KEYS[1], window_ms)
+return {allowed, tostring(tokens)}
+"#;
+
+/// Redis implementation of RateLimitStore
+#[derive(Clone)]
+pub struct RedisRateLimitStore {
+    cache: RedisCache,
+    script: Script,
+}
+
+impl RedisRateLimitStore {
+    /// Create a store on top of a Redis cache client
+    pub fn new(cache: RedisCache) -> Self {
+        Self {
+            cache,
+            script: Script::new(TOKEN_BUCKET_SCRIPT),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    #[instrument(skip(self))]
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        window: Duration,
+    ) -> DbResult<RateLimitDecision> {
+        let window_ms = window.as_millis().max(1) as u64;
+        let mut conn = self.cache.connection();
+
+        let (allowed, tokens): (i64, String) = self
+            .script
+            .key(self.cache.custom_key("ratelimit", key))
+            .arg(capacity)
+            .arg(window_ms)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| DbError::Cache(format!("Rate limit script failed: {}", e)))?;
+
+        let tokens = tokens
+            .parse::<f64>()
+            .map_err(|e| DbError::Cache(format!("Invalid rate limit bucket state: {}", e)))?;
+        Ok(RateLimitDecision::from_bucket(
+            allowed == 1,
+            tokens,
+            capacity,
+            window,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+
+    #[test]
+    fn test_bucket_consume_and_refill() {
+        let store = InMemoryRateLimitStore::new();
+        let window = Duration::from_secs(10); // 1 token per second
+        let start = Instant::now();
+
+        for remaining in (0..10).rev() {
+            let decision = store.acquire_at("user", 10, window, start);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+
+        let denied = store.acquire_at("user", 10, window, start);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, Some(Duration::from_secs(1)));
+        assert_eq!(denied.reset_after, Duration::from_secs(10));
+
+        // 5 seconds later 5 tokens are back, one of which is taken
+        let later = store.acquire_at("user", 10, window, start + Duration::from_secs(5));
+        assert!(later.allowed);
+        assert_eq!(later.remaining, 4);
+
+        // Other keys have their own bucket
+        assert!(store.acquire_at("other", 10, window, start).allowed);
+    }
+
+    #[test]
+    fn test_sweep_drops_full_buckets() {
+        let store = InMemoryRateLimitStore::new();
+        let start = Instant::now();
+        store.acquire_at("short", 5, Duration::from_secs(1), start);
+        store.acquire_at("long", 5, Duration::from_secs(60), start);
+
+        assert_eq!(store.sweep(start + Duration::from_secs(2)), 1);
+        assert_eq!(store.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_rate_limit_store_conformance() {
+        conformance::rate_limit_store(&InMemoryRateLimitStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_redis_rate_limit_store_conformance() {
+        if let Some(cache) = conformance::redis_cache().await {
+            conformance::rate_limit_store(&RedisRateLimitStore::new(cache)).await;
+        }
+    }
+}
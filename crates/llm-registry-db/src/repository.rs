@@ -4,15 +4,25 @@
 //! allowing for different implementations (PostgreSQL, SQLite, in-memory, etc.).
 
 use async_trait::async_trait;
-use llm_registry_core::{Asset, AssetId, AssetType};
+use chrono::{DateTime, Utc};
+use llm_registry_core::{Asset, AssetId, AssetType, DependencyKind};
 use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
+use crate::acl::AclReadFilter;
+use crate::dependency_graph::{DependencyClosure, DependencyDirection};
 use crate::error::DbResult;
+use crate::filter::{AnnotationFilter, Range};
 
 /// Query parameters for searching assets
 #[derive(Debug, Clone, Default)]
 pub struct SearchQuery {
-    /// Text search across name, description, and annotations
+    /// Text search across name, tags, description and annotations
+    ///
+    /// See [`TextQuery`](crate::text_query::TextQuery) for the syntax.
     pub text: Option<String>,
 
     /// Filter by asset types
@@ -27,20 +37,51 @@ pub struct SearchQuery {
     /// Filter by storage backend
     pub storage_backend: Option<String>,
 
+    /// Filter by exact storage path or key
+    pub storage_path: Option<String>,
+
+    /// Assets to leave out
+    pub exclude_ids: Vec<AssetId>,
+
+    /// Only include assets a caller may read under the stored ACL entries
+    pub readable_by: Option<AclReadFilter>,
+
     /// Only include non-deprecated assets
     pub exclude_deprecated: bool,
 
+    /// Filters on annotation values (AND logic - all must hold)
+    pub annotations: Vec<AnnotationFilter>,
+
+    /// Bounds on the size in bytes; assets without a size only match when
+    /// the range is unbounded
+    pub size_bytes: Range<u64>,
+
+    /// Bounds on the creation timestamp
+    pub created_at: Range<DateTime<Utc>>,
+
+    /// Bounds on the last update timestamp
+    pub updated_at: Range<DateTime<Utc>>,
+
     /// Maximum number of results to return
     pub limit: i64,
 
     /// Number of results to skip (for pagination)
     pub offset: i64,
 
+    /// Opaque cursor from a previous page's `next_cursor`
+    ///
+    /// When set, results start right after the cursor position and `offset`
+    /// is ignored. The cursor must come from a query with the same sorting.
+    pub cursor: Option<String>,
+
     /// Sort field
     pub sort_by: SortField,
 
     /// Sort order
     pub sort_order: SortOrder,
+
+    /// Fields to count the matching assets by
+    pub facets: Vec<Facet>,
 }
 
 impl SearchQuery {
@@ -86,12 +127,54 @@ impl SearchQuery {
         self
     }
 
+    /// Set storage path filter
+    pub fn storage_path(mut self, path: impl Into<String>) -> Self {
+        self.storage_path = Some(path.into());
+        self
+    }
+
+    /// Leave out specific assets
+    pub fn exclude_ids(mut self, ids: impl IntoIterator<Item = AssetId>) -> Self {
+        self.exclude_ids.extend(ids);
+        self
+    }
+
+    /// Only include assets a caller may read
+    pub fn readable_by(mut self, filter: AclReadFilter) -> Self {
+        self.readable_by = Some(filter);
+        self
+    }
+
     /// Include or exclude deprecated assets
     pub fn exclude_deprecated(mut self, exclude: bool) -> Self {
         self.exclude_deprecated = exclude;
         self
     }
 
+    /// Add an annotation filter
+    pub fn annotation(mut self, filter: AnnotationFilter) -> Self {
+        self.annotations.push(filter);
+        self
+    }
+
+    /// Set size bounds
+    pub fn size_bytes(mut self, range: Range<u64>) -> Self {
+        self.size_bytes = range;
+        self
+    }
+
+    /// Set creation timestamp bounds
+    pub fn created_at(mut self, range: Range<DateTime<Utc>>) -> Self {
+        self.created_at = range;
+        self
+    }
+
+    /// Set last update timestamp bounds
+    pub fn updated_at(mut self, range: Range<DateTime<Utc>>) -> Self {
+        self.updated_at = range;
+        self
+    }
+
     /// Set pagination limit
     pub fn limit(mut self, limit: i64) -> Self {
         self.limit = limit;
@@ -104,6 +187,12 @@ impl SearchQuery {
         self
     }
 
+    /// Continue after the given cursor instead of an offset
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
     /// Set sort field
     pub fn sort_by(mut self, field: SortField) -> Self {
         self.sort_by = field;
@@ -115,10 +204,19 @@ impl SearchQuery {
         self.sort_order = order;
         self
     }
+
+    /// Request counts for a facet
+    pub fn facet(mut self, facet: Facet) -> Self {
+        if !self.facets.contains(&facet) {
+            self.facets.push(facet);
+        }
+        self
+    }
 }
 
 /// Fields that can be used for sorting
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortField {
     /// Sort by creation timestamp
     CreatedAt,
@@ -130,6 +228,8 @@ pub enum SortField {
     Version,
     /// Sort by size in bytes
     SizeBytes,
+    /// Sort by relevance to the search text; without text all assets tie
+    Relevance,
 }
 
 impl Default for SortField {
@@ -139,7 +239,8 @@ impl Default for SortField {
 }
 
 /// Sort order
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortOrder {
     /// Ascending order
     Ascending,
@@ -153,6 +254,81 @@ impl Default for SortOrder {
     }
 }
 
+/// Asset fields that search results can be counted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Facet {
+    /// Asset type
+    AssetType,
+    /// Tags; an asset counts once for each of its tags
+    Tag,
+    /// License, for assets that have one
+    License,
+    /// Asset status
+    Status,
+    /// Provenance author, for assets that have one
+    Author,
+    /// Storage backend type
+    StorageBackend,
+}
+
+impl Facet {
+    /// Every facet
+    pub const ALL: [Facet; 6] = [
+        Facet::AssetType,
+        Facet::Tag,
+        Facet::License,
+        Facet::Status,
+        Facet::Author,
+        Facet::StorageBackend,
+    ];
+
+    /// Name of the facet, as used in query strings
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Facet::AssetType => "asset_type",
+            Facet::Tag => "tag",
+            Facet::License => "license",
+            Facet::Status => "status",
+            Facet::Author => "author",
+            Facet::StorageBackend => "storage_backend",
+        }
+    }
+}
+
+impl fmt::Display for Facet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Facet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Facet::ALL
+            .into_iter()
+            .find(|facet| facet.as_str() == s)
+            .ok_or_else(|| format!("Unknown facet: {}", s))
+    }
+}
+
+/// Number of matching assets with one value of a facet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetCount {
+    /// Facet value, such as a tag or license
+    pub value: String,
+
+    /// Number of matching assets with the value
+    pub count: i64,
+}
+
+/// Value counts of each requested facet, most common values first
+///
+/// Ties are ordered by value. Assets without a value, such as those with no
+/// license, are not counted.
+pub type FacetCounts = BTreeMap<Facet, Vec<FacetCount>>;
+
 /// Search results with pagination metadata
 #[derive(Debug, Clone)]
 pub struct SearchResults {
@@ -167,6 +343,16 @@ pub struct SearchResults {
 
     /// Current limit
     pub limit: i64,
+
+    /// Cursor for the next page, if there are more results
+    pub next_cursor: Option<String>,
+
+    /// Cursor of each returned asset, in the same order; searching after one
+    /// continues just past that asset
+    pub cursors: Vec<String>,
+
+    /// Counts of the facets requested by the query, over all matching assets
+    pub facets: FacetCounts,
 }
 
 impl SearchResults {
@@ -245,25 +431,47 @@ pub trait AssetRepository: Send + Sync {
 
     /// Update an existing asset
     ///
+    /// Every successful update bumps the stored revision by one. When
+    /// `expected_revision` is given, the update only applies if the stored
+    /// revision still matches it.
+    ///
     /// # Arguments
     /// * `asset` - The asset with updated fields (must have existing ID)
+    /// * `expected_revision` - Revision the caller last read, if any
     ///
     /// # Returns
-    /// * `Ok(Asset)` - The updated asset
+    /// * `Ok(Asset)` - The updated asset, carrying its new revision
     /// * `Err(DbError::NotFound)` - If the asset doesn't exist
+    /// * `Err(DbError::RevisionConflict)` - If the stored revision differs
     /// * `Err(DbError)` - For other database errors
-    async fn update(&self, asset: Asset) -> DbResult<Asset>;
+    async fn update(&self, asset: Asset, expected_revision: Option<u64>) -> DbResult<Asset>;
 
     /// Delete an asset by ID
     ///
+    /// When `expected_revision` is given, the asset is only deleted if the
+    /// stored revision still matches it. When `blocking` is not empty, the
+    /// asset is only deleted if no other asset depends on it through one of
+    /// those kinds; the check is atomic with the delete, so a dependency
+    /// added concurrently cannot be left dangling.
+    ///
     /// # Arguments
     /// * `id` - The asset ID to delete
+    /// * `expected_revision` - Revision the caller last read, if any
+    /// * `blocking` - Dependency kinds that keep the asset from being deleted
     ///
     /// # Returns
     /// * `Ok(())` - If deletion was successful
     /// * `Err(DbError::NotFound)` - If the asset doesn't exist
+    /// * `Err(DbError::RevisionConflict)` - If the stored revision differs
+    /// * `Err(DbError::HasDependents)` - If another asset depends on it
+    ///   through a blocking kind
     /// * `Err(DbError)` - For other database errors
-    async fn delete(&self, id: &AssetId) -> DbResult<()>;
+    async fn delete(
+        &self,
+        id: &AssetId,
+        expected_revision: Option<u64>,
+        blocking: &[DependencyKind],
+    ) -> DbResult<()>;
 
     /// List all versions of an asset by name
     ///
@@ -292,6 +500,30 @@ pub trait AssetRepository: Send + Sync {
     /// * Vector of assets that depend on this asset
     async fn list_reverse_dependencies(&self, id: &AssetId) -> DbResult<Vec<Asset>>;
 
+    /// Resolve the transitive dependencies or dependents of an asset
+    ///
+    /// Edges deeper than `max_depth` are left out and the closure is marked
+    /// truncated. The limit is capped at, and defaults to,
+    /// [`MAX_DEPENDENCY_DEPTH`](crate::MAX_DEPENDENCY_DEPTH).
+    /// Edges on a cycle are flagged instead of being followed again. An
+    /// unknown asset has an empty closure.
+    ///
+    /// # Arguments
+    /// * `id` - The asset to start from
+    /// * `direction` - Whether to follow edges to dependencies or dependents
+    /// * `max_depth` - Largest edge depth to include, direct edges being 1
+    /// * `kinds` - Kinds of edge to follow; empty follows every kind
+    ///
+    /// # Returns
+    /// * The reached assets and the edges between them
+    async fn dependency_closure(
+        &self,
+        id: &AssetId,
+        direction: DependencyDirection,
+        max_depth: Option<u32>,
+        kinds: &[DependencyKind],
+    ) -> DbResult<DependencyClosure>;
+
     /// Add a tag to an asset
     ///
     /// # Arguments
@@ -327,11 +559,13 @@ pub trait AssetRepository: Send + Sync {
     /// * `asset_id` - The asset that has the dependency
     /// * `dependency_id` - The asset being depended upon
     /// * `version_constraint` - Optional version constraint
+    /// * `kind` - What the dependency is used for
     async fn add_dependency(
         &self,
         asset_id: &AssetId,
         dependency_id: &AssetId,
         version_constraint: Option<&str>,
+        kind: DependencyKind,
     ) -> DbResult<()>;
 
     /// Remove a dependency relationship
@@ -395,6 +629,9 @@ mod tests {
             total: 100,
             offset: 0,
             limit: 50,
+            next_cursor: None,
+            cursors: vec![],
+            facets: FacetCounts::new(),
         };
 
         // Since offset (0) + count (0) <= total (100), has_more should be false
@@ -408,4 +645,19 @@ mod tests {
         assert_eq!(SortField::default(), SortField::CreatedAt);
         assert_eq!(SortOrder::default(), SortOrder::Descending);
     }
+
+    #[test]
+    fn test_facet_names() {
+        for facet in Facet::ALL {
+            assert_eq!(facet.to_string().parse::<Facet>(), Ok(facet));
+            assert_eq!(
+                serde_json::to_value(facet).unwrap(),
+                serde_json::json!(facet.as_str())
+            );
+        }
+        assert!("owner".parse::<Facet>().is_err());
+
+        let query = SearchQuery::new().facet(Facet::Tag).facet(Facet::Tag);
+        assert_eq!(query.facets, vec![Facet::Tag]);
+    }
 }
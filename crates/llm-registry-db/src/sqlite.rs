@@ -0,0 +1,2481 @@
+//! SQLite implementation of the registry stores
+//!
+//! SQLite needs no database server, which suits laptops, CI and single-node
+//! edge deployments. The schema in `migrations/sqlite` mirrors the PostgreSQL
+//! one with two differences:
+//! - Timestamps are fixed-width RFC 3339 text, so comparing and sorting them
+//!   as strings matches time order
+//! - JSONB and array columns are JSON text, queried with SQLite's JSON
+//!   functions
+//!
+//! Text search uses `LIKE`, which ignores case for ASCII letters only.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use llm_registry_core::asset::INITIAL_REVISION;
+use llm_registry_core::{
+    Asset, AssetId, AssetMetadata, AssetReference, AssetType, Checksum, DependencyKind,
+    EventType, Provenance, RegistryEvent, StorageLocation,
+};
+use semver::Version;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteRow,
+};
+use sqlx::{ConnectOptions, QueryBuilder, Row, Sqlite};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+use tracing::{debug, info, instrument};
+
+use crate::acl::{AclEffect, AclEntry, AclReadFilter, AclStore, AclSubject, AclTarget};
+use crate::api_key::{ApiKey, ApiKeyStore};
+use crate::cursor::{AssetCursor, EventCursor, SortKey};
+use crate::dependency_graph::{
+    DependencyClosure, DependencyDirection, DependencyEdge, MAX_DEPENDENCY_DEPTH,
+};
+use crate::error::{DbError, DbResult};
+use crate::event_store::{next_event_id, EventQuery, EventQueryResults, EventStore, StoredEvent};
+use crate::filter::{AnnotationCondition, AnnotationFilter, Range};
+use crate::pool::PoolConfig;
+use crate::postgres::{
+    blocked_asset_filter, closure_columns, closure_kind_filter, delete_error,
+    map_unique_violation, parse_asset_id, parse_asset_status, parse_asset_type,
+    parse_hash_algorithm, parse_storage_backend_from_db,
+};
+use crate::repository::{
+    AssetRepository, Facet, FacetCount, FacetCounts, SearchQuery, SearchResults, SortField,
+    SortOrder,
+};
+use crate::text_query::{
+    TextField, TextQuery, TextTerm, ANNOTATION_WEIGHT, SEARCHABLE_ANNOTATIONS,
+};
+use crate::upload_session::{UploadChunk, UploadSession, UploadSessionStatus, UploadSessionStore};
+use crate::user_store::{User, UserStore};
+
+/// Create a SQLite connection pool from configuration
+///
+/// The database file is created if it does not exist. In-memory databases
+/// (`sqlite::memory:`) only live as long as their connection, so they get a
+/// single connection that is never closed.
+pub async fn create_sqlite_pool(config: &PoolConfig) -> DbResult<SqlitePool> {
+    config.validate()?;
+
+    info!(
+        "Creating SQLite connection pool: max={}, database={}",
+        config.max_connections, config.database_url
+    );
+
+    let mut connect_opts = SqliteConnectOptions::from_str(&config.database_url)
+        .map_err(|e| DbError::Configuration(format!("Invalid database URL: {}", e)))?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal);
+
+    // Configure statement logging
+    if config.enable_logging {
+        connect_opts = connect_opts.log_statements(tracing::log::LevelFilter::Debug);
+    } else {
+        connect_opts = connect_opts.log_statements(tracing::log::LevelFilter::Off);
+    }
+
+    let pool_opts = if is_in_memory(&config.database_url) {
+        SqlitePoolOptions::new()
+            .min_connections(1)
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        SqlitePoolOptions::new()
+            .min_connections(config.min_connections)
+            .max_connections(config.max_connections)
+            .idle_timeout(config.idle_timeout)
+            .max_lifetime(config.max_lifetime)
+    };
+
+    let pool = pool_opts
+        .acquire_timeout(config.connect_timeout)
+        .connect_with(connect_opts)
+        .await
+        .map_err(|e| DbError::Connection(format!("Failed to create pool: {}", e)))?;
+
+    info!("SQLite connection pool created successfully");
+
+    if config.run_migrations {
+        run_sqlite_migrations(&pool).await?;
+    }
+
+    Ok(pool)
+}
+
+/// Run SQLite database migrations
+pub async fn run_sqlite_migrations(pool: &SqlitePool) -> DbResult<()> {
+    info!("Running SQLite database migrations");
+
+    sqlx::migrate!("./migrations/sqlite")
+        .run(pool)
+        .await
+        .map_err(|e| DbError::Migration(format!("Migration failed: {}", e)))?;
+
+    info!("SQLite database migrations completed successfully");
+    Ok(())
+}
+
+/// Whether a SQLite URL names an in-memory database
+fn is_in_memory(url: &str) -> bool {
+    url.contains(":memory:") || url.contains("mode=memory")
+}
+
+/// SQLite implementation of AssetRepository
+#[derive(Debug, Clone)]
+pub struct SqliteAssetRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAssetRepository {
+    /// Create a new SQLite asset repository
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a reference to the connection pool
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+/// Columns of a full asset row from `assets a`
+const ASSET_COLUMNS: &str = r#"
+    a.id, a.name, a.version, a.asset_type, a.status,
+    a.storage_backend, a.storage_uri, a.storage_path, a.size_bytes,
+    a.checksum_algorithm, a.checksum_value,
+    a.signature_algorithm, a.signature_value, a.signature_key_id,
+    a.description, a.license, a.content_type,
+    a.author, a.source_repo, a.commit_hash, a.build_id,
+    a.created_at, a.updated_at, a.deprecated_at, a.metadata, a.revision
+"#;
+
+/// Start a query selecting full asset rows from `assets a`
+fn select_assets<'a>() -> QueryBuilder<'a, Sqlite> {
+    QueryBuilder::new(format!("SELECT {} FROM assets a", ASSET_COLUMNS))
+}
+
+#[async_trait]
+impl AssetRepository for SqliteAssetRepository {
+    #[instrument(skip(self, asset), fields(asset_id = %asset.id, asset_name = %asset.metadata.name))]
+    async fn create(&self, mut asset: Asset) -> DbResult<Asset> {
+        debug!("Creating asset in database");
+
+        // New rows always start at the initial revision (column default)
+        asset.revision = INITIAL_REVISION;
+        let provenance = asset.provenance.as_ref();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO assets (
+                id, name, version, asset_type, status,
+                storage_backend, storage_uri, storage_path, size_bytes,
+                checksum_algorithm, checksum_value,
+                signature_algorithm, signature_value, signature_key_id,
+                description, license, content_type,
+                author, source_repo, commit_hash, build_id,
+                created_at, updated_at, deprecated_at, metadata
+            ) VALUES (
+                ?, ?, ?, ?, ?,
+                ?, ?, ?, ?,
+                ?, ?,
+                ?, ?, ?,
+                ?, ?, ?,
+                ?, ?, ?, ?,
+                ?, ?, ?, ?
+            )
+            "#,
+        )
+        .bind(asset.id.to_string())
+        .bind(&asset.metadata.name)
+        .bind(asset.metadata.version.to_string())
+        .bind(asset.asset_type.to_string())
+        .bind(asset.status.to_string())
+        .bind(asset.storage.backend.to_string())
+        .bind(storage_uri(&asset))
+        .bind(storage_path(&asset))
+        .bind(asset.metadata.size_bytes.map(|s| s as i64))
+        .bind(asset.checksum.algorithm.to_string())
+        .bind(&asset.checksum.value)
+        .bind(None::<&str>)
+        .bind(None::<&str>)
+        .bind(None::<&str>)
+        .bind(&asset.metadata.description)
+        .bind(&asset.metadata.license)
+        .bind(&asset.metadata.content_type)
+        .bind(provenance.and_then(|p| p.author.as_deref()))
+        .bind(provenance.and_then(|p| p.source_repo.as_deref()))
+        .bind(provenance.and_then(|p| p.commit_hash.as_deref()))
+        .bind(provenance.and_then(|p| p.build_id.as_deref()))
+        .bind(encode_timestamp(&asset.created_at))
+        .bind(encode_timestamp(&asset.updated_at))
+        .bind(asset.deprecated_at.as_ref().map(encode_timestamp))
+        .bind(serde_json::to_string(&asset.metadata.annotations)?)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| map_unique_violation(e, &asset))?;
+
+        for tag in &asset.metadata.tags {
+            sqlx::query(
+                r#"
+                INSERT INTO asset_tags (asset_id, tag)
+                VALUES (?, ?)
+                ON CONFLICT (asset_id, tag) DO NOTHING
+                "#,
+            )
+            .bind(asset.id.to_string())
+            .bind(tag)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for dep in &asset.dependencies {
+            let dep_id = dep.as_id().ok_or_else(|| {
+                DbError::InvalidData(
+                    "Dependency must be resolved to ID before persisting".to_string(),
+                )
+            })?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO asset_dependencies
+                    (asset_id, dependency_id, dependency_type, version_constraint)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (asset_id, dependency_id) DO NOTHING
+                "#,
+            )
+            .bind(asset.id.to_string())
+            .bind(dep_id.to_string())
+            .bind(dep.kind().as_str())
+            .bind(dep.as_name_version().map(|(_, v)| v))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        debug!("Asset created successfully");
+        Ok(asset)
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn find_by_id(&self, id: &AssetId) -> DbResult<Option<Asset>> {
+        debug!("Finding asset by ID");
+
+        let mut builder = select_assets();
+        builder.push(" WHERE a.id = ");
+        builder.push_bind(id.to_string());
+
+        match builder.build().fetch_optional(&self.pool).await? {
+            Some(row) => Ok(Some(self.load_asset_relations(row_to_asset(row)?).await?)),
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_name_and_version(
+        &self,
+        name: &str,
+        version: &Version,
+    ) -> DbResult<Option<Asset>> {
+        debug!("Finding asset by name and version");
+
+        let mut builder = select_assets();
+        builder.push(" WHERE a.name = ");
+        builder.push_bind(name.to_string());
+        builder.push(" AND a.version = ");
+        builder.push_bind(version.to_string());
+
+        match builder.build().fetch_optional(&self.pool).await? {
+            Some(row) => Ok(Some(self.load_asset_relations(row_to_asset(row)?).await?)),
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, ids))]
+    async fn find_by_ids(&self, ids: &[AssetId]) -> DbResult<Vec<Asset>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!("Finding {} assets by IDs", ids.len());
+
+        let mut builder = select_assets();
+        builder.push(" WHERE a.id IN (");
+        let mut separated = builder.separated(", ");
+        for id in ids {
+            separated.push_bind(id.to_string());
+        }
+        builder.push(")");
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        self.rows_to_assets(rows).await
+    }
+
+    #[instrument(skip(self, query))]
+    async fn search(&self, query: &SearchQuery) -> DbResult<SearchResults> {
+        debug!("Searching assets with filters");
+
+        let text = TextQuery::parse(query.text.as_deref().unwrap_or_default());
+        let mut builder = QueryBuilder::<Sqlite>::new(format!("SELECT {}, ", ASSET_COLUMNS));
+        push_rank(&mut builder, &text);
+        builder.push(" AS rank FROM assets a WHERE 1=1");
+
+        let cursor = AssetCursor::from_query(query)?;
+        push_search_filters(&mut builder, query);
+        if let Some(ref cursor) = cursor {
+            push_cursor_filter(&mut builder, cursor, &text);
+        }
+
+        // SQLite puts NULLs first in ascending order; match PostgreSQL instead
+        let (sort_order, nulls) = match query.sort_order {
+            SortOrder::Ascending => ("ASC", "NULLS LAST"),
+            SortOrder::Descending => ("DESC", "NULLS FIRST"),
+        };
+
+        builder.push(format!(
+            " ORDER BY {} {} {}, a.id {}",
+            sort_column(query.sort_by),
+            sort_order,
+            nulls,
+            sort_order
+        ));
+
+        // Fetch one extra row to learn whether another page follows
+        let limit = query.limit.max(0);
+        builder.push(" LIMIT ");
+        builder.push_bind(limit + 1);
+        builder.push(" OFFSET ");
+        builder.push_bind(if cursor.is_some() { 0 } else { query.offset });
+
+        let mut rows = builder.build().fetch_all(&self.pool).await?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+        let ranks: Vec<f64> = rows.iter().map(|row| row.get("rank")).collect();
+        let assets = self.rows_to_assets(rows).await?;
+
+        let total = self.count_search_results(query).await?;
+        let facets = self.count_facets(query).await?;
+        let (cursors, next_cursor) = AssetCursor::page(query, &assets, &ranks, has_more);
+
+        Ok(SearchResults {
+            assets,
+            total,
+            offset: query.offset,
+            limit: query.limit,
+            next_cursor,
+            cursors,
+            facets,
+        })
+    }
+
+    #[instrument(skip(self, asset), fields(asset_id = %asset.id))]
+    async fn update(&self, mut asset: Asset, expected_revision: Option<u64>) -> DbResult<Asset> {
+        debug!("Updating asset");
+
+        let provenance = asset.provenance.as_ref();
+        let mut tx = self.pool.begin().await?;
+
+        let revision: Option<i64> = sqlx::query_scalar(
+            r#"
+            UPDATE assets SET
+                name = ?2,
+                version = ?3,
+                asset_type = ?4,
+                status = ?5,
+                storage_backend = ?6,
+                storage_uri = ?7,
+                storage_path = ?8,
+                size_bytes = ?9,
+                checksum_algorithm = ?10,
+                checksum_value = ?11,
+                signature_algorithm = ?12,
+                signature_value = ?13,
+                signature_key_id = ?14,
+                description = ?15,
+                license = ?16,
+                content_type = ?17,
+                author = ?18,
+                source_repo = ?19,
+                commit_hash = ?20,
+                build_id = ?21,
+                deprecated_at = ?22,
+                metadata = ?23,
+                updated_at = ?24,
+                revision = revision + 1
+            WHERE id = ?1 AND (?25 IS NULL OR revision = ?25)
+            RETURNING revision
+            "#,
+        )
+        .bind(asset.id.to_string())
+        .bind(&asset.metadata.name)
+        .bind(asset.metadata.version.to_string())
+        .bind(asset.asset_type.to_string())
+        .bind(asset.status.to_string())
+        .bind(asset.storage.backend.to_string())
+        .bind(storage_uri(&asset))
+        .bind(storage_path(&asset))
+        .bind(asset.metadata.size_bytes.map(|s| s as i64))
+        .bind(asset.checksum.algorithm.to_string())
+        .bind(&asset.checksum.value)
+        .bind(None::<&str>)
+        .bind(None::<&str>)
+        .bind(None::<&str>)
+        .bind(&asset.metadata.description)
+        .bind(&asset.metadata.license)
+        .bind(&asset.metadata.content_type)
+        .bind(provenance.and_then(|p| p.author.as_deref()))
+        .bind(provenance.and_then(|p| p.source_repo.as_deref()))
+        .bind(provenance.and_then(|p| p.commit_hash.as_deref()))
+        .bind(provenance.and_then(|p| p.build_id.as_deref()))
+        .bind(asset.deprecated_at.as_ref().map(encode_timestamp))
+        .bind(serde_json::to_string(&asset.metadata.annotations)?)
+        .bind(encode_timestamp(&Utc::now()))
+        .bind(expected_revision.map(|r| r as i64))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| map_unique_violation(e, &asset))?;
+
+        asset.revision = match revision {
+            Some(revision) => revision as u64,
+            None => {
+                // Either the row is gone or another writer got there first
+                let actual: Option<i64> =
+                    sqlx::query_scalar("SELECT revision FROM assets WHERE id = ?")
+                        .bind(asset.id.to_string())
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                return Err(match (actual, expected_revision) {
+                    (Some(actual), Some(expected)) => DbError::RevisionConflict {
+                        expected,
+                        actual: actual as u64,
+                    },
+                    _ => DbError::NotFound(format!("Asset {} not found", asset.id)),
+                });
+            }
+        };
+
+        // Update tags - delete and re-insert for simplicity
+        sqlx::query("DELETE FROM asset_tags WHERE asset_id = ?")
+            .bind(asset.id.to_string())
+            .execute(&mut *tx)
+            .await?;
+
+        for tag in &asset.metadata.tags {
+            sqlx::query(
+                r#"
+                INSERT INTO asset_tags (asset_id, tag)
+                VALUES (?, ?)
+                ON CONFLICT (asset_id, tag) DO NOTHING
+                "#,
+            )
+            .bind(asset.id.to_string())
+            .bind(tag)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        debug!("Asset updated successfully");
+        Ok(asset)
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn delete(
+        &self,
+        id: &AssetId,
+        expected_revision: Option<u64>,
+        blocking: &[DependencyKind],
+    ) -> DbResult<()> {
+        debug!("Deleting asset");
+
+        // Tags and dependency edges are removed by ON DELETE CASCADE. SQLite
+        // runs one write at a time, so blocking dependencies are checked in
+        // the same statement as the delete.
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query(&format!(
+            "DELETE FROM assets WHERE id = ?1 AND (?2 IS NULL OR revision = ?2) AND NOT {}",
+            blocked_asset_filter(blocking)
+        ))
+        .bind(id.to_string())
+        .bind(expected_revision.map(|r| r as i64))
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            // The row is gone, another writer got there first, or other
+            // assets still need it
+            let actual: Option<i64> =
+                sqlx::query_scalar("SELECT revision FROM assets WHERE id = ?")
+                    .bind(id.to_string())
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let blocked: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM asset_dependencies d WHERE d.dependency_id = ? AND {}",
+                closure_kind_filter(blocking)
+            ))
+            .bind(id.to_string())
+            .fetch_one(&mut *tx)
+            .await?;
+            return Err(delete_error(
+                id,
+                actual,
+                expected_revision,
+                blocking,
+                blocked,
+            ));
+        }
+
+        tx.commit().await?;
+
+        debug!("Asset deleted successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_versions(&self, name: &str) -> DbResult<Vec<Asset>> {
+        debug!("Listing versions for asset");
+
+        let mut builder = select_assets();
+        builder.push(" WHERE a.name = ");
+        builder.push_bind(name.to_string());
+        builder.push(" ORDER BY a.created_at DESC");
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        self.rows_to_assets(rows).await
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn list_dependencies(&self, id: &AssetId) -> DbResult<Vec<Asset>> {
+        debug!("Listing dependencies");
+
+        let mut builder = select_assets();
+        builder.push(" INNER JOIN asset_dependencies d ON a.id = d.dependency_id");
+        builder.push(" WHERE d.asset_id = ");
+        builder.push_bind(id.to_string());
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        self.rows_to_assets(rows).await
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn list_reverse_dependencies(&self, id: &AssetId) -> DbResult<Vec<Asset>> {
+        debug!("Listing reverse dependencies");
+
+        let mut builder = select_assets();
+        builder.push(" INNER JOIN asset_dependencies d ON a.id = d.asset_id");
+        builder.push(" WHERE d.dependency_id = ");
+        builder.push_bind(id.to_string());
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        self.rows_to_assets(rows).await
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn dependency_closure(
+        &self,
+        id: &AssetId,
+        direction: DependencyDirection,
+        max_depth: Option<u32>,
+        kinds: &[DependencyKind],
+    ) -> DbResult<DependencyClosure> {
+        debug!("Resolving dependency closure");
+
+        let depth_limit =
+            max_depth.map_or(MAX_DEPENDENCY_DEPTH, |depth| depth.min(MAX_DEPENDENCY_DEPTH));
+        let (near, far) = closure_columns(direction);
+        let kind_filter = closure_kind_filter(kinds);
+
+        // Same query as the PostgreSQL store
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH RECURSIVE reachable(id, depth) AS (
+                SELECT ?, 0
+                UNION
+                SELECT d.{far}, r.depth + 1
+                FROM reachable r
+                INNER JOIN asset_dependencies d ON d.{near} = r.id
+                WHERE r.depth < ? AND {kind_filter}
+            ),
+            nearest AS (
+                SELECT id, MIN(depth) AS depth FROM reachable GROUP BY id
+            )
+            SELECT
+                d.asset_id, d.dependency_id, d.dependency_type, d.version_constraint,
+                n.depth + 1 AS depth, a.name, a.version
+            FROM nearest n
+            INNER JOIN asset_dependencies d ON d.{near} = n.id
+            INNER JOIN assets a ON a.id = d.{far}
+            WHERE {kind_filter}
+            "#,
+        ))
+        .bind(id.to_string())
+        .bind(depth_limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let found = rows
+            .into_iter()
+            .map(row_to_closure_entry)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        Ok(DependencyClosure::assemble(
+            *id,
+            direction,
+            depth_limit,
+            found,
+        ))
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id, tag = %tag))]
+    async fn add_tag(&self, id: &AssetId, tag: &str) -> DbResult<()> {
+        debug!("Adding tag to asset");
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_tags (asset_id, tag)
+            VALUES (?, ?)
+            ON CONFLICT (asset_id, tag) DO NOTHING
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(tag)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id, tag = %tag))]
+    async fn remove_tag(&self, id: &AssetId, tag: &str) -> DbResult<()> {
+        debug!("Removing tag from asset");
+
+        sqlx::query("DELETE FROM asset_tags WHERE asset_id = ? AND tag = ?")
+            .bind(id.to_string())
+            .bind(tag)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(asset_id = %id))]
+    async fn get_tags(&self, id: &AssetId) -> DbResult<Vec<String>> {
+        debug!("Getting tags for asset");
+
+        let rows = sqlx::query("SELECT tag FROM asset_tags WHERE asset_id = ? ORDER BY tag")
+            .bind(id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get::<String, _>("tag")).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_all_tags(&self) -> DbResult<Vec<String>> {
+        debug!("Listing all tags");
+
+        let rows = sqlx::query("SELECT DISTINCT tag FROM asset_tags ORDER BY tag")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get::<String, _>("tag")).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn add_dependency(
+        &self,
+        asset_id: &AssetId,
+        dependency_id: &AssetId,
+        version_constraint: Option<&str>,
+        kind: DependencyKind,
+    ) -> DbResult<()> {
+        debug!("Adding dependency relationship");
+
+        if self.would_create_cycle(asset_id, dependency_id).await? {
+            return Err(DbError::CircularDependency(format!(
+                "Adding dependency from {} to {} would create a cycle",
+                asset_id, dependency_id
+            )));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_dependencies
+                (asset_id, dependency_id, dependency_type, version_constraint)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (asset_id, dependency_id) DO UPDATE
+            SET dependency_type = excluded.dependency_type,
+                version_constraint = excluded.version_constraint
+            "#,
+        )
+        .bind(asset_id.to_string())
+        .bind(dependency_id.to_string())
+        .bind(kind.as_str())
+        .bind(version_constraint)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn remove_dependency(&self, asset_id: &AssetId, dependency_id: &AssetId) -> DbResult<()> {
+        debug!("Removing dependency relationship");
+
+        sqlx::query("DELETE FROM asset_dependencies WHERE asset_id = ? AND dependency_id = ?")
+            .bind(asset_id.to_string())
+            .bind(dependency_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_assets(&self) -> DbResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM assets")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    #[instrument(skip(self))]
+    async fn count_by_type(&self, asset_type: &AssetType) -> DbResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM assets WHERE asset_type = ?")
+            .bind(asset_type.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> DbResult<()> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
+
+impl SqliteAssetRepository {
+    /// Convert rows to assets with their tags and dependencies
+    async fn rows_to_assets(&self, rows: Vec<SqliteRow>) -> DbResult<Vec<Asset>> {
+        let mut assets = Vec::with_capacity(rows.len());
+        for row in rows {
+            assets.push(self.load_asset_relations(row_to_asset(row)?).await?);
+        }
+        Ok(assets)
+    }
+
+    /// Load tags and dependencies for an asset
+    async fn load_asset_relations(&self, mut asset: Asset) -> DbResult<Asset> {
+        asset.metadata.tags = self.get_tags(&asset.id).await?;
+
+        let dep_rows = sqlx::query(
+            "SELECT dependency_id, dependency_type FROM asset_dependencies WHERE asset_id = ?",
+        )
+        .bind(asset.id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        asset.dependencies = dep_rows
+            .iter()
+            .filter_map(|row| {
+                let dep_id_str: String = row.get("dependency_id");
+                let kind: String = row.get("dependency_type");
+                let kind = DependencyKind::from_str(&kind).unwrap_or_default();
+                AssetId::from_str(&dep_id_str)
+                    .ok()
+                    .map(|id| AssetReference::by_id(id).with_kind(kind))
+            })
+            .collect();
+
+        Ok(asset)
+    }
+
+    /// Check if adding a dependency would create a cycle
+    async fn would_create_cycle(&self, from_id: &AssetId, to_id: &AssetId) -> DbResult<bool> {
+        let row = sqlx::query(
+            r#"
+            WITH RECURSIVE dep_tree AS (
+                SELECT dependency_id, 1 as depth
+                FROM asset_dependencies
+                WHERE asset_id = ?1
+
+                UNION ALL
+
+                SELECT d.dependency_id, dt.depth + 1
+                FROM asset_dependencies d
+                INNER JOIN dep_tree dt ON d.asset_id = dt.dependency_id
+                WHERE dt.depth < 100
+            )
+            SELECT COUNT(*) > 0 as has_cycle
+            FROM dep_tree
+            WHERE dependency_id = ?2
+            "#,
+        )
+        .bind(to_id.to_string())
+        .bind(from_id.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.get("has_cycle"))
+    }
+
+    /// Count search results without pagination
+    async fn count_search_results(&self, query: &SearchQuery) -> DbResult<i64> {
+        let mut builder =
+            QueryBuilder::<Sqlite>::new("SELECT COUNT(*) as count FROM assets a WHERE 1=1");
+        push_search_filters(&mut builder, query);
+
+        let row = builder.build().fetch_one(&self.pool).await?;
+
+        Ok(row.get("count"))
+    }
+
+    /// Count the values of a facet among assets matching the query filters
+    async fn count_facet(&self, query: &SearchQuery, facet: Facet) -> DbResult<Vec<FacetCount>> {
+        let column = facet_column(facet);
+        let mut builder = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT {} AS value, COUNT(*) AS count FROM assets a",
+            column
+        ));
+        if facet == Facet::Tag {
+            builder.push(" JOIN asset_tags t ON t.asset_id = a.id");
+        }
+        builder.push(format!(" WHERE {} IS NOT NULL", column));
+        push_search_filters(&mut builder, query);
+        builder.push(format!(
+            " GROUP BY {} ORDER BY count DESC, {}",
+            column, column
+        ));
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| FacetCount {
+                value: row.get("value"),
+                count: row.get("count"),
+            })
+            .collect())
+    }
+
+    /// Count every facet the query requests
+    async fn count_facets(&self, query: &SearchQuery) -> DbResult<FacetCounts> {
+        let mut facets = FacetCounts::new();
+        for facet in &query.facets {
+            facets.insert(*facet, self.count_facet(query, *facet).await?);
+        }
+        Ok(facets)
+    }
+}
+
+/// Column holding the values of a facet; tags need a join with `asset_tags t`
+fn facet_column(facet: Facet) -> &'static str {
+    match facet {
+        Facet::AssetType => "a.asset_type",
+        Facet::Tag => "t.tag",
+        Facet::License => "a.license",
+        Facet::Status => "a.status",
+        Facet::Author => "a.author",
+        Facet::StorageBackend => "a.storage_backend",
+    }
+}
+
+/// Column a search is sorted by
+fn sort_column(field: SortField) -> &'static str {
+    match field {
+        SortField::CreatedAt => "a.created_at",
+        SortField::UpdatedAt => "a.updated_at",
+        SortField::Name => "a.name",
+        SortField::Version => "a.version",
+        SortField::SizeBytes => "a.size_bytes",
+        SortField::Relevance => "rank",
+    }
+}
+
+/// Fields a term is searched in, best first, with the weight of a match
+///
+/// `None` stands for the searchable annotations.
+fn term_fields(term: &TextTerm) -> Vec<(Option<TextField>, f64)> {
+    match term.field {
+        Some(field) => vec![(Some(field), field.weight())],
+        None => vec![
+            (Some(TextField::Name), TextField::Name.weight()),
+            (Some(TextField::Tag), TextField::Tag.weight()),
+            (
+                Some(TextField::Description),
+                TextField::Description.weight(),
+            ),
+            (None, ANNOTATION_WEIGHT),
+        ],
+    }
+}
+
+/// Append a condition that holds when a field contains `text`
+///
+/// `LIKE` ignores case for ASCII letters only. `None` stands for the
+/// searchable annotations.
+fn push_field_match(builder: &mut QueryBuilder<'_, Sqlite>, field: Option<TextField>, text: &str) {
+    let pattern = format!(
+        "%{}%",
+        text.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    // Missing fields count as empty, so that `NOT` of a match is never NULL
+    let like = |builder: &mut QueryBuilder<'_, Sqlite>, column: &str| {
+        builder.push(format!("coalesce({}, '') LIKE ", column));
+        builder.push_bind(pattern.clone());
+        builder.push(" ESCAPE '\\'");
+    };
+
+    match field {
+        Some(TextField::Name) => like(builder, "a.name"),
+        Some(TextField::Description) => like(builder, "a.description"),
+        Some(TextField::Tag) => {
+            builder.push("EXISTS (SELECT 1 FROM asset_tags t WHERE t.asset_id = a.id AND ");
+            like(builder, "t.tag");
+            builder.push(")");
+        }
+        None => {
+            builder.push("(");
+            for (i, key) in SEARCHABLE_ANNOTATIONS.iter().enumerate() {
+                if i > 0 {
+                    builder.push(" OR ");
+                }
+                like(builder, &format!("json_extract(a.metadata, '$.{}')", key));
+            }
+            builder.push(")");
+        }
+    }
+}
+
+/// Append the relevance of an asset to the search text
+///
+/// Each term adds the weight of the best field it occurs in, as the
+/// in-memory store ranks. Without search text every asset ranks zero.
+fn push_rank(builder: &mut QueryBuilder<'_, Sqlite>, text: &TextQuery) {
+    builder.push("(0.0");
+    for term in text.terms.iter().filter(|term| !term.exclude) {
+        builder.push(" + CASE");
+        for (field, weight) in term_fields(term) {
+            builder.push(" WHEN ");
+            push_field_match(builder, field, &term.text);
+            builder.push(format!(" THEN {:?}", weight));
+        }
+        builder.push(" ELSE 0.0 END");
+    }
+    builder.push(")");
+}
+
+/// Restrict a search to the rows after a cursor position
+///
+/// Searches sort NULL sizes last in ascending and first in descending order,
+/// so they need their own conditions.
+fn push_cursor_filter(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    cursor: &AssetCursor,
+    text: &TextQuery,
+) {
+    let id = cursor.id.to_string();
+    match (&cursor.key, cursor.sort_order) {
+        (SortKey::Size(None), SortOrder::Ascending) => {
+            builder.push(" AND a.size_bytes IS NULL AND a.id > ");
+            builder.push_bind(id);
+        }
+        (SortKey::Size(None), SortOrder::Descending) => {
+            builder.push(" AND (a.size_bytes IS NOT NULL OR a.id < ");
+            builder.push_bind(id);
+            builder.push(")");
+        }
+        (key, order) => {
+            let comparison = match order {
+                SortOrder::Ascending => ">",
+                SortOrder::Descending => "<",
+            };
+            builder.push(" AND ((");
+            match cursor.sort_by {
+                SortField::Relevance => push_rank(builder, text),
+                field => {
+                    builder.push(sort_column(field));
+                }
+            }
+            builder.push(format!(", a.id) {} (", comparison));
+            match key {
+                SortKey::Timestamp(timestamp) => builder.push_bind(encode_timestamp(timestamp)),
+                SortKey::Text(text) => builder.push_bind(text.clone()),
+                SortKey::Size(size) => builder.push_bind(*size),
+                SortKey::Rank(rank) => builder.push_bind(*rank),
+            };
+            builder.push(", ");
+            builder.push_bind(id);
+            builder.push(")");
+            if order == SortOrder::Ascending && matches!(key, SortKey::Size(Some(_))) {
+                builder.push(" OR a.size_bytes IS NULL");
+            }
+            builder.push(")");
+        }
+    }
+}
+
+/// Append the filter conditions of a search query
+fn push_search_filters(builder: &mut QueryBuilder<'_, Sqlite>, query: &SearchQuery) {
+    // Text search: every term must occur in one of its fields, and no
+    // excluded term may
+    let text = TextQuery::parse(query.text.as_deref().unwrap_or_default());
+    for term in &text.terms {
+        builder.push(if term.exclude { " AND NOT (" } else { " AND (" });
+        for (i, (field, _)) in term_fields(term).into_iter().enumerate() {
+            if i > 0 {
+                builder.push(" OR ");
+            }
+            push_field_match(builder, field, &term.text);
+        }
+        builder.push(")");
+    }
+
+    // Asset type filter
+    if !query.asset_types.is_empty() {
+        builder.push(" AND a.asset_type IN (");
+        let mut separated = builder.separated(", ");
+        for asset_type in &query.asset_types {
+            separated.push_bind(asset_type.to_string());
+        }
+        builder.push(")");
+    }
+
+    // Author filter
+    if let Some(ref author) = query.author {
+        builder.push(" AND a.author = ");
+        builder.push_bind(author.clone());
+    }
+
+    // Storage backend filter
+    if let Some(ref backend) = query.storage_backend {
+        builder.push(" AND a.storage_backend = ");
+        builder.push_bind(backend.clone());
+    }
+
+    // Storage path filter
+    if let Some(ref path) = query.storage_path {
+        builder.push(" AND a.storage_path = ");
+        builder.push_bind(path.clone());
+    }
+
+    // Excluded assets
+    if !query.exclude_ids.is_empty() {
+        let ids: Vec<String> = query.exclude_ids.iter().map(|id| id.to_string()).collect();
+        builder.push(" AND a.id NOT IN (SELECT value FROM json_each(");
+        builder.push_bind(serde_json::to_string(&ids).unwrap_or_default());
+        builder.push("))");
+    }
+
+    // ACL filter
+    if let Some(ref filter) = query.readable_by {
+        push_acl_filter(builder, filter);
+    }
+
+    // Deprecated filter
+    if query.exclude_deprecated {
+        builder.push(" AND a.deprecated_at IS NULL");
+    }
+
+    // Timestamps are stored in an encoding whose text order is time order
+    let size_bytes = query.size_bytes.map(|size| size as i64);
+    let created_at = query.created_at.map(|t| encode_timestamp(&t));
+    let updated_at = query.updated_at.map(|t| encode_timestamp(&t));
+    push_range(builder, "a.size_bytes", size_bytes);
+    push_range(builder, "a.created_at", created_at);
+    push_range(builder, "a.updated_at", updated_at);
+
+    for filter in &query.annotations {
+        push_annotation_filter(builder, filter);
+    }
+
+    // Tag filter - must have all specified tags
+    if !query.tags.is_empty() {
+        let tags: BTreeSet<&String> = query.tags.iter().collect();
+        builder.push(" AND a.id IN (SELECT asset_id FROM asset_tags WHERE tag IN (");
+        let mut separated = builder.separated(", ");
+        for tag in &tags {
+            separated.push_bind(tag.to_string());
+        }
+        builder.push(format!(
+            ") GROUP BY asset_id HAVING COUNT(DISTINCT tag) = {})",
+            tags.len()
+        ));
+    }
+}
+
+/// Append the condition that a caller may read the asset under the ACL
+/// entries covering it, as in the PostgreSQL implementation
+fn push_acl_filter(builder: &mut QueryBuilder<'_, Sqlite>, filter: &AclReadFilter) {
+    builder.push(" AND NOT");
+    push_acl_entries(builder, filter, AclEffect::Deny, true);
+    builder.push(" AND (");
+    push_acl_entries(builder, filter, AclEffect::Allow, true);
+    if filter.role_allows {
+        builder.push(" OR NOT");
+        push_acl_entries(builder, filter, AclEffect::Allow, false);
+    }
+    builder.push(")");
+}
+
+/// Append whether ACL entries with an effect cover the asset, optionally
+/// only those matching the caller
+///
+/// Prefixes are compared with substr, as LIKE would treat `%` and `_` in
+/// them as wildcards.
+fn push_acl_entries(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    filter: &AclReadFilter,
+    effect: AclEffect,
+    matching_caller: bool,
+) {
+    builder.push(
+        " EXISTS (SELECT 1 FROM asset_acl_entries e \
+         WHERE (e.asset_id = a.id OR substr(a.name, 1, length(e.name_prefix)) = e.name_prefix) \
+         AND e.permission IN (SELECT value FROM json_each(",
+    );
+    builder.push_bind(serde_json::to_string(&filter.permissions).unwrap_or_default());
+    builder.push(format!(")) AND e.effect = '{}'", effect.as_str()));
+    if matching_caller {
+        builder.push(
+            " AND (e.subject_type = 'everyone' \
+             OR (e.subject_type = 'principal' AND e.subject = ",
+        );
+        builder.push_bind(filter.principal.clone());
+        builder.push(
+            ") OR (e.subject_type = 'role' \
+             AND e.subject IN (SELECT value FROM json_each(",
+        );
+        builder.push_bind(serde_json::to_string(&filter.roles).unwrap_or_default());
+        builder.push("))))");
+    }
+    builder.push(")");
+}
+
+/// Storage URI column value
+fn storage_uri(asset: &Asset) -> String {
+    asset
+        .storage
+        .uri
+        .clone()
+        .unwrap_or_else(|| asset.storage.get_uri())
+}
+
+/// Storage path column value
+fn storage_path(asset: &Asset) -> Option<&str> {
+    Some(asset.storage.path.as_str()).filter(|path| !path.is_empty())
+}
+
+/// Append inclusive bounds on a column; NULLs fail any bound
+fn push_range<'a, T>(builder: &mut QueryBuilder<'a, Sqlite>, column: &str, range: Range<T>)
+where
+    T: 'a + Send + sqlx::Encode<'a, Sqlite> + sqlx::Type<Sqlite>,
+{
+    if let Some(min) = range.min {
+        builder.push(format!(" AND {} >= ", column));
+        builder.push_bind(min);
+    }
+    if let Some(max) = range.max {
+        builder.push(format!(" AND {} <= ", column));
+        builder.push_bind(max);
+    }
+}
+
+/// Append a condition on one annotation in the `metadata` JSON column
+///
+/// Numeric comparisons only cast canonical JSON numbers, as the other
+/// stores do.
+fn push_annotation_filter(builder: &mut QueryBuilder<'_, Sqlite>, filter: &AnnotationFilter) {
+    builder.push(" AND EXISTS (SELECT 1 FROM json_each(a.metadata) m WHERE m.key = ");
+    builder.push_bind(filter.key.clone());
+    match &filter.condition {
+        AnnotationCondition::Exists => {}
+        AnnotationCondition::Equals(value) => {
+            builder.push(" AND m.value = ");
+            builder.push_bind(value.clone());
+        }
+        AnnotationCondition::In(values) if values.is_empty() => {
+            builder.push(" AND 0");
+        }
+        AnnotationCondition::In(values) => {
+            builder.push(" AND m.value IN (");
+            let mut separated = builder.separated(", ");
+            for value in values {
+                separated.push_bind(value.clone());
+            }
+            builder.push(")");
+        }
+        AnnotationCondition::Compare { op, value } => {
+            builder.push(
+                " AND CASE WHEN json_valid(m.value) AND trim(m.value) = m.value \
+                 THEN CASE WHEN json_type(m.value) IN ('integer', 'real') \
+                 THEN CAST(m.value AS REAL) END END ",
+            );
+            builder.push(op.as_sql());
+            builder.push(" ");
+            builder.push_bind(*value);
+        }
+    }
+    builder.push(")");
+}
+
+/// Format a timestamp so that text order matches time order
+///
+/// Always writes six fractional digits: with a variable number of digits,
+/// `12:00:00Z` would sort after `12:00:00.5Z`.
+fn encode_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}
+
+/// Parse a timestamp written by [`encode_timestamp`]
+fn decode_timestamp(value: &str) -> DbResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| DbError::InvalidData(format!("Invalid timestamp {}: {}", value, e)))
+}
+
+/// Convert a dependency closure row to an edge and the name and version of
+/// the asset it leads to
+fn row_to_closure_entry(row: SqliteRow) -> DbResult<(DependencyEdge, String, Version)> {
+    let dependency_type: String = row.get("dependency_type");
+    let depth: i64 = row.get("depth");
+    let version: String = row.get("version");
+
+    let edge = DependencyEdge {
+        asset_id: parse_asset_id(row.get("asset_id"))?,
+        dependency_id: parse_asset_id(row.get("dependency_id"))?,
+        dependency_type: DependencyKind::from_str(&dependency_type)
+            .map_err(DbError::InvalidData)?,
+        version_constraint: row.get("version_constraint"),
+        depth: depth as u32,
+        cycle: false,
+    };
+    let version = Version::parse(&version)
+        .map_err(|e| DbError::InvalidData(format!("Invalid version: {}", e)))?;
+
+    Ok((edge, row.get("name"), version))
+}
+
+/// Convert a database row to an Asset
+fn row_to_asset(row: SqliteRow) -> DbResult<Asset> {
+    let id_str: String = row.get("id");
+    let id = AssetId::from_str(&id_str)
+        .map_err(|e| DbError::InvalidData(format!("Invalid asset ID: {}", e)))?;
+
+    let version_str: String = row.get("version");
+    let version = Version::parse(&version_str)
+        .map_err(|e| DbError::InvalidData(format!("Invalid version: {}", e)))?;
+
+    let asset_type = parse_asset_type(row.get("asset_type"))?;
+    let status = parse_asset_status(row.get("status"))?;
+    let backend = parse_storage_backend_from_db(row.get("storage_backend"))?;
+
+    let storage_uri: String = row.get("storage_uri");
+    let storage_path: Option<String> = row.get("storage_path");
+
+    let checksum_algorithm = parse_hash_algorithm(row.get("checksum_algorithm"))?;
+    let checksum_value: String = row.get("checksum_value");
+
+    let metadata_json: String = row.get("metadata");
+    let annotations: HashMap<String, String> =
+        serde_json::from_str(&metadata_json).unwrap_or_default();
+
+    let created_at = decode_timestamp(row.get("created_at"))?;
+    let updated_at = decode_timestamp(row.get("updated_at"))?;
+    let deprecated_at = row
+        .get::<Option<&str>, _>("deprecated_at")
+        .map(decode_timestamp)
+        .transpose()?;
+
+    let size_bytes: Option<i64> = row.get("size_bytes");
+
+    let provenance = {
+        let author: Option<String> = row.get("author");
+        let source_repo: Option<String> = row.get("source_repo");
+        let commit_hash: Option<String> = row.get("commit_hash");
+        let build_id: Option<String> = row.get("build_id");
+
+        if author.is_some() || source_repo.is_some() {
+            Some(Provenance {
+                author,
+                source_repo,
+                commit_hash,
+                build_id,
+                created_at: Utc::now(),
+                build_metadata: HashMap::new(),
+            })
+        } else {
+            None
+        }
+    };
+
+    let metadata = AssetMetadata {
+        name: row.get("name"),
+        version,
+        description: row.get("description"),
+        license: row.get("license"),
+        tags: Vec::new(), // Loaded separately
+        annotations,
+        size_bytes: size_bytes.map(|s| s as u64),
+        content_type: row.get("content_type"),
+    };
+
+    Ok(Asset {
+        id,
+        asset_type,
+        metadata,
+        status,
+        storage: StorageLocation {
+            backend,
+            path: storage_path.unwrap_or_default(),
+            uri: Some(storage_uri),
+        },
+        checksum: Checksum {
+            algorithm: checksum_algorithm,
+            value: checksum_value,
+        },
+        provenance,
+        dependencies: Vec::new(), // Loaded separately
+        created_at,
+        updated_at,
+        deprecated_at,
+        revision: row.get::<i64, _>("revision") as u64,
+    })
+}
+
+/// SQLite implementation of EventStore
+#[derive(Debug, Clone)]
+pub struct SqliteEventStore {
+    pool: SqlitePool,
+}
+
+impl SqliteEventStore {
+    /// Create a new SQLite event store
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a reference to the connection pool
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Count query results without pagination
+    async fn count_query_results(&self, query: &EventQuery) -> DbResult<i64> {
+        let mut builder =
+            QueryBuilder::<Sqlite>::new("SELECT COUNT(*) as count FROM registry_events WHERE 1=1");
+        push_event_filters(&mut builder, query);
+
+        let row = builder.build().fetch_one(&self.pool).await?;
+
+        Ok(row.get("count"))
+    }
+}
+
+/// Insert one event
+async fn insert_event<'e, E>(executor: E, event: &RegistryEvent) -> DbResult<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO registry_events (
+            event_id, event_type, asset_id, timestamp,
+            actor, payload, metadata
+        ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(next_event_id())
+    .bind(event.event_type.event_name())
+    .bind(event.event_type.asset_id().map(|id| id.to_string()))
+    .bind(encode_timestamp(&event.timestamp))
+    .bind(event.actor.as_deref().unwrap_or("system"))
+    .bind(serde_json::to_string(&event.event_type)?)
+    .bind(serde_json::to_string(&event.context)?)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl EventStore for SqliteEventStore {
+    #[instrument(skip(self, event))]
+    async fn append(&self, event: RegistryEvent) -> DbResult<RegistryEvent> {
+        debug!("Appending event to store");
+
+        insert_event(&self.pool, &event).await?;
+
+        debug!("Event appended successfully");
+        Ok(event)
+    }
+
+    #[instrument(skip(self, events), fields(count = events.len()))]
+    async fn append_batch(&self, events: Vec<RegistryEvent>) -> DbResult<Vec<RegistryEvent>> {
+        debug!("Appending batch of events");
+
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for event in &events {
+            insert_event(&mut *tx, event).await?;
+        }
+        tx.commit().await?;
+
+        debug!("Event batch appended successfully");
+        Ok(events)
+    }
+
+    #[instrument(skip(self, query))]
+    async fn query(&self, query: &EventQuery) -> DbResult<EventQueryResults> {
+        debug!("Querying events");
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT
+                event_id, event_type, asset_id, timestamp,
+                actor, payload, metadata
+            FROM registry_events
+            WHERE 1=1
+            "#,
+        );
+        push_event_filters(&mut builder, query);
+
+        let cursor = query
+            .cursor
+            .as_deref()
+            .map(EventCursor::decode)
+            .transpose()?;
+        if let Some(cursor) = cursor.as_ref() {
+            builder.push(" AND (timestamp, event_id) < (");
+            builder.push_bind(encode_timestamp(&cursor.timestamp));
+            builder.push(", ");
+            builder.push_bind(cursor.event_id.clone());
+            builder.push(")");
+        }
+
+        // Fetch one extra row to learn whether another page follows
+        let limit = query.limit.max(0);
+        builder.push(" ORDER BY timestamp DESC, event_id DESC LIMIT ");
+        builder.push_bind(limit + 1);
+        builder.push(" OFFSET ");
+        builder.push_bind(if cursor.is_some() { 0 } else { query.offset });
+
+        let mut rows = builder.build().fetch_all(&self.pool).await?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = match rows.last().filter(|_| has_more) {
+            Some(row) => Some(
+                EventCursor {
+                    timestamp: decode_timestamp(row.get("timestamp"))?,
+                    event_id: row.get("event_id"),
+                }
+                .encode(),
+            ),
+            None => None,
+        };
+
+        let events = rows
+            .into_iter()
+            .map(row_to_event)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        let total = self.count_query_results(query).await?;
+
+        Ok(EventQueryResults {
+            events,
+            total,
+            offset: query.offset,
+            limit: query.limit,
+            next_cursor,
+        })
+    }
+
+    #[instrument(skip(self), fields(asset_id = %asset_id))]
+    async fn get_asset_events(
+        &self,
+        asset_id: &AssetId,
+        limit: i64,
+    ) -> DbResult<Vec<RegistryEvent>> {
+        debug!("Getting events for asset");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                event_id, event_type, asset_id, timestamp,
+                actor, payload, metadata
+            FROM registry_events
+            WHERE asset_id = ?
+            ORDER BY timestamp DESC, event_id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(asset_id.to_string())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_event).collect()
+    }
+
+    #[instrument(skip(self), fields(asset_id = %asset_id))]
+    async fn get_latest_event(&self, asset_id: &AssetId) -> DbResult<Option<RegistryEvent>> {
+        debug!("Getting latest event for asset");
+
+        let row = sqlx::query(
+            r#"
+            SELECT
+                event_id, event_type, asset_id, timestamp,
+                actor, payload, metadata
+            FROM registry_events
+            WHERE asset_id = ?
+            ORDER BY timestamp DESC, event_id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(asset_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_event).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn read_after(&self, after: Option<i64>, limit: i64) -> DbResult<Vec<StoredEvent>> {
+        debug!("Reading events after cursor");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                event_id, rowid AS sequence, event_type, asset_id, timestamp,
+                actor, payload, metadata
+            FROM registry_events
+            WHERE ?1 IS NULL OR rowid > ?1
+            ORDER BY rowid ASC
+            LIMIT ?2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let event_id: String = row.get("event_id");
+                let sequence: i64 = row.get("sequence");
+                Ok(StoredEvent {
+                    event_id,
+                    sequence,
+                    event: row_to_event(row)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn latest_sequence(&self) -> DbResult<Option<i64>> {
+        let row = sqlx::query("SELECT MAX(rowid) as sequence FROM registry_events")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("sequence"))
+    }
+
+    async fn find_sequence(&self, event_id: &str) -> DbResult<Option<i64>> {
+        let row = sqlx::query("SELECT rowid AS sequence FROM registry_events WHERE event_id = ?")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|row| row.get("sequence")))
+    }
+
+    async fn count_events(&self) -> DbResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM registry_events")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    async fn count_by_type(&self, event_type: &str) -> DbResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM registry_events WHERE event_type = ?")
+            .bind(event_type)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    async fn health_check(&self) -> DbResult<()> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
+
+/// Append the filter conditions of an event query
+fn push_event_filters(builder: &mut QueryBuilder<'_, Sqlite>, query: &EventQuery) {
+    if let Some(asset_id) = query.asset_id {
+        builder.push(" AND asset_id = ");
+        builder.push_bind(asset_id.to_string());
+    }
+
+    if !query.event_types.is_empty() {
+        builder.push(" AND event_type IN (");
+        let mut separated = builder.separated(", ");
+        for event_type in &query.event_types {
+            separated.push_bind(event_type.clone());
+        }
+        builder.push(")");
+    }
+
+    if let Some(ref actor) = query.actor {
+        builder.push(" AND actor = ");
+        builder.push_bind(actor.clone());
+    }
+
+    if let Some(after) = query.after {
+        builder.push(" AND timestamp > ");
+        builder.push_bind(encode_timestamp(&after));
+    }
+
+    if let Some(before) = query.before {
+        builder.push(" AND timestamp < ");
+        builder.push_bind(encode_timestamp(&before));
+    }
+}
+
+/// Convert a database row to a RegistryEvent
+fn row_to_event(row: SqliteRow) -> DbResult<RegistryEvent> {
+    let payload: String = row.get("payload");
+    let event_type: EventType = serde_json::from_str(&payload)
+        .map_err(|e| DbError::Serialization(format!("Failed to parse event type: {}", e)))?;
+
+    let timestamp = decode_timestamp(row.get("timestamp"))?;
+    let actor: Option<String> = row.get("actor");
+
+    let metadata_json: String = row.get("metadata");
+    let context = serde_json::from_str(&metadata_json).unwrap_or_default();
+
+    Ok(RegistryEvent {
+        event_type,
+        timestamp,
+        correlation_id: None,
+        actor,
+        source: None,
+        context,
+    })
+}
+
+/// SQLite implementation of UserStore
+#[derive(Debug, Clone)]
+pub struct SqliteUserStore {
+    pool: SqlitePool,
+}
+
+impl SqliteUserStore {
+    /// Create a new SQLite user store
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const USER_COLUMNS: &str = "id, username, email, password_hash, roles, disabled, \
+     failed_login_attempts, locked_until, created_at, updated_at, last_login_at";
+
+#[async_trait]
+impl UserStore for SqliteUserStore {
+    #[instrument(skip(self, user), fields(username = %user.username))]
+    async fn create(&self, user: User) -> DbResult<User> {
+        debug!("Creating user");
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (
+                id, username, email, password_hash, roles, disabled,
+                failed_login_attempts, locked_until, created_at, updated_at, last_login_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&user.id)
+        .bind(&user.username)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(serde_json::to_string(&user.roles)?)
+        .bind(user.disabled)
+        .bind(user.failed_login_attempts as i64)
+        .bind(user.locked_until.as_ref().map(encode_timestamp))
+        .bind(encode_timestamp(&user.created_at))
+        .bind(encode_timestamp(&user.updated_at))
+        .bind(user.last_login_at.as_ref().map(encode_timestamp))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match DbError::from(e) {
+            DbError::UniqueViolation(_) => {
+                DbError::AlreadyExists(format!("User already exists: {}", user.username))
+            }
+            other => other,
+        })?;
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_id(&self, id: &str) -> DbResult<Option<User>> {
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE id = ?", USER_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(row_to_user).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_username(&self, username: &str) -> DbResult<Option<User>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM users WHERE username = ?",
+            USER_COLUMNS
+        ))
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_user).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<User>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM users ORDER BY username",
+            USER_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_user).collect()
+    }
+
+    #[instrument(skip(self, user), fields(user_id = %user.id))]
+    async fn update(&self, user: User) -> DbResult<User> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET email = ?2, password_hash = ?3, roles = ?4, disabled = ?5,
+                failed_login_attempts = ?6, locked_until = ?7, updated_at = ?8,
+                last_login_at = ?9
+            WHERE id = ?1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(&user.id)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(serde_json::to_string(&user.roles)?)
+        .bind(user.disabled)
+        .bind(user.failed_login_attempts as i64)
+        .bind(user.locked_until.as_ref().map(encode_timestamp))
+        .bind(encode_timestamp(&Utc::now()))
+        .bind(user.last_login_at.as_ref().map(encode_timestamp))
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", user.id)))?;
+
+        row_to_user(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn record_login_failure(
+        &self,
+        id: &str,
+        max_attempts: u32,
+        lock_until: DateTime<Utc>,
+    ) -> DbResult<User> {
+        // Increment and lock in a single statement so concurrent failures
+        // cannot slip past the limit.
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET failed_login_attempts = CASE
+                    WHEN failed_login_attempts + 1 >= ?2 THEN 0
+                    ELSE failed_login_attempts + 1
+                END,
+                locked_until = CASE
+                    WHEN failed_login_attempts + 1 >= ?2 THEN ?3
+                    ELSE locked_until
+                END,
+                updated_at = ?4
+            WHERE id = ?1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(id)
+        .bind(max_attempts as i64)
+        .bind(encode_timestamp(&lock_until))
+        .bind(encode_timestamp(&Utc::now()))
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", id)))?;
+
+        row_to_user(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn record_login_success(&self, id: &str, at: DateTime<Utc>) -> DbResult<User> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET failed_login_attempts = 0, locked_until = NULL, last_login_at = ?2,
+                updated_at = ?3
+            WHERE id = ?1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(id)
+        .bind(encode_timestamp(&at))
+        .bind(encode_timestamp(&Utc::now()))
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", id)))?;
+
+        row_to_user(row)
+    }
+}
+
+/// SQLite implementation of ApiKeyStore
+#[derive(Debug, Clone)]
+pub struct SqliteApiKeyStore {
+    pool: SqlitePool,
+}
+
+impl SqliteApiKeyStore {
+    /// Create a new SQLite API key store
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const API_KEY_COLUMNS: &str = "id, name, owner, roles, permissions, secret_hash, expires_at, \
+     last_used_at, revoked_at, created_at";
+
+#[async_trait]
+impl ApiKeyStore for SqliteApiKeyStore {
+    #[instrument(skip(self, key), fields(api_key_id = %key.id))]
+    async fn create(&self, key: ApiKey) -> DbResult<ApiKey> {
+        debug!("Creating API key");
+
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys (
+                id, name, owner, roles, permissions, secret_hash,
+                expires_at, last_used_at, revoked_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&key.id)
+        .bind(&key.name)
+        .bind(&key.owner)
+        .bind(serde_json::to_string(&key.roles)?)
+        .bind(serde_json::to_string(&key.permissions)?)
+        .bind(&key.secret_hash)
+        .bind(key.expires_at.as_ref().map(encode_timestamp))
+        .bind(key.last_used_at.as_ref().map(encode_timestamp))
+        .bind(key.revoked_at.as_ref().map(encode_timestamp))
+        .bind(encode_timestamp(&key.created_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self))]
+    async fn find(&self, id: &str) -> DbResult<Option<ApiKey>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE id = ?",
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_api_key).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_for_owner(&self, owner: &str) -> DbResult<Vec<ApiKey>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE owner = ? ORDER BY id DESC",
+            API_KEY_COLUMNS
+        ))
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<ApiKey>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys ORDER BY id DESC",
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke(&self, id: &str, at: DateTime<Utc>) -> DbResult<ApiKey> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, ?2)
+            WHERE id = ?1
+            RETURNING {}
+            "#,
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(encode_timestamp(&at))
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("API key not found: {}", id)))?;
+
+        row_to_api_key(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn touch(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> {
+        sqlx::query("UPDATE api_keys SET last_used_at = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(encode_timestamp(&at))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// SQLite implementation of UploadSessionStore
+#[derive(Debug, Clone)]
+pub struct SqliteUploadSessionStore {
+    pool: SqlitePool,
+}
+
+impl SqliteUploadSessionStore {
+    /// Create a new SQLite upload session store
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the chunk records of a session
+    async fn load_chunks(&self, id: &str) -> DbResult<BTreeMap<u32, UploadChunk>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT chunk_index, size_bytes, checksum_algorithm, checksum_value, received_at
+            FROM upload_session_chunks
+            WHERE session_id = ?
+            ORDER BY chunk_index
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| row_to_chunk(row).map(|chunk| (chunk.index, chunk)))
+            .collect()
+    }
+}
+
+const UPLOAD_SESSION_COLUMNS: &str =
+    "id, asset_id, total_size, chunk_size, status, created_by, created_at, expires_at";
+
+#[async_trait]
+impl UploadSessionStore for SqliteUploadSessionStore {
+    #[instrument(skip(self, session), fields(session_id = %session.id))]
+    async fn create(&self, session: UploadSession) -> DbResult<UploadSession> {
+        debug!("Creating upload session");
+
+        sqlx::query(
+            r#"
+            INSERT INTO upload_sessions (
+                id, asset_id, total_size, chunk_size, status,
+                created_by, created_at, expires_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&session.id)
+        .bind(session.asset_id.to_string())
+        .bind(session.total_size as i64)
+        .bind(session.chunk_size as i64)
+        .bind(session.status.as_str())
+        .bind(&session.created_by)
+        .bind(encode_timestamp(&session.created_at))
+        .bind(encode_timestamp(&session.expires_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    #[instrument(skip(self))]
+    async fn find(&self, id: &str) -> DbResult<Option<UploadSession>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM upload_sessions WHERE id = ?",
+            UPLOAD_SESSION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => {
+                let mut session = row_to_session(row)?;
+                session.chunks = self.load_chunks(id).await?;
+                Ok(Some(session))
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, chunk), fields(chunk_index = chunk.index))]
+    async fn record_chunk(&self, id: &str, chunk: UploadChunk) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO upload_session_chunks (
+                session_id, chunk_index, size_bytes,
+                checksum_algorithm, checksum_value, received_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (session_id, chunk_index) DO UPDATE SET
+                size_bytes = excluded.size_bytes,
+                checksum_algorithm = excluded.checksum_algorithm,
+                checksum_value = excluded.checksum_value,
+                received_at = excluded.received_at
+            "#,
+        )
+        .bind(id)
+        .bind(chunk.index as i64)
+        .bind(chunk.size_bytes as i64)
+        .bind(chunk.checksum.algorithm.to_string())
+        .bind(&chunk.checksum.value)
+        .bind(encode_timestamp(&chunk.received_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn set_status(&self, id: &str, status: UploadSessionStatus) -> DbResult<()> {
+        let result = sqlx::query("UPDATE upload_sessions SET status = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(status.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!(
+                "Upload session not found: {}",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn transition_status(
+        &self,
+        id: &str,
+        from: UploadSessionStatus,
+        to: UploadSessionStatus,
+    ) -> DbResult<bool> {
+        let result =
+            sqlx::query("UPDATE upload_sessions SET status = ?3 WHERE id = ?1 AND status = ?2")
+                .bind(id)
+                .bind(from.as_str())
+                .bind(to.as_str())
+                .execute(&self.pool)
+                .await?;
+
+        if result.rows_affected() == 0 && self.find(id).await?.is_none() {
+            return Err(DbError::NotFound(format!(
+                "Upload session not found: {}",
+                id
+            )));
+        }
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_expired(&self, now: DateTime<Utc>) -> DbResult<Vec<UploadSession>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM upload_sessions WHERE expires_at <= ? ORDER BY expires_at",
+            UPLOAD_SESSION_COLUMNS
+        ))
+        .bind(encode_timestamp(&now))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sessions = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut session = row_to_session(row)?;
+            session.chunks = self.load_chunks(&session.id).await?;
+            sessions.push(session);
+        }
+        Ok(sessions)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        sqlx::query("DELETE FROM upload_sessions WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// SQLite implementation of AclStore
+#[derive(Debug, Clone)]
+pub struct SqliteAclStore {
+    pool: SqlitePool,
+}
+
+impl SqliteAclStore {
+    /// Create a new SQLite ACL store
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const ACL_ENTRY_COLUMNS: &str = "id, asset_id, name_prefix, subject_type, subject, \
+     permission, effect, created_by, created_at";
+
+#[async_trait]
+impl AclStore for SqliteAclStore {
+    #[instrument(skip(self, entry), fields(acl_id = %entry.id))]
+    async fn create(&self, entry: AclEntry) -> DbResult<AclEntry> {
+        debug!("Creating ACL entry");
+
+        let (asset_id, name_prefix) = match &entry.target {
+            AclTarget::Asset(id) => (Some(id.to_string()), None),
+            AclTarget::NamePrefix(prefix) => (None, Some(prefix.as_str())),
+        };
+        let (subject_type, subject) = entry.subject.to_columns();
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_acl_entries (
+                id, asset_id, name_prefix, subject_type, subject,
+                permission, effect, created_by, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&entry.id)
+        .bind(asset_id)
+        .bind(name_prefix)
+        .bind(subject_type)
+        .bind(subject)
+        .bind(&entry.permission)
+        .bind(entry.effect.as_str())
+        .bind(&entry.created_by)
+        .bind(encode_timestamp(&entry.created_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM asset_acl_entries WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("ACL entry not found: {}", id)));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get(&self, id: &str) -> DbResult<AclEntry> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM asset_acl_entries WHERE id = ?",
+            ACL_ENTRY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("ACL entry not found: {}", id)))?;
+
+        row_to_acl_entry(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<AclEntry>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM asset_acl_entries ORDER BY id",
+            ACL_ENTRY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_acl_entry).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn list_for_asset(
+        &self,
+        asset_id: &AssetId,
+        asset_name: &str,
+    ) -> DbResult<Vec<AclEntry>> {
+        // Compare the prefix with substr, as LIKE would treat `%` and `_` in
+        // it as wildcards
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM asset_acl_entries
+            WHERE asset_id = ?1 OR substr(?2, 1, length(name_prefix)) = name_prefix
+            ORDER BY id
+            "#,
+            ACL_ENTRY_COLUMNS
+        ))
+        .bind(asset_id.to_string())
+        .bind(asset_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_acl_entry).collect()
+    }
+}
+
+/// Parse an optional timestamp written by [`encode_timestamp`]
+fn decode_optional_timestamp(value: Option<&str>) -> DbResult<Option<DateTime<Utc>>> {
+    value.map(decode_timestamp).transpose()
+}
+
+/// Convert a database row to a User
+fn row_to_user(row: SqliteRow) -> DbResult<User> {
+    let failed_login_attempts: i64 = row.get("failed_login_attempts");
+
+    Ok(User {
+        id: row.get("id"),
+        username: row.get("username"),
+        email: row.get("email"),
+        password_hash: row.get("password_hash"),
+        roles: serde_json::from_str(row.get("roles"))?,
+        disabled: row.get("disabled"),
+        failed_login_attempts: failed_login_attempts.max(0) as u32,
+        locked_until: decode_optional_timestamp(row.get("locked_until"))?,
+        created_at: decode_timestamp(row.get("created_at"))?,
+        updated_at: decode_timestamp(row.get("updated_at"))?,
+        last_login_at: decode_optional_timestamp(row.get("last_login_at"))?,
+    })
+}
+
+/// Convert a database row to an ApiKey
+fn row_to_api_key(row: SqliteRow) -> DbResult<ApiKey> {
+    Ok(ApiKey {
+        id: row.get("id"),
+        name: row.get("name"),
+        owner: row.get("owner"),
+        roles: serde_json::from_str(row.get("roles"))?,
+        permissions: serde_json::from_str(row.get("permissions"))?,
+        secret_hash: row.get("secret_hash"),
+        expires_at: decode_optional_timestamp(row.get("expires_at"))?,
+        last_used_at: decode_optional_timestamp(row.get("last_used_at"))?,
+        revoked_at: decode_optional_timestamp(row.get("revoked_at"))?,
+        created_at: decode_timestamp(row.get("created_at"))?,
+    })
+}
+
+/// Convert a database row to an UploadSession (without chunks)
+fn row_to_session(row: SqliteRow) -> DbResult<UploadSession> {
+    let status: String = row.get("status");
+    let total_size: i64 = row.get("total_size");
+    let chunk_size: i64 = row.get("chunk_size");
+
+    Ok(UploadSession {
+        id: row.get("id"),
+        asset_id: parse_asset_id(row.get("asset_id"))?,
+        total_size: total_size as u64,
+        chunk_size: chunk_size as u64,
+        status: status.parse()?,
+        created_by: row.get("created_by"),
+        created_at: decode_timestamp(row.get("created_at"))?,
+        expires_at: decode_timestamp(row.get("expires_at"))?,
+        chunks: BTreeMap::new(),
+    })
+}
+
+/// Convert a database row to an UploadChunk
+fn row_to_chunk(row: SqliteRow) -> DbResult<UploadChunk> {
+    let index: i64 = row.get("chunk_index");
+    let size_bytes: i64 = row.get("size_bytes");
+    let checksum = Checksum::new(
+        parse_hash_algorithm(row.get("checksum_algorithm"))?,
+        row.get("checksum_value"),
+    )
+    .map_err(|e| DbError::InvalidData(format!("Invalid checksum: {}", e)))?;
+
+    Ok(UploadChunk {
+        index: index as u32,
+        size_bytes: size_bytes as u64,
+        checksum,
+        received_at: decode_timestamp(row.get("received_at"))?,
+    })
+}
+
+/// Convert a database row to an AclEntry
+fn row_to_acl_entry(row: SqliteRow) -> DbResult<AclEntry> {
+    let asset_id: Option<&str> = row.get("asset_id");
+    let name_prefix: Option<String> = row.get("name_prefix");
+    let target = match (asset_id, name_prefix) {
+        (Some(id), _) => AclTarget::Asset(parse_asset_id(id)?),
+        (None, Some(prefix)) => AclTarget::NamePrefix(prefix),
+        (None, None) => {
+            return Err(DbError::InvalidData("ACL entry has no target".to_string()));
+        }
+    };
+    let subject_type: &str = row.get("subject_type");
+    let effect: &str = row.get("effect");
+
+    Ok(AclEntry {
+        id: row.get("id"),
+        target,
+        subject: AclSubject::from_columns(subject_type, row.get("subject"))?,
+        permission: row.get("permission"),
+        effect: effect.parse()?,
+        created_by: row.get("created_by"),
+        created_at: decode_timestamp(row.get("created_at"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use chrono::TimeZone;
+
+    async fn memory_pool() -> SqlitePool {
+        create_sqlite_pool(&PoolConfig::new("sqlite::memory:"))
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_timestamps_sort_as_text() {
+        let whole = Utc.with_ymd_and_hms(2025, 1, 17, 12, 0, 0).unwrap();
+        let later = whole + chrono::Duration::milliseconds(500);
+
+        assert_eq!(encode_timestamp(&whole), "2025-01-17T12:00:00.000000Z");
+        assert!(encode_timestamp(&whole) < encode_timestamp(&later));
+        assert_eq!(decode_timestamp(&encode_timestamp(&later)).unwrap(), later);
+    }
+
+    #[test]
+    fn test_is_in_memory() {
+        assert!(is_in_memory("sqlite::memory:"));
+        assert!(is_in_memory("sqlite://registry?mode=memory"));
+        assert!(!is_in_memory("sqlite://registry.db"));
+    }
+
+    #[tokio::test]
+    async fn test_asset_repository_conformance() {
+        conformance::asset_repository(&SqliteAssetRepository::new(memory_pool().await)).await;
+    }
+
+    #[tokio::test]
+    async fn test_event_store_conformance() {
+        conformance::event_store(&SqliteEventStore::new(memory_pool().await)).await;
+    }
+
+    #[tokio::test]
+    async fn test_user_store_conformance() {
+        conformance::user_store(&SqliteUserStore::new(memory_pool().await)).await;
+    }
+
+    #[tokio::test]
+    async fn test_api_key_store_conformance() {
+        conformance::api_key_store(&SqliteApiKeyStore::new(memory_pool().await)).await;
+    }
+
+    #[tokio::test]
+    async fn test_upload_session_store_conformance() {
+        let pool = memory_pool().await;
+        let asset = conformance::owning_asset(&SqliteAssetRepository::new(pool.clone())).await;
+        conformance::upload_session_store(&SqliteUploadSessionStore::new(pool), asset.id).await;
+    }
+
+    #[tokio::test]
+    async fn test_acl_store_conformance() {
+        let pool = memory_pool().await;
+        let asset = conformance::owning_asset(&SqliteAssetRepository::new(pool.clone())).await;
+        conformance::acl_store(&SqliteAclStore::new(pool), &asset).await;
+    }
+
+    #[tokio::test]
+    async fn test_acl_search_conformance() {
+        let pool = memory_pool().await;
+        conformance::acl_search(
+            &SqliteAssetRepository::new(pool.clone()),
+            &SqliteAclStore::new(pool),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_file_database_persists() {
+        let path = std::env::temp_dir().join(format!("llm-registry-{}.db", AssetId::new()));
+        let config = PoolConfig::new(format!("sqlite://{}", path.display()));
+
+        let pool = create_sqlite_pool(&config).await.unwrap();
+        SqliteEventStore::new(pool.clone())
+            .append(RegistryEvent::new(EventType::AssetDeleted {
+                asset_id: AssetId::new(),
+                asset_name: "persisted".to_string(),
+                asset_version: "1.0.0".to_string(),
+            }))
+            .await
+            .unwrap();
+        pool.close().await;
+
+        // Reopening runs the migrations again, which must be a no-op
+        let pool = create_sqlite_pool(&config).await.unwrap();
+        assert_eq!(
+            SqliteEventStore::new(pool.clone())
+                .count_events()
+                .await
+                .unwrap(),
+            1
+        );
+        pool.close().await;
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Parsed text search queries
+//!
+//! Search text is a list of whitespace-separated terms, all of which must
+//! match:
+//! - `word` matches words starting with `word`
+//! - `"two words"` matches the words next to each other
+//! - `-word` or `-"two words"` excludes assets that match
+//! - `name:word`, `tag:word` and `description:word` only look at one field
+//!
+//! Terms are searched in the name, tags, description and the annotations
+//! listed in [`SEARCHABLE_ANNOTATIONS`], in that order of weight. A prefix
+//! that is not a known field is part of the term, so `llama:7b` is searched
+//! as is.
+
+use llm_registry_core::Asset;
+
+/// Annotation keys whose values are included in text search
+///
+/// The PostgreSQL `asset_search_vector` function lists the same keys.
+pub const SEARCHABLE_ANNOTATIONS: &[&str] = &["summary", "keywords", "task", "framework"];
+
+/// Field a term can be restricted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    /// Asset name
+    Name,
+    /// Any tag
+    Tag,
+    /// Description
+    Description,
+}
+
+impl TextField {
+    fn parse(prefix: &str) -> Option<Self> {
+        match prefix.to_lowercase().as_str() {
+            "name" => Some(TextField::Name),
+            "tag" => Some(TextField::Tag),
+            "description" => Some(TextField::Description),
+            _ => None,
+        }
+    }
+
+    /// PostgreSQL `tsvector` weight label of the field
+    pub fn weight_label(self) -> char {
+        match self {
+            TextField::Name => 'A',
+            TextField::Tag => 'B',
+            TextField::Description => 'C',
+        }
+    }
+
+    /// Relevance of a match in this field, as in PostgreSQL's `ts_rank`
+    pub fn weight(self) -> f64 {
+        match self {
+            TextField::Name => 1.0,
+            TextField::Tag => 0.4,
+            TextField::Description => 0.2,
+        }
+    }
+}
+
+/// Relevance of a match in a searchable annotation
+pub const ANNOTATION_WEIGHT: f64 = 0.1;
+
+/// One term of a text query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextTerm {
+    /// Lowercased text of the term
+    pub text: String,
+
+    /// Whether the term was quoted, so its words must be adjacent
+    pub phrase: bool,
+
+    /// Whether matching assets are excluded
+    pub exclude: bool,
+
+    /// Field the term is restricted to, if any
+    pub field: Option<TextField>,
+}
+
+/// Parsed text search query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextQuery {
+    /// Terms in query order
+    pub terms: Vec<TextTerm>,
+}
+
+impl TextQuery {
+    /// Parse search text
+    ///
+    /// Parsing never fails: an unmatched quote runs to the end of the text,
+    /// and empty terms are dropped.
+    pub fn parse(text: &str) -> Self {
+        let mut terms = Vec::new();
+        let mut rest = text.trim_start();
+
+        while !rest.is_empty() {
+            let mut exclude = false;
+            if let Some(stripped) = rest.strip_prefix('-') {
+                exclude = true;
+                rest = stripped;
+            }
+
+            let mut field = None;
+            if let Some((prefix, value)) = rest.split_once(':') {
+                if !prefix.contains(char::is_whitespace) {
+                    if let Some(parsed) = TextField::parse(prefix) {
+                        field = Some(parsed);
+                        rest = value;
+                    }
+                }
+            }
+
+            let (raw, phrase, remainder) = match rest.strip_prefix('"') {
+                Some(quoted) => match quoted.split_once('"') {
+                    Some((phrase, remainder)) => (phrase, true, remainder),
+                    None => (quoted, true, ""),
+                },
+                None => match rest.find(char::is_whitespace) {
+                    Some(end) => (&rest[..end], false, &rest[end..]),
+                    None => (rest, false, ""),
+                },
+            };
+            rest = remainder.trim_start();
+
+            let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !text.is_empty() {
+                terms.push(TextTerm {
+                    text: text.to_lowercase(),
+                    phrase,
+                    exclude,
+                    field,
+                });
+            }
+        }
+
+        Self { terms }
+    }
+
+    /// Whether the query has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Render as PostgreSQL `to_tsquery` input
+    ///
+    /// Single words match as prefixes. Every word is quoted, so characters
+    /// that are operators in `tsquery` syntax are searched for literally.
+    pub fn to_tsquery(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| {
+                let suffix = match (term.phrase, term.field) {
+                    (false, Some(field)) => format!(":*{}", field.weight_label()),
+                    (false, None) => ":*".to_string(),
+                    (true, Some(field)) => format!(":{}", field.weight_label()),
+                    (true, None) => String::new(),
+                };
+                let words: Vec<String> = term
+                    .text
+                    .split(' ')
+                    .map(|word| {
+                        format!(
+                            "'{}'{}",
+                            word.replace('\\', "\\\\").replace('\'', "''"),
+                            suffix
+                        )
+                    })
+                    .collect();
+                let clause = format!("({})", words.join(" <-> "));
+                if term.exclude {
+                    format!("!{}", clause)
+                } else {
+                    clause
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+
+    /// Whether an asset matches, comparing text case-insensitively
+    ///
+    /// Used by stores without a full-text index. Terms match anywhere in a
+    /// field rather than at word starts.
+    pub fn matches(&self, asset: &Asset) -> bool {
+        let document = SearchDocument::of(asset);
+        self.terms
+            .iter()
+            .all(|term| document.weight(term).is_some() != term.exclude)
+    }
+
+    /// Relevance of an asset, the sum of the best field weight of each term
+    pub fn rank(&self, asset: &Asset) -> f64 {
+        let document = SearchDocument::of(asset);
+        self.terms
+            .iter()
+            .filter(|term| !term.exclude)
+            .filter_map(|term| document.weight(term))
+            .sum()
+    }
+}
+
+/// Lowercased searchable text of an asset
+struct SearchDocument {
+    name: String,
+    tags: Vec<String>,
+    description: String,
+    annotations: Vec<String>,
+}
+
+impl SearchDocument {
+    fn of(asset: &Asset) -> Self {
+        let metadata = &asset.metadata;
+        Self {
+            name: metadata.name.to_lowercase(),
+            tags: metadata.tags.iter().map(|tag| tag.to_lowercase()).collect(),
+            description: metadata
+                .description
+                .as_deref()
+                .unwrap_or_default()
+                .to_lowercase(),
+            annotations: SEARCHABLE_ANNOTATIONS
+                .iter()
+                .filter_map(|key| metadata.annotations.get(*key))
+                .map(|value| value.to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn contains(&self, field: TextField, text: &str) -> bool {
+        match field {
+            TextField::Name => self.name.contains(text),
+            TextField::Tag => self.tags.iter().any(|tag| tag.contains(text)),
+            TextField::Description => self.description.contains(text),
+        }
+    }
+
+    /// Weight of the best field the term matches, if any
+    fn weight(&self, term: &TextTerm) -> Option<f64> {
+        let fields = [TextField::Name, TextField::Tag, TextField::Description];
+        let matched = fields
+            .into_iter()
+            .filter(|field| term.field.map_or(true, |only| only == *field))
+            .find(|field| self.contains(*field, &term.text));
+        match matched {
+            Some(field) => Some(field.weight()),
+            None if term.field.is_none()
+                && self
+                    .annotations
+                    .iter()
+                    .any(|value| value.contains(&term.text)) =>
+            {
+                Some(ANNOTATION_WEIGHT)
+            }
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str, phrase: bool, exclude: bool, field: Option<TextField>) -> TextTerm {
+        TextTerm {
+            text: text.to_string(),
+            phrase,
+            exclude,
+            field,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let query = TextQuery::parse(
+            r#"  GPT "long  documents" -draft tag:NLP -name:"old model" llama:7b"#,
+        );
+        assert_eq!(
+            query.terms,
+            vec![
+                term("gpt", false, false, None),
+                term("long documents", true, false, None),
+                term("draft", false, true, None),
+                term("nlp", false, false, Some(TextField::Tag)),
+                term("old model", true, true, Some(TextField::Name)),
+                term("llama:7b", false, false, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_edge_cases() {
+        assert!(TextQuery::parse("   ").is_empty());
+        assert!(TextQuery::parse(r#"- "" tag:"#).is_empty());
+        assert_eq!(
+            TextQuery::parse(r#""unterminated phrase"#).terms,
+            vec![term("unterminated phrase", true, false, None)]
+        );
+    }
+
+    #[test]
+    fn test_to_tsquery() {
+        let query = TextQuery::parse(r#"gpt "long documents" -draft tag:nlp it's"#);
+        assert_eq!(
+            query.to_tsquery(),
+            "('gpt':*) & ('long' <-> 'documents') & !('draft':*) & ('nlp':*B) & ('it''s':*)"
+        );
+    }
+}
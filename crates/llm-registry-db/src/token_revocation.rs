@@ -0,0 +1,312 @@
+//! Revoked token tracking
+//!
+//! Access and refresh tokens are stateless JWTs, so logging out has to be
+//! recorded server side. Two kinds of revocation are tracked:
+//!
+//! - a single token, keyed by its `jti` claim
+//! - every token of a subject issued at or before a cutoff time, used to end
+//!   all sessions of a user at once
+//!
+//! Entries only need to live as long as the tokens they revoke, so each one
+//! carries an expiry after which it can be purged.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::Script;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, instrument, warn};
+
+use crate::cache::RedisCache;
+use crate::error::{DbError, DbResult};
+
+/// Store for revoked tokens
+#[async_trait]
+pub trait TokenRevocationStore: Send + Sync {
+    /// Revoke a single token until it expires
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> DbResult<()>;
+
+    /// Revoke every token of a subject issued before `revoked_at`
+    ///
+    /// Cutoffs are compared with microsecond precision.
+    ///
+    /// The cutoff is kept until `expires_at`, which should be at least the
+    /// expiry of the longest-lived token the subject can hold.
+    async fn revoke_subject(
+        &self,
+        subject: &str,
+        revoked_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()>;
+
+    /// Whether a token has been revoked, individually or through its subject
+    async fn is_revoked(&self, jti: &str, subject: &str, issued_at: DateTime<Utc>)
+        -> DbResult<bool>;
+
+    /// Remove entries whose tokens have expired
+    ///
+    /// Returns the number of entries removed.
+    async fn purge_expired(&self, now: DateTime<Utc>) -> DbResult<usize>;
+}
+
+/// Subject-wide revocation cutoff
+#[derive(Debug, Clone, Copy)]
+struct SubjectCutoff {
+    revoked_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+/// In-memory implementation of TokenRevocationStore
+///
+/// Revocations are lost on restart and are not shared between replicas, so
+/// this is intended for tests and single-node setups.
+#[derive(Debug, Default)]
+pub struct InMemoryTokenRevocationStore {
+    tokens: RwLock<HashMap<String, DateTime<Utc>>>,
+    subjects: RwLock<HashMap<String, SubjectCutoff>>,
+}
+
+impl InMemoryTokenRevocationStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for InMemoryTokenRevocationStore {
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> DbResult<()> {
+        self.tokens
+            .write()
+            .unwrap()
+            .insert(jti.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn revoke_subject(
+        &self,
+        subject: &str,
+        revoked_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let mut subjects = self.subjects.write().unwrap();
+        let cutoff = subjects.entry(subject.to_string()).or_insert(SubjectCutoff {
+            revoked_at,
+            expires_at,
+        });
+        cutoff.revoked_at = cutoff.revoked_at.max(revoked_at);
+        cutoff.expires_at = cutoff.expires_at.max(expires_at);
+        Ok(())
+    }
+
+    async fn is_revoked(
+        &self,
+        jti: &str,
+        subject: &str,
+        issued_at: DateTime<Utc>,
+    ) -> DbResult<bool> {
+        if self.tokens.read().unwrap().contains_key(jti) {
+            return Ok(true);
+        }
+        Ok(self
+            .subjects
+            .read()
+            .unwrap()
+            .get(subject)
+            .is_some_and(|cutoff| issued_at < cutoff.revoked_at))
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> DbResult<usize> {
+        let mut tokens = self.tokens.write().unwrap();
+        let mut subjects = self.subjects.write().unwrap();
+        let before = tokens.len() + subjects.len();
+
+        tokens.retain(|_, expires_at| *expires_at > now);
+        subjects.retain(|_, cutoff| cutoff.expires_at > now);
+
+        Ok(before - tokens.len() - subjects.len())
+    }
+}
+
+/// Subject cutoff update, run atomically by Redis
+///
+/// Keeps the later of the stored and the new cutoff, and the later of the
+/// two expiries. Cutoffs are compared as numbers but stored as given, since
+/// microsecond timestamps do not survive Lua's number formatting.
+const SUBJECT_CUTOFF_SCRIPT: &str = r#"
+local cutoff = ARGV[1]
+local ttl_ms = tonumber(ARGV[2])
+
+local existing = redis.call('GET', KEYS[1])
+if existing and tonumber(existing) > tonumber(cutoff) then
+    cutoff = existing
+end
+ttl_ms = math.max(ttl_ms, redis.call('PTTL', KEYS[1]))
+
+redis.call('SET', KEYS[1], cutoff, 'PX', ttl_ms)
+return 1
+"#;
+
+/// Redis implementation of TokenRevocationStore
+///
+/// Entries are stored with a TTL matching the revoked token's expiry, so
+/// Redis drops them on its own and purging is a no-op.
+#[derive(Clone)]
+pub struct RedisTokenRevocationStore {
+    cache: RedisCache,
+    subject_script: Script,
+}
+
+impl RedisTokenRevocationStore {
+    /// Create a store on top of a Redis cache client
+    pub fn new(cache: RedisCache) -> Self {
+        Self {
+            cache,
+            subject_script: Script::new(SUBJECT_CUTOFF_SCRIPT),
+        }
+    }
+
+    fn token_key(&self, jti: &str) -> String {
+        self.cache.custom_key("revoked_token", jti)
+    }
+
+    fn subject_key(&self, subject: &str) -> String {
+        self.cache.custom_key("revoked_subject", subject)
+    }
+}
+
+/// Time left until `expires_at`, or `None` if it has passed
+fn ttl_until(expires_at: DateTime<Utc>) -> Option<Duration> {
+    (expires_at - Utc::now())
+        .to_std()
+        .ok()
+        .filter(|ttl| ttl.as_secs() > 0)
+}
+
+#[async_trait]
+impl TokenRevocationStore for RedisTokenRevocationStore {
+    #[instrument(skip(self))]
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> DbResult<()> {
+        let Some(ttl) = ttl_until(expires_at) else {
+            debug!("Token already expired, nothing to revoke");
+            return Ok(());
+        };
+        self.cache.set(&self.token_key(jti), &true, ttl).await
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke_subject(
+        &self,
+        subject: &str,
+        revoked_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let Some(ttl) = ttl_until(expires_at) else {
+            return Ok(());
+        };
+        let mut conn = self.cache.connection();
+        let _: i64 = self
+            .subject_script
+            .key(self.subject_key(subject))
+            .arg(revoked_at.timestamp_micros())
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| DbError::Cache(format!("Subject revocation script failed: {}", e)))?;
+        Ok(())
+    }
+
+    async fn is_revoked(
+        &self,
+        jti: &str,
+        subject: &str,
+        issued_at: DateTime<Utc>,
+    ) -> DbResult<bool> {
+        if self.cache.exists(&self.token_key(jti)).await? {
+            return Ok(true);
+        }
+        Ok(self
+            .cache
+            .get::<i64>(&self.subject_key(subject))
+            .await?
+            .is_some_and(|cutoff| issued_at.timestamp_micros() < cutoff))
+    }
+
+    async fn purge_expired(&self, _now: DateTime<Utc>) -> DbResult<usize> {
+        Ok(0)
+    }
+}
+
+/// Periodically purge expired revocations in the background
+pub fn spawn_revocation_purge(
+    store: Arc<dyn TokenRevocationStore>,
+    period: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match store.purge_expired(Utc::now()).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} expired token revocations", purged),
+                Err(e) => warn!("Failed to purge expired token revocations: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_in_memory_revocation() {
+        let store = InMemoryTokenRevocationStore::new();
+        let now = Utc::now();
+        let expires = now + Duration::hours(1);
+
+        assert!(!store.is_revoked("a", "alice", now).await.unwrap());
+        store.revoke("a", expires).await.unwrap();
+        assert!(store.is_revoked("a", "alice", now).await.unwrap());
+        assert!(!store.is_revoked("b", "alice", now).await.unwrap());
+
+        store.revoke_subject("alice", now, expires).await.unwrap();
+        let before = now - Duration::microseconds(1);
+        assert!(store.is_revoked("b", "alice", before).await.unwrap());
+        assert!(!store.is_revoked("c", "alice", now).await.unwrap());
+        assert!(!store.is_revoked("b", "bob", before).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let store = InMemoryTokenRevocationStore::new();
+        let now = Utc::now();
+
+        store.revoke("old", now - Duration::seconds(1)).await.unwrap();
+        store.revoke("live", now + Duration::hours(1)).await.unwrap();
+        store
+            .revoke_subject("alice", now - Duration::hours(2), now - Duration::hours(1))
+            .await
+            .unwrap();
+
+        assert_eq!(store.purge_expired(now).await.unwrap(), 2);
+        assert!(!store.is_revoked("old", "alice", now).await.unwrap());
+        assert!(store.is_revoked("live", "alice", now).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_token_revocation_store_conformance() {
+        conformance::token_revocation_store(&InMemoryTokenRevocationStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_redis_token_revocation_store_conformance() {
+        if let Some(cache) = conformance::redis_cache().await {
+            conformance::token_revocation_store(&RedisTokenRevocationStore::new(cache)).await;
+        }
+    }
+}
@@ -0,0 +1,607 @@
+//! Upload session persistence for resumable chunked uploads
+//!
+//! An upload session tracks which chunks of an asset's content have been
+//! received. Chunk data lives in the blob store; this module only stores the
+//! session bookkeeping, so a client can resume an interrupted upload and
+//! abandoned sessions can be found and garbage-collected.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use llm_registry_core::{AssetId, Checksum, HashAlgorithm};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use tracing::{debug, instrument};
+
+use crate::error::{DbError, DbResult};
+
+/// Blob key prefix under which session chunks are stored
+pub const UPLOAD_CHUNK_PREFIX: &str = "uploads";
+
+/// Lifecycle state of an upload session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadSessionStatus {
+    /// Accepting chunks
+    Active,
+    /// Chunks are being assembled into the asset's content
+    Committing,
+    /// Content was assembled and stored
+    Committed,
+    /// Cancelled by the client
+    Aborted,
+}
+
+impl UploadSessionStatus {
+    /// Database representation of the status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadSessionStatus::Active => "active",
+            UploadSessionStatus::Committing => "committing",
+            UploadSessionStatus::Committed => "committed",
+            UploadSessionStatus::Aborted => "aborted",
+        }
+    }
+}
+
+impl fmt::Display for UploadSessionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for UploadSessionStatus {
+    type Err = DbError;
+
+    fn from_str(s: &str) -> DbResult<Self> {
+        match s {
+            "active" => Ok(UploadSessionStatus::Active),
+            "committing" => Ok(UploadSessionStatus::Committing),
+            "committed" => Ok(UploadSessionStatus::Committed),
+            "aborted" => Ok(UploadSessionStatus::Aborted),
+            other => Err(DbError::InvalidData(format!(
+                "Invalid upload session status: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A chunk received by an upload session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadChunk {
+    /// Zero-based chunk index
+    pub index: u32,
+
+    /// Size of the chunk in bytes
+    pub size_bytes: u64,
+
+    /// Checksum of the chunk
+    pub checksum: Checksum,
+
+    /// When the chunk was received
+    pub received_at: DateTime<Utc>,
+}
+
+/// A resumable chunked upload of an asset's content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadSession {
+    /// Session identifier (ULID)
+    pub id: String,
+
+    /// Asset receiving the content
+    pub asset_id: AssetId,
+
+    /// Total size of the content in bytes
+    pub total_size: u64,
+
+    /// Size of every chunk except possibly the last
+    pub chunk_size: u64,
+
+    /// Current status
+    pub status: UploadSessionStatus,
+
+    /// User or service that created the session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+
+    /// When the session was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the session expires and becomes eligible for garbage collection
+    pub expires_at: DateTime<Utc>,
+
+    /// Chunks received so far, keyed by index
+    #[serde(default)]
+    pub chunks: BTreeMap<u32, UploadChunk>,
+}
+
+impl UploadSession {
+    /// Create a new active session that expires after `ttl`
+    pub fn new(asset_id: AssetId, total_size: u64, chunk_size: u64, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: ulid::Ulid::new().to_string(),
+            asset_id,
+            total_size,
+            chunk_size,
+            status: UploadSessionStatus::Active,
+            created_by: None,
+            created_at: now,
+            expires_at: now + ttl,
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// Set the creator of the session
+    pub fn with_created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = Some(created_by.into());
+        self
+    }
+
+    /// Number of chunks the content is split into
+    pub fn total_chunks(&self) -> u32 {
+        if self.chunk_size == 0 {
+            return 0;
+        }
+        self.total_size.div_ceil(self.chunk_size) as u32
+    }
+
+    /// Expected size of the chunk at `index`, or `None` if out of range
+    pub fn expected_chunk_size(&self, index: u32) -> Option<u64> {
+        if index >= self.total_chunks() {
+            return None;
+        }
+        let start = index as u64 * self.chunk_size;
+        Some(self.chunk_size.min(self.total_size - start))
+    }
+
+    /// Indices of chunks that have not been received yet
+    pub fn missing_chunks(&self) -> Vec<u32> {
+        (0..self.total_chunks())
+            .filter(|index| !self.chunks.contains_key(index))
+            .collect()
+    }
+
+    /// Whether every chunk has been received
+    pub fn is_complete(&self) -> bool {
+        self.missing_chunks().is_empty()
+    }
+
+    /// Whether the session has expired at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Blob key under which the chunk at `index` is stored
+    pub fn chunk_key(&self, index: u32) -> String {
+        format!("{}/{}/{:08}", UPLOAD_CHUNK_PREFIX, self.id, index)
+    }
+}
+
+/// Store for upload session bookkeeping
+#[async_trait]
+pub trait UploadSessionStore: Send + Sync {
+    /// Persist a new session
+    async fn create(&self, session: UploadSession) -> DbResult<UploadSession>;
+
+    /// Find a session, including its received chunks
+    async fn find(&self, id: &str) -> DbResult<Option<UploadSession>>;
+
+    /// Record a received chunk, replacing any earlier record for the same index
+    async fn record_chunk(&self, id: &str, chunk: UploadChunk) -> DbResult<()>;
+
+    /// Update the status of a session
+    async fn set_status(&self, id: &str, status: UploadSessionStatus) -> DbResult<()>;
+
+    /// Atomically move a session from status `from` to `to`
+    ///
+    /// Returns `false` without changing anything if the session is not in
+    /// status `from`, so only one caller can win a transition.
+    async fn transition_status(
+        &self,
+        id: &str,
+        from: UploadSessionStatus,
+        to: UploadSessionStatus,
+    ) -> DbResult<bool>;
+
+    /// List sessions that expired before `now`, whatever their status
+    async fn list_expired(&self, now: DateTime<Utc>) -> DbResult<Vec<UploadSession>>;
+
+    /// Delete a session and its chunk records
+    async fn delete(&self, id: &str) -> DbResult<()>;
+}
+
+/// PostgreSQL implementation of UploadSessionStore
+#[derive(Debug, Clone)]
+pub struct PostgresUploadSessionStore {
+    pool: PgPool,
+}
+
+impl PostgresUploadSessionStore {
+    /// Create a new PostgreSQL upload session store
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the chunk records of a session
+    async fn load_chunks(&self, id: &str) -> DbResult<BTreeMap<u32, UploadChunk>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT chunk_index, size_bytes, checksum_algorithm, checksum_value, received_at
+            FROM upload_session_chunks
+            WHERE session_id = $1
+            ORDER BY chunk_index
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| row_to_chunk(row).map(|chunk| (chunk.index, chunk)))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UploadSessionStore for PostgresUploadSessionStore {
+    #[instrument(skip(self, session), fields(session_id = %session.id))]
+    async fn create(&self, session: UploadSession) -> DbResult<UploadSession> {
+        debug!("Creating upload session");
+
+        sqlx::query(
+            r#"
+            INSERT INTO upload_sessions (
+                id, asset_id, total_size, chunk_size, status,
+                created_by, created_at, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(&session.id)
+        .bind(session.asset_id.to_string())
+        .bind(session.total_size as i64)
+        .bind(session.chunk_size as i64)
+        .bind(session.status.as_str())
+        .bind(&session.created_by)
+        .bind(session.created_at)
+        .bind(session.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    #[instrument(skip(self))]
+    async fn find(&self, id: &str) -> DbResult<Option<UploadSession>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, asset_id, total_size, chunk_size, status,
+                   created_by, created_at, expires_at
+            FROM upload_sessions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => {
+                let mut session = row_to_session(row)?;
+                session.chunks = self.load_chunks(id).await?;
+                Ok(Some(session))
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, chunk), fields(chunk_index = chunk.index))]
+    async fn record_chunk(&self, id: &str, chunk: UploadChunk) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO upload_session_chunks (
+                session_id, chunk_index, size_bytes,
+                checksum_algorithm, checksum_value, received_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (session_id, chunk_index) DO UPDATE SET
+                size_bytes = EXCLUDED.size_bytes,
+                checksum_algorithm = EXCLUDED.checksum_algorithm,
+                checksum_value = EXCLUDED.checksum_value,
+                received_at = EXCLUDED.received_at
+            "#,
+        )
+        .bind(id)
+        .bind(chunk.index as i32)
+        .bind(chunk.size_bytes as i64)
+        .bind(chunk.checksum.algorithm.to_string())
+        .bind(&chunk.checksum.value)
+        .bind(chunk.received_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn set_status(&self, id: &str, status: UploadSessionStatus) -> DbResult<()> {
+        let result = sqlx::query("UPDATE upload_sessions SET status = $2 WHERE id = $1")
+            .bind(id)
+            .bind(status.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("Upload session not found: {}", id)));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn transition_status(
+        &self,
+        id: &str,
+        from: UploadSessionStatus,
+        to: UploadSessionStatus,
+    ) -> DbResult<bool> {
+        let result =
+            sqlx::query("UPDATE upload_sessions SET status = $3 WHERE id = $1 AND status = $2")
+                .bind(id)
+                .bind(from.as_str())
+                .bind(to.as_str())
+                .execute(&self.pool)
+                .await?;
+
+        if result.rows_affected() == 0 && self.find(id).await?.is_none() {
+            return Err(DbError::NotFound(format!("Upload session not found: {}", id)));
+        }
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_expired(&self, now: DateTime<Utc>) -> DbResult<Vec<UploadSession>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, asset_id, total_size, chunk_size, status,
+                   created_by, created_at, expires_at
+            FROM upload_sessions
+            WHERE expires_at <= $1
+            ORDER BY expires_at
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sessions = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut session = row_to_session(row)?;
+            session.chunks = self.load_chunks(&session.id).await?;
+            sessions.push(session);
+        }
+        Ok(sessions)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        sqlx::query("DELETE FROM upload_sessions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// In-memory implementation of UploadSessionStore
+///
+/// Sessions are lost on restart, so this is intended for tests and
+/// single-node development setups.
+#[derive(Debug, Default)]
+pub struct InMemoryUploadSessionStore {
+    sessions: RwLock<HashMap<String, UploadSession>>,
+}
+
+impl InMemoryUploadSessionStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UploadSessionStore for InMemoryUploadSessionStore {
+    async fn create(&self, session: UploadSession) -> DbResult<UploadSession> {
+        let mut sessions = self.sessions.write().unwrap();
+        if sessions.contains_key(&session.id) {
+            return Err(DbError::AlreadyExists(format!(
+                "Upload session already exists: {}",
+                session.id
+            )));
+        }
+        sessions.insert(session.id.clone(), session.clone());
+        Ok(session)
+    }
+
+    async fn find(&self, id: &str) -> DbResult<Option<UploadSession>> {
+        Ok(self.sessions.read().unwrap().get(id).cloned())
+    }
+
+    async fn record_chunk(&self, id: &str, chunk: UploadChunk) -> DbResult<()> {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions
+            .get_mut(id)
+            .ok_or_else(|| DbError::NotFound(format!("Upload session not found: {}", id)))?;
+        session.chunks.insert(chunk.index, chunk);
+        Ok(())
+    }
+
+    async fn set_status(&self, id: &str, status: UploadSessionStatus) -> DbResult<()> {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions
+            .get_mut(id)
+            .ok_or_else(|| DbError::NotFound(format!("Upload session not found: {}", id)))?;
+        session.status = status;
+        Ok(())
+    }
+
+    async fn transition_status(
+        &self,
+        id: &str,
+        from: UploadSessionStatus,
+        to: UploadSessionStatus,
+    ) -> DbResult<bool> {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions
+            .get_mut(id)
+            .ok_or_else(|| DbError::NotFound(format!("Upload session not found: {}", id)))?;
+        if session.status != from {
+            return Ok(false);
+        }
+        session.status = to;
+        Ok(true)
+    }
+
+    async fn list_expired(&self, now: DateTime<Utc>) -> DbResult<Vec<UploadSession>> {
+        let mut expired: Vec<UploadSession> = self
+            .sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| s.is_expired(now))
+            .cloned()
+            .collect();
+        expired.sort_by_key(|s| s.expires_at);
+        Ok(expired)
+    }
+
+    async fn delete(&self, id: &str) -> DbResult<()> {
+        self.sessions.write().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+/// Convert database row to UploadSession (without chunks)
+fn row_to_session(row: PgRow) -> DbResult<UploadSession> {
+    let asset_id_str: String = row.get("asset_id");
+    let asset_id = AssetId::from_str(&asset_id_str)
+        .map_err(|e| DbError::InvalidData(format!("Invalid asset ID: {}", e)))?;
+    let status_str: String = row.get("status");
+    let total_size: i64 = row.get("total_size");
+    let chunk_size: i64 = row.get("chunk_size");
+
+    Ok(UploadSession {
+        id: row.get("id"),
+        asset_id,
+        total_size: total_size as u64,
+        chunk_size: chunk_size as u64,
+        status: status_str.parse()?,
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        expires_at: row.get("expires_at"),
+        chunks: BTreeMap::new(),
+    })
+}
+
+/// Convert database row to UploadChunk
+fn row_to_chunk(row: PgRow) -> DbResult<UploadChunk> {
+    let index: i32 = row.get("chunk_index");
+    let size_bytes: i64 = row.get("size_bytes");
+    let algorithm_str: String = row.get("checksum_algorithm");
+    let algorithm = HashAlgorithm::from_str(&algorithm_str)
+        .map_err(|e| DbError::InvalidData(format!("Invalid hash algorithm: {}", e)))?;
+    let checksum = Checksum::new(algorithm, row.get("checksum_value"))
+        .map_err(|e| DbError::InvalidData(format!("Invalid checksum: {}", e)))?;
+
+    Ok(UploadChunk {
+        index: index as u32,
+        size_bytes: size_bytes as u64,
+        checksum,
+        received_at: row.get("received_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use crate::postgres::PostgresAssetRepository;
+
+    fn chunk(index: u32, size_bytes: u64) -> UploadChunk {
+        UploadChunk {
+            index,
+            size_bytes,
+            checksum: Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap(),
+            received_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_session_chunk_layout() {
+        let session = UploadSession::new(AssetId::new(), 25, 10, Duration::hours(1));
+        assert_eq!(session.total_chunks(), 3);
+        assert_eq!(session.expected_chunk_size(0), Some(10));
+        assert_eq!(session.expected_chunk_size(2), Some(5));
+        assert_eq!(session.expected_chunk_size(3), None);
+        assert_eq!(session.missing_chunks(), vec![0, 1, 2]);
+        assert!(session.chunk_key(2).ends_with("/00000002"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryUploadSessionStore::new();
+        let session = store
+            .create(UploadSession::new(AssetId::new(), 20, 10, Duration::hours(1)))
+            .await
+            .unwrap();
+
+        store.record_chunk(&session.id, chunk(1, 10)).await.unwrap();
+        let found = store.find(&session.id).await.unwrap().unwrap();
+        assert_eq!(found.missing_chunks(), vec![0]);
+        assert!(!found.is_complete());
+
+        store.record_chunk(&session.id, chunk(0, 10)).await.unwrap();
+        assert!(store.find(&session.id).await.unwrap().unwrap().is_complete());
+
+        let commit = |from, to| store.transition_status(&session.id, from, to);
+        assert!(
+            commit(UploadSessionStatus::Active, UploadSessionStatus::Committing)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !commit(UploadSessionStatus::Active, UploadSessionStatus::Committing)
+                .await
+                .unwrap()
+        );
+        store
+            .set_status(&session.id, UploadSessionStatus::Committed)
+            .await
+            .unwrap();
+        assert!(store.list_expired(Utc::now()).await.unwrap().is_empty());
+        let later = Utc::now() + Duration::hours(2);
+        assert_eq!(store.list_expired(later).await.unwrap().len(), 1);
+
+        store.delete(&session.id).await.unwrap();
+        assert!(store.find(&session.id).await.unwrap().is_none());
+        assert!(store.record_chunk(&session.id, chunk(0, 10)).await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_upload_session_store_conformance() {
+        conformance::upload_session_store(&InMemoryUploadSessionStore::new(), AssetId::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_postgres_upload_session_store_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            let asset =
+                conformance::owning_asset(&PostgresAssetRepository::new(pool.clone())).await;
+            conformance::upload_session_store(&PostgresUploadSessionStore::new(pool), asset.id)
+                .await;
+        }
+    }
+}
@@ -0,0 +1,481 @@
+//! User account persistence
+//!
+//! Users are the principals that log in to the registry with a username and
+//! password. Each account stores its password hash, the roles granted to it,
+//! whether it is disabled, and the failed login counter used for lockout.
+//! Hashing and verifying passwords is left to the service layer.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::{debug, instrument};
+
+use crate::error::{DbError, DbResult};
+
+/// A registry user account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    /// User identifier (ULID)
+    pub id: String,
+
+    /// Unique login name
+    pub username: String,
+
+    /// Contact email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// Encoded password hash (PHC string format)
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+
+    /// Roles granted to the user
+    pub roles: Vec<String>,
+
+    /// Disabled accounts cannot log in
+    pub disabled: bool,
+
+    /// Consecutive failed logins since the last success or lockout
+    pub failed_login_attempts: u32,
+
+    /// Logins are refused until this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_until: Option<DateTime<Utc>>,
+
+    /// When the account was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the account was last modified
+    pub updated_at: DateTime<Utc>,
+
+    /// When the user last logged in successfully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+impl User {
+    /// Create a new enabled user with no roles
+    pub fn new(username: impl Into<String>, password_hash: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: ulid::Ulid::new().to_string(),
+            username: username.into(),
+            email: None,
+            password_hash: password_hash.into(),
+            roles: Vec::new(),
+            disabled: false,
+            failed_login_attempts: 0,
+            locked_until: None,
+            created_at: now,
+            updated_at: now,
+            last_login_at: None,
+        }
+    }
+
+    /// Set the email address
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Set the granted roles
+    pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Whether the account is locked out at the given time
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Store for user accounts
+#[async_trait]
+pub trait UserStore: Send + Sync {
+    /// Persist a new user
+    ///
+    /// Returns `AlreadyExists` if the username is taken.
+    async fn create(&self, user: User) -> DbResult<User>;
+
+    /// Find a user by ID
+    async fn find_by_id(&self, id: &str) -> DbResult<Option<User>>;
+
+    /// Find a user by username
+    async fn find_by_username(&self, username: &str) -> DbResult<Option<User>>;
+
+    /// List every user, ordered by username
+    async fn list(&self) -> DbResult<Vec<User>>;
+
+    /// Replace a stored user
+    async fn update(&self, user: User) -> DbResult<User>;
+
+    /// Count a failed login
+    ///
+    /// Once the counter reaches `max_attempts` the account is locked until
+    /// `lock_until` and the counter starts over.
+    async fn record_login_failure(
+        &self,
+        id: &str,
+        max_attempts: u32,
+        lock_until: DateTime<Utc>,
+    ) -> DbResult<User>;
+
+    /// Record a successful login, clearing the failure counter and lockout
+    async fn record_login_success(&self, id: &str, at: DateTime<Utc>) -> DbResult<User>;
+}
+
+/// PostgreSQL implementation of UserStore
+#[derive(Debug, Clone)]
+pub struct PostgresUserStore {
+    pool: PgPool,
+}
+
+impl PostgresUserStore {
+    /// Create a new PostgreSQL user store
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const USER_COLUMNS: &str = "id, username, email, password_hash, roles, disabled, \
+     failed_login_attempts, locked_until, created_at, updated_at, last_login_at";
+
+#[async_trait]
+impl UserStore for PostgresUserStore {
+    #[instrument(skip(self, user), fields(username = %user.username))]
+    async fn create(&self, user: User) -> DbResult<User> {
+        debug!("Creating user");
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (
+                id, username, email, password_hash, roles, disabled,
+                failed_login_attempts, locked_until, created_at, updated_at, last_login_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(&user.id)
+        .bind(&user.username)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(&user.roles)
+        .bind(user.disabled)
+        .bind(user.failed_login_attempts as i32)
+        .bind(user.locked_until)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .bind(user.last_login_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match DbError::from(e) {
+            DbError::UniqueViolation(_) => {
+                DbError::AlreadyExists(format!("User already exists: {}", user.username))
+            }
+            other => other,
+        })?;
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_id(&self, id: &str) -> DbResult<Option<User>> {
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE id = $1", USER_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(row_to_user).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_username(&self, username: &str) -> DbResult<Option<User>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM users WHERE username = $1",
+            USER_COLUMNS
+        ))
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_user).transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> DbResult<Vec<User>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM users ORDER BY username",
+            USER_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_user).collect()
+    }
+
+    #[instrument(skip(self, user), fields(user_id = %user.id))]
+    async fn update(&self, user: User) -> DbResult<User> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET email = $2, password_hash = $3, roles = $4, disabled = $5,
+                failed_login_attempts = $6, locked_until = $7, updated_at = NOW(),
+                last_login_at = $8
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(&user.id)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(&user.roles)
+        .bind(user.disabled)
+        .bind(user.failed_login_attempts as i32)
+        .bind(user.locked_until)
+        .bind(user.last_login_at)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", user.id)))?;
+
+        row_to_user(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn record_login_failure(
+        &self,
+        id: &str,
+        max_attempts: u32,
+        lock_until: DateTime<Utc>,
+    ) -> DbResult<User> {
+        // Increment and lock in a single statement so concurrent failures
+        // cannot slip past the limit.
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET failed_login_attempts = CASE
+                    WHEN failed_login_attempts + 1 >= $2 THEN 0
+                    ELSE failed_login_attempts + 1
+                END,
+                locked_until = CASE
+                    WHEN failed_login_attempts + 1 >= $2 THEN $3
+                    ELSE locked_until
+                END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(id)
+        .bind(max_attempts as i32)
+        .bind(lock_until)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", id)))?;
+
+        row_to_user(row)
+    }
+
+    #[instrument(skip(self))]
+    async fn record_login_success(&self, id: &str, at: DateTime<Utc>) -> DbResult<User> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE users
+            SET failed_login_attempts = 0, locked_until = NULL, last_login_at = $2,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            USER_COLUMNS
+        ))
+        .bind(id)
+        .bind(at)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("User not found: {}", id)))?;
+
+        row_to_user(row)
+    }
+}
+
+/// In-memory implementation of UserStore
+///
+/// Accounts are lost on restart, so this is intended for tests and
+/// single-node development setups.
+#[derive(Debug, Default)]
+pub struct InMemoryUserStore {
+    users: RwLock<HashMap<String, User>>,
+}
+
+impl InMemoryUserStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn modify(&self, id: &str, f: impl FnOnce(&mut User)) -> DbResult<User> {
+        let mut users = self.users.write().unwrap();
+        let user = users
+            .get_mut(id)
+            .ok_or_else(|| DbError::NotFound(format!("User not found: {}", id)))?;
+        f(user);
+        user.updated_at = Utc::now();
+        Ok(user.clone())
+    }
+}
+
+#[async_trait]
+impl UserStore for InMemoryUserStore {
+    async fn create(&self, user: User) -> DbResult<User> {
+        let mut users = self.users.write().unwrap();
+        if users
+            .values()
+            .any(|u| u.id == user.id || u.username == user.username)
+        {
+            return Err(DbError::AlreadyExists(format!(
+                "User already exists: {}",
+                user.username
+            )));
+        }
+        users.insert(user.id.clone(), user.clone());
+        Ok(user)
+    }
+
+    async fn find_by_id(&self, id: &str) -> DbResult<Option<User>> {
+        Ok(self.users.read().unwrap().get(id).cloned())
+    }
+
+    async fn find_by_username(&self, username: &str) -> DbResult<Option<User>> {
+        Ok(self
+            .users
+            .read()
+            .unwrap()
+            .values()
+            .find(|u| u.username == username)
+            .cloned())
+    }
+
+    async fn list(&self) -> DbResult<Vec<User>> {
+        let mut users: Vec<User> = self.users.read().unwrap().values().cloned().collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(users)
+    }
+
+    async fn update(&self, user: User) -> DbResult<User> {
+        let id = user.id.clone();
+        self.modify(&id, |stored| *stored = user)
+    }
+
+    async fn record_login_failure(
+        &self,
+        id: &str,
+        max_attempts: u32,
+        lock_until: DateTime<Utc>,
+    ) -> DbResult<User> {
+        self.modify(id, |user| {
+            user.failed_login_attempts += 1;
+            if user.failed_login_attempts >= max_attempts {
+                user.failed_login_attempts = 0;
+                user.locked_until = Some(lock_until);
+            }
+        })
+    }
+
+    async fn record_login_success(&self, id: &str, at: DateTime<Utc>) -> DbResult<User> {
+        self.modify(id, |user| {
+            user.failed_login_attempts = 0;
+            user.locked_until = None;
+            user.last_login_at = Some(at);
+        })
+    }
+}
+
+/// Convert database row to User
+fn row_to_user(row: PgRow) -> DbResult<User> {
+    let failed_login_attempts: i32 = row.get("failed_login_attempts");
+
+    Ok(User {
+        id: row.get("id"),
+        username: row.get("username"),
+        email: row.get("email"),
+        password_hash: row.get("password_hash"),
+        roles: row.get("roles"),
+        disabled: row.get("disabled"),
+        failed_login_attempts: failed_login_attempts.max(0) as u32,
+        locked_until: row.get("locked_until"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        last_login_at: row.get("last_login_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryUserStore::new();
+        let user = store
+            .create(User::new("alice", "hash").with_roles(vec!["user".to_string()]))
+            .await
+            .unwrap();
+
+        assert!(store
+            .create(User::new("alice", "other"))
+            .await
+            .is_err());
+        assert_eq!(
+            store.find_by_username("alice").await.unwrap().unwrap().id,
+            user.id
+        );
+        assert!(store.find_by_id("missing").await.unwrap().is_none());
+
+        let mut updated = user.clone();
+        updated.roles.push("admin".to_string());
+        store.update(updated).await.unwrap();
+        let stored = store.find_by_id(&user.id).await.unwrap().unwrap();
+        assert_eq!(stored.roles, vec!["user", "admin"]);
+        assert_eq!(store.list().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_login_failure_lockout() {
+        let store = InMemoryUserStore::new();
+        let user = store.create(User::new("bob", "hash")).await.unwrap();
+        let now = Utc::now();
+        let until = now + Duration::minutes(15);
+
+        let after_one = store.record_login_failure(&user.id, 2, until).await.unwrap();
+        assert_eq!(after_one.failed_login_attempts, 1);
+        assert!(!after_one.is_locked(now));
+
+        let after_two = store.record_login_failure(&user.id, 2, until).await.unwrap();
+        assert_eq!(after_two.failed_login_attempts, 0);
+        assert!(after_two.is_locked(now));
+        assert!(!after_two.is_locked(until));
+
+        let success = store.record_login_success(&user.id, now).await.unwrap();
+        assert!(success.locked_until.is_none());
+        assert_eq!(success.last_login_at, Some(now));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_user_store_conformance() {
+        conformance::user_store(&InMemoryUserStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_postgres_user_store_conformance() {
+        if let Some(pool) = conformance::postgres_pool().await {
+            conformance::user_store(&PostgresUserStore::new(pool)).await;
+        }
+    }
+}
@@ -7,7 +7,11 @@
 //! - Command-line arguments
 
 use config::{Config, ConfigError, Environment, File};
+use jsonwebtoken::Algorithm;
+use llm_registry_api::RateLimitConfig;
+use llm_registry_core::StorageBackend;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Server configuration
@@ -29,6 +33,14 @@ pub struct ServerConfig {
     /// CORS settings
     #[serde(default)]
     pub cors: CorsConfig,
+
+    /// Asset content storage settings
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Authentication and authorization settings
+    #[serde(default)]
+    pub auth: AuthConfig,
 }
 
 /// HTTP server configuration
@@ -168,6 +180,30 @@ fn default_max_lifetime() -> u64 {
     1800
 }
 
+/// Database backend, selected by the scheme of the database URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    /// PostgreSQL (`postgres://` or `postgresql://`)
+    Postgres,
+    /// SQLite (`sqlite:`), for single-node and edge deployments
+    Sqlite,
+}
+
+impl DatabaseConfig {
+    /// Get the backend named by the URL scheme
+    pub fn backend(&self) -> Result<DatabaseBackend, ConfigError> {
+        let scheme = self.url.split(':').next().unwrap_or_default();
+        match scheme.to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(DatabaseBackend::Postgres),
+            "sqlite" => Ok(DatabaseBackend::Sqlite),
+            _ => Err(ConfigError::Message(format!(
+                "Unsupported database URL scheme '{}': expected postgres or sqlite",
+                scheme
+            ))),
+        }
+    }
+}
+
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
@@ -252,6 +288,206 @@ impl Default for CorsConfig {
     }
 }
 
+/// Asset content storage configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Enable content upload and download
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Backend that stores asset content
+    #[serde(default = "default_storage_backend")]
+    pub backend: StorageBackend,
+
+    /// Time before an abandoned chunked upload session expires, in seconds
+    #[serde(default = "default_upload_session_ttl")]
+    pub upload_session_ttl_seconds: u64,
+
+    /// Interval between sweeps that purge expired upload sessions, in seconds
+    #[serde(default = "default_upload_gc_interval")]
+    pub upload_gc_interval_seconds: u64,
+}
+
+fn default_storage_backend() -> StorageBackend {
+    StorageBackend::FileSystem {
+        base_path: "data/blobs".to_string(),
+    }
+}
+
+fn default_upload_session_ttl() -> u64 {
+    86400
+}
+
+fn default_upload_gc_interval() -> u64 {
+    3600
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            backend: default_storage_backend(),
+            upload_session_ttl_seconds: default_upload_session_ttl(),
+            upload_gc_interval_seconds: default_upload_gc_interval(),
+        }
+    }
+}
+
+/// Authentication and authorization configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Secret for signing tokens with HS256
+    #[serde(default = "default_jwt_secret")]
+    pub jwt_secret: String,
+
+    /// Asymmetric signing keys; the first one signs new tokens, the others are
+    /// only accepted for verification
+    #[serde(default)]
+    pub signing_keys: Vec<SigningKeyConfig>,
+
+    /// Access token lifetime in seconds
+    #[serde(default = "default_token_expiration")]
+    pub token_expiration_seconds: i64,
+
+    /// Refresh token lifetime in seconds
+    #[serde(default = "default_refresh_token_expiration")]
+    pub refresh_token_expiration_seconds: i64,
+
+    /// Token issuer
+    #[serde(default = "default_issuer")]
+    pub issuer: String,
+
+    /// Token audience
+    #[serde(default = "default_audience")]
+    pub audience: String,
+
+    /// Roles granted to callers without credentials (none by default)
+    #[serde(default)]
+    pub anonymous_roles: Vec<String>,
+
+    /// Account created with the admin role if no account exists yet
+    #[serde(default)]
+    pub bootstrap_admin: Option<BootstrapAdminConfig>,
+
+    /// Redis URL for sharing token revocations and rate limits between
+    /// instances; both are kept in memory if unset
+    #[serde(default)]
+    pub redis_url: Option<String>,
+
+    /// Interval between sweeps that purge expired token revocations, in seconds
+    #[serde(default = "default_revocation_purge_interval")]
+    pub revocation_purge_interval_seconds: u64,
+
+    /// External OpenID Connect provider whose tokens are also accepted
+    #[serde(default)]
+    pub oidc: Option<OidcProviderConfig>,
+
+    /// Rate limiting of API requests
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+}
+
+/// Private key used to sign tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningKeyConfig {
+    /// Key ID, published in the JWKS and set in token headers
+    pub kid: String,
+
+    /// Signing algorithm (e.g. RS256, ES256, EdDSA)
+    pub algorithm: Algorithm,
+
+    /// Path to the PEM encoded private key
+    pub private_key_path: PathBuf,
+}
+
+/// Initial administrator account
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BootstrapAdminConfig {
+    /// Login name
+    pub username: String,
+
+    /// Initial password
+    pub password: String,
+}
+
+impl std::fmt::Debug for BootstrapAdminConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BootstrapAdminConfig")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// External OpenID Connect provider configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcProviderConfig {
+    /// Issuer URL, matched against the `iss` claim
+    pub issuer: String,
+
+    /// Expected `aud` claim
+    pub audience: String,
+
+    /// JWKS URL; discovered from the issuer if not set
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+
+    /// Claim holding the user's roles, as a dotted path
+    #[serde(default = "default_roles_claim")]
+    pub roles_claim: String,
+
+    /// Mapping from provider role names to registry roles; unmapped provider
+    /// roles grant nothing
+    #[serde(default)]
+    pub role_mapping: HashMap<String, String>,
+}
+
+fn default_jwt_secret() -> String {
+    "change-me-in-production".to_string()
+}
+
+fn default_token_expiration() -> i64 {
+    3600
+}
+
+fn default_refresh_token_expiration() -> i64 {
+    86400 * 7
+}
+
+fn default_issuer() -> String {
+    "llm-registry".to_string()
+}
+
+fn default_audience() -> String {
+    "llm-registry-api".to_string()
+}
+
+fn default_revocation_purge_interval() -> u64 {
+    3600
+}
+
+fn default_roles_claim() -> String {
+    "roles".to_string()
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            jwt_secret: default_jwt_secret(),
+            signing_keys: vec![],
+            token_expiration_seconds: default_token_expiration(),
+            refresh_token_expiration_seconds: default_refresh_token_expiration(),
+            issuer: default_issuer(),
+            audience: default_audience(),
+            anonymous_roles: vec![],
+            bootstrap_admin: None,
+            redis_url: None,
+            revocation_purge_interval_seconds: default_revocation_purge_interval(),
+            oidc: None,
+            rate_limit: RateLimitConfig::default(),
+        }
+    }
+}
+
 impl ServerConfig {
     /// Load configuration from files and environment
     ///
@@ -280,6 +516,7 @@ impl ServerConfig {
             // e.g., LLM_REGISTRY_SERVER__PORT=8080
             .add_source(
                 Environment::with_prefix("LLM_REGISTRY")
+                    .prefix_separator("_")
                     .separator("__")
                     .try_parsing(true),
             )
@@ -316,6 +553,8 @@ impl Default for ServerConfig {
             database: DatabaseConfig::default(),
             logging: LoggingConfig::default(),
             cors: CorsConfig::default(),
+            storage: StorageConfig::default(),
+            auth: AuthConfig::default(),
         }
     }
 }
@@ -365,6 +604,32 @@ mod tests {
         assert_eq!(config.min_connections, 2);
     }
 
+    #[test]
+    fn test_database_backend_from_url() {
+        let backend = |url: &str| {
+            DatabaseConfig {
+                url: url.to_string(),
+                ..DatabaseConfig::default()
+            }
+            .backend()
+        };
+
+        assert_eq!(
+            backend("postgresql://localhost/db").unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            backend("postgres://localhost/db").unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            backend("sqlite://registry.db").unwrap(),
+            DatabaseBackend::Sqlite
+        );
+        assert_eq!(backend("sqlite::memory:").unwrap(), DatabaseBackend::Sqlite);
+        assert!(backend("mysql://localhost/db").is_err());
+    }
+
     #[test]
     fn test_logging_config_default() {
         let config = LoggingConfig::default();
@@ -373,6 +638,91 @@ mod tests {
         assert!(config.include_timestamps);
     }
 
+    #[test]
+    fn test_storage_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("default.toml"),
+            r#"
+[server]
+[database]
+url = "postgresql://localhost/test"
+[logging]
+
+[storage.backend]
+type = "s3"
+bucket = "models"
+region = "eu-west-1"
+endpoint = "http://localhost:9000"
+"#,
+        )
+        .unwrap();
+
+        let config = ServerConfig::load(dir.path(), "test").unwrap();
+        assert!(config.storage.enabled);
+        assert_eq!(config.storage.upload_session_ttl_seconds, 86400);
+        assert_eq!(
+            config.storage.backend,
+            StorageBackend::S3 {
+                bucket: "models".to_string(),
+                region: "eu-west-1".to_string(),
+                endpoint: Some("http://localhost:9000".to_string()),
+            }
+        );
+
+        let default = StorageConfig::default();
+        assert_eq!(default.backend.backend_type(), "FileSystem");
+    }
+
+    #[test]
+    fn test_auth_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("default.toml"),
+            r#"
+[server]
+[database]
+url = "postgresql://localhost/test"
+[logging]
+
+[auth]
+anonymous_roles = ["viewer"]
+redis_url = "redis://localhost:6379"
+
+[[auth.signing_keys]]
+kid = "2025-01"
+algorithm = "ES256"
+private_key_path = "/etc/llm-registry/signing.pem"
+
+[auth.oidc]
+issuer = "https://idp.example.com"
+audience = "llm-registry"
+role_mapping = { registry-admins = "admin" }
+
+[auth.rate_limit]
+max_requests = 20
+"#,
+        )
+        .unwrap();
+
+        let auth = ServerConfig::load(dir.path(), "test").unwrap().auth;
+        assert_eq!(auth.anonymous_roles, vec!["viewer".to_string()]);
+        assert_eq!(auth.signing_keys[0].algorithm, Algorithm::ES256);
+        assert_eq!(auth.token_expiration_seconds, 3600);
+
+        let oidc = auth.oidc.unwrap();
+        assert_eq!(oidc.roles_claim, "roles");
+        assert_eq!(oidc.role_mapping["registry-admins"], "admin");
+
+        assert_eq!(auth.rate_limit.max_requests, 20);
+        assert!(auth.rate_limit.enabled);
+        assert_eq!(auth.rate_limit.window_secs, 60);
+
+        let default = AuthConfig::default();
+        assert!(default.anonymous_roles.is_empty());
+        assert!(default.bootstrap_admin.is_none());
+    }
+
     #[test]
     fn test_get_environment_default() {
         // Clear env var for test
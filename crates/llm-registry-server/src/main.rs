@@ -10,17 +10,27 @@ mod tracing_setup;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use llm_registry_api::build_api_server;
-use llm_registry_db::{create_pool, PoolConfig, PostgresAssetRepository, PostgresEventStore};
-use llm_registry_service::ServiceRegistry;
-use sqlx::PgPool;
+use llm_registry_api::{
+    build_api_server_with_auth, AppState, AuthHandlerState, AuthState, Authorizer, JwtConfig,
+    JwtManager, KeyRing, OidcConfig, OidcProvider, RateLimiterState, RbacPolicy, SigningKey,
+};
+use llm_registry_db::{
+    create_blob_store, create_pool, create_sqlite_pool, spawn_revocation_purge, AclStore,
+    ApiKeyStore, AssetRepository, CacheConfig, EventStore, InMemoryRateLimitStore,
+    InMemoryTokenRevocationStore, PoolConfig, PostgresAclStore, PostgresApiKeyStore,
+    PostgresAssetRepository, PostgresEventStore, PostgresUploadSessionStore, PostgresUserStore,
+    RateLimitStore, RedisCache, RedisRateLimitStore, RedisTokenRevocationStore, SqliteAclStore,
+    SqliteApiKeyStore, SqliteAssetRepository, SqliteEventStore, SqliteUploadSessionStore,
+    SqliteUserStore, TokenRevocationStore, UploadSessionStore, UserStore,
+};
+use llm_registry_service::{CreateUserRequest, ServiceRegistry, ServiceRegistryBuilder};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal;
-use tracing::info;
+use tracing::{info, warn};
 
-use config::ServerConfig;
+use config::{AuthConfig, BootstrapAdminConfig, DatabaseBackend, ServerConfig};
 
 /// Command-line arguments
 #[derive(Parser, Debug)]
@@ -91,19 +101,52 @@ async fn main() -> Result<()> {
     info!("Server: {}", config.bind_address());
     info!("Database: {}", mask_database_url(&config.database.url));
 
-    // Setup database connection pool
+    // Setup database and repositories
     // (migrations are run automatically by PoolConfig if enabled)
-    let pool = setup_database(&config).await?;
-
-    // Create repositories
-    let asset_repository = Arc::new(PostgresAssetRepository::new(pool.clone()));
-    let event_store = Arc::new(PostgresEventStore::new(pool.clone()));
+    let stores = setup_database(&config).await?;
 
     // Create service registry (wrapped in Arc for sharing between servers)
-    let services = Arc::new(ServiceRegistry::new(asset_repository, event_store));
+    let mut builder = ServiceRegistryBuilder::new()
+        .repository(stores.assets)
+        .event_store(stores.events)
+        .user_store(stores.users)
+        .api_key_store(stores.api_keys);
+
+    if config.storage.enabled {
+        let blob_store = create_blob_store(&config.storage.backend)
+            .context("Failed to create blob store")?;
+        info!("Content storage: {}", config.storage.backend);
+        builder = builder
+            .blob_store(blob_store)
+            .upload_session_store(stores.upload_sessions)
+            .upload_session_ttl(chrono::Duration::seconds(
+                config.storage.upload_session_ttl_seconds as i64,
+            ));
+    }
+
+    let services = Arc::new(builder.build().map_err(anyhow::Error::msg)?);
+
+    // Periodically purge abandoned upload sessions
+    if services.uploads().is_some() {
+        spawn_upload_gc(
+            Arc::clone(&services),
+            Duration::from_secs(config.storage.upload_gc_interval_seconds.max(1)),
+        );
+    }
+
+    // Create the initial administrator account
+    if let Some(admin) = &config.auth.bootstrap_admin {
+        bootstrap_admin(&services, admin).await?;
+    }
+
+    // Setup authentication, authorization and rate limiting
+    let auth = setup_auth(&config.auth, &services, stores.acls).await?;
 
     // Build API server
-    let app = build_api_server((*services).clone());
+    let state = AppState::new((*services).clone())
+        .with_authorizer(Arc::clone(&auth.authorizer))
+        .with_rate_limiter(auth.rate_limiter);
+    let app = build_api_server_with_auth(state, auth.handler_state, auth.state.clone());
 
     // Parse HTTP bind address
     let http_addr: SocketAddr = config
@@ -127,7 +170,11 @@ async fn main() -> Result<()> {
         info!("gRPC Server listening on grpc://{}", grpc_addr);
 
         // Build gRPC service
-        let grpc_service = llm_registry_api::RegistryServiceImpl::new(Arc::clone(&services));
+        let grpc_service = llm_registry_api::RegistryServiceImpl::new(
+            Arc::clone(&services),
+            auth.state,
+            auth.authorizer,
+        );
 
         // Spawn gRPC server in background
         Some(tokio::spawn(async move {
@@ -141,14 +188,15 @@ async fn main() -> Result<()> {
         None
     };
 
-    // Serve HTTP with graceful shutdown
+    // Serve HTTP with graceful shutdown (client addresses are needed to rate limit by IP)
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
     let http_result = if config.server.graceful_shutdown {
-        axum::serve(http_listener, app.into_make_service())
+        axum::serve(http_listener, make_service)
             .with_graceful_shutdown(shutdown_signal(config.server.shutdown_timeout_seconds))
             .await
             .context("HTTP Server error")
     } else {
-        axum::serve(http_listener, app.into_make_service())
+        axum::serve(http_listener, make_service)
             .await
             .context("HTTP Server error")
     };
@@ -172,9 +220,190 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Setup database connection pool
-async fn setup_database(config: &ServerConfig) -> Result<PgPool> {
-    info!("Connecting to database");
+/// Spawn a background task that purges expired upload sessions and their chunks
+fn spawn_upload_gc(services: Arc<ServiceRegistry>, period: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let Some(uploads) = services.uploads() else {
+                return;
+            };
+            match uploads.purge_expired_sessions().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} expired upload sessions", purged),
+                Err(e) => warn!("Failed to purge expired upload sessions: {}", e),
+            }
+        }
+    });
+}
+
+/// Create an administrator account if no account exists yet
+async fn bootstrap_admin(services: &ServiceRegistry, admin: &BootstrapAdminConfig) -> Result<()> {
+    let Some(users) = services.users() else {
+        return Ok(());
+    };
+    if !users.list_users().await?.is_empty() {
+        return Ok(());
+    }
+
+    users
+        .create_user(CreateUserRequest {
+            username: admin.username.clone(),
+            password: admin.password.clone(),
+            email: None,
+            roles: vec!["admin".to_string()],
+        })
+        .await
+        .context("Failed to create bootstrap admin account")?;
+    info!("Created bootstrap admin account {}", admin.username);
+    Ok(())
+}
+
+/// Authentication and authorization shared by the HTTP and gRPC servers
+struct Auth {
+    authorizer: Arc<Authorizer>,
+    state: AuthState,
+    handler_state: AuthHandlerState,
+    rate_limiter: RateLimiterState,
+}
+
+/// Setup token verification, revocation, permission checks and rate limiting
+async fn setup_auth(
+    config: &AuthConfig,
+    services: &ServiceRegistry,
+    acls: Arc<dyn AclStore>,
+) -> Result<Auth> {
+    let redis = match &config.redis_url {
+        Some(url) => Some(
+            RedisCache::new(CacheConfig::new(url))
+                .await
+                .context("Failed to connect to Redis")?,
+        ),
+        None => {
+            warn!(
+                "No Redis configured; token revocations and rate limits are kept in memory \
+                 and not shared between instances"
+            );
+            None
+        }
+    };
+
+    let (revocations, rate_limit_store): (Arc<dyn TokenRevocationStore>, Arc<dyn RateLimitStore>) =
+        match redis {
+            Some(cache) => (
+                Arc::new(RedisTokenRevocationStore::new(cache.clone())),
+                Arc::new(RedisRateLimitStore::new(cache)),
+            ),
+            None => (
+                Arc::new(InMemoryTokenRevocationStore::new()),
+                Arc::new(InMemoryRateLimitStore::new()),
+            ),
+        };
+    spawn_revocation_purge(
+        Arc::clone(&revocations),
+        Duration::from_secs(config.revocation_purge_interval_seconds.max(1)),
+    );
+
+    let key_ring = load_key_ring(config)?;
+    let oidc = config.oidc.as_ref().map(|oidc| {
+        info!("Accepting tokens issued by {}", oidc.issuer);
+        let mut provider =
+            OidcConfig::new(&oidc.issuer, &oidc.audience).with_roles_claim(&oidc.roles_claim);
+        if let Some(jwks_uri) = &oidc.jwks_uri {
+            provider = provider.with_jwks_uri(jwks_uri);
+        }
+        if oidc.role_mapping.is_empty() {
+            warn!("No OIDC role mapping configured; external tokens grant no roles");
+        }
+        for (provider_role, registry_role) in &oidc.role_mapping {
+            provider = provider.with_role_mapping(provider_role, registry_role);
+        }
+        Arc::new(OidcProvider::new(provider))
+    });
+
+    // The HTTP handlers and the authentication middleware each own a manager
+    let jwt_manager = || -> Result<JwtManager> {
+        let jwt_config = JwtConfig::new(&config.jwt_secret)
+            .with_expiration(config.token_expiration_seconds)
+            .with_refresh_expiration(config.refresh_token_expiration_seconds)
+            .with_issuer(&config.issuer)
+            .with_audience(&config.audience);
+        let manager = match &key_ring {
+            Some(keys) => JwtManager::with_key_ring(jwt_config, keys.clone()),
+            None => JwtManager::new(jwt_config),
+        }
+        .context("Invalid JWT configuration")?
+        .with_revocation_store(Arc::clone(&revocations));
+        Ok(match &oidc {
+            Some(provider) => manager.with_oidc_provider(Arc::clone(provider)),
+            None => manager,
+        })
+    };
+
+    let mut authorizer = Authorizer::new(RbacPolicy::default())
+        .with_anonymous_roles(config.anonymous_roles.clone())
+        .with_acl(acls, Arc::clone(services.search()));
+    if let Some(uploads) = services.uploads() {
+        authorizer = authorizer.with_upload_sessions(Arc::clone(uploads));
+    }
+
+    let mut state = AuthState::new(jwt_manager()?);
+    if let Some(api_keys) = services.api_keys() {
+        state = state.with_api_key_service(Arc::clone(api_keys));
+    }
+    if let Some(users) = services.users() {
+        state = state.with_user_service(Arc::clone(users));
+    }
+
+    Ok(Auth {
+        authorizer: Arc::new(authorizer),
+        state,
+        handler_state: AuthHandlerState::new(jwt_manager()?),
+        rate_limiter: RateLimiterState::with_store(config.rate_limit.clone(), rate_limit_store),
+    })
+}
+
+/// Load the configured signing keys, if any
+fn load_key_ring(config: &AuthConfig) -> Result<Option<KeyRing>> {
+    let mut keys = config.signing_keys.iter().map(|key| {
+        let pem = std::fs::read(&key.private_key_path).with_context(|| {
+            format!(
+                "Failed to read signing key {}",
+                key.private_key_path.display()
+            )
+        })?;
+        SigningKey::from_pem(&key.kid, key.algorithm, &pem)
+            .with_context(|| format!("Invalid signing key {}", key.kid))
+    });
+
+    let Some(current) = keys.next().transpose()? else {
+        return Ok(None);
+    };
+    info!("Signing tokens with key {}", current.kid());
+    keys.try_fold(KeyRing::new(current), |ring, key| {
+        Ok(ring.with_previous_key(key?))
+    })
+    .map(Some)
+}
+
+/// Stores backing the services
+struct Stores {
+    assets: Arc<dyn AssetRepository>,
+    events: Arc<dyn EventStore>,
+    users: Arc<dyn UserStore>,
+    api_keys: Arc<dyn ApiKeyStore>,
+    upload_sessions: Arc<dyn UploadSessionStore>,
+    acls: Arc<dyn AclStore>,
+}
+
+/// Setup the database selected by the URL scheme and its stores
+async fn setup_database(config: &ServerConfig) -> Result<Stores> {
+    let backend = config
+        .database
+        .backend()
+        .context("Invalid database configuration")?;
+    info!("Connecting to {:?} database", backend);
 
     let pool_config = PoolConfig::new(&config.database.url)
         .min_connections(config.database.min_connections)
@@ -185,12 +414,39 @@ async fn setup_database(config: &ServerConfig) -> Result<PgPool> {
         .run_migrations(config.database.run_migrations)
         .enable_logging(config.logging.level != "error");
 
-    let pool = create_pool(&pool_config)
-        .await
-        .context("Failed to create database connection pool")?;
+    let stores = match backend {
+        DatabaseBackend::Postgres => {
+            let pool = create_pool(&pool_config)
+                .await
+                .context("Failed to create database connection pool")?;
+
+            Stores {
+                assets: Arc::new(PostgresAssetRepository::new(pool.clone())),
+                events: Arc::new(PostgresEventStore::new(pool.clone())),
+                users: Arc::new(PostgresUserStore::new(pool.clone())),
+                api_keys: Arc::new(PostgresApiKeyStore::new(pool.clone())),
+                upload_sessions: Arc::new(PostgresUploadSessionStore::new(pool.clone())),
+                acls: Arc::new(PostgresAclStore::new(pool)),
+            }
+        }
+        DatabaseBackend::Sqlite => {
+            let pool = create_sqlite_pool(&pool_config)
+                .await
+                .context("Failed to create database connection pool")?;
+
+            Stores {
+                assets: Arc::new(SqliteAssetRepository::new(pool.clone())),
+                events: Arc::new(SqliteEventStore::new(pool.clone())),
+                users: Arc::new(SqliteUserStore::new(pool.clone())),
+                api_keys: Arc::new(SqliteApiKeyStore::new(pool.clone())),
+                upload_sessions: Arc::new(SqliteUploadSessionStore::new(pool.clone())),
+                acls: Arc::new(SqliteAclStore::new(pool)),
+            }
+        }
+    };
 
     info!("Database connection established");
-    Ok(pool)
+    Ok(stores)
 }
 
 /// Graceful shutdown signal handler
@@ -0,0 +1,262 @@
+//! API key service
+//!
+//! This module issues and verifies managed API keys. A key has the form
+//! `llmr_<id>_<secret>`: the ID locates the stored record and the secret is
+//! checked against its SHA-256 hash. Secrets are 256 random bits, so a fast
+//! hash is sufficient and keeps per-request verification cheap. Whether the
+//! requested permissions are a subset of the owner's is decided by the API
+//! layer, which owns the role policy.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{Duration, Utc};
+use llm_registry_db::{ApiKey, ApiKeyStore};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::dto::{CreateApiKeyRequest, CreatedApiKey};
+use crate::error::{ServiceError, ServiceResult};
+
+/// Prefix of every API key, to make leaked keys easy to recognize
+pub const API_KEY_PREFIX: &str = "llmr";
+
+/// Number of random bytes in an API key secret
+const SECRET_BYTES: usize = 32;
+
+/// Minimum time between two `last_used_at` updates of the same key
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+/// Trait for API key operations
+#[async_trait]
+pub trait ApiKeyService: Send + Sync {
+    /// Create a key, returning the only copy of its secret
+    async fn create_key(&self, request: CreateApiKeyRequest) -> ServiceResult<CreatedApiKey>;
+
+    /// Verify a full API key, returning its record on success
+    ///
+    /// Unknown, malformed, revoked and expired keys all fail with
+    /// `InvalidCredentials`.
+    async fn authenticate(&self, api_key: &str) -> ServiceResult<ApiKey>;
+
+    /// Get a key by ID
+    async fn get_key(&self, id: &str) -> ServiceResult<ApiKey>;
+
+    /// List keys, optionally only those of one owner
+    async fn list_keys(&self, owner: Option<&str>) -> ServiceResult<Vec<ApiKey>>;
+
+    /// Revoke a key
+    async fn revoke_key(&self, id: &str) -> ServiceResult<ApiKey>;
+}
+
+/// Default implementation of ApiKeyService
+pub struct DefaultApiKeyService {
+    store: Arc<dyn ApiKeyStore>,
+}
+
+impl DefaultApiKeyService {
+    /// Create a new API key service
+    pub fn new(store: Arc<dyn ApiKeyStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl ApiKeyService for DefaultApiKeyService {
+    #[instrument(skip(self, request), fields(owner = %request.owner, name = %request.name))]
+    async fn create_key(&self, request: CreateApiKeyRequest) -> ServiceResult<CreatedApiKey> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "API key name must not be empty".to_string(),
+            ));
+        }
+        if request.permissions.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "API key must have at least one permission".to_string(),
+            ));
+        }
+        if let Some(invalid) = request.permissions.iter().find(|p| !is_permission(p)) {
+            return Err(ServiceError::InvalidInput(format!(
+                "Invalid permission '{}', expected resource:action",
+                invalid
+            )));
+        }
+        let now = Utc::now();
+        if request.expires_at.is_some_and(|expires| expires <= now) {
+            return Err(ServiceError::InvalidInput(
+                "API key expiry must be in the future".to_string(),
+            ));
+        }
+
+        let id = ulid::Ulid::new().to_string();
+        let secret = generate_secret();
+        let key = ApiKey {
+            id: id.clone(),
+            name: name.to_string(),
+            owner: request.owner,
+            roles: request.roles,
+            permissions: request.permissions,
+            secret_hash: hash_secret(&secret),
+            expires_at: request.expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: now,
+        };
+
+        let key = self.store.create(key).await?;
+        info!("Created API key {} for {}", key.id, key.owner);
+
+        Ok(CreatedApiKey {
+            api_key: format!("{}_{}_{}", API_KEY_PREFIX, id, secret),
+            key,
+        })
+    }
+
+    async fn authenticate(&self, api_key: &str) -> ServiceResult<ApiKey> {
+        let Some((id, secret)) = parse_key(api_key) else {
+            return Err(ServiceError::InvalidCredentials);
+        };
+        let Some(key) = self.store.find(id).await? else {
+            return Err(ServiceError::InvalidCredentials);
+        };
+
+        if !constant_time_eq(hash_secret(secret).as_bytes(), key.secret_hash.as_bytes()) {
+            warn!("API key {} presented with a wrong secret", key.id);
+            return Err(ServiceError::InvalidCredentials);
+        }
+        let now = Utc::now();
+        if !key.is_active(now) {
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        // Avoid a write on every request from busy keys
+        let stale = key
+            .last_used_at
+            .map_or(true, |at| now - at >= Duration::seconds(LAST_USED_RESOLUTION_SECS));
+        if stale {
+            if let Err(e) = self.store.touch(&key.id, now).await {
+                warn!("Failed to record use of API key {}: {}", key.id, e);
+            }
+        }
+
+        Ok(key)
+    }
+
+    async fn get_key(&self, id: &str) -> ServiceResult<ApiKey> {
+        self.store
+            .find(id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("API key {}", id)))
+    }
+
+    async fn list_keys(&self, owner: Option<&str>) -> ServiceResult<Vec<ApiKey>> {
+        let keys = match owner {
+            Some(owner) => self.store.list_for_owner(owner).await?,
+            None => self.store.list().await?,
+        };
+        Ok(keys)
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke_key(&self, id: &str) -> ServiceResult<ApiKey> {
+        let key = self.store.revoke(id, Utc::now()).await?;
+        info!("Revoked API key {} of {}", key.id, key.owner);
+        Ok(key)
+    }
+}
+
+/// Split a full key into its ID and secret
+fn parse_key(api_key: &str) -> Option<(&str, &str)> {
+    let rest = api_key.strip_prefix(API_KEY_PREFIX)?.strip_prefix('_')?;
+    let (id, secret) = rest.split_once('_')?;
+    (!id.is_empty() && !secret.is_empty()).then_some((id, secret))
+}
+
+fn is_permission(permission: &str) -> bool {
+    matches!(
+        permission.split_once(':'),
+        Some((resource, action)) if !resource.is_empty() && !action.is_empty() && !action.contains(':')
+    )
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_registry_db::InMemoryApiKeyStore;
+
+    fn request(permissions: &[&str]) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            name: "ci".to_string(),
+            owner: "alice".to_string(),
+            roles: vec!["developer".to_string()],
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate() {
+        let service = DefaultApiKeyService::new(Arc::new(InMemoryApiKeyStore::new()));
+        let created = service.create_key(request(&["asset:read"])).await.unwrap();
+
+        assert!(created.api_key.starts_with("llmr_"));
+        assert!(!created.api_key.contains(&created.key.secret_hash));
+
+        let key = service.authenticate(&created.api_key).await.unwrap();
+        assert_eq!(key.id, created.key.id);
+        assert_eq!(key.permissions, vec!["asset:read"]);
+        assert!(service
+            .get_key(&key.id)
+            .await
+            .unwrap()
+            .last_used_at
+            .is_some());
+
+        let tampered = format!("{}x", created.api_key);
+        assert!(matches!(
+            service.authenticate(&tampered).await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            service.authenticate("not-a-key").await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+
+        service.revoke_key(&key.id).await.unwrap();
+        assert!(matches!(
+            service.authenticate(&created.api_key).await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+        assert_eq!(service.list_keys(Some("alice")).await.unwrap().len(), 1);
+        assert!(service.list_keys(Some("bob")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_validation() {
+        let service = DefaultApiKeyService::new(Arc::new(InMemoryApiKeyStore::new()));
+
+        assert!(service.create_key(request(&[])).await.is_err());
+        assert!(service.create_key(request(&["asset"])).await.is_err());
+        assert!(service.create_key(request(&["asset:read:x"])).await.is_err());
+
+        let mut expired = request(&["asset:read"]);
+        expired.expires_at = Some(Utc::now() - Duration::minutes(1));
+        assert!(service.create_key(expired).await.is_err());
+    }
+}
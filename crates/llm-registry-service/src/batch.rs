@@ -0,0 +1,228 @@
+//! Batch registration planning
+//!
+//! Items of a batch may depend on each other by name and version, or by name
+//! and version requirement. This module works out an order in which every
+//! item comes after the batch items it depends on, and flags items that can
+//! never be registered because they are duplicates or part of a dependency
+//! cycle.
+
+use llm_registry_core::AssetReference;
+use semver::Version;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::dto::RegisterAssetRequest;
+
+/// A dependency of a batch item on another item of the same batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BatchLink {
+    /// Position of the reference in the item's dependency list
+    pub dependency: usize,
+
+    /// Index of the batch item the reference points to; for a requirement,
+    /// the newest matching item
+    pub target: usize,
+
+    /// Whether the reference is a version requirement rather than an exact
+    /// version
+    pub requirement: bool,
+}
+
+/// Registration order for a batch
+#[derive(Debug, Clone)]
+pub(crate) struct BatchPlan {
+    /// Item indices, each after the batch items it depends on
+    pub order: Vec<usize>,
+
+    /// Dependencies of each item on other batch items
+    pub links: Vec<Vec<BatchLink>>,
+
+    /// Items rejected before registration, with the reason
+    pub rejected: Vec<Option<String>>,
+}
+
+/// Plan the registration order of a batch
+///
+/// Items keep their request order unless a dependency forces otherwise.
+/// Duplicate name/version pairs and items caught in (or behind) a dependency
+/// cycle are rejected; they are placed at the end of the order.
+pub(crate) fn plan_batch(items: &[RegisterAssetRequest]) -> BatchPlan {
+    let mut rejected = vec![None; items.len()];
+    let mut by_key: HashMap<(String, Version), usize> = HashMap::new();
+
+    for (index, item) in items.iter().enumerate() {
+        let key = (item.name.clone(), item.version.clone());
+        if let Some(&first) = by_key.get(&key) {
+            rejected[index] = Some(format!(
+                "Duplicate of batch item {} ({}@{})",
+                first, item.name, item.version
+            ));
+        } else {
+            by_key.insert(key, index);
+        }
+    }
+
+    let mut by_name: HashMap<&str, Vec<(&Version, usize)>> = HashMap::new();
+    for ((name, version), &index) in &by_key {
+        by_name
+            .entry(name.as_str())
+            .or_default()
+            .push((version, index));
+    }
+
+    let links: Vec<Vec<BatchLink>> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            item.dependencies
+                .iter()
+                .enumerate()
+                .filter_map(|(dependency, reference)| {
+                    let (name, requirement) = reference.requirement()?;
+                    let (_, target) = by_name
+                        .get(name)?
+                        .iter()
+                        .filter(|(version, _)| requirement.matches(version))
+                        .max()?;
+                    (*target != index).then_some(BatchLink {
+                        dependency,
+                        target: *target,
+                        requirement: matches!(reference, AssetReference::ByRequirement { .. }),
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    // Kahn's algorithm, always taking the lowest ready index
+    let mut dependents = vec![Vec::new(); items.len()];
+    let mut waiting_on: Vec<usize> = links.iter().map(Vec::len).collect();
+    for (index, item_links) in links.iter().enumerate() {
+        for link in item_links {
+            dependents[link.target].push(index);
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..items.len()).filter(|&i| waiting_on[i] == 0).collect();
+    let mut order = Vec::with_capacity(items.len());
+    while let Some(index) = ready.pop_first() {
+        order.push(index);
+        for &dependent in &dependents[index] {
+            waiting_on[dependent] -= 1;
+            if waiting_on[dependent] == 0 {
+                ready.insert(dependent);
+            }
+        }
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if waiting_on[index] > 0 {
+            rejected[index].get_or_insert_with(|| {
+                format!(
+                    "Dependency cycle within the batch prevents registering {}@{}",
+                    item.name, item.version
+                )
+            });
+            order.push(index);
+        }
+    }
+
+    BatchPlan {
+        order,
+        links,
+        rejected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_registry_core::{AssetType, Checksum, HashAlgorithm, StorageBackend, StorageLocation};
+    use semver::VersionReq;
+
+    fn item(name: &str, version: &str, dependencies: &[(&str, &str)]) -> RegisterAssetRequest {
+        RegisterAssetRequest {
+            asset_type: AssetType::Model,
+            name: name.to_string(),
+            version: Version::parse(version).unwrap(),
+            description: None,
+            license: None,
+            tags: vec![],
+            annotations: HashMap::new(),
+            storage: StorageLocation::new(
+                StorageBackend::FileSystem {
+                    base_path: "/tmp".to_string(),
+                },
+                format!("{}.bin", name),
+                None,
+            )
+            .unwrap(),
+            checksum: Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap(),
+            provenance: None,
+            dependencies: dependencies
+                .iter()
+                .map(|(n, v)| AssetReference::by_name_version(*n, *v).unwrap())
+                .collect(),
+            size_bytes: None,
+            content_type: None,
+        }
+    }
+
+    fn link(dependency: usize, target: usize, requirement: bool) -> BatchLink {
+        BatchLink {
+            dependency,
+            target,
+            requirement,
+        }
+    }
+
+    #[test]
+    fn test_plan_orders_dependencies_first() {
+        let items = vec![
+            item("app", "1.0.0", &[("tokenizer", "2.0.0"), ("external", "1.0.0")]),
+            item("tokenizer", "2.0.0", &[("vocab", "1.0.0")]),
+            item("vocab", "1.0.0", &[]),
+        ];
+
+        let plan = plan_batch(&items);
+        assert_eq!(plan.order, vec![2, 1, 0]);
+        assert_eq!(plan.links[0], vec![link(0, 1, false)]);
+        assert_eq!(plan.links[1], vec![link(0, 2, false)]);
+        assert!(plan.rejected.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_plan_links_requirements_to_newest_match() {
+        let requirement = VersionReq::parse("^1.0").unwrap();
+        let mut app = item("app", "1.0.0", &[]);
+        app.dependencies = vec![AssetReference::by_requirement("tokenizer", requirement).unwrap()];
+        let items = vec![
+            app,
+            item("tokenizer", "1.2.0", &[]),
+            item("tokenizer", "1.4.0", &[]),
+            item("tokenizer", "2.0.0", &[]),
+        ];
+
+        let plan = plan_batch(&items);
+        assert_eq!(plan.links[0], vec![link(0, 2, true)]);
+        assert_eq!(plan.order, vec![1, 2, 0, 3]);
+    }
+
+    #[test]
+    fn test_plan_rejects_duplicates_and_cycles() {
+        let items = vec![
+            item("a", "1.0.0", &[("b", "1.0.0")]),
+            item("b", "1.0.0", &[("a", "1.0.0")]),
+            item("c", "1.0.0", &[("a", "1.0.0")]),
+            item("d", "1.0.0", &[]),
+            item("d", "1.0.0", &[]),
+        ];
+
+        let plan = plan_batch(&items);
+        assert_eq!(plan.order, vec![3, 4, 0, 1, 2]);
+        assert!(plan.rejected[0].as_ref().unwrap().contains("cycle"));
+        assert!(plan.rejected[1].is_some());
+        assert!(plan.rejected[2].is_some());
+        assert!(plan.rejected[3].is_none());
+        assert!(plan.rejected[4].as_ref().unwrap().contains("Duplicate"));
+    }
+}
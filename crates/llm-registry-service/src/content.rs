@@ -0,0 +1,500 @@
+//! Content service
+//!
+//! This module streams asset content into and out of the blob store. Uploads
+//! are hashed with the asset's declared algorithm while they stream and are
+//! discarded if the checksum does not match; downloads support byte ranges
+//! and emit `AssetDownloaded` events.
+
+use async_trait::async_trait;
+use futures::stream::{self, Stream, StreamExt};
+use llm_registry_core::{
+    Asset, AssetId, Checksum, EventType, HashAlgorithm, RegistryError, RegistryEvent,
+};
+use llm_registry_db::{AssetRepository, BlobStore, BlobStream, DbError, EventStore, SearchQuery};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, instrument, warn};
+
+use crate::dto::{ByteRange, DownloadContentRequest, UploadContentResponse};
+use crate::error::{ServiceError, ServiceResult};
+use crate::integrity::ChecksumHasher;
+
+/// Stream of asset content chunks
+pub type ContentStream = Pin<Box<dyn Stream<Item = ServiceResult<Vec<u8>>> + Send>>;
+
+/// Content returned by a download
+pub struct ContentDownload {
+    /// Asset the content belongs to
+    pub asset: Asset,
+
+    /// Total size of the stored content in bytes
+    pub total_size: u64,
+
+    /// Range being returned, or `None` for the full content
+    pub range: Option<ByteRange>,
+
+    /// Content chunks
+    pub stream: ContentStream,
+}
+
+impl ContentDownload {
+    /// Number of bytes the stream will yield
+    pub fn content_length(&self) -> u64 {
+        self.range.map(|r| r.len()).unwrap_or(self.total_size)
+    }
+}
+
+/// Trait for asset content operations
+#[async_trait]
+pub trait ContentService: Send + Sync {
+    /// Upload content for a registered asset
+    ///
+    /// The content is hashed with the asset's declared algorithm while it
+    /// streams. If the checksum does not match, nothing is stored.
+    async fn upload_content(
+        &self,
+        asset_id: &AssetId,
+        content: ContentStream,
+    ) -> ServiceResult<UploadContentResponse>;
+
+    /// Download the content of an asset, optionally restricted to a byte range
+    async fn download_content(&self, request: DownloadContentRequest) -> ServiceResult<ContentDownload>;
+}
+
+/// Default implementation of ContentService
+pub struct DefaultContentService {
+    repository: Arc<dyn AssetRepository>,
+    event_store: Arc<dyn EventStore>,
+    blob_store: Arc<dyn BlobStore>,
+}
+
+impl DefaultContentService {
+    /// Create a new content service
+    pub fn new(
+        repository: Arc<dyn AssetRepository>,
+        event_store: Arc<dyn EventStore>,
+        blob_store: Arc<dyn BlobStore>,
+    ) -> Self {
+        Self {
+            repository,
+            event_store,
+            blob_store,
+        }
+    }
+
+    async fn find_asset(&self, asset_id: &AssetId) -> ServiceResult<Asset> {
+        self.repository
+            .find_by_id(asset_id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(asset_id.to_string()))
+    }
+
+    /// Emit checksum verification events
+    async fn emit_checksum_events(&self, asset: &Asset, actual: &Checksum, success: bool) {
+        let mut events = vec![RegistryEvent::new(EventType::ChecksumVerified {
+            asset_id: asset.id,
+            success,
+            algorithm: asset.checksum.algorithm.to_string(),
+        })];
+
+        if !success {
+            events.push(RegistryEvent::new(EventType::ChecksumFailed {
+                asset_id: asset.id,
+                expected: asset.checksum.value.clone(),
+                actual: actual.value.clone(),
+            }));
+        }
+
+        if let Err(e) = self.event_store.append_batch(events).await {
+            warn!("Failed to emit checksum events: {}", e);
+        }
+    }
+
+    /// Emit asset downloaded event
+    async fn emit_downloaded_event(&self, asset: &Asset, downloader: Option<String>) {
+        let event = RegistryEvent::new(EventType::AssetDownloaded {
+            asset_id: asset.id,
+            asset_name: asset.metadata.name.clone(),
+            asset_version: asset.metadata.version.to_string(),
+            downloader,
+        });
+
+        if let Err(e) = self.event_store.append(event).await {
+            warn!("Failed to emit asset downloaded event: {}", e);
+        }
+    }
+}
+
+/// Result of streaming content into the blob store while hashing it
+pub(crate) enum VerifiedWrite {
+    /// Content was stored
+    Stored { checksum: Checksum, size_bytes: u64 },
+    /// Content did not match the expected checksum and was discarded
+    Mismatch { actual: Checksum },
+}
+
+/// How a verifying stream finished, recorded for the caller of the blob store
+enum WriteOutcome {
+    Stored { checksum: Checksum, size_bytes: u64 },
+    Mismatch { actual: Checksum },
+    Failed(ServiceError),
+}
+
+/// Stream content into the blob store at `key`, hashing it on the way
+///
+/// If `expected` is set and the computed checksum differs, the stream ends
+/// with an error so the blob store discards the partially written content.
+pub(crate) async fn write_verified(
+    blob_store: &dyn BlobStore,
+    key: &str,
+    content: ContentStream,
+    algorithm: HashAlgorithm,
+    expected: Option<&Checksum>,
+) -> ServiceResult<VerifiedWrite> {
+    let outcome = Arc::new(Mutex::new(None));
+    let verifying = verifying_stream(content, algorithm, expected.cloned(), outcome.clone());
+    let result = blob_store.put_stream(key, verifying).await;
+    let outcome = outcome.lock().unwrap().take();
+
+    match (result, outcome) {
+        (Ok(_), Some(WriteOutcome::Stored { checksum, size_bytes })) => Ok(VerifiedWrite::Stored {
+            checksum,
+            size_bytes,
+        }),
+        (Err(_), Some(WriteOutcome::Mismatch { actual })) => Ok(VerifiedWrite::Mismatch { actual }),
+        (Err(_), Some(WriteOutcome::Failed(e))) => Err(e),
+        (Err(e), _) => Err(e.into()),
+        (Ok(_), _) => Err(ServiceError::Internal(
+            "Upload finished without checksum verification".to_string(),
+        )),
+    }
+}
+
+/// Wrap an upload stream so it is hashed as it is written
+fn verifying_stream(
+    content: ContentStream,
+    algorithm: HashAlgorithm,
+    expected: Option<Checksum>,
+    outcome: Arc<Mutex<Option<WriteOutcome>>>,
+) -> BlobStream {
+    let state = Some((content, ChecksumHasher::new(algorithm), 0u64));
+
+    Box::pin(stream::unfold(state, move |state| {
+        let expected = expected.clone();
+        let outcome = outcome.clone();
+        async move {
+            let (mut content, mut hasher, mut size_bytes) = state?;
+
+            match content.next().await {
+                Some(Ok(chunk)) => {
+                    hasher.update(&chunk);
+                    size_bytes += chunk.len() as u64;
+                    Some((Ok(chunk), Some((content, hasher, size_bytes))))
+                }
+                Some(Err(e)) => {
+                    *outcome.lock().unwrap() = Some(WriteOutcome::Failed(e));
+                    Some((Err(DbError::Storage("Upload stream failed".to_string())), None))
+                }
+                None => {
+                    let actual = match hasher.finalize() {
+                        Ok(actual) => actual,
+                        Err(e) => {
+                            *outcome.lock().unwrap() = Some(WriteOutcome::Failed(e));
+                            return Some((
+                                Err(DbError::Internal("Failed to finalize checksum".to_string())),
+                                None,
+                            ));
+                        }
+                    };
+
+                    match expected {
+                        Some(expected) if !actual.verify(&expected) => {
+                            let error = DbError::Domain(RegistryError::ChecksumMismatch {
+                                expected: expected.value.clone(),
+                                actual: actual.value.clone(),
+                            });
+                            *outcome.lock().unwrap() = Some(WriteOutcome::Mismatch { actual });
+                            Some((Err(error), None))
+                        }
+                        _ => {
+                            *outcome.lock().unwrap() = Some(WriteOutcome::Stored {
+                                checksum: actual,
+                                size_bytes,
+                            });
+                            None
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+#[async_trait]
+impl ContentService for DefaultContentService {
+    #[instrument(skip(self, content))]
+    async fn upload_content(
+        &self,
+        asset_id: &AssetId,
+        content: ContentStream,
+    ) -> ServiceResult<UploadContentResponse> {
+        let mut asset = self.find_asset(asset_id).await?;
+        let key = asset.storage.path.clone();
+        debug!("Uploading content for asset {} to {}", asset_id, key);
+
+        // Never overwrite content that another asset points at
+        let sharing = self
+            .repository
+            .search(
+                &SearchQuery::new()
+                    .storage_path(key.clone())
+                    .exclude_deprecated(false)
+                    .limit(2),
+            )
+            .await?;
+        if sharing.assets.iter().any(|other| other.id != asset.id) {
+            return Err(ServiceError::InvalidInput(format!(
+                "Storage path already in use: {}",
+                key
+            )));
+        }
+
+        let written = write_verified(
+            self.blob_store.as_ref(),
+            &key,
+            content,
+            asset.checksum.algorithm,
+            Some(&asset.checksum),
+        )
+        .await?;
+
+        let (checksum, size_bytes) = match written {
+            VerifiedWrite::Stored {
+                checksum,
+                size_bytes,
+            } => (checksum, size_bytes),
+            VerifiedWrite::Mismatch { actual } => {
+                self.emit_checksum_events(&asset, &actual, false).await;
+                return Err(ServiceError::ChecksumVerificationFailed(format!(
+                    "expected {}, computed {}",
+                    asset.checksum.value, actual.value
+                )));
+            }
+        };
+
+        self.emit_checksum_events(&asset, &checksum, true).await;
+
+        // Record the actual size and where the content now lives
+        let backend = self.blob_store.backend();
+        if asset.metadata.size_bytes != Some(size_bytes) || &asset.storage.backend != backend {
+            asset.metadata.size_bytes = Some(size_bytes);
+            asset.storage.backend = backend.clone();
+            asset.updated_at = chrono::Utc::now();
+            let revision = asset.revision;
+            asset = self.repository.update(asset, Some(revision)).await?;
+        }
+
+        info!("Stored {} bytes of content for asset {}", size_bytes, asset_id);
+
+        Ok(UploadContentResponse {
+            asset,
+            size_bytes,
+            checksum,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn download_content(&self, request: DownloadContentRequest) -> ServiceResult<ContentDownload> {
+        let asset = self.find_asset(&request.asset_id).await?;
+        let key = asset.storage.path.clone();
+
+        let metadata = self
+            .blob_store
+            .head(&key)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("No content uploaded for asset {}", asset.id))
+            })?;
+        let total_size = metadata.size_bytes;
+
+        let range = match request.range {
+            Some(requested) => Some(
+                requested
+                    .resolve(total_size)
+                    .ok_or(ServiceError::RangeNotSatisfiable { size: total_size })?,
+            ),
+            None => None,
+        };
+
+        let blob_stream = match range {
+            Some(r) => self.blob_store.stream_range(&key, r.start, r.end).await?,
+            None => self.blob_store.stream(&key).await?,
+        };
+        let stream: ContentStream = Box::pin(blob_stream.map(|chunk| chunk.map_err(ServiceError::from)));
+
+        self.emit_downloaded_event(&asset, request.downloader).await;
+
+        Ok(ContentDownload {
+            asset,
+            total_size,
+            range,
+            stream,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::ByteRangeRequest;
+    use crate::integrity::utils;
+    use futures::TryStreamExt;
+    use llm_registry_core::{
+        AssetMetadata, AssetType, HashAlgorithm, StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::{FileSystemBlobStore, InMemoryAssetRepository, InMemoryEventStore};
+    use semver::Version;
+
+    const CONTENT: &[u8] = b"model weights for content service tests";
+
+    fn create_test_asset(checksum: Checksum) -> Asset {
+        let metadata = AssetMetadata::new("content-asset", Version::parse("1.0.0").unwrap());
+        let storage = StorageLocation::new(
+            StorageBackend::S3 {
+                bucket: "test".to_string(),
+                region: "us-east-1".to_string(),
+                endpoint: None,
+            },
+            "content-asset/1.0.0/weights.bin".to_string(),
+            None,
+        )
+        .unwrap();
+        Asset::new(AssetId::new(), AssetType::Model, metadata, storage, checksum).unwrap()
+    }
+
+    async fn create_service(
+        assets: Vec<Asset>,
+    ) -> (DefaultContentService, Arc<InMemoryEventStore>, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("llm-registry-content-{}", AssetId::new()));
+        let repository = InMemoryAssetRepository::new();
+        for asset in assets {
+            repository.create(asset).await.unwrap();
+        }
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let service = DefaultContentService::new(
+            Arc::new(repository),
+            event_store.clone(),
+            Arc::new(FileSystemBlobStore::new(&dir)),
+        );
+        (service, event_store, dir)
+    }
+
+    async fn event_types(events: &InMemoryEventStore) -> Vec<String> {
+        events
+            .read_after(None, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|stored| stored.event.event_type.event_name().to_string())
+            .collect()
+    }
+
+    fn chunked(data: &[u8]) -> ContentStream {
+        let chunks: Vec<ServiceResult<Vec<u8>>> = data.chunks(8).map(|c| Ok(c.to_vec())).collect();
+        Box::pin(stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn test_upload_and_download_content() {
+        let checksum = utils::compute_checksum(CONTENT, HashAlgorithm::BLAKE3).unwrap();
+        let asset = create_test_asset(checksum.clone());
+        let asset_id = asset.id;
+        let (service, events, dir) = create_service(vec![asset]).await;
+
+        let uploaded = service.upload_content(&asset_id, chunked(CONTENT)).await.unwrap();
+        assert_eq!(uploaded.size_bytes, CONTENT.len() as u64);
+        assert_eq!(uploaded.checksum, checksum);
+        assert_eq!(uploaded.asset.metadata.size_bytes, Some(CONTENT.len() as u64));
+        assert_eq!(uploaded.asset.storage.backend.backend_type(), "FileSystem");
+
+        let download = service
+            .download_content(DownloadContentRequest {
+                asset_id,
+                range: Some(ByteRangeRequest::Suffix { length: 7 }),
+                downloader: Some("user-1".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(download.total_size, CONTENT.len() as u64);
+        assert_eq!(download.content_length(), 7);
+        let chunks: Vec<Vec<u8>> = download.stream.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), &CONTENT[CONTENT.len() - 7..]);
+
+        let recorded = event_types(&events).await;
+        assert!(recorded.contains(&"checksum_verified".to_string()));
+        assert!(recorded.contains(&"asset_downloaded".to_string()));
+
+        let unsatisfiable = service
+            .download_content(DownloadContentRequest {
+                asset_id,
+                range: Some(ByteRangeRequest::From { start: 10_000 }),
+                downloader: None,
+            })
+            .await;
+        assert!(matches!(
+            unsatisfiable.err().unwrap(),
+            ServiceError::RangeNotSatisfiable { .. }
+        ));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_upload_checksum_mismatch_discards_content() {
+        let checksum = utils::compute_checksum(b"something else", HashAlgorithm::SHA256).unwrap();
+        let asset = create_test_asset(checksum);
+        let asset_id = asset.id;
+        let (service, events, dir) = create_service(vec![asset]).await;
+
+        let result = service.upload_content(&asset_id, chunked(CONTENT)).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            ServiceError::ChecksumVerificationFailed(_)
+        ));
+        assert!(event_types(&events).await.contains(&"checksum_failed".to_string()));
+
+        let download = service
+            .download_content(DownloadContentRequest {
+                asset_id,
+                range: None,
+                downloader: None,
+            })
+            .await;
+        assert!(matches!(download.err().unwrap(), ServiceError::NotFound(_)));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_upload_refuses_storage_path_of_another_asset() {
+        let checksum = utils::compute_checksum(CONTENT, HashAlgorithm::SHA256).unwrap();
+        let owner = create_test_asset(checksum.clone());
+        let mut other = create_test_asset(checksum);
+        other.metadata.name = "content-asset-copy".to_string();
+
+        let (service, _, dir) = create_service(vec![owner.clone(), other]).await;
+
+        let result = service.upload_content(&owner.id, chunked(CONTENT)).await;
+        assert!(matches!(result.unwrap_err(), ServiceError::InvalidInput(_)));
+        let download = service
+            .download_content(DownloadContentRequest {
+                asset_id: owner.id,
+                range: None,
+                downloader: None,
+            })
+            .await;
+        assert!(matches!(download.err().unwrap(), ServiceError::NotFound(_)));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -5,11 +5,15 @@
 
 use chrono::{DateTime, Utc};
 use llm_registry_core::{
-    Asset, AssetId, AssetReference, AssetStatus, AssetType, Checksum,
-    HashAlgorithm, Provenance, StorageLocation,
+    Asset, AssetId, AssetReference, AssetStatus, AssetType, Checksum, DependencyKind,
+    HashAlgorithm, Provenance, RegistryEvent, StorageLocation,
 };
-use semver::Version;
-use serde::{Deserialize, Serialize};
+use llm_registry_db::{
+    AclReadFilter, AnnotationFilter, ApiKey, DependencyDirection, DependencyEdge, Facet,
+    FacetCounts, UploadSession,
+};
+use semver::{Version, VersionReq};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 // ============================================================================
@@ -55,6 +59,9 @@ pub struct RegisterAssetRequest {
     pub provenance: Option<Provenance>,
 
     /// List of dependencies
+    ///
+    /// References by name are resolved to registered assets, choosing one
+    /// version of each asset across the whole dependency graph.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<AssetReference>,
 
@@ -76,6 +83,97 @@ pub struct RegisterAssetResponse {
     /// Any warnings generated during registration
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+
+    /// Assets chosen for dependencies that were given by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved_dependencies: Vec<ResolvedDependency>,
+}
+
+/// Registered asset chosen for a dependency given by name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedDependency {
+    /// Asset name
+    pub name: String,
+
+    /// Requirement the dependency was declared with
+    pub requirement: VersionReq,
+
+    /// Chosen asset
+    pub asset_id: AssetId,
+
+    /// Version of the chosen asset
+    pub version: Version,
+}
+
+/// Request to register several assets at once
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchRegisterRequest {
+    /// Assets to register
+    ///
+    /// Name and version dependencies on other items of the batch are resolved
+    /// regardless of the order the items appear in.
+    pub items: Vec<RegisterAssetRequest>,
+
+    /// Roll back every registration if any item fails
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// Outcome of one item of a batch registration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+
+    /// Asset name
+    pub name: String,
+
+    /// Asset version
+    pub version: Version,
+
+    /// The registered asset, if the item succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<Asset>,
+
+    /// Why the item was not registered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Any warnings generated during registration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl BatchItemResult {
+    /// Check if the item was registered
+    pub fn is_success(&self) -> bool {
+        self.asset.is_some()
+    }
+}
+
+/// Response from a batch registration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRegisterResponse {
+    /// One result per item, in request order
+    pub results: Vec<BatchItemResult>,
+
+    /// Whether registrations were undone because an item failed in atomic mode
+    ///
+    /// False if any registration could not be undone. Such items keep their
+    /// asset and report the rollback failure as their error.
+    pub rolled_back: bool,
+}
+
+impl BatchRegisterResponse {
+    /// Number of items registered
+    pub fn total_registered(&self) -> usize {
+        self.results.iter().filter(|r| r.is_success()).count()
+    }
+
+    /// Number of items not registered
+    pub fn total_failed(&self) -> usize {
+        self.results.len() - self.total_registered()
+    }
 }
 
 // ============================================================================
@@ -83,9 +181,10 @@ pub struct RegisterAssetResponse {
 // ============================================================================
 
 /// Search query parameters
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchAssetsRequest {
-    /// Text search across name, description, and annotations
+    /// Text search across name, tags, description, and annotations, with
+    /// phrase, exclusion, and field syntax
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 
@@ -109,6 +208,34 @@ pub struct SearchAssetsRequest {
     #[serde(default = "default_exclude_deprecated")]
     pub exclude_deprecated: bool,
 
+    /// Filter by annotation values (asset must pass all filters)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<AnnotationFilter>,
+
+    /// Smallest size in bytes; assets without a size are excluded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size_bytes: Option<u64>,
+
+    /// Largest size in bytes; assets without a size are excluded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_bytes: Option<u64>,
+
+    /// Earliest creation time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_created_at: Option<DateTime<Utc>>,
+
+    /// Latest creation time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_created_at: Option<DateTime<Utc>>,
+
+    /// Earliest last update time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_updated_at: Option<DateTime<Utc>>,
+
+    /// Latest last update time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_updated_at: Option<DateTime<Utc>>,
+
     /// Maximum number of results
     #[serde(default = "default_limit")]
     pub limit: i64,
@@ -117,6 +244,11 @@ pub struct SearchAssetsRequest {
     #[serde(default)]
     pub offset: i64,
 
+    /// Continue after this cursor from a previous response; `offset` is
+    /// ignored when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+
     /// Sort field
     #[serde(default)]
     pub sort_by: SortField,
@@ -124,12 +256,74 @@ pub struct SearchAssetsRequest {
     /// Sort order
     #[serde(default)]
     pub sort_order: SortOrder,
+
+    /// Fields to count the matching assets by, as a list or a
+    /// comma-separated string such as `tag,license`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_facets",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub facets: Vec<Facet>,
+
+    /// Only include assets the caller may read under ACL entries; set by the
+    /// API rather than by clients
+    #[serde(skip)]
+    pub readable_by: Option<AclReadFilter>,
+}
+
+impl Default for SearchAssetsRequest {
+    /// The same defaults as an empty query string
+    fn default() -> Self {
+        Self {
+            text: None,
+            asset_types: Vec::new(),
+            tags: Vec::new(),
+            author: None,
+            storage_backend: None,
+            exclude_deprecated: default_exclude_deprecated(),
+            annotations: Vec::new(),
+            min_size_bytes: None,
+            max_size_bytes: None,
+            min_created_at: None,
+            max_created_at: None,
+            min_updated_at: None,
+            max_updated_at: None,
+            limit: default_limit(),
+            offset: 0,
+            cursor: None,
+            sort_by: SortField::default(),
+            sort_order: SortOrder::default(),
+            facets: Vec::new(),
+            readable_by: None,
+        }
+    }
 }
 
 fn default_exclude_deprecated() -> bool {
     true
 }
 
+/// Accept facets as a list, or as one comma-separated string for query strings
+fn deserialize_facets<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Facet>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Facets {
+        List(Vec<Facet>),
+        Joined(String),
+    }
+
+    match Facets::deserialize(deserializer)? {
+        Facets::List(facets) => Ok(facets),
+        Facets::Joined(joined) => joined
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| name.parse().map_err(D::Error::custom))
+            .collect(),
+    }
+}
+
 fn default_limit() -> i64 {
     50
 }
@@ -143,6 +337,8 @@ pub enum SortField {
     Name,
     Version,
     SizeBytes,
+    /// Relevance to the search text, most relevant first when descending
+    Relevance,
 }
 
 impl Default for SortField {
@@ -182,6 +378,19 @@ pub struct SearchAssetsResponse {
 
     /// Whether there are more results
     pub has_more: bool,
+
+    /// Cursor for the next page, if there is one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// Cursor of each returned asset, in the same order; searching after
+    /// one continues just past that asset
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cursors: Vec<String>,
+
+    /// Value counts of the requested facets over all matching assets
+    #[serde(default, skip_serializing_if = "FacetCounts::is_empty")]
+    pub facets: FacetCounts,
 }
 
 // ============================================================================
@@ -377,9 +586,17 @@ pub struct GetDependencyGraphRequest {
     /// Asset ID
     pub asset_id: AssetId,
 
-    /// Maximum depth to traverse (-1 for unlimited)
+    /// Maximum depth to traverse, at most 100 (-1 for unlimited)
     #[serde(default = "default_max_depth")]
     pub max_depth: i32,
+
+    /// Whether to follow dependencies or dependents
+    #[serde(default)]
+    pub direction: DependencyDirection,
+
+    /// Only follow edges of these kinds (empty follows every kind)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<DependencyKind>,
 }
 
 fn default_max_depth() -> i32 {
@@ -395,8 +612,16 @@ pub struct DependencyGraphResponse {
     /// All dependencies (direct and transitive)
     pub dependencies: Vec<DependencyNode>,
 
+    /// Edges between the nodes, with their type and version constraint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<DependencyEdge>,
+
     /// Whether the graph was truncated due to max_depth
     pub truncated: bool,
+
+    /// Whether any edge lies on a dependency cycle
+    #[serde(default)]
+    pub has_cycles: bool,
 }
 
 /// Node in dependency graph
@@ -411,7 +636,7 @@ pub struct DependencyNode {
     /// Asset version
     pub version: Version,
 
-    /// Depth from root (0 = direct dependency)
+    /// Depth from root (0 = the root itself)
     pub depth: i32,
 
     /// Direct dependencies of this node
@@ -419,6 +644,110 @@ pub struct DependencyNode {
     pub dependencies: Vec<AssetId>,
 }
 
+// ============================================================================
+// Lockfile DTOs
+// ============================================================================
+
+/// Pinned dependency closure of an asset
+///
+/// Lists the asset itself and everything it depends on, directly or
+/// transitively, with the checksum each one had when the lockfile was
+/// generated. Serialized as JSON, or as TOML with [`Lockfile::to_toml`];
+/// both encodings carry the same fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Lockfile format version
+    pub version: u32,
+
+    /// Asset the lockfile was generated for
+    pub root: AssetId,
+
+    /// When the lockfile was generated
+    pub generated_at: DateTime<Utc>,
+
+    /// Locked assets, dependencies before the assets that need them
+    #[serde(default)]
+    pub assets: Vec<LockedAsset>,
+}
+
+/// One asset pinned by a lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedAsset {
+    /// Asset ID
+    pub id: AssetId,
+
+    /// Asset name
+    pub name: String,
+
+    /// Asset version
+    pub version: Version,
+
+    /// Asset type
+    pub asset_type: AssetType,
+
+    /// Checksum of the asset when it was locked
+    pub checksum: Checksum,
+
+    /// Direct dependencies, all of which are locked as well
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<AssetId>,
+}
+
+/// Result of verifying a lockfile against the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockfileVerification {
+    /// Asset the lockfile was generated for
+    pub root: AssetId,
+
+    /// Whether every locked asset is still available unchanged
+    pub valid: bool,
+
+    /// Locked assets that changed since the lockfile was generated
+    pub drift: Vec<LockfileDrift>,
+}
+
+/// A locked asset that no longer matches the registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockfileDrift {
+    /// Asset ID
+    pub asset_id: AssetId,
+
+    /// Asset name, as locked
+    pub name: String,
+
+    /// Asset version, as locked
+    pub version: Version,
+
+    /// How the asset changed
+    #[serde(flatten)]
+    pub kind: DriftKind,
+}
+
+/// How a locked asset changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DriftKind {
+    /// The asset was deleted
+    Deleted,
+
+    /// The asset was deprecated or archived
+    Deprecated {
+        /// Current status
+        status: AssetStatus,
+    },
+
+    /// The asset was found to violate a compliance policy
+    NonCompliant,
+
+    /// The asset's checksum differs from the locked one
+    ChecksumChanged {
+        /// Checksum recorded in the lockfile
+        locked: Checksum,
+        /// Checksum currently registered
+        current: Checksum,
+    },
+}
+
 // ============================================================================
 // Update DTOs
 // ============================================================================
@@ -456,6 +785,10 @@ pub struct UpdateAssetRequest {
     /// New status
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<AssetStatus>,
+
+    /// Only apply the update if the asset is still at this revision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_revision: Option<u64>,
 }
 
 /// Response from updating an asset
@@ -468,6 +801,291 @@ pub struct UpdateAssetResponse {
     pub updated_fields: Vec<String>,
 }
 
+// ============================================================================
+// Deletion DTOs
+// ============================================================================
+
+/// What deleting an asset does to the assets that still need it
+///
+/// Only dependencies whose kind blocks deletion count; evaluation and
+/// optional links are dropped with the asset in every mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteMode {
+    /// Refuse while any asset depends on it
+    #[default]
+    Refuse,
+    /// Delete anyway and mark its direct dependents non-compliant
+    Force,
+    /// Delete and deprecate every asset depending on it, transitively
+    Cascade,
+}
+
+/// Response after deleting an asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAssetResponse {
+    /// Deleted asset
+    pub asset_id: AssetId,
+
+    /// Mode the asset was deleted in
+    pub mode: DeleteMode,
+
+    /// Dependents whose status was changed, nearest first
+    #[serde(default)]
+    pub updated_dependents: Vec<AssetId>,
+
+    /// Dependents whose status could not be changed, nearest first
+    #[serde(default)]
+    pub failed_dependents: Vec<AssetId>,
+}
+
+// ============================================================================
+// Content DTOs
+// ============================================================================
+
+/// Byte range requested by a content download
+///
+/// Mirrors the three forms of an HTTP `Range: bytes=` specifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteRangeRequest {
+    /// Bytes `start..=end`, with `end` clamped to the content length
+    Bounded { start: u64, end: u64 },
+    /// Bytes from `start` to the end of the content
+    From { start: u64 },
+    /// The last `length` bytes of the content
+    Suffix { length: u64 },
+}
+
+impl ByteRangeRequest {
+    /// Resolve the request against content of `size` bytes
+    ///
+    /// Returns `None` if the range cannot be satisfied.
+    pub fn resolve(&self, size: u64) -> Option<ByteRange> {
+        if size == 0 {
+            return None;
+        }
+
+        match *self {
+            ByteRangeRequest::Bounded { start, end } if start <= end && start < size => Some(ByteRange {
+                start,
+                end: end.min(size - 1),
+            }),
+            ByteRangeRequest::From { start } if start < size => Some(ByteRange {
+                start,
+                end: size - 1,
+            }),
+            ByteRangeRequest::Suffix { length } if length > 0 => Some(ByteRange {
+                start: size.saturating_sub(length),
+                end: size - 1,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Resolved, inclusive byte range of asset content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    /// First byte offset
+    pub start: u64,
+    /// Last byte offset (inclusive)
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Number of bytes covered by the range
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Whether the range covers no bytes (never true for a resolved range)
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+/// Request to download asset content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadContentRequest {
+    /// Asset ID
+    pub asset_id: AssetId,
+
+    /// Optional byte range to download
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<ByteRangeRequest>,
+
+    /// User or service downloading the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloader: Option<String>,
+}
+
+/// Response from uploading asset content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadContentResponse {
+    /// Asset after the upload
+    pub asset: Asset,
+
+    /// Number of bytes stored
+    pub size_bytes: u64,
+
+    /// Checksum computed while streaming the upload
+    pub checksum: Checksum,
+}
+
+// ============================================================================
+// Upload Session DTOs
+// ============================================================================
+
+/// Request to start a resumable chunked upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUploadSessionRequest {
+    /// Asset receiving the content
+    pub asset_id: AssetId,
+
+    /// Total size of the content in bytes
+    pub total_size: u64,
+
+    /// Chunk size in bytes (defaults to 64 MiB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<u64>,
+
+    /// User or service creating the session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// Upload session state returned to clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSessionResponse {
+    /// Session details and received chunks
+    pub session: UploadSession,
+
+    /// Number of chunks the content is split into
+    pub total_chunks: u32,
+
+    /// Indices of chunks still to be uploaded
+    pub missing_chunks: Vec<u32>,
+}
+
+impl From<UploadSession> for UploadSessionResponse {
+    fn from(session: UploadSession) -> Self {
+        Self {
+            total_chunks: session.total_chunks(),
+            missing_chunks: session.missing_chunks(),
+            session,
+        }
+    }
+}
+
+// ============================================================================
+// Watch DTOs
+// ============================================================================
+
+/// Request to watch registry events
+///
+/// Empty filter lists match everything; filters of different kinds are
+/// combined with AND, values within one filter with OR.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchAssetsRequest {
+    /// Only events for one of these assets
+    #[serde(default)]
+    pub asset_ids: Vec<AssetId>,
+
+    /// Only events for assets with one of these names
+    #[serde(default)]
+    pub asset_names: Vec<String>,
+
+    /// Only events for assets of one of these types
+    #[serde(default)]
+    pub asset_types: Vec<AssetType>,
+
+    /// Only events for assets carrying at least one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Only events with one of these event names (e.g. `asset_registered`)
+    #[serde(default)]
+    pub event_types: Vec<String>,
+
+    /// ID of the last event the client received
+    ///
+    /// When set, every later event is replayed before live events are
+    /// delivered. When unset, only events appended after the watch starts are
+    /// delivered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_after: Option<String>,
+}
+
+/// An event delivered to a watcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedEvent {
+    /// Event ID, usable as a resume cursor
+    pub event_id: String,
+
+    /// The registry event
+    pub event: RegistryEvent,
+
+    /// Current state of the asset, if it still exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<Asset>,
+}
+
+// ============================================================================
+// Account DTOs
+// ============================================================================
+
+/// Request to create a user account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUserRequest {
+    /// Unique login name
+    pub username: String,
+
+    /// Initial password (plaintext, hashed before storage)
+    pub password: String,
+
+    /// Contact email
+    #[serde(default)]
+    pub email: Option<String>,
+
+    /// Roles granted to the user
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Request to create an API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Human readable name
+    pub name: String,
+
+    /// Principal the key acts on behalf of
+    pub owner: String,
+
+    /// Owner's roles
+    #[serde(default)]
+    pub roles: Vec<String>,
+
+    /// Permissions (`resource:action`) the key is restricted to
+    pub permissions: Vec<String>,
+
+    /// When the key stops working (never, if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A newly created API key
+///
+/// The secret is only available here; the registry only keeps its hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    /// Full key to send in the `X-API-Key` header
+    pub api_key: String,
+
+    /// Stored key metadata
+    pub key: ApiKey,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,6 +1100,51 @@ mod tests {
         assert_eq!(req.sort_order, SortOrder::Descending);
     }
 
+    #[test]
+    fn test_search_request_facets() {
+        let req: SearchAssetsRequest =
+            serde_json::from_value(serde_json::json!({ "facets": "tag, license" })).unwrap();
+        assert_eq!(req.facets, vec![Facet::Tag, Facet::License]);
+
+        let req: SearchAssetsRequest =
+            serde_json::from_value(serde_json::json!({ "facets": ["asset_type"] })).unwrap();
+        assert_eq!(req.facets, vec![Facet::AssetType]);
+
+        let invalid = serde_json::json!({ "facets": "tag,owner" });
+        assert!(serde_json::from_value::<SearchAssetsRequest>(invalid).is_err());
+    }
+
+    #[test]
+    fn test_search_request_filters() {
+        use llm_registry_db::Comparison;
+
+        let req: SearchAssetsRequest = serde_json::from_value(serde_json::json!({
+            "annotations": [
+                { "key": "framework", "condition": { "equals": "pytorch" } },
+                { "key": "params_b", "condition": { "compare": { "op": "ge", "value": 7 } } },
+                { "key": "task", "condition": "exists" }
+            ],
+            "min_size_bytes": 1024,
+            "max_created_at": "2025-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            req.annotations,
+            vec![
+                AnnotationFilter::equals("framework", "pytorch"),
+                AnnotationFilter::compare("params_b", Comparison::Ge, 7.0),
+                AnnotationFilter::exists("task"),
+            ]
+        );
+        assert_eq!(req.min_size_bytes, Some(1024));
+        assert!(req.max_size_bytes.is_none());
+        assert_eq!(
+            req.max_created_at.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2025-01-01T00:00:00+00:00")
+        );
+    }
+
     #[test]
     fn test_validation_result_is_valid() {
         let result = ValidationResult {
@@ -498,7 +1161,27 @@ mod tests {
         let req = GetDependencyGraphRequest {
             asset_id: AssetId::new(),
             max_depth: default_max_depth(),
+            direction: DependencyDirection::default(),
+            kinds: Vec::new(),
         };
         assert_eq!(req.max_depth, -1);
+        assert_eq!(req.direction, DependencyDirection::Dependencies);
+    }
+
+    #[test]
+    fn test_byte_range_resolve() {
+        let bounded = ByteRangeRequest::Bounded { start: 10, end: 200 };
+        assert_eq!(bounded.resolve(100), Some(ByteRange { start: 10, end: 99 }));
+        assert_eq!(bounded.resolve(10), None);
+
+        let from = ByteRangeRequest::From { start: 90 };
+        assert_eq!(from.resolve(100), Some(ByteRange { start: 90, end: 99 }));
+
+        let suffix = ByteRangeRequest::Suffix { length: 500 };
+        assert_eq!(suffix.resolve(100), Some(ByteRange { start: 0, end: 99 }));
+        assert_eq!(suffix.resolve(100).unwrap().len(), 100);
+
+        assert_eq!(ByteRangeRequest::Suffix { length: 0 }.resolve(100), None);
+        assert_eq!(ByteRangeRequest::From { start: 0 }.resolve(0), None);
     }
 }
@@ -4,7 +4,7 @@
 //! mapping domain and database errors to service-level errors.
 
 use llm_registry_core::RegistryError;
-use llm_registry_db::DbError;
+use llm_registry_db::{ClosureNode, DbError};
 use thiserror::Error;
 
 /// Result type alias for service operations
@@ -41,6 +41,14 @@ pub enum ServiceError {
     #[error("Version conflict: {0}")]
     VersionConflict(String),
 
+    /// Asset was changed by another writer since it was read
+    #[error("Revision conflict: expected revision {expected}, found {actual}")]
+    RevisionConflict { expected: u64, actual: u64 },
+
+    /// Caller-supplied expected revision does not match the stored one
+    #[error("Precondition failed: expected revision {expected}, found {actual}")]
+    PreconditionFailed { expected: u64, actual: u64 },
+
     /// Policy validation failed
     #[error("Policy validation failed: {policy_name}: {message}")]
     PolicyValidationFailed {
@@ -56,6 +64,25 @@ pub enum ServiceError {
     #[error("Operation not permitted: {0}")]
     NotPermitted(String),
 
+    /// Asset cannot be deleted while other assets depend on it
+    ///
+    /// `hidden` counts dependents left out of `dependents`, such as those the
+    /// caller may not read.
+    #[error("Asset {asset} is required by {}", describe_dependents(.dependents, .hidden))]
+    HasDependents {
+        asset: String,
+        dependents: Vec<ClosureNode>,
+        hidden: usize,
+    },
+
+    /// Username or password is wrong
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+
+    /// Requested byte range lies outside the content
+    #[error("Requested range not satisfiable for content of {size} bytes")]
+    RangeNotSatisfiable { size: u64 },
+
     /// Database error
     #[error("Database error: {0}")]
     Database(String),
@@ -111,6 +138,14 @@ impl From<DbError> for ServiceError {
                     }
                 }
             }
+            DbError::RevisionConflict { expected, actual } => {
+                ServiceError::RevisionConflict { expected, actual }
+            }
+            DbError::HasDependents { asset, count } => ServiceError::HasDependents {
+                asset,
+                dependents: Vec::new(),
+                hidden: count,
+            },
             DbError::ConstraintViolation(msg) => ServiceError::ValidationFailed(msg),
             DbError::ForeignKeyViolation(msg) => ServiceError::ValidationFailed(msg),
             DbError::UniqueViolation(msg) => ServiceError::ValidationFailed(msg),
@@ -124,6 +159,7 @@ impl From<DbError> for ServiceError {
             DbError::InvalidQuery(msg) => ServiceError::InvalidInput(msg),
             DbError::Configuration(msg) => ServiceError::Internal(msg),
             DbError::Cache(msg) => ServiceError::Internal(msg),
+            DbError::Storage(msg) => ServiceError::Internal(msg),
             DbError::Migration(msg) => ServiceError::Internal(msg),
             DbError::Internal(msg) => ServiceError::Internal(msg),
             DbError::Domain(err) => ServiceError::from(err),
@@ -144,6 +180,23 @@ impl From<serde_json::Error> for ServiceError {
     }
 }
 
+/// List the dependents named by a `HasDependents` error
+fn describe_dependents(dependents: &[ClosureNode], hidden: &usize) -> String {
+    let mut description = dependents
+        .iter()
+        .map(|node| format!("{}@{} ({})", node.name, node.version, node.asset_id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if *hidden > 0 {
+        if !description.is_empty() {
+            description.push_str(" and ");
+        }
+        let noun = if *hidden == 1 { "asset" } else { "assets" };
+        description.push_str(&format!("{} other {}", hidden, noun));
+    }
+    description
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,9 +215,51 @@ mod tests {
         assert!(matches!(service_err, ServiceError::NotFound(_)));
     }
 
+    #[test]
+    fn test_service_error_from_revision_conflict() {
+        let db_err = DbError::RevisionConflict {
+            expected: 3,
+            actual: 4,
+        };
+        let service_err: ServiceError = db_err.into();
+        assert!(matches!(
+            service_err,
+            ServiceError::RevisionConflict {
+                expected: 3,
+                actual: 4
+            }
+        ));
+    }
+
     #[test]
     fn test_service_error_display() {
         let err = ServiceError::ValidationFailed("Invalid name".to_string());
         assert_eq!(err.to_string(), "Validation failed: Invalid name");
+
+        let app = ClosureNode {
+            asset_id: llm_registry_core::AssetId::new(),
+            name: "app".to_string(),
+            version: semver::Version::new(2, 0, 0),
+            depth: 1,
+        };
+        let err = ServiceError::HasDependents {
+            asset: "base@1.0.0".to_string(),
+            dependents: vec![app.clone()],
+            hidden: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Asset base@1.0.0 is required by app@2.0.0 ({}) and 2 other assets",
+                app.asset_id
+            )
+        );
+
+        let err = ServiceError::HasDependents {
+            asset: "base@1.0.0".to_string(),
+            dependents: vec![],
+            hidden: 1,
+        };
+        assert_eq!(err.to_string(), "Asset base@1.0.0 is required by 1 other asset");
     }
 }
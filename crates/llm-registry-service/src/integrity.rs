@@ -208,8 +208,9 @@ impl IntegrityService for DefaultIntegrityService {
         asset.checksum = new_checksum;
         asset.updated_at = chrono::Utc::now();
 
-        // Persist the update
-        let updated = self.repository.update(asset).await?;
+        // Persist the update, failing if the asset changed since it was read
+        let revision = asset.revision;
+        let updated = self.repository.update(asset, Some(revision)).await?;
 
         // Emit update event
         let event = RegistryEvent::new(EventType::AssetUpdated {
@@ -226,6 +227,65 @@ impl IntegrityService for DefaultIntegrityService {
     }
 }
 
+/// Incremental checksum computation for content that arrives in chunks
+pub enum ChecksumHasher {
+    /// SHA-256 state
+    Sha256(sha2::Sha256),
+    /// SHA3-256 state
+    Sha3_256(Box<sha3::Sha3_256>),
+    /// BLAKE3 state
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl ChecksumHasher {
+    /// Create a hasher for the given algorithm
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::SHA256 => {
+                use sha2::Digest;
+                ChecksumHasher::Sha256(sha2::Sha256::new())
+            }
+            HashAlgorithm::SHA3_256 => {
+                use sha3::Digest;
+                ChecksumHasher::Sha3_256(Box::new(sha3::Sha3_256::new()))
+            }
+            HashAlgorithm::BLAKE3 => ChecksumHasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /// Algorithm used by this hasher
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            ChecksumHasher::Sha256(_) => HashAlgorithm::SHA256,
+            ChecksumHasher::Sha3_256(_) => HashAlgorithm::SHA3_256,
+            ChecksumHasher::Blake3(_) => HashAlgorithm::BLAKE3,
+        }
+    }
+
+    /// Feed a chunk of data into the hasher
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            ChecksumHasher::Sha256(hasher) => sha2::Digest::update(hasher, data),
+            ChecksumHasher::Sha3_256(hasher) => sha3::Digest::update(hasher.as_mut(), data),
+            ChecksumHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    /// Consume the hasher and produce the checksum
+    pub fn finalize(self) -> ServiceResult<Checksum> {
+        let algorithm = self.algorithm();
+        let hash_value = match self {
+            ChecksumHasher::Sha256(hasher) => format!("{:x}", sha2::Digest::finalize(hasher)),
+            ChecksumHasher::Sha3_256(hasher) => format!("{:x}", sha3::Digest::finalize(*hasher)),
+            ChecksumHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        };
+        Checksum::new(algorithm, hash_value)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create checksum: {}", e)))
+    }
+}
+
 /// Utility functions for computing checksums
 pub mod utils {
     use super::*;
@@ -251,6 +311,13 @@ pub mod utils {
             .map_err(|e| ServiceError::Internal(format!("Failed to create checksum: {}", e)))
     }
 
+    /// Compute a checksum from bytes with the given algorithm
+    pub fn compute_checksum(data: &[u8], algorithm: HashAlgorithm) -> ServiceResult<Checksum> {
+        let hash_value = DefaultIntegrityService::hash_data(data, algorithm);
+        Checksum::new(algorithm, hash_value)
+            .map_err(|e| ServiceError::Internal(format!("Failed to create checksum: {}", e)))
+    }
+
     /// Verify data against checksum
     pub fn verify_data(data: &[u8], expected: &Checksum) -> bool {
         let computed_hash = DefaultIntegrityService::hash_data(data, expected.algorithm());
@@ -284,6 +351,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_checksum_util() {
+        let data = b"hello world";
+        let checksum = utils::compute_checksum(data, HashAlgorithm::BLAKE3).unwrap();
+        assert_eq!(checksum.algorithm(), HashAlgorithm::BLAKE3);
+        assert_eq!(
+            checksum.value(),
+            "d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24"
+        );
+    }
+
+    #[test]
+    fn test_checksum_hasher_matches_one_shot() {
+        let data = b"hello streaming world";
+        for algorithm in [HashAlgorithm::SHA256, HashAlgorithm::SHA3_256, HashAlgorithm::BLAKE3] {
+            let mut hasher = ChecksumHasher::new(algorithm);
+            for chunk in data.chunks(4) {
+                hasher.update(chunk);
+            }
+            let streamed = hasher.finalize().unwrap();
+            assert_eq!(streamed, utils::compute_checksum(data, algorithm).unwrap());
+        }
+    }
+
     #[test]
     fn test_compute_sha256_util() {
         let data = b"test data";
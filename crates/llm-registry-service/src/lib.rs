@@ -12,6 +12,12 @@
 //! - **ValidationService**: Schema and policy validation
 //! - **IntegrityService**: Checksum computation and verification
 //! - **VersioningService**: Version management and conflict detection
+//! - **ContentService**: Streaming content upload and download through the blob store
+//! - **UploadSessionService**: Resumable chunked uploads with expiry and garbage collection
+//! - **WatchService**: Live, resumable feed of registry events
+//! - **LockfileService**: Pinned dependency closures and drift detection
+//! - **UserService**: User accounts with Argon2 password login and lockout
+//! - **ApiKeyService**: Hashed, scoped API keys
 //!
 //! # Example
 //!
@@ -49,7 +55,6 @@
 //!     repository.clone(),
 //!     event_store.clone(),
 //!     validation_service.clone(),
-//!     integrity_service.clone(),
 //!     versioning_service.clone(),
 //! ));
 //!
@@ -57,13 +62,21 @@
 //! # }
 //! ```
 
+pub mod api_key;
+mod batch;
+pub mod content;
 pub mod dto;
 pub mod error;
 pub mod integrity;
+pub mod lockfile;
 pub mod registration;
+mod resolver;
 pub mod search;
+pub mod upload_session;
+pub mod user;
 pub mod validation;
 pub mod versioning;
+pub mod watch;
 
 // LLM-Dev-Ops upstream integration adapters (Phase 2B)
 pub mod adapters;
@@ -73,18 +86,32 @@ pub use dto::*;
 pub use error::{ServiceError, ServiceResult};
 
 // Re-export service traits and implementations
+pub use api_key::{ApiKeyService, DefaultApiKeyService, API_KEY_PREFIX};
+pub use content::{ContentDownload, ContentService, ContentStream, DefaultContentService};
 pub use integrity::{DefaultIntegrityService, IntegrityService};
-pub use registration::{DefaultRegistrationService, RegistrationService};
+pub use lockfile::{DefaultLockfileService, LockfileService, LOCKFILE_FORMAT_VERSION};
+pub use registration::{DefaultRegistrationService, RegistrationService, MAX_BATCH_SIZE};
 pub use search::{DefaultSearchService, SearchService};
+pub use upload_session::{DefaultUploadSessionService, UploadSessionService};
+pub use llm_registry_db::{
+    spawn_revocation_purge, AclEffect, AclEntry, AclReadFilter, AclStore, AclSubject, AclTarget,
+    AnnotationCondition, AnnotationFilter, ApiKey, ApiKeyStore, ClosureNode, Comparison,
+    DependencyDirection, DependencyEdge, Facet, FacetCount, FacetCounts, InMemoryAclStore,
+    InMemoryApiKeyStore, InMemoryRateLimitStore, InMemoryTokenRevocationStore, InMemoryUserStore,
+    RateLimitDecision, RateLimitStore, TokenRevocationStore, UploadChunk, UploadSession,
+    UploadSessionStatus, User, UserStore,
+};
+pub use user::{DefaultUserService, UserService};
 pub use validation::{DefaultValidationService, ValidationService};
 pub use versioning::{DefaultVersioningService, VersioningService};
+pub use watch::{DefaultWatchService, EventStream, WatchService};
 
 // Re-export upstream adapters for convenience
 pub use adapters::{
     ConfigManagerAdapter, ObservatoryAdapter, SchemaRegistryAdapter,
 };
 
-use llm_registry_db::{AssetRepository, EventStore};
+use llm_registry_db::{AssetRepository, BlobStore, EventStore, UploadSessionStore};
 use std::sync::Arc;
 
 /// Service registry that holds all service instances
@@ -103,6 +130,18 @@ pub struct ServiceRegistry {
     pub integrity: Arc<dyn IntegrityService>,
     /// Versioning service
     pub versioning: Arc<dyn VersioningService>,
+    /// Content service, available when a blob store is configured
+    pub content: Option<Arc<dyn ContentService>>,
+    /// Upload session service, available when a blob store and session store are configured
+    pub uploads: Option<Arc<dyn UploadSessionService>>,
+    /// Watch service
+    pub watch: Option<Arc<dyn WatchService>>,
+    /// Lockfile service
+    pub lockfiles: Option<Arc<dyn LockfileService>>,
+    /// User service, available when a user store is configured
+    pub users: Option<Arc<dyn UserService>>,
+    /// API key service, available when an API key store is configured
+    pub api_keys: Option<Arc<dyn ApiKeyService>>,
 }
 
 impl ServiceRegistry {
@@ -148,11 +187,17 @@ impl ServiceRegistry {
 
         let search = Arc::new(DefaultSearchService::new(repository.clone()));
 
+        let lockfiles = Arc::new(DefaultLockfileService::new(repository.clone()));
+
+        let watch = Arc::new(DefaultWatchService::new(
+            repository.clone(),
+            event_store.clone(),
+        ));
+
         let registration = Arc::new(DefaultRegistrationService::new(
             repository.clone(),
             event_store.clone(),
             validation.clone(),
-            integrity.clone(),
             versioning.clone(),
         ));
 
@@ -162,6 +207,12 @@ impl ServiceRegistry {
             validation,
             integrity,
             versioning,
+            content: None,
+            uploads: None,
+            watch: Some(watch),
+            lockfiles: Some(lockfiles),
+            users: None,
+            api_keys: None,
         }
     }
 
@@ -182,9 +233,51 @@ impl ServiceRegistry {
             validation,
             integrity,
             versioning,
+            content: None,
+            uploads: None,
+            watch: None,
+            lockfiles: None,
+            users: None,
+            api_keys: None,
         }
     }
 
+    /// Attach a content service
+    pub fn with_content_service(mut self, content: Arc<dyn ContentService>) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Attach an upload session service
+    pub fn with_upload_session_service(mut self, uploads: Arc<dyn UploadSessionService>) -> Self {
+        self.uploads = Some(uploads);
+        self
+    }
+
+    /// Attach a watch service
+    pub fn with_watch_service(mut self, watch: Arc<dyn WatchService>) -> Self {
+        self.watch = Some(watch);
+        self
+    }
+
+    /// Attach a lockfile service
+    pub fn with_lockfile_service(mut self, lockfiles: Arc<dyn LockfileService>) -> Self {
+        self.lockfiles = Some(lockfiles);
+        self
+    }
+
+    /// Attach a user service
+    pub fn with_user_service(mut self, users: Arc<dyn UserService>) -> Self {
+        self.users = Some(users);
+        self
+    }
+
+    /// Attach an API key service
+    pub fn with_api_key_service(mut self, api_keys: Arc<dyn ApiKeyService>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
     /// Get the registration service
     pub fn registration(&self) -> &Arc<dyn RegistrationService> {
         &self.registration
@@ -209,17 +302,54 @@ impl ServiceRegistry {
     pub fn versioning(&self) -> &Arc<dyn VersioningService> {
         &self.versioning
     }
+
+    /// Get the content service, if a blob store is configured
+    pub fn content(&self) -> Option<&Arc<dyn ContentService>> {
+        self.content.as_ref()
+    }
+
+    /// Get the upload session service, if chunked uploads are configured
+    pub fn uploads(&self) -> Option<&Arc<dyn UploadSessionService>> {
+        self.uploads.as_ref()
+    }
+
+    /// Get the watch service
+    pub fn watch(&self) -> Option<&Arc<dyn WatchService>> {
+        self.watch.as_ref()
+    }
+
+    /// Get the lockfile service
+    pub fn lockfiles(&self) -> Option<&Arc<dyn LockfileService>> {
+        self.lockfiles.as_ref()
+    }
+
+    /// Get the user service, if a user store is configured
+    pub fn users(&self) -> Option<&Arc<dyn UserService>> {
+        self.users.as_ref()
+    }
+
+    /// Get the API key service, if an API key store is configured
+    pub fn api_keys(&self) -> Option<&Arc<dyn ApiKeyService>> {
+        self.api_keys.as_ref()
+    }
 }
 
 /// Builder for ServiceRegistry with custom configuration
 pub struct ServiceRegistryBuilder {
     repository: Option<Arc<dyn AssetRepository>>,
     event_store: Option<Arc<dyn EventStore>>,
+    blob_store: Option<Arc<dyn BlobStore>>,
+    upload_sessions: Option<Arc<dyn UploadSessionStore>>,
+    upload_session_ttl: Option<chrono::Duration>,
+    user_store: Option<Arc<dyn UserStore>>,
+    api_key_store: Option<Arc<dyn ApiKeyStore>>,
     validation: Option<Arc<dyn ValidationService>>,
     integrity: Option<Arc<dyn IntegrityService>>,
     versioning: Option<Arc<dyn VersioningService>>,
     search: Option<Arc<dyn SearchService>>,
     registration: Option<Arc<dyn RegistrationService>>,
+    watch: Option<Arc<dyn WatchService>>,
+    lockfiles: Option<Arc<dyn LockfileService>>,
 }
 
 impl ServiceRegistryBuilder {
@@ -228,11 +358,18 @@ impl ServiceRegistryBuilder {
         Self {
             repository: None,
             event_store: None,
+            blob_store: None,
+            upload_sessions: None,
+            upload_session_ttl: None,
+            user_store: None,
+            api_key_store: None,
             validation: None,
             integrity: None,
             versioning: None,
             search: None,
             registration: None,
+            watch: None,
+            lockfiles: None,
         }
     }
 
@@ -248,6 +385,40 @@ impl ServiceRegistryBuilder {
         self
     }
 
+    /// Set the blob store used to store asset content
+    ///
+    /// This enables the content service and content uploads during registration.
+    pub fn blob_store(mut self, blob_store: Arc<dyn BlobStore>) -> Self {
+        self.blob_store = Some(blob_store);
+        self
+    }
+
+    /// Set the store for upload sessions, enabling resumable chunked uploads
+    ///
+    /// Chunked uploads also require a blob store.
+    pub fn upload_session_store(mut self, store: Arc<dyn UploadSessionStore>) -> Self {
+        self.upload_sessions = Some(store);
+        self
+    }
+
+    /// Set how long upload sessions stay open before they expire
+    pub fn upload_session_ttl(mut self, ttl: chrono::Duration) -> Self {
+        self.upload_session_ttl = Some(ttl);
+        self
+    }
+
+    /// Set the store for user accounts, enabling password login
+    pub fn user_store(mut self, store: Arc<dyn UserStore>) -> Self {
+        self.user_store = Some(store);
+        self
+    }
+
+    /// Set the store for API keys, enabling API key authentication
+    pub fn api_key_store(mut self, store: Arc<dyn ApiKeyStore>) -> Self {
+        self.api_key_store = Some(store);
+        self
+    }
+
     /// Set a custom validation service
     pub fn validation_service(mut self, service: Arc<dyn ValidationService>) -> Self {
         self.validation = Some(service);
@@ -278,6 +449,18 @@ impl ServiceRegistryBuilder {
         self
     }
 
+    /// Set a custom watch service
+    pub fn watch_service(mut self, service: Arc<dyn WatchService>) -> Self {
+        self.watch = Some(service);
+        self
+    }
+
+    /// Set a custom lockfile service
+    pub fn lockfile_service(mut self, service: Arc<dyn LockfileService>) -> Self {
+        self.lockfiles = Some(service);
+        self
+    }
+
     /// Build the service registry
     ///
     /// This will create default implementations for any services not explicitly set.
@@ -316,14 +499,61 @@ impl ServiceRegistryBuilder {
             .search
             .unwrap_or_else(|| Arc::new(DefaultSearchService::new(repository.clone())));
 
+        let watch = self.watch.unwrap_or_else(|| {
+            Arc::new(DefaultWatchService::new(
+                repository.clone(),
+                event_store.clone(),
+            ))
+        });
+
+        let lockfiles = self
+            .lockfiles
+            .unwrap_or_else(|| Arc::new(DefaultLockfileService::new(repository.clone())));
+
+        let blob_store = self.blob_store;
+        let content = blob_store.clone().map(|store| {
+            Arc::new(DefaultContentService::new(
+                repository.clone(),
+                event_store.clone(),
+                store,
+            )) as Arc<dyn ContentService>
+        });
+
+        let uploads = match (&blob_store, &content, self.upload_sessions) {
+            (Some(store), Some(content), Some(sessions)) => {
+                let mut service = DefaultUploadSessionService::new(
+                    repository.clone(),
+                    sessions,
+                    store.clone(),
+                    content.clone(),
+                );
+                if let Some(ttl) = self.upload_session_ttl {
+                    service = service.with_session_ttl(ttl);
+                }
+                Some(Arc::new(service) as Arc<dyn UploadSessionService>)
+            }
+            _ => None,
+        };
+
+        let users = self
+            .user_store
+            .map(|store| Arc::new(DefaultUserService::new(store)) as Arc<dyn UserService>);
+
+        let api_keys = self
+            .api_key_store
+            .map(|store| Arc::new(DefaultApiKeyService::new(store)) as Arc<dyn ApiKeyService>);
+
         let registration = self.registration.unwrap_or_else(|| {
-            Arc::new(DefaultRegistrationService::new(
+            let service = DefaultRegistrationService::new(
                 repository.clone(),
                 event_store.clone(),
                 validation.clone(),
-                integrity.clone(),
                 versioning.clone(),
-            ))
+            );
+            match blob_store {
+                Some(store) => Arc::new(service.with_blob_store(store)),
+                None => Arc::new(service),
+            }
         });
 
         Ok(ServiceRegistry {
@@ -332,6 +562,12 @@ impl ServiceRegistryBuilder {
             validation,
             integrity,
             versioning,
+            content,
+            uploads,
+            watch: Some(watch),
+            lockfiles: Some(lockfiles),
+            users,
+            api_keys,
         })
     }
 }
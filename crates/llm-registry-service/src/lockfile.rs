@@ -0,0 +1,380 @@
+//! Lockfile service
+//!
+//! A lockfile pins the complete dependency closure of an asset, typically a
+//! pipeline, so a deployment loads exactly the models, datasets and policies
+//! it was tested with. Every locked asset is listed once with its version
+//! and checksum, dependencies before the assets that need them. Assets at
+//! the same distance from the leaves of the graph are ordered by name and
+//! version, so the same graph always yields the same lockfile.
+//!
+//! Verification checks a lockfile against the registry and reports the
+//! locked assets that were deleted, deprecated or re-checksummed since.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use llm_registry_core::{AssetId, AssetReference, AssetStatus, DependencyGraph};
+use llm_registry_db::{AssetRepository, DependencyDirection};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+use crate::dto::{DriftKind, LockedAsset, Lockfile, LockfileDrift, LockfileVerification};
+use crate::error::{ServiceError, ServiceResult};
+
+/// Format version written to new lockfiles
+///
+/// Lockfiles with a higher version are rejected rather than misread.
+pub const LOCKFILE_FORMAT_VERSION: u32 = 1;
+
+/// Trait for lockfile operations
+#[async_trait]
+pub trait LockfileService: Send + Sync {
+    /// Generate a lockfile for an asset and its whole dependency closure
+    async fn generate_lockfile(&self, asset_id: &AssetId) -> ServiceResult<Lockfile>;
+
+    /// Compare a lockfile with the registry and report drift
+    async fn verify_lockfile(&self, lockfile: &Lockfile) -> ServiceResult<LockfileVerification>;
+}
+
+/// Default implementation of LockfileService
+pub struct DefaultLockfileService {
+    repository: Arc<dyn AssetRepository>,
+}
+
+impl DefaultLockfileService {
+    /// Create a new lockfile service
+    pub fn new(repository: Arc<dyn AssetRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl LockfileService for DefaultLockfileService {
+    #[instrument(skip(self), fields(asset_id = %asset_id))]
+    async fn generate_lockfile(&self, asset_id: &AssetId) -> ServiceResult<Lockfile> {
+        debug!("Generating lockfile");
+
+        if self.repository.find_by_id(asset_id).await?.is_none() {
+            return Err(ServiceError::NotFound(asset_id.to_string()));
+        }
+
+        let closure = self
+            .repository
+            .dependency_closure(asset_id, DependencyDirection::Dependencies, None, &[])
+            .await?;
+        if closure.truncated {
+            return Err(ServiceError::ValidationFailed(format!(
+                "Dependency graph of {} is too deep to be locked",
+                asset_id
+            )));
+        }
+
+        let mut graph = DependencyGraph::new();
+        graph.add_dependencies(*asset_id, Vec::new())?;
+        for edge in &closure.edges {
+            graph.add_dependency(edge.asset_id, AssetReference::by_id(edge.dependency_id))?;
+        }
+        let order = graph.topological_sort()?;
+
+        let mut ids: Vec<AssetId> = graph.get_all_dependencies(asset_id).into_iter().collect();
+        ids.push(*asset_id);
+        let mut assets: HashMap<AssetId, _> = self
+            .repository
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|asset| (asset.id, asset))
+            .collect();
+
+        // Layer 0 holds the assets without dependencies; every other asset
+        // sits one layer above its highest dependency.
+        let mut layers: HashMap<AssetId, usize> = HashMap::new();
+        for id in &order {
+            let layer = direct_dependencies(&graph, id)
+                .iter()
+                .filter_map(|dep| layers.get(dep))
+                .map(|layer| layer + 1)
+                .max()
+                .unwrap_or(0);
+            layers.insert(*id, layer);
+        }
+
+        let mut locked = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let asset = assets
+                .remove(id)
+                .ok_or_else(|| ServiceError::DependencyNotFound(id.to_string()))?;
+            locked.push((
+                layers.get(id).copied().unwrap_or(0),
+                LockedAsset {
+                    id: asset.id,
+                    name: asset.metadata.name,
+                    version: asset.metadata.version,
+                    asset_type: asset.asset_type,
+                    checksum: asset.checksum,
+                    dependencies: direct_dependencies(&graph, id),
+                },
+            ));
+        }
+        locked.sort_by(|(a_layer, a), (b_layer, b)| {
+            a_layer
+                .cmp(b_layer)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.version.cmp(&b.version))
+                .then_with(|| a.id.to_string().cmp(&b.id.to_string()))
+        });
+
+        Ok(Lockfile {
+            version: LOCKFILE_FORMAT_VERSION,
+            root: *asset_id,
+            generated_at: Utc::now(),
+            assets: locked.into_iter().map(|(_, asset)| asset).collect(),
+        })
+    }
+
+    #[instrument(skip(self, lockfile), fields(root = %lockfile.root, asset_count = lockfile.assets.len()))]
+    async fn verify_lockfile(&self, lockfile: &Lockfile) -> ServiceResult<LockfileVerification> {
+        debug!("Verifying lockfile");
+
+        if lockfile.version == 0 || lockfile.version > LOCKFILE_FORMAT_VERSION {
+            return Err(ServiceError::InvalidInput(format!(
+                "Unsupported lockfile version {}",
+                lockfile.version
+            )));
+        }
+
+        let ids: Vec<AssetId> = lockfile.assets.iter().map(|asset| asset.id).collect();
+        let current: HashMap<AssetId, _> = self
+            .repository
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|asset| (asset.id, asset))
+            .collect();
+
+        let mut drift = Vec::new();
+        for locked in &lockfile.assets {
+            let mut report = |kind| {
+                drift.push(LockfileDrift {
+                    asset_id: locked.id,
+                    name: locked.name.clone(),
+                    version: locked.version.clone(),
+                    kind,
+                })
+            };
+
+            let Some(asset) = current.get(&locked.id) else {
+                report(DriftKind::Deleted);
+                continue;
+            };
+            match asset.status {
+                AssetStatus::Active => {}
+                AssetStatus::Deprecated | AssetStatus::Archived => {
+                    report(DriftKind::Deprecated {
+                        status: asset.status,
+                    });
+                }
+                AssetStatus::NonCompliant => report(DriftKind::NonCompliant),
+            }
+            if asset.checksum != locked.checksum {
+                report(DriftKind::ChecksumChanged {
+                    locked: locked.checksum.clone(),
+                    current: asset.checksum.clone(),
+                });
+            }
+        }
+
+        Ok(LockfileVerification {
+            root: lockfile.root,
+            valid: drift.is_empty(),
+            drift,
+        })
+    }
+}
+
+impl Lockfile {
+    /// Encode the lockfile as TOML
+    ///
+    /// Locked assets become an array of `[[assets]]` tables.
+    pub fn to_toml(&self) -> ServiceResult<String> {
+        toml::to_string(self)
+            .map_err(|e| ServiceError::Internal(format!("Failed to encode lockfile: {}", e)))
+    }
+
+    /// Decode a lockfile from TOML
+    pub fn from_toml(input: &str) -> ServiceResult<Self> {
+        toml::from_str(input)
+            .map_err(|e| ServiceError::InvalidInput(format!("Invalid lockfile: {}", e)))
+    }
+}
+
+/// IDs an asset depends on directly, in a stable order
+fn direct_dependencies(graph: &DependencyGraph, id: &AssetId) -> Vec<AssetId> {
+    let mut deps: Vec<AssetId> = graph
+        .get_dependencies(id)
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| dep.as_id().copied())
+        .collect();
+    deps.sort_by_key(|dep| dep.to_string());
+    deps.dedup();
+    deps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_registry_core::{
+        Asset, AssetMetadata, AssetType, Checksum, HashAlgorithm, StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::InMemoryAssetRepository;
+    use semver::Version;
+
+    async fn register(
+        repository: &InMemoryAssetRepository,
+        name: &str,
+        asset_type: AssetType,
+        dependencies: &[AssetId],
+    ) -> AssetId {
+        let metadata = AssetMetadata::new(name, Version::new(1, 0, 0));
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}/1.0.0.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        let mut asset = Asset::new(AssetId::new(), asset_type, metadata, storage, checksum).unwrap();
+        for id in dependencies {
+            asset.add_dependency(AssetReference::by_id(*id)).unwrap();
+        }
+        repository.create(asset).await.unwrap().id
+    }
+
+    /// A pipeline using a model trained on a dataset, and a policy
+    async fn register_pipeline(repository: &InMemoryAssetRepository) -> (AssetId, AssetId, AssetId, AssetId) {
+        let dataset = register(repository, "dataset", AssetType::Dataset, &[]).await;
+        let policy = register(repository, "policy", AssetType::Policy, &[]).await;
+        let model = register(repository, "model", AssetType::Model, &[dataset]).await;
+        let pipeline =
+            register(repository, "pipeline", AssetType::Pipeline, &[model, policy, dataset]).await;
+        (pipeline, model, dataset, policy)
+    }
+
+    #[tokio::test]
+    async fn test_generate_lockfile() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let (pipeline, model, dataset, policy) = register_pipeline(&repository).await;
+        let service = DefaultLockfileService::new(repository);
+
+        let lockfile = service.generate_lockfile(&pipeline).await.unwrap();
+        assert_eq!(lockfile.version, LOCKFILE_FORMAT_VERSION);
+        assert_eq!(lockfile.root, pipeline);
+        let order: Vec<(AssetId, &str)> = lockfile
+            .assets
+            .iter()
+            .map(|asset| (asset.id, asset.name.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (dataset, "dataset"),
+                (policy, "policy"),
+                (model, "model"),
+                (pipeline, "pipeline"),
+            ]
+        );
+        assert_eq!(lockfile.assets[2].dependencies, vec![dataset]);
+        assert_eq!(lockfile.assets[3].dependencies.len(), 3);
+
+        let again = service.generate_lockfile(&pipeline).await.unwrap();
+        assert_eq!(again.assets, lockfile.assets);
+
+        assert!(matches!(
+            service.generate_lockfile(&AssetId::new()).await,
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lockfile_toml_round_trip() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let (pipeline, ..) = register_pipeline(&repository).await;
+        let service = DefaultLockfileService::new(repository);
+
+        let lockfile = service.generate_lockfile(&pipeline).await.unwrap();
+        let encoded = lockfile.to_toml().unwrap();
+        assert!(encoded.contains("[[assets]]"));
+        assert_eq!(Lockfile::from_toml(&encoded).unwrap(), lockfile);
+
+        let json = serde_json::to_string(&lockfile).unwrap();
+        assert_eq!(serde_json::from_str::<Lockfile>(&json).unwrap(), lockfile);
+
+        assert!(matches!(
+            Lockfile::from_toml("version = 1"),
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_lockfile_reports_drift() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let (pipeline, model, dataset, policy) = register_pipeline(&repository).await;
+        let service = DefaultLockfileService::new(repository.clone());
+
+        let lockfile = service.generate_lockfile(&pipeline).await.unwrap();
+        let verification = service.verify_lockfile(&lockfile).await.unwrap();
+        assert!(verification.valid);
+        assert!(verification.drift.is_empty());
+
+        let mut asset = repository.find_by_id(&model).await.unwrap().unwrap();
+        asset.checksum = Checksum::new(HashAlgorithm::SHA256, "b".repeat(64)).unwrap();
+        repository.update(asset, None).await.unwrap();
+
+        let mut asset = repository.find_by_id(&dataset).await.unwrap().unwrap();
+        asset.status = AssetStatus::Deprecated;
+        repository.update(asset, None).await.unwrap();
+
+        repository.delete(&policy, None, &[]).await.unwrap();
+
+        let verification = service.verify_lockfile(&lockfile).await.unwrap();
+        assert!(!verification.valid);
+        let drift: Vec<(AssetId, &DriftKind)> = verification
+            .drift
+            .iter()
+            .map(|drift| (drift.asset_id, &drift.kind))
+            .collect();
+        assert_eq!(drift.len(), 3);
+        assert_eq!(
+            drift[0],
+            (
+                dataset,
+                &DriftKind::Deprecated {
+                    status: AssetStatus::Deprecated
+                }
+            )
+        );
+        assert_eq!(drift[1], (policy, &DriftKind::Deleted));
+        assert!(matches!(
+            drift[2],
+            (id, DriftKind::ChecksumChanged { current, .. })
+                if id == model && current.value == "b".repeat(64)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_lockfile_rejects_unknown_version() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let (pipeline, ..) = register_pipeline(&repository).await;
+        let service = DefaultLockfileService::new(repository);
+
+        let mut lockfile = service.generate_lockfile(&pipeline).await.unwrap();
+        lockfile.version = LOCKFILE_FORMAT_VERSION + 1;
+        assert!(matches!(
+            service.verify_lockfile(&lockfile).await,
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+}
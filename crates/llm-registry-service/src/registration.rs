@@ -5,32 +5,85 @@
 
 use async_trait::async_trait;
 use llm_registry_core::{
-    Asset, AssetId, AssetMetadata, DependencyGraph, EventType, RegistryEvent,
+    Asset, AssetId, AssetMetadata, AssetReference, AssetStatus, DependencyGraph, DependencyKind,
+    EventType, RegistryEvent, StorageLocation,
+};
+use llm_registry_db::{
+    AssetRepository, BlobStore, ClosureNode, DbError, DependencyDirection, EventStore,
+    SearchQuery,
 };
-use llm_registry_db::{AssetRepository, EventStore};
 use std::sync::Arc;
 use tracing::{debug, info, instrument, warn};
 
+use crate::batch::plan_batch;
+use crate::content::{write_verified, ContentStream, VerifiedWrite};
 use crate::dto::{
+    BatchItemResult, BatchRegisterRequest, BatchRegisterResponse, DeleteAssetResponse, DeleteMode,
     RegisterAssetRequest, RegisterAssetResponse, UpdateAssetRequest, UpdateAssetResponse,
     ValidateAssetRequest, ValidationResult,
 };
 use crate::error::{ServiceError, ServiceResult};
-use crate::integrity::IntegrityService;
+use crate::resolver::DependencyResolver;
 use crate::validation::ValidationService;
 use crate::versioning::VersioningService;
 
+/// Maximum number of assets accepted in one batch registration
+pub const MAX_BATCH_SIZE: usize = 1000;
+
 /// Trait for asset registration operations
 #[async_trait]
 pub trait RegistrationService: Send + Sync {
     /// Register a new asset with full validation
     async fn register_asset(&self, request: RegisterAssetRequest) -> ServiceResult<RegisterAssetResponse>;
 
+    /// Register a new asset and stream its content into the blob store
+    ///
+    /// The recorded `size_bytes` and `Checksum` are computed from `content`
+    /// as it streams rather than taken from the request. The declared
+    /// checksum must match, or nothing is stored.
+    async fn register_asset_with_content(
+        &self,
+        request: RegisterAssetRequest,
+        content: ContentStream,
+    ) -> ServiceResult<RegisterAssetResponse>;
+
+    /// Register several assets at once
+    ///
+    /// Items are registered after the batch items they depend on by name and
+    /// version, and each item gets its own result. In atomic mode the first
+    /// failure stops the batch and every registration made so far is undone.
+    async fn register_batch(&self, request: BatchRegisterRequest) -> ServiceResult<BatchRegisterResponse>;
+
     /// Update an existing asset
+    ///
+    /// Fails with `PreconditionFailed` if `expected_revision` is set and no
+    /// longer matches, and with `RevisionConflict` if another writer changed
+    /// the asset while the update was being applied.
     async fn update_asset(&self, request: UpdateAssetRequest) -> ServiceResult<UpdateAssetResponse>;
 
-    /// Delete an asset
-    async fn delete_asset(&self, asset_id: &AssetId) -> ServiceResult<()>;
+    /// Delete an asset, optionally only if it is still at `expected_revision`
+    ///
+    /// Assets that still need it make the delete fail with `HasDependents`,
+    /// unless `mode` says to mark them non-compliant or to deprecate them
+    /// and everything that needs them in turn. Dependents that cannot be
+    /// marked do not undo the delete; they are reported in the response.
+    async fn delete_asset(
+        &self,
+        asset_id: &AssetId,
+        expected_revision: Option<u64>,
+        mode: DeleteMode,
+    ) -> ServiceResult<DeleteAssetResponse>;
+
+    /// Assets that a delete in `mode` would be refused for or would change,
+    /// nearest first
+    ///
+    /// These are the direct dependents through dependencies that block
+    /// deletion, or all transitive ones for a cascade.
+    async fn blocking_dependents(
+        &self,
+        asset_id: &AssetId,
+        mode: DeleteMode,
+    ) -> ServiceResult<Vec<ClosureNode>>;
 
     /// Validate dependencies before registration
     async fn validate_dependencies(&self, dependencies: &[llm_registry_core::AssetReference]) -> ServiceResult<ValidationResult>;
@@ -44,8 +97,8 @@ pub struct DefaultRegistrationService {
     repository: Arc<dyn AssetRepository>,
     event_store: Arc<dyn EventStore>,
     validation_service: Arc<dyn ValidationService>,
-    integrity_service: Arc<dyn IntegrityService>,
     versioning_service: Arc<dyn VersioningService>,
+    blob_store: Option<Arc<dyn BlobStore>>,
 }
 
 impl DefaultRegistrationService {
@@ -54,18 +107,23 @@ impl DefaultRegistrationService {
         repository: Arc<dyn AssetRepository>,
         event_store: Arc<dyn EventStore>,
         validation_service: Arc<dyn ValidationService>,
-        integrity_service: Arc<dyn IntegrityService>,
         versioning_service: Arc<dyn VersioningService>,
     ) -> Self {
         Self {
             repository,
             event_store,
             validation_service,
-            integrity_service,
             versioning_service,
+            blob_store: None,
         }
     }
 
+    /// Set the blob store used to persist asset content
+    pub fn with_blob_store(mut self, blob_store: Arc<dyn BlobStore>) -> Self {
+        self.blob_store = Some(blob_store);
+        self
+    }
+
     /// Build asset metadata from request
     fn build_metadata(&self, request: &RegisterAssetRequest) -> ServiceResult<AssetMetadata> {
         let mut builder = AssetMetadata::builder(request.name.clone(), request.version.clone());
@@ -134,6 +192,35 @@ impl DefaultRegistrationService {
         }
     }
 
+    /// Move a dependent of a deleted asset to `status`
+    ///
+    /// Archived assets and those already at `status` are left alone.
+    /// Returns whether the dependent was changed.
+    async fn mark_dependent(&self, id: &AssetId, status: AssetStatus) -> ServiceResult<bool> {
+        let Some(mut dependent) = self.repository.find_by_id(id).await? else {
+            return Ok(false);
+        };
+        let old_status = dependent.status;
+        if old_status == status || old_status == AssetStatus::Archived {
+            return Ok(false);
+        }
+
+        dependent.set_status(status);
+        let revision = dependent.revision;
+        let updated = self.repository.update(dependent, Some(revision)).await?;
+
+        let event = RegistryEvent::new(EventType::AssetStatusChanged {
+            asset_id: updated.id,
+            asset_name: updated.metadata.name.clone(),
+            old_status,
+            new_status: status,
+        });
+        if let Err(e) = self.event_store.append(event).await {
+            warn!("Failed to emit status change event: {}", e);
+        }
+        Ok(true)
+    }
+
     /// Validate asset before registration
     async fn validate_for_registration(&self, asset: &Asset) -> ServiceResult<Vec<String>> {
         let mut warnings = Vec::new();
@@ -172,6 +259,26 @@ impl DefaultRegistrationService {
         }
         Ok(())
     }
+
+    /// Check that no registered asset points at the storage path
+    async fn check_storage_path(&self, path: &str) -> ServiceResult<()> {
+        let sharing = self
+            .repository
+            .search(
+                &SearchQuery::new()
+                    .storage_path(path.to_string())
+                    .exclude_deprecated(false)
+                    .limit(1),
+            )
+            .await?;
+        if !sharing.assets.is_empty() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Storage path already in use: {}",
+                path
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -182,6 +289,7 @@ impl RegistrationService for DefaultRegistrationService {
 
         // Check for duplicate
         self.check_duplicate(&request.name, &request.version).await?;
+        self.check_storage_path(&request.storage.path).await?;
 
         // Build metadata
         let metadata = self.build_metadata(&request)?;
@@ -203,7 +311,19 @@ impl RegistrationService for DefaultRegistrationService {
             asset_builder = asset_builder.provenance(prov);
         }
 
-        asset_builder = asset_builder.dependencies(request.dependencies.clone());
+        // Resolve dependencies given by name to registered assets
+        let mut dependencies = request.dependencies.clone();
+        let mut resolved_dependencies = Vec::new();
+        if dependencies.iter().any(|dep| dep.as_id().is_none()) {
+            let resolution =
+                DependencyResolver::new(self.repository.as_ref(), self.versioning_service.as_ref())
+                    .resolve(&request.name, &dependencies)
+                    .await?;
+            dependencies = resolution.references;
+            resolved_dependencies = resolution.resolved;
+        }
+
+        asset_builder = asset_builder.dependencies(dependencies);
 
         let asset = asset_builder.build().map_err(|e| {
             ServiceError::ValidationFailed(format!("Failed to build asset: {}", e))
@@ -221,6 +341,27 @@ impl RegistrationService for DefaultRegistrationService {
         // Persist the asset
         let created = self.repository.create(asset).await?;
 
+        // Record the requirement each resolved dependency was declared with
+        for resolved in &resolved_dependencies {
+            let requirement = resolved.requirement.to_string();
+            let kind = created
+                .dependencies
+                .iter()
+                .find(|dep| dep.as_id() == Some(&resolved.asset_id))
+                .map(AssetReference::kind)
+                .unwrap_or_default();
+            if let Err(e) = self
+                .repository
+                .add_dependency(&created.id, &resolved.asset_id, Some(&requirement), kind)
+                .await
+            {
+                warn!(
+                    "Failed to record version requirement of {}: {}",
+                    resolved.name, e
+                );
+            }
+        }
+
         // Emit dependencies added events
         for dep in &created.dependencies {
             if let Some(dep_id) = dep.as_id() {
@@ -252,9 +393,207 @@ impl RegistrationService for DefaultRegistrationService {
         Ok(RegisterAssetResponse {
             asset: created,
             warnings,
+            resolved_dependencies,
         })
     }
 
+    #[instrument(skip(self, request, content), fields(name = %request.name, version = %request.version))]
+    async fn register_asset_with_content(
+        &self,
+        mut request: RegisterAssetRequest,
+        content: ContentStream,
+    ) -> ServiceResult<RegisterAssetResponse> {
+        let blob_store = self.blob_store.as_ref().ok_or_else(|| {
+            ServiceError::Internal("No blob store configured for asset content".to_string())
+        })?;
+
+        // Fail fast before writing any bytes
+        self.check_duplicate(&request.name, &request.version).await?;
+
+        let key = request.storage.path.clone();
+        self.check_storage_path(&key).await?;
+        if blob_store.head(&key).await?.is_some() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Storage path already in use: {}",
+                key
+            )));
+        }
+
+        // Stream the payload, hashing it with the declared algorithm
+        let written = write_verified(
+            blob_store.as_ref(),
+            &key,
+            content,
+            request.checksum.algorithm(),
+            Some(&request.checksum),
+        )
+        .await?;
+        let (actual, size_bytes) = match written {
+            VerifiedWrite::Stored {
+                checksum,
+                size_bytes,
+            } => (checksum, size_bytes),
+            VerifiedWrite::Mismatch { actual } => {
+                return Err(ServiceError::ChecksumVerificationFailed(format!(
+                    "expected {}, computed {}",
+                    request.checksum, actual
+                )));
+            }
+        };
+
+        let mut warnings = Vec::new();
+        if let Some(declared) = request.size_bytes {
+            if declared != size_bytes {
+                warnings.push(format!(
+                    "size_bytes: declared {} bytes but content is {} bytes",
+                    declared, size_bytes
+                ));
+            }
+        }
+
+        // Record where and what was actually written
+        request.size_bytes = Some(size_bytes);
+        request.checksum = actual;
+        request.storage = StorageLocation::new(blob_store.backend().clone(), key.clone(), None)
+            .map_err(|e| ServiceError::Internal(format!("Invalid storage location: {}", e)))?;
+
+        match self.register_asset(request).await {
+            Ok(mut response) => {
+                response.warnings.extend(warnings);
+                Ok(response)
+            }
+            Err(e) => {
+                // Do not leave orphaned content behind
+                if let Err(cleanup) = blob_store.delete(&key).await {
+                    warn!("Failed to remove content for rejected asset {}: {}", key, cleanup);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    #[instrument(skip(self, request), fields(items = request.items.len(), atomic = request.atomic))]
+    async fn register_batch(&self, request: BatchRegisterRequest) -> ServiceResult<BatchRegisterResponse> {
+        if request.items.len() > MAX_BATCH_SIZE {
+            return Err(ServiceError::InvalidInput(format!(
+                "Batch of {} items exceeds the limit of {}",
+                request.items.len(),
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        info!("Registering batch of {} assets", request.items.len());
+
+        let plan = plan_batch(&request.items);
+        let mut results: Vec<BatchItemResult> = request
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| BatchItemResult {
+                index,
+                name: item.name.clone(),
+                version: item.version.clone(),
+                asset: None,
+                error: None,
+                warnings: Vec::new(),
+            })
+            .collect();
+        let mut registered: Vec<usize> = Vec::new();
+        let mut failed = plan.rejected.iter().any(Option::is_some);
+
+        for &index in &plan.order {
+            let outcome = match plan.rejected[index].clone() {
+                Some(reason) => Err(reason),
+                None if request.atomic && failed => {
+                    results[index].error =
+                        Some("Not attempted: another item of the atomic batch failed".to_string());
+                    continue;
+                }
+                None => {
+                    let mut item = request.items[index].clone();
+                    let mut unresolved = None;
+                    for link in &plan.links[index] {
+                        match &results[link.target].asset {
+                            // Requirements are left to the resolver, which
+                            // may settle on another version
+                            Some(_) if link.requirement => {}
+                            Some(dependency) => {
+                                let kind = item.dependencies[link.dependency].kind();
+                                item.dependencies[link.dependency] =
+                                    AssetReference::by_id(dependency.id).with_kind(kind);
+                            }
+                            None => {
+                                unresolved = Some(format!(
+                                    "Dependency {}@{} in the same batch was not registered",
+                                    results[link.target].name, results[link.target].version
+                                ));
+                                break;
+                            }
+                        }
+                    }
+                    match unresolved {
+                        Some(reason) => Err(reason),
+                        None => self.register_asset(item).await.map_err(|e| e.to_string()),
+                    }
+                }
+            };
+
+            match outcome {
+                Ok(response) => {
+                    results[index].asset = Some(response.asset);
+                    results[index].warnings = response.warnings;
+                    registered.push(index);
+                }
+                Err(reason) => {
+                    debug!("Batch item {} failed: {}", index, reason);
+                    results[index].error = Some(reason);
+                    failed = true;
+                }
+            }
+        }
+
+        let mut rolled_back = request.atomic && failed;
+        if rolled_back {
+            // Undo in reverse so dependents are removed before their dependencies
+            for &index in registered.iter().rev() {
+                let Some(asset) = results[index].asset.take() else {
+                    continue;
+                };
+                match self.delete_asset(&asset.id, None, DeleteMode::Refuse).await {
+                    Ok(_) => {
+                        results[index].error = Some(
+                            "Rolled back: another item of the atomic batch failed".to_string(),
+                        );
+                    }
+                    Err(e) => {
+                        // The asset is still registered, so report it as such
+                        warn!(
+                            "Failed to roll back batch registration of {}: {}",
+                            asset.id, e
+                        );
+                        results[index].error =
+                            Some(format!("Rollback failed, asset is still registered: {}", e));
+                        results[index].asset = Some(asset);
+                        rolled_back = false;
+                    }
+                }
+            }
+        }
+
+        let response = BatchRegisterResponse {
+            results,
+            rolled_back,
+        };
+
+        info!(
+            "Batch registration finished: {} registered, {} failed",
+            response.total_registered(),
+            response.total_failed()
+        );
+
+        Ok(response)
+    }
+
     #[instrument(skip(self, request), fields(asset_id = %request.asset_id))]
     async fn update_asset(&self, request: UpdateAssetRequest) -> ServiceResult<UpdateAssetResponse> {
         debug!("Updating asset: {}", request.asset_id);
@@ -265,6 +604,8 @@ impl RegistrationService for DefaultRegistrationService {
             .find_by_id(&request.asset_id)
             .await?
             .ok_or_else(|| ServiceError::NotFound(request.asset_id.to_string()))?;
+        check_revision(&asset, request.expected_revision)?;
+        let expected_revision = request.expected_revision.unwrap_or(asset.revision);
 
         let mut updated_fields = Vec::new();
 
@@ -320,8 +661,17 @@ impl RegistrationService for DefaultRegistrationService {
             ServiceError::ValidationFailed(format!("Updated asset is invalid: {}", e))
         })?;
 
-        // Persist the update
-        let updated = self.repository.update(asset).await?;
+        // Persist the update, unless the asset changed since it was read
+        let updated = self
+            .repository
+            .update(asset, Some(expected_revision))
+            .await
+            .map_err(|e| match (ServiceError::from(e), request.expected_revision) {
+                (ServiceError::RevisionConflict { expected, actual }, Some(_)) => {
+                    ServiceError::PreconditionFailed { expected, actual }
+                }
+                (e, _) => e,
+            })?;
 
         // Emit update event
         self.emit_updated_event(&updated, updated_fields.clone()).await;
@@ -332,8 +682,13 @@ impl RegistrationService for DefaultRegistrationService {
         })
     }
 
-    #[instrument(skip(self), fields(asset_id = %asset_id))]
-    async fn delete_asset(&self, asset_id: &AssetId) -> ServiceResult<()> {
+    #[instrument(skip(self), fields(asset_id = %asset_id, mode = ?mode))]
+    async fn delete_asset(
+        &self,
+        asset_id: &AssetId,
+        expected_revision: Option<u64>,
+        mode: DeleteMode,
+    ) -> ServiceResult<DeleteAssetResponse> {
         debug!("Deleting asset: {}", asset_id);
 
         // Fetch the asset first for event emission
@@ -342,25 +697,124 @@ impl RegistrationService for DefaultRegistrationService {
             .find_by_id(asset_id)
             .await?
             .ok_or_else(|| ServiceError::NotFound(asset_id.to_string()))?;
-
-        // Check if any assets depend on this one
-        let dependents = self.repository.list_reverse_dependencies(asset_id).await?;
-        if !dependents.is_empty() {
-            return Err(ServiceError::NotPermitted(format!(
-                "Cannot delete asset: {} other assets depend on it",
-                dependents.len()
-            )));
+        check_revision(&asset, expected_revision)?;
+        let revision = expected_revision.unwrap_or(asset.revision);
+
+        // Check if any assets need this one; links that do not block
+        // deletion, such as evaluations, are dropped with it
+        let describe = || {
+            format!(
+                "{}@{} ({})",
+                asset.metadata.name, asset.metadata.version, asset.id
+            )
+        };
+        let dependents = self.blocking_dependents(asset_id, mode).await?;
+        if mode == DeleteMode::Refuse && !dependents.is_empty() {
+            return Err(ServiceError::HasDependents {
+                asset: describe(),
+                dependents,
+                hidden: 0,
+            });
         }
 
-        // Delete from repository
-        self.repository.delete(asset_id).await?;
+        // Delete from repository, unless the asset changed since it was read.
+        // A refused delete is checked again as it deletes, so a dependency
+        // added since the check above is not left dangling.
+        let blocking = if mode == DeleteMode::Refuse {
+            blocking_kinds()
+        } else {
+            Vec::new()
+        };
+        match self
+            .repository
+            .delete(asset_id, Some(revision), &blocking)
+            .await
+        {
+            Ok(()) => {}
+            Err(DbError::HasDependents { .. }) => {
+                return Err(ServiceError::HasDependents {
+                    asset: describe(),
+                    dependents: self.blocking_dependents(asset_id, mode).await?,
+                    hidden: 0,
+                });
+            }
+            Err(e) => {
+                return Err(match (ServiceError::from(e), expected_revision) {
+                    (ServiceError::RevisionConflict { expected, actual }, Some(_)) => {
+                        ServiceError::PreconditionFailed { expected, actual }
+                    }
+                    (e, _) => e,
+                });
+            }
+        }
 
         // Emit deletion event
         self.emit_deleted_event(&asset).await;
 
-        info!("Asset deleted successfully: {}", asset_id);
+        // Dependents are only changed once the asset is gone, so a refused
+        // delete leaves them as they were. The asset cannot be restored, so
+        // dependents that fail to update are reported rather than failing
+        // the delete.
+        let status = if mode == DeleteMode::Force {
+            AssetStatus::NonCompliant
+        } else {
+            AssetStatus::Deprecated
+        };
+        let mut updated_dependents = Vec::new();
+        let mut failed_dependents = Vec::new();
+        for node in &dependents {
+            match self.mark_dependent(&node.asset_id, status).await {
+                Ok(true) => updated_dependents.push(node.asset_id),
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(
+                        "Failed to mark {} as {} after deleting {}: {}",
+                        node.asset_id, status, asset_id, e
+                    );
+                    failed_dependents.push(node.asset_id);
+                }
+            }
+        }
+
+        info!(
+            "Asset deleted successfully: {} ({} dependents updated, {} failed)",
+            asset_id,
+            updated_dependents.len(),
+            failed_dependents.len()
+        );
 
-        Ok(())
+        Ok(DeleteAssetResponse {
+            asset_id: *asset_id,
+            mode,
+            updated_dependents,
+            failed_dependents,
+        })
+    }
+
+    #[instrument(skip(self), fields(asset_id = %asset_id, mode = ?mode))]
+    async fn blocking_dependents(
+        &self,
+        asset_id: &AssetId,
+        mode: DeleteMode,
+    ) -> ServiceResult<Vec<ClosureNode>> {
+        let transitive = mode == DeleteMode::Cascade;
+        let kinds = blocking_kinds();
+        let closure = self
+            .repository
+            .dependency_closure(
+                asset_id,
+                DependencyDirection::Dependents,
+                (!transitive).then_some(1),
+                &kinds,
+            )
+            .await?;
+        if transitive && closure.truncated {
+            return Err(ServiceError::ValidationFailed(format!(
+                "Dependents of {} are nested too deeply to cascade",
+                asset_id
+            )));
+        }
+        Ok(closure.nodes)
     }
 
     #[instrument(skip(self, dependencies), fields(dep_count = dependencies.len()))]
@@ -466,6 +920,180 @@ impl RegistrationService for DefaultRegistrationService {
     }
 }
 
+/// Dependency kinds that keep an asset from being deleted
+fn blocking_kinds() -> Vec<DependencyKind> {
+    DependencyKind::ALL
+        .into_iter()
+        .filter(DependencyKind::blocks_deletion)
+        .collect()
+}
+
+/// Fail if the caller expects a different revision than the stored one
+fn check_revision(asset: &Asset, expected_revision: Option<u64>) -> ServiceResult<()> {
+    match expected_revision {
+        Some(expected) if expected != asset.revision => Err(ServiceError::PreconditionFailed {
+            expected,
+            actual: asset.revision,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod delete_tests {
+    use super::*;
+    use crate::ServiceRegistry;
+    use llm_registry_core::{AssetType, Checksum, HashAlgorithm, StorageBackend};
+    use llm_registry_db::{InMemoryAssetRepository, InMemoryEventStore};
+
+    fn create_test_asset(name: &str) -> Asset {
+        let metadata = AssetMetadata::new(name, semver::Version::new(1, 0, 0));
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}/weights.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(AssetId::new(), AssetType::Model, metadata, storage, checksum).unwrap()
+    }
+
+    /// `app` is built from `model`, which runs on `base` and is evaluated
+    /// by `suite`
+    async fn setup() -> (
+        Arc<InMemoryAssetRepository>,
+        Arc<InMemoryEventStore>,
+        ServiceRegistry,
+        [AssetId; 4],
+    ) {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let mut ids = Vec::new();
+        for name in ["base", "model", "app", "suite"] {
+            ids.push(repository.create(create_test_asset(name)).await.unwrap().id);
+        }
+        let [base, model, app, suite] = [ids[0], ids[1], ids[2], ids[3]];
+        for (from, to, kind) in [
+            (model, base, DependencyKind::Runtime),
+            (app, model, DependencyKind::Build),
+            (model, suite, DependencyKind::Evaluation),
+        ] {
+            repository.add_dependency(&from, &to, None, kind).await.unwrap();
+        }
+
+        let events = Arc::new(InMemoryEventStore::new());
+        let services = ServiceRegistry::new(repository.clone(), events.clone());
+        (repository, events, services, [base, model, app, suite])
+    }
+
+    async fn status(repository: &InMemoryAssetRepository, id: &AssetId) -> AssetStatus {
+        repository.find_by_id(id).await.unwrap().unwrap().status
+    }
+
+    #[tokio::test]
+    async fn test_delete_refuses_while_needed() {
+        let (repository, _, services, [base, model, _, suite]) = setup().await;
+
+        let err = services
+            .registration()
+            .delete_asset(&base, None, DeleteMode::Refuse)
+            .await
+            .unwrap_err();
+        match err {
+            ServiceError::HasDependents {
+                dependents, hidden, ..
+            } => {
+                let ids: Vec<AssetId> = dependents.iter().map(|node| node.asset_id).collect();
+                assert_eq!(ids, vec![model]);
+                assert_eq!(hidden, 0);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(repository.find_by_id(&base).await.unwrap().is_some());
+
+        // Evaluation links do not keep an asset alive
+        let response = services
+            .registration()
+            .delete_asset(&suite, None, DeleteMode::Refuse)
+            .await
+            .unwrap();
+        assert!(response.updated_dependents.is_empty());
+        assert_eq!(status(&repository, &model).await, AssetStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_delete_drops_optional_and_evaluation_links() {
+        let (repository, _, services, [_, model, app, suite]) = setup().await;
+        repository
+            .add_dependency(&app, &suite, None, DependencyKind::Optional)
+            .await
+            .unwrap();
+
+        // Neither link blocks the delete, and a cascade leaves both ends alone
+        let response = services
+            .registration()
+            .delete_asset(&suite, None, DeleteMode::Cascade)
+            .await
+            .unwrap();
+        assert!(response.updated_dependents.is_empty());
+        assert_eq!(status(&repository, &model).await, AssetStatus::Active);
+        assert_eq!(status(&repository, &app).await, AssetStatus::Active);
+        assert!(repository
+            .list_dependencies(&model)
+            .await
+            .unwrap()
+            .iter()
+            .all(|a| a.id != suite));
+        assert!(repository
+            .list_dependencies(&app)
+            .await
+            .unwrap()
+            .iter()
+            .all(|a| a.id != suite));
+    }
+
+    #[tokio::test]
+    async fn test_delete_force_marks_direct_dependents() {
+        let (repository, _, services, [base, model, app, _]) = setup().await;
+
+        let response = services
+            .registration()
+            .delete_asset(&base, None, DeleteMode::Force)
+            .await
+            .unwrap();
+        assert_eq!(response.updated_dependents, vec![model]);
+        assert!(response.failed_dependents.is_empty());
+        assert!(repository.find_by_id(&base).await.unwrap().is_none());
+        assert_eq!(status(&repository, &model).await, AssetStatus::NonCompliant);
+        assert_eq!(status(&repository, &app).await, AssetStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_delete_cascade_deprecates_dependent_subtree() {
+        let (repository, events, services, [base, model, app, suite]) = setup().await;
+
+        let response = services
+            .registration()
+            .delete_asset(&base, None, DeleteMode::Cascade)
+            .await
+            .unwrap();
+        assert_eq!(response.updated_dependents, vec![model, app]);
+        assert_eq!(status(&repository, &model).await, AssetStatus::Deprecated);
+        assert_eq!(status(&repository, &app).await, AssetStatus::Deprecated);
+        assert_eq!(status(&repository, &suite).await, AssetStatus::Active);
+
+        let events = events.get_asset_events(&app, 10).await.unwrap();
+        assert!(events.iter().any(|event| matches!(
+            event.event_type,
+            EventType::AssetStatusChanged {
+                new_status: AssetStatus::Deprecated,
+                ..
+            }
+        )));
+    }
+}
+
 // TODO: Complete mock implementations for unit tests
 #[cfg(all(test, feature = "incomplete_tests"))]
 mod tests {
@@ -506,7 +1134,6 @@ mod tests {
             repository: Arc::new(MockRepository),
             event_store: Arc::new(MockEventStore),
             validation_service: Arc::new(MockValidationService),
-            integrity_service: Arc::new(MockIntegrityService),
             versioning_service: Arc::new(MockVersioningService),
         };
 
@@ -523,7 +1150,6 @@ mod tests {
     struct MockRepository;
     struct MockEventStore;
     struct MockValidationService;
-    struct MockIntegrityService;
     struct MockVersioningService;
 
     #[async_trait]
@@ -568,13 +1194,6 @@ mod tests {
         }
     }
 
-    #[async_trait]
-    impl IntegrityService for MockIntegrityService {
-        async fn verify_checksum(&self, _asset: &Asset) -> ServiceResult<bool> {
-            Ok(true)
-        }
-    }
-
     #[async_trait]
     impl VersioningService for MockVersioningService {
         async fn validate_version(&self, _name: &str, _version: &Version) -> ServiceResult<()> {
@@ -0,0 +1,586 @@
+//! Dependency resolution
+//!
+//! Dependencies declared by name and version requirement are resolved when
+//! an asset is registered. The resolver picks one version of every asset in
+//! the dependency graph: each requirement must be met, and the registered
+//! dependencies of every chosen asset must agree with the choices made for
+//! the others. Candidates are the active versions matching a requirement,
+//! tried newest first; a choice that conflicts with another is undone and
+//! the next candidate tried. The dependencies of a candidate are only looked
+//! up once it is tried, and a candidate whose dependency graph is too deep
+//! to load fails the resolution.
+//!
+//! Already registered assets depend on fixed IDs, so only the new asset's
+//! own requirements are open. When no combination works, the conflicts met
+//! along the way are reported.
+
+use futures::future::BoxFuture;
+use llm_registry_core::{Asset, AssetId, AssetReference, AssetStatus};
+use llm_registry_db::{AssetRepository, DependencyDirection};
+use semver::{Version, VersionReq};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::dto::ResolvedDependency;
+use crate::error::{ServiceError, ServiceResult};
+use crate::versioning::VersioningService;
+
+/// Number of candidates tried before resolution gives up
+const MAX_RESOLUTION_STEPS: usize = 10_000;
+
+/// Most conflicts listed in a resolution error
+const MAX_REPORTED_CONFLICTS: usize = 10;
+
+/// Dependencies of a new asset, resolved to registered assets
+#[derive(Debug, Clone)]
+pub(crate) struct Resolution {
+    /// The declared dependencies, in order, all referring to IDs
+    pub references: Vec<AssetReference>,
+
+    /// Assets chosen for the dependencies declared by name
+    pub resolved: Vec<ResolvedDependency>,
+}
+
+/// Version of an asset the resolution is bound to, and why
+#[derive(Debug, Clone)]
+struct Pin {
+    name: String,
+    asset_id: AssetId,
+    version: Version,
+    origin: String,
+}
+
+impl fmt::Display for Pin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ({})", self.name, self.version, self.origin)
+    }
+}
+
+/// A dependency declared by name
+#[derive(Debug, Clone)]
+struct Requirement {
+    /// Position in the declared dependencies
+    position: usize,
+    name: String,
+    requirement: VersionReq,
+
+    /// Registered assets that may satisfy the requirement
+    candidates: Vec<Asset>,
+}
+
+/// Resolves dependencies declared by name for a new asset
+pub(crate) struct DependencyResolver<'a> {
+    repository: &'a dyn AssetRepository,
+    versioning: &'a dyn VersioningService,
+}
+
+impl<'a> DependencyResolver<'a> {
+    /// Create a resolver over the registered assets
+    pub fn new(repository: &'a dyn AssetRepository, versioning: &'a dyn VersioningService) -> Self {
+        Self {
+            repository,
+            versioning,
+        }
+    }
+
+    /// Resolve the dependencies of the asset `name`
+    ///
+    /// Fails with `DependencyNotFound` if nothing is registered under a
+    /// required name, and with `VersionConflict` if no consistent set of
+    /// versions exists.
+    pub async fn resolve(
+        &self,
+        name: &str,
+        dependencies: &[AssetReference],
+    ) -> ServiceResult<Resolution> {
+        let mut fixed = Vec::new();
+        let mut requirements = Vec::new();
+
+        for (position, reference) in dependencies.iter().enumerate() {
+            if let Some(id) = reference.as_id() {
+                let asset = self
+                    .repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or_else(|| ServiceError::DependencyNotFound(id.to_string()))?;
+                fixed.extend(self.pins(&asset, "direct dependency".to_string()).await?);
+            } else if let Some((dep_name, requirement)) = reference.requirement() {
+                let candidates = self.candidates(dep_name, &requirement).await?;
+                requirements.push(Requirement {
+                    position,
+                    name: dep_name.to_string(),
+                    requirement,
+                    candidates,
+                });
+            } else {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Invalid dependency reference: {}",
+                    reference
+                )));
+            }
+        }
+
+        let mut solver = Solver::new(self, name, &requirements);
+        let mut chosen = HashMap::new();
+        for pin in fixed {
+            if !solver.bind(&mut chosen, pin) {
+                return Err(solver.error());
+            }
+        }
+
+        // Requirements with the fewest candidates are decided first
+        let mut order: Vec<usize> = (0..requirements.len()).collect();
+        order.sort_by_key(|&index| requirements[index].candidates.len());
+
+        let mut choices = vec![0; requirements.len()];
+        if !solver.search(&order, chosen, &mut choices).await? {
+            return Err(solver.error());
+        }
+
+        let mut references = dependencies.to_vec();
+        let mut resolved = Vec::with_capacity(requirements.len());
+        for (requirement, &choice) in requirements.iter().zip(&choices) {
+            let candidate = &requirement.candidates[choice];
+            references[requirement.position] = AssetReference::by_id(candidate.id)
+                .with_kind(dependencies[requirement.position].kind());
+            resolved.push(ResolvedDependency {
+                name: requirement.name.clone(),
+                requirement: requirement.requirement.clone(),
+                asset_id: candidate.id,
+                version: candidate.metadata.version.clone(),
+            });
+        }
+
+        Ok(Resolution {
+            references,
+            resolved,
+        })
+    }
+
+    /// Active versions matching a requirement, newest first
+    async fn candidates(&self, name: &str, requirement: &VersionReq) -> ServiceResult<Vec<Asset>> {
+        let candidates: Vec<Asset> = self
+            .versioning
+            .find_by_version_req(name, requirement)
+            .await?
+            .into_iter()
+            .filter(|a| a.status == AssetStatus::Active)
+            .collect();
+
+        if candidates.is_empty() {
+            let versions = self.repository.list_versions(name).await?;
+            if versions.is_empty() {
+                return Err(ServiceError::DependencyNotFound(format!(
+                    "{}@{}",
+                    name, requirement
+                )));
+            }
+            let available: Vec<String> = versions
+                .iter()
+                .map(|a| match a.status {
+                    AssetStatus::Active => a.metadata.version.to_string(),
+                    status => format!("{} ({})", a.metadata.version, status),
+                })
+                .collect();
+            return Err(ServiceError::VersionConflict(format!(
+                "No active version of {} matches {}; registered versions: {}",
+                name,
+                requirement,
+                available.join(", ")
+            )));
+        }
+
+        Ok(candidates)
+    }
+
+    /// An asset and everything it depends on, as pins
+    ///
+    /// Fails with `ValidationFailed` if the dependency graph is too deep to
+    /// load in full.
+    async fn pins(&self, asset: &Asset, origin: String) -> ServiceResult<Vec<Pin>> {
+        let closure = self
+            .repository
+            .dependency_closure(&asset.id, DependencyDirection::Dependencies, None, &[])
+            .await?;
+        if closure.truncated {
+            return Err(ServiceError::ValidationFailed(format!(
+                "Dependency graph of {}@{} is too deep to resolve",
+                asset.metadata.name, asset.metadata.version
+            )));
+        }
+
+        let mut labels: HashMap<AssetId, String> = HashMap::from([(
+            asset.id,
+            format!("{}@{}", asset.metadata.name, asset.metadata.version),
+        )]);
+        for node in &closure.nodes {
+            labels.insert(node.asset_id, format!("{}@{}", node.name, node.version));
+        }
+        let mut required_by: HashMap<AssetId, AssetId> = HashMap::new();
+        for edge in &closure.edges {
+            required_by
+                .entry(edge.dependency_id)
+                .or_insert(edge.asset_id);
+        }
+
+        let mut pins = vec![Pin {
+            name: asset.metadata.name.clone(),
+            asset_id: asset.id,
+            version: asset.metadata.version.clone(),
+            origin,
+        }];
+        for node in closure.nodes {
+            let origin = match required_by
+                .get(&node.asset_id)
+                .and_then(|id| labels.get(id))
+            {
+                Some(label) => format!("required by {}", label),
+                None => "required transitively".to_string(),
+            };
+            pins.push(Pin {
+                name: node.name,
+                asset_id: node.asset_id,
+                version: node.version,
+                origin,
+            });
+        }
+        Ok(pins)
+    }
+}
+
+/// Backtracking search over the candidates of each requirement
+struct Solver<'r> {
+    resolver: &'r DependencyResolver<'r>,
+
+    /// Name of the asset being registered; other versions of it may appear
+    /// in the graph without conflicting with it
+    root: &'r str,
+    requirements: &'r [Requirement],
+
+    /// Pins of the candidates tried so far, by requirement and candidate
+    loaded: HashMap<(usize, usize), Vec<Pin>>,
+    steps: usize,
+    conflicts: Vec<String>,
+}
+
+impl<'r> Solver<'r> {
+    fn new(
+        resolver: &'r DependencyResolver<'r>,
+        root: &'r str,
+        requirements: &'r [Requirement],
+    ) -> Self {
+        Self {
+            resolver,
+            root,
+            requirements,
+            loaded: HashMap::new(),
+            steps: 0,
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Decide the requirements in `order`, recording the chosen candidate
+    /// of each in `choices`
+    fn search<'s>(
+        &'s mut self,
+        order: &'s [usize],
+        chosen: HashMap<String, Pin>,
+        choices: &'s mut [usize],
+    ) -> BoxFuture<'s, ServiceResult<bool>> {
+        Box::pin(async move {
+            let Some((&index, rest)) = order.split_first() else {
+                return Ok(true);
+            };
+
+            for choice in 0..self.requirements[index].candidates.len() {
+                self.steps += 1;
+                if self.steps > MAX_RESOLUTION_STEPS {
+                    return Ok(false);
+                }
+
+                let pins = self.pins(index, choice).await?;
+                let mut next = chosen.clone();
+                if pins.into_iter().all(|pin| self.bind(&mut next, pin))
+                    && self.search(rest, next, choices).await?
+                {
+                    choices[index] = choice;
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        })
+    }
+
+    /// Pins of a candidate, looked up the first time it is tried
+    async fn pins(&mut self, index: usize, choice: usize) -> ServiceResult<Vec<Pin>> {
+        if let Some(pins) = self.loaded.get(&(index, choice)) {
+            return Ok(pins.clone());
+        }
+        let requirement = &self.requirements[index];
+        let origin = format!("for {} {}", requirement.name, requirement.requirement);
+        let pins = self
+            .resolver
+            .pins(&requirement.candidates[choice], origin)
+            .await?;
+        self.loaded.insert((index, choice), pins.clone());
+        Ok(pins)
+    }
+
+    /// Add a pin, unless it conflicts with one already chosen or with a
+    /// requirement on the same name
+    fn bind(&mut self, chosen: &mut HashMap<String, Pin>, pin: Pin) -> bool {
+        if pin.name == self.root {
+            return true;
+        }
+
+        if let Some(existing) = chosen.get(&pin.name) {
+            if existing.asset_id == pin.asset_id {
+                return true;
+            }
+            self.conflict(format!("{} conflicts with {}", pin, existing));
+            return false;
+        }
+
+        let requirements = self.requirements;
+        if let Some(unmet) = requirements
+            .iter()
+            .find(|r| r.name == pin.name && !r.requirement.matches(&pin.version))
+        {
+            self.conflict(format!(
+                "{} does not match the requirement {} {}",
+                pin, unmet.name, unmet.requirement
+            ));
+            return false;
+        }
+
+        chosen.insert(pin.name.clone(), pin);
+        true
+    }
+
+    fn conflict(&mut self, message: String) {
+        if !self.conflicts.contains(&message) {
+            self.conflicts.push(message);
+        }
+    }
+
+    /// Error explaining why no resolution was found
+    fn error(&self) -> ServiceError {
+        let mut message = format!("Cannot resolve the dependencies of {}", self.root);
+        if self.steps > MAX_RESOLUTION_STEPS {
+            message.push_str(&format!(
+                ": gave up after trying {} candidates",
+                MAX_RESOLUTION_STEPS
+            ));
+        }
+        if !self.conflicts.is_empty() {
+            let shown = &self.conflicts[..self.conflicts.len().min(MAX_REPORTED_CONFLICTS)];
+            message.push_str(": ");
+            message.push_str(&shown.join("; "));
+            if self.conflicts.len() > shown.len() {
+                message.push_str(&format!(
+                    "; and {} more",
+                    self.conflicts.len() - shown.len()
+                ));
+            }
+        }
+        ServiceError::VersionConflict(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versioning::DefaultVersioningService;
+    use llm_registry_core::{
+        AssetMetadata, AssetType, Checksum, HashAlgorithm, StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::{InMemoryAssetRepository, InMemoryEventStore, MAX_DEPENDENCY_DEPTH};
+    use std::sync::Arc;
+
+    struct Fixture {
+        repository: Arc<InMemoryAssetRepository>,
+        versioning: DefaultVersioningService,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let repository = Arc::new(InMemoryAssetRepository::new());
+            let versioning = DefaultVersioningService::new(
+                repository.clone(),
+                Arc::new(InMemoryEventStore::new()),
+            );
+            Self {
+                repository,
+                versioning,
+            }
+        }
+
+        async fn register(&self, name: &str, version: &str, dependencies: &[AssetId]) -> AssetId {
+            let metadata = AssetMetadata::new(name, Version::parse(version).unwrap());
+            let storage = StorageLocation::new(
+                StorageBackend::FileSystem {
+                    base_path: "/tmp".to_string(),
+                },
+                format!("{}/{}.bin", name, version),
+                None,
+            )
+            .unwrap();
+            let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+            let mut asset = Asset::new(
+                AssetId::new(),
+                AssetType::Model,
+                metadata,
+                storage,
+                checksum,
+            )
+            .unwrap();
+            for id in dependencies {
+                asset.add_dependency(AssetReference::by_id(*id)).unwrap();
+            }
+            self.repository.create(asset).await.unwrap().id
+        }
+
+        async fn resolve(&self, requirements: &[(&str, &str)]) -> ServiceResult<Resolution> {
+            let dependencies: Vec<AssetReference> = requirements
+                .iter()
+                .map(|(name, req)| {
+                    AssetReference::by_requirement(*name, VersionReq::parse(req).unwrap()).unwrap()
+                })
+                .collect();
+            DependencyResolver::new(self.repository.as_ref(), &self.versioning)
+                .resolve("app", &dependencies)
+                .await
+        }
+    }
+
+    fn versions(resolution: &Resolution) -> Vec<String> {
+        resolution
+            .resolved
+            .iter()
+            .map(|r| format!("{}@{}", r.name, r.version))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_picks_consistent_versions() {
+        let fixture = Fixture::new();
+        fixture.register("tokenizer", "1.0.0", &[]).await;
+        let tokenizer_12 = fixture.register("tokenizer", "1.2.0", &[]).await;
+        let tokenizer_14 = fixture.register("tokenizer", "1.4.0", &[]).await;
+        let embeddings_10 = fixture
+            .register("embeddings", "1.0.0", &[tokenizer_12])
+            .await;
+        fixture
+            .register("embeddings", "1.1.0", &[tokenizer_14])
+            .await;
+
+        // The newest embeddings needs a tokenizer outside the range
+        let resolution = fixture
+            .resolve(&[("embeddings", "^1.0"), ("tokenizer", "<1.4")])
+            .await
+            .unwrap();
+        assert_eq!(
+            versions(&resolution),
+            vec!["embeddings@1.0.0", "tokenizer@1.2.0"]
+        );
+        assert_eq!(
+            resolution.references,
+            vec![
+                AssetReference::by_id(embeddings_10),
+                AssetReference::by_id(tokenizer_12),
+            ]
+        );
+
+        let resolution = fixture.resolve(&[("embeddings", "^1.0")]).await.unwrap();
+        assert_eq!(versions(&resolution), vec!["embeddings@1.1.0"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_explains_conflicts() {
+        let fixture = Fixture::new();
+        let tokenizer_12 = fixture.register("tokenizer", "1.2.0", &[]).await;
+        let tokenizer_14 = fixture.register("tokenizer", "1.4.0", &[]).await;
+        fixture
+            .register("embeddings", "1.0.0", &[tokenizer_12])
+            .await;
+        let embeddings_11 = fixture
+            .register("embeddings", "1.1.0", &[tokenizer_14])
+            .await;
+        fixture.register("reranker", "1.0.0", &[tokenizer_14]).await;
+        let mut deprecated = fixture
+            .repository
+            .find_by_id(&embeddings_11)
+            .await
+            .unwrap()
+            .unwrap();
+        deprecated.set_status(AssetStatus::Deprecated);
+        fixture.repository.update(deprecated, None).await.unwrap();
+
+        let error = fixture
+            .resolve(&[("embeddings", "^1.0"), ("tokenizer", "^1.4")])
+            .await
+            .unwrap_err();
+        match error {
+            ServiceError::VersionConflict(message) => assert!(
+                message.contains(
+                    "tokenizer 1.2.0 (required by embeddings@1.0.0) does not match \
+                     the requirement tokenizer ^1.4"
+                ),
+                "{}",
+                message
+            ),
+            other => panic!("unexpected error: {}", other),
+        }
+
+        let error = fixture
+            .resolve(&[("embeddings", "=1.0.0"), ("reranker", "*")])
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains(
+                "tokenizer 1.4.0 (required by reranker@1.0.0) conflicts with \
+                 tokenizer 1.2.0 (required by embeddings@1.0.0)"
+            ),
+            "{}",
+            error
+        );
+
+        let error = fixture
+            .resolve(&[("embeddings", ">=1.1")])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, ServiceError::VersionConflict(m) if m.contains("1.1.0 (deprecated)")),
+            "{}",
+            error
+        );
+        assert!(matches!(
+            fixture.resolve(&[("missing", "*")]).await,
+            Err(ServiceError::DependencyNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_loads_candidates_when_tried() {
+        let fixture = Fixture::new();
+        let mut layer = fixture.register("layer", "0.0.0", &[]).await;
+        for depth in 1..=MAX_DEPENDENCY_DEPTH {
+            layer = fixture
+                .register("layer", &format!("{}.0.0", depth), &[layer])
+                .await;
+        }
+        fixture.register("model", "1.0.0", &[layer]).await;
+        fixture.register("model", "2.0.0", &[]).await;
+
+        // The older version's graph is too deep, but it is never tried
+        let resolution = fixture.resolve(&[("model", "*")]).await.unwrap();
+        assert_eq!(versions(&resolution), vec!["model@2.0.0"]);
+
+        let error = fixture.resolve(&[("model", "<2.0")]).await.unwrap_err();
+        assert!(
+            matches!(&error, ServiceError::ValidationFailed(m) if m.contains("model@1.0.0")),
+            "{}",
+            error
+        );
+    }
+}
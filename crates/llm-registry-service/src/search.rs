@@ -5,8 +5,11 @@
 
 use async_trait::async_trait;
 use llm_registry_core::{Asset, AssetId, AssetType};
-use llm_registry_db::{AssetRepository, SearchQuery, SortField as DbSortField, SortOrder as DbSortOrder};
-use std::collections::{HashMap, HashSet};
+use llm_registry_db::{
+    AssetRepository, Range, SearchQuery, SortField as DbSortField, SortOrder as DbSortOrder,
+    TextQuery, MAX_DEPENDENCY_DEPTH,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, instrument};
 
@@ -63,6 +66,7 @@ impl DefaultSearchService {
             SortField::Name => DbSortField::Name,
             SortField::Version => DbSortField::Version,
             SortField::SizeBytes => DbSortField::SizeBytes,
+            SortField::Relevance => DbSortField::Relevance,
         }
     }
 
@@ -73,63 +77,6 @@ impl DefaultSearchService {
             SortOrder::Descending => DbSortOrder::Descending,
         }
     }
-
-    /// Build dependency graph recursively
-    fn build_dependency_graph_recursive<'a>(
-        &'a self,
-        asset_id: &'a AssetId,
-        max_depth: i32,
-        current_depth: i32,
-        visited: &'a mut HashSet<AssetId>,
-        nodes: &'a mut HashMap<AssetId, DependencyNode>,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ServiceResult<()>> + 'a + Send>> {
-        Box::pin(async move {
-        // Check depth limit
-        if max_depth >= 0 && current_depth >= max_depth {
-            return Ok(());
-        }
-
-        // Avoid cycles
-        if visited.contains(asset_id) {
-            return Ok(());
-        }
-        visited.insert(*asset_id);
-
-        // Get the asset
-        let asset = match self.repository.find_by_id(asset_id).await? {
-            Some(a) => a,
-            None => return Ok(()), // Skip if asset not found
-        };
-
-        // Get dependencies
-        let deps = self.repository.list_dependencies(asset_id).await?;
-        let dep_ids: Vec<AssetId> = deps.iter().map(|d| d.id).collect();
-
-        // Create node
-        let node = DependencyNode {
-            asset_id: *asset_id,
-            name: asset.metadata.name.clone(),
-            version: asset.metadata.version.clone(),
-            depth: current_depth,
-            dependencies: dep_ids.clone(),
-        };
-        nodes.insert(*asset_id, node);
-
-        // Recursively process dependencies
-        for dep in deps {
-            self.build_dependency_graph_recursive(
-                &dep.id,
-                max_depth,
-                current_depth + 1,
-                visited,
-                nodes,
-            )
-            .await?;
-        }
-
-        Ok(())
-        })
-    }
 }
 
 #[async_trait]
@@ -146,7 +93,8 @@ impl SearchService for DefaultSearchService {
             .sort_order(self.convert_sort_order(request.sort_order))
             .exclude_deprecated(request.exclude_deprecated);
 
-        if let Some(text) = request.text {
+        if let Some(text) = request.text.filter(|text| !text.trim().is_empty()) {
+            utils::parse_search_query(&text)?;
             query = query.text(text);
         }
 
@@ -166,16 +114,39 @@ impl SearchService for DefaultSearchService {
             query = query.storage_backend(backend);
         }
 
-        // Execute search
+        if let Some(cursor) = request.cursor {
+            query = query.cursor(cursor);
+        }
+
+        for facet in request.facets {
+            query = query.facet(facet);
+        }
+
+        if let Some(filter) = request.readable_by {
+            query = query.readable_by(filter);
+        }
+
+        for filter in request.annotations {
+            query = query.annotation(filter);
+        }
+
+        query = query
+            .size_bytes(Range::new(request.min_size_bytes, request.max_size_bytes))
+            .created_at(Range::new(request.min_created_at, request.max_created_at))
+            .updated_at(Range::new(request.min_updated_at, request.max_updated_at));
+
+        // Execute search; a next page exists exactly when there is a cursor for it
         let results = self.repository.search(&query).await?;
-        let has_more = results.has_more();
 
         Ok(SearchAssetsResponse {
             assets: results.assets,
             total: results.total,
             offset: results.offset,
             limit: results.limit,
-            has_more,
+            has_more: results.next_cursor.is_some(),
+            next_cursor: results.next_cursor,
+            cursors: results.cursors,
+            facets: results.facets,
         })
     }
 
@@ -205,33 +176,68 @@ impl SearchService for DefaultSearchService {
     async fn get_dependency_graph(&self, request: GetDependencyGraphRequest) -> ServiceResult<DependencyGraphResponse> {
         debug!("Building dependency graph");
 
-        let mut visited = HashSet::new();
-        let mut nodes = HashMap::new();
+        // Negative depths leave the traversal to the repository's limit
+        let max_depth = u32::try_from(request.max_depth).ok();
+        if max_depth.is_some_and(|depth| depth > MAX_DEPENDENCY_DEPTH) {
+            return Err(ServiceError::InvalidInput(format!(
+                "max_depth must be at most {}",
+                MAX_DEPENDENCY_DEPTH
+            )));
+        }
 
-        self.build_dependency_graph_recursive(
-            &request.asset_id,
-            request.max_depth,
-            0,
-            &mut visited,
-            &mut nodes,
-        )
-        .await?;
-
-        // Check if truncated
-        let truncated = if request.max_depth >= 0 {
-            // If max_depth is set, we might have truncated
-            nodes.values().any(|n| n.depth == request.max_depth - 1 && !n.dependencies.is_empty())
-        } else {
-            false
+        let mut response = DependencyGraphResponse {
+            root: request.asset_id,
+            dependencies: Vec::new(),
+            edges: Vec::new(),
+            truncated: false,
+            has_cycles: false,
         };
 
-        let dependencies: Vec<DependencyNode> = nodes.into_values().collect();
+        // An unknown root has an empty graph
+        let Some(root) = self.repository.find_by_id(&request.asset_id).await? else {
+            return Ok(response);
+        };
 
-        Ok(DependencyGraphResponse {
-            root: request.asset_id,
-            dependencies,
-            truncated,
-        })
+        // The whole closure comes from one query rather than one per node
+        let closure = self
+            .repository
+            .dependency_closure(
+                &request.asset_id,
+                request.direction,
+                max_depth,
+                &request.kinds,
+            )
+            .await?;
+
+        let mut adjacency: HashMap<AssetId, Vec<AssetId>> = HashMap::new();
+        for edge in &closure.edges {
+            adjacency
+                .entry(closure.direction.source(edge))
+                .or_default()
+                .push(closure.direction.target(edge));
+        }
+
+        response.dependencies.push(DependencyNode {
+            asset_id: root.id,
+            name: root.metadata.name,
+            version: root.metadata.version,
+            depth: 0,
+            dependencies: adjacency.remove(&root.id).unwrap_or_default(),
+        });
+        response
+            .dependencies
+            .extend(closure.nodes.iter().map(|node| DependencyNode {
+                asset_id: node.asset_id,
+                name: node.name.clone(),
+                version: node.version.clone(),
+                depth: node.depth as i32,
+                dependencies: adjacency.remove(&node.asset_id).unwrap_or_default(),
+            }));
+        response.truncated = closure.truncated;
+        response.has_cycles = closure.has_cycles();
+        response.edges = closure.edges;
+
+        Ok(response)
     }
 
     #[instrument(skip(self))]
@@ -286,17 +292,16 @@ pub mod utils {
     }
 
     /// Parse and validate a search query string
-    pub fn parse_search_query(query: &str) -> ServiceResult<Vec<String>> {
-        if query.trim().is_empty() {
+    ///
+    /// Supports quoted phrases, `-excluded` terms and `field:value` terms
+    /// for the name, tag and description fields; see [`TextQuery`].
+    pub fn parse_search_query(query: &str) -> ServiceResult<TextQuery> {
+        let parsed = TextQuery::parse(query);
+        if parsed.is_empty() {
             return Err(ServiceError::InvalidInput("Empty search query".to_string()));
         }
 
-        let terms: Vec<String> = query
-            .split_whitespace()
-            .map(|s| s.to_lowercase())
-            .collect();
-
-        Ok(terms)
+        Ok(parsed)
     }
 
     /// Build a tag filter from comma-separated string
@@ -319,8 +324,11 @@ pub mod utils {
             exclude_deprecated: true,
             limit: 50,
             offset: 0,
+            cursor: None,
             sort_by: SortField::CreatedAt,
             sort_order: SortOrder::Descending,
+            facets: vec![],
+            ..Default::default()
         }
     }
 
@@ -348,6 +356,105 @@ pub mod utils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use llm_registry_core::{
+        AssetMetadata, Checksum, DependencyKind, HashAlgorithm, StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::{DependencyDirection, InMemoryAssetRepository, TextField};
+
+    fn create_test_asset(name: &str) -> Asset {
+        let metadata = AssetMetadata::new(name, semver::Version::new(1, 0, 0));
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}/weights.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        let id = AssetId::new();
+        Asset::new(id, AssetType::Model, metadata, storage, checksum).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_dependency_graph() {
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        let mut ids = Vec::new();
+        for name in ["pipeline", "model", "dataset"] {
+            ids.push(repository.create(create_test_asset(name)).await.unwrap().id);
+        }
+        let (pipeline, model, dataset) = (ids[0], ids[1], ids[2]);
+        repository
+            .add_dependency(&pipeline, &model, Some("^1.0"), DependencyKind::Runtime)
+            .await
+            .unwrap();
+        repository
+            .add_dependency(&model, &dataset, None, DependencyKind::TrainingData)
+            .await
+            .unwrap();
+
+        let service = DefaultSearchService::new(repository);
+        let graph = service
+            .get_dependency_graph(GetDependencyGraphRequest {
+                asset_id: pipeline,
+                max_depth: -1,
+                direction: DependencyDirection::Dependencies,
+                kinds: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let nodes: Vec<(&str, i32, Vec<AssetId>)> = graph
+            .dependencies
+            .iter()
+            .map(|node| (node.name.as_str(), node.depth, node.dependencies.clone()))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("pipeline", 0, vec![model]),
+                ("model", 1, vec![dataset]),
+                ("dataset", 2, vec![]),
+            ]
+        );
+        assert_eq!(graph.edges[0].version_constraint.as_deref(), Some("^1.0"));
+        assert_eq!(graph.edges[1].dependency_type, DependencyKind::TrainingData);
+        assert!(!graph.truncated && !graph.has_cycles);
+
+        let graph = service
+            .get_dependency_graph(GetDependencyGraphRequest {
+                asset_id: pipeline,
+                max_depth: -1,
+                direction: DependencyDirection::Dependencies,
+                kinds: vec![DependencyKind::Runtime],
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.dependencies.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+
+        let graph = service
+            .get_dependency_graph(GetDependencyGraphRequest {
+                asset_id: dataset,
+                max_depth: 1,
+                direction: DependencyDirection::Dependents,
+                kinds: Vec::new(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.dependencies.len(), 2);
+        assert_eq!(graph.dependencies[1].asset_id, model);
+        assert!(graph.truncated);
+
+        let result = service
+            .get_dependency_graph(GetDependencyGraphRequest {
+                asset_id: pipeline,
+                max_depth: MAX_DEPENDENCY_DEPTH as i32 + 1,
+                direction: DependencyDirection::Dependencies,
+                kinds: Vec::new(),
+            })
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
 
     #[test]
     fn test_build_text_query() {
@@ -357,13 +464,24 @@ mod tests {
 
     #[test]
     fn test_parse_search_query() {
-        let terms = utils::parse_search_query("  Hello  World  ").unwrap();
+        let query = utils::parse_search_query("  Hello  World  ").unwrap();
+        let terms: Vec<&str> = query.terms.iter().map(|t| t.text.as_str()).collect();
         assert_eq!(terms, vec!["hello", "world"]);
     }
 
+    #[test]
+    fn test_parse_search_query_syntax() {
+        let query = utils::parse_search_query(r#"tag:nlp "long documents" -draft"#).unwrap();
+        assert_eq!(query.terms.len(), 3);
+        assert_eq!(query.terms[0].field, Some(TextField::Tag));
+        assert!(query.terms[1].phrase);
+        assert!(query.terms[2].exclude);
+    }
+
     #[test]
     fn test_parse_search_query_empty() {
         assert!(utils::parse_search_query("   ").is_err());
+        assert!(utils::parse_search_query(r#"- "" tag:"#).is_err());
     }
 
     #[test]
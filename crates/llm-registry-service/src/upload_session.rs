@@ -0,0 +1,638 @@
+//! Upload session service
+//!
+//! This module implements resumable chunked uploads. A client creates a
+//! session for an asset, uploads numbered chunks (each verified against its
+//! own checksum), queries which chunks are still missing, and commits. On
+//! commit the session is first moved to `committing`, so concurrent commits
+//! and chunk uploads are refused, and the chunks are then streamed in order
+//! through the content service, which verifies the asset's declared checksum.
+//! Expired sessions and their partial data are garbage-collected.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use llm_registry_core::Checksum;
+use llm_registry_db::{
+    AssetRepository, BlobStore, UploadChunk, UploadSession, UploadSessionStatus,
+    UploadSessionStore,
+};
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+
+use crate::content::{write_verified, ContentService, ContentStream, VerifiedWrite};
+use crate::dto::{CreateUploadSessionRequest, UploadContentResponse, UploadSessionResponse};
+use crate::error::{ServiceError, ServiceResult};
+use crate::validation::MAX_ASSET_SIZE_BYTES;
+
+/// Default chunk size for upload sessions (64 MiB)
+pub const DEFAULT_UPLOAD_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Maximum number of chunks in a single upload session
+pub const MAX_UPLOAD_CHUNKS: u32 = 10_000;
+
+/// Default lifetime of an upload session (24 hours)
+pub const DEFAULT_UPLOAD_SESSION_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Trait for resumable chunked upload operations
+#[async_trait]
+pub trait UploadSessionService: Send + Sync {
+    /// Start an upload session for an asset
+    async fn create_session(&self, request: CreateUploadSessionRequest) -> ServiceResult<UploadSessionResponse>;
+
+    /// Get a session and the chunks it is still missing
+    async fn get_session(&self, session_id: &str) -> ServiceResult<UploadSessionResponse>;
+
+    /// Upload one chunk, optionally verifying it against a checksum
+    ///
+    /// Re-uploading a chunk replaces it.
+    async fn upload_chunk(
+        &self,
+        session_id: &str,
+        index: u32,
+        checksum: Option<Checksum>,
+        content: ContentStream,
+    ) -> ServiceResult<UploadChunk>;
+
+    /// Assemble the chunks into the asset's content and verify its checksum
+    async fn commit_session(&self, session_id: &str) -> ServiceResult<UploadContentResponse>;
+
+    /// Abort a session and discard its chunks
+    async fn abort_session(&self, session_id: &str) -> ServiceResult<()>;
+
+    /// Delete expired sessions and their chunk data
+    ///
+    /// Returns the number of sessions removed.
+    async fn purge_expired_sessions(&self) -> ServiceResult<usize>;
+}
+
+/// Default implementation of UploadSessionService
+pub struct DefaultUploadSessionService {
+    repository: Arc<dyn AssetRepository>,
+    sessions: Arc<dyn UploadSessionStore>,
+    blob_store: Arc<dyn BlobStore>,
+    content_service: Arc<dyn ContentService>,
+    session_ttl: Duration,
+}
+
+impl DefaultUploadSessionService {
+    /// Create a new upload session service
+    pub fn new(
+        repository: Arc<dyn AssetRepository>,
+        sessions: Arc<dyn UploadSessionStore>,
+        blob_store: Arc<dyn BlobStore>,
+        content_service: Arc<dyn ContentService>,
+    ) -> Self {
+        Self {
+            repository,
+            sessions,
+            blob_store,
+            content_service,
+            session_ttl: Duration::seconds(DEFAULT_UPLOAD_SESSION_TTL_SECS),
+        }
+    }
+
+    /// Set how long sessions stay open before they expire
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    async fn find_session(&self, session_id: &str) -> ServiceResult<UploadSession> {
+        self.sessions
+            .find(session_id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Upload session {}", session_id)))
+    }
+
+    /// Find a session that can still accept chunks or be committed
+    async fn find_active_session(&self, session_id: &str) -> ServiceResult<UploadSession> {
+        let session = self.find_session(session_id).await?;
+
+        if session.status != UploadSessionStatus::Active {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload session {} is {}",
+                session_id, session.status
+            )));
+        }
+        if session.is_expired(Utc::now()) {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload session {} has expired",
+                session_id
+            )));
+        }
+
+        Ok(session)
+    }
+
+    /// Delete the chunk blobs of a session, logging failures
+    async fn delete_chunks(&self, session: &UploadSession) {
+        for index in session.chunks.keys() {
+            if let Err(e) = self.blob_store.delete(&session.chunk_key(*index)).await {
+                warn!(
+                    "Failed to delete chunk {} of upload session {}: {}",
+                    index, session.id, e
+                );
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl UploadSessionService for DefaultUploadSessionService {
+    #[instrument(skip(self, request), fields(asset_id = %request.asset_id))]
+    async fn create_session(&self, request: CreateUploadSessionRequest) -> ServiceResult<UploadSessionResponse> {
+        let chunk_size = request.chunk_size.unwrap_or(DEFAULT_UPLOAD_CHUNK_SIZE);
+
+        if request.total_size == 0 {
+            return Err(ServiceError::InvalidInput(
+                "Total size must be greater than zero".to_string(),
+            ));
+        }
+        if request.total_size > MAX_ASSET_SIZE_BYTES {
+            return Err(ServiceError::ValidationFailed(format!(
+                "Asset size {} exceeds maximum allowed size of {}",
+                request.total_size, MAX_ASSET_SIZE_BYTES
+            )));
+        }
+        if chunk_size == 0 {
+            return Err(ServiceError::InvalidInput(
+                "Chunk size must be greater than zero".to_string(),
+            ));
+        }
+        if request.total_size.div_ceil(chunk_size) > MAX_UPLOAD_CHUNKS as u64 {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload would need more than {} chunks; use a larger chunk size",
+                MAX_UPLOAD_CHUNKS
+            )));
+        }
+
+        if self.repository.find_by_id(&request.asset_id).await?.is_none() {
+            return Err(ServiceError::NotFound(request.asset_id.to_string()));
+        }
+
+        let mut session =
+            UploadSession::new(request.asset_id, request.total_size, chunk_size, self.session_ttl);
+        if let Some(created_by) = request.created_by {
+            session = session.with_created_by(created_by);
+        }
+
+        let session = self.sessions.create(session).await?;
+        info!(
+            "Created upload session {} for asset {} ({} chunks)",
+            session.id,
+            session.asset_id,
+            session.total_chunks()
+        );
+
+        Ok(session.into())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_session(&self, session_id: &str) -> ServiceResult<UploadSessionResponse> {
+        Ok(self.find_session(session_id).await?.into())
+    }
+
+    #[instrument(skip(self, checksum, content))]
+    async fn upload_chunk(
+        &self,
+        session_id: &str,
+        index: u32,
+        checksum: Option<Checksum>,
+        content: ContentStream,
+    ) -> ServiceResult<UploadChunk> {
+        let session = self.find_active_session(session_id).await?;
+
+        let expected_size = session.expected_chunk_size(index).ok_or_else(|| {
+            ServiceError::InvalidInput(format!(
+                "Chunk index {} is out of range (session has {} chunks)",
+                index,
+                session.total_chunks()
+            ))
+        })?;
+
+        // Hash with the algorithm the client used, falling back to the asset's
+        let algorithm = match &checksum {
+            Some(checksum) => checksum.algorithm,
+            None => {
+                self.repository
+                    .find_by_id(&session.asset_id)
+                    .await?
+                    .ok_or_else(|| ServiceError::NotFound(session.asset_id.to_string()))?
+                    .checksum
+                    .algorithm
+            }
+        };
+
+        let key = session.chunk_key(index);
+        debug!("Writing chunk {} of upload session {} to {}", index, session_id, key);
+
+        let written = write_verified(
+            self.blob_store.as_ref(),
+            &key,
+            limit_size(content, index, expected_size),
+            algorithm,
+            checksum.as_ref(),
+        )
+        .await?;
+
+        let (checksum, size_bytes) = match written {
+            VerifiedWrite::Stored {
+                checksum,
+                size_bytes,
+            } => (checksum, size_bytes),
+            VerifiedWrite::Mismatch { actual } => {
+                return Err(ServiceError::ChecksumVerificationFailed(format!(
+                    "chunk {}: expected {}, computed {}",
+                    index,
+                    checksum.map(|c| c.value).unwrap_or_default(),
+                    actual.value
+                )));
+            }
+        };
+
+        if size_bytes != expected_size {
+            if let Err(e) = self.blob_store.delete(&key).await {
+                warn!("Failed to delete rejected chunk {}: {}", key, e);
+            }
+            return Err(ServiceError::InvalidInput(format!(
+                "Chunk {} has {} bytes, expected {}",
+                index, size_bytes, expected_size
+            )));
+        }
+
+        let chunk = UploadChunk {
+            index,
+            size_bytes,
+            checksum,
+            received_at: Utc::now(),
+        };
+        self.sessions.record_chunk(session_id, chunk.clone()).await?;
+
+        Ok(chunk)
+    }
+
+    #[instrument(skip(self))]
+    async fn commit_session(&self, session_id: &str) -> ServiceResult<UploadContentResponse> {
+        let session = self.find_active_session(session_id).await?;
+
+        let missing = session.missing_chunks();
+        if !missing.is_empty() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload session {} is missing {} chunk(s), first missing index {}",
+                session_id,
+                missing.len(),
+                missing[0]
+            )));
+        }
+
+        // Claim the session so a concurrent commit, abort or chunk upload
+        // cannot interleave with assembling the content
+        if !self
+            .sessions
+            .transition_status(
+                session_id,
+                UploadSessionStatus::Active,
+                UploadSessionStatus::Committing,
+            )
+            .await?
+        {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload session {} is no longer active",
+                session_id
+            )));
+        }
+
+        // Stream the chunks in order without buffering the whole asset
+        let blob_store = self.blob_store.clone();
+        let keys: Vec<String> = session.chunks.keys().map(|i| session.chunk_key(*i)).collect();
+        let content: ContentStream = Box::pin(
+            stream::iter(keys)
+                .then(move |key| {
+                    let blob_store = blob_store.clone();
+                    async move { blob_store.stream(&key).await }
+                })
+                .try_flatten()
+                .map(|chunk| chunk.map_err(ServiceError::from)),
+        );
+
+        let response = match self
+            .content_service
+            .upload_content(&session.asset_id, content)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                // Reopen the session so the client can fix chunks and retry
+                if let Err(reopen) = self
+                    .sessions
+                    .transition_status(
+                        session_id,
+                        UploadSessionStatus::Committing,
+                        UploadSessionStatus::Active,
+                    )
+                    .await
+                {
+                    warn!("Failed to reopen upload session {}: {}", session_id, reopen);
+                }
+                return Err(e);
+            }
+        };
+
+        self.sessions
+            .set_status(session_id, UploadSessionStatus::Committed)
+            .await?;
+        self.delete_chunks(&session).await;
+
+        info!(
+            "Committed upload session {} for asset {}",
+            session_id, session.asset_id
+        );
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn abort_session(&self, session_id: &str) -> ServiceResult<()> {
+        let session = self.find_active_session(session_id).await?;
+
+        if !self
+            .sessions
+            .transition_status(
+                session_id,
+                UploadSessionStatus::Active,
+                UploadSessionStatus::Aborted,
+            )
+            .await?
+        {
+            return Err(ServiceError::InvalidInput(format!(
+                "Upload session {} is no longer active",
+                session_id
+            )));
+        }
+        self.delete_chunks(&session).await;
+
+        info!("Aborted upload session {}", session_id);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_expired_sessions(&self) -> ServiceResult<usize> {
+        let expired = self.sessions.list_expired(Utc::now()).await?;
+
+        for session in &expired {
+            self.delete_chunks(session).await;
+            self.sessions.delete(&session.id).await?;
+        }
+
+        if !expired.is_empty() {
+            info!("Purged {} expired upload session(s)", expired.len());
+        }
+        Ok(expired.len())
+    }
+}
+
+/// Fail a chunk's stream as soon as it yields more than `max_bytes`
+///
+/// This stops an oversized chunk while it is being written rather than after
+/// the whole body has reached the blob store.
+fn limit_size(content: ContentStream, index: u32, max_bytes: u64) -> ContentStream {
+    let mut received = 0u64;
+    Box::pin(content.map(move |data| {
+        let data = data?;
+        received += data.len() as u64;
+        if received > max_bytes {
+            return Err(ServiceError::InvalidInput(format!(
+                "Chunk {} exceeds its expected size of {} bytes",
+                index, max_bytes
+            )));
+        }
+        Ok(data)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::DefaultContentService;
+    use crate::integrity::utils;
+    use llm_registry_core::{
+        Asset, AssetId, AssetMetadata, AssetType, HashAlgorithm, StorageBackend, StorageLocation,
+    };
+    use llm_registry_db::{
+        FileSystemBlobStore, InMemoryAssetRepository, InMemoryEventStore,
+        InMemoryUploadSessionStore,
+    };
+    use semver::Version;
+
+    const CONTENT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    struct Fixture {
+        service: DefaultUploadSessionService,
+        blob_store: Arc<FileSystemBlobStore>,
+        asset_id: AssetId,
+        dir: std::path::PathBuf,
+    }
+
+    async fn fixture(ttl: Duration) -> Fixture {
+        let checksum = utils::compute_checksum(CONTENT, HashAlgorithm::SHA256).unwrap();
+        let metadata = AssetMetadata::new("chunked-asset", Version::parse("1.0.0").unwrap());
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            "chunked-asset/1.0.0/weights.bin".to_string(),
+            None,
+        )
+        .unwrap();
+        let asset = Asset::new(AssetId::new(), AssetType::Model, metadata, storage, checksum).unwrap();
+        let asset_id = asset.id;
+
+        let dir = std::env::temp_dir().join(format!("llm-registry-uploads-{}", AssetId::new()));
+        let blob_store = Arc::new(FileSystemBlobStore::new(&dir));
+        let repository = Arc::new(InMemoryAssetRepository::new());
+        repository.create(asset).await.unwrap();
+        let content_service = Arc::new(DefaultContentService::new(
+            repository.clone(),
+            Arc::new(InMemoryEventStore::new()),
+            blob_store.clone(),
+        ));
+        let service = DefaultUploadSessionService::new(
+            repository,
+            Arc::new(InMemoryUploadSessionStore::new()),
+            blob_store.clone(),
+            content_service,
+        )
+        .with_session_ttl(ttl);
+
+        Fixture {
+            service,
+            blob_store,
+            asset_id,
+            dir,
+        }
+    }
+
+    fn single(data: &[u8]) -> ContentStream {
+        let data = data.to_vec();
+        Box::pin(stream::once(async move { Ok(data) }))
+    }
+
+    async fn create(fixture: &Fixture) -> UploadSessionResponse {
+        fixture
+            .service
+            .create_session(CreateUploadSessionRequest {
+                asset_id: fixture.asset_id,
+                total_size: CONTENT.len() as u64,
+                chunk_size: Some(16),
+                created_by: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chunked_upload_and_commit() {
+        let fixture = fixture(Duration::hours(1)).await;
+        let created = create(&fixture).await;
+        let session_id = created.session.id.clone();
+        assert_eq!(created.total_chunks, 3);
+        assert_eq!(created.missing_chunks, vec![0, 1, 2]);
+
+        // Chunks may arrive out of order; a bad per-chunk checksum is rejected
+        let chunk_2 = &CONTENT[32..];
+        fixture
+            .service
+            .upload_chunk(&session_id, 2, None, single(chunk_2))
+            .await
+            .unwrap();
+
+        let wrong = utils::compute_checksum(b"not chunk 0", HashAlgorithm::BLAKE3).unwrap();
+        let err = fixture
+            .service
+            .upload_chunk(&session_id, 0, Some(wrong), single(&CONTENT[..16]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::ChecksumVerificationFailed(_)));
+
+        let err = fixture
+            .service
+            .upload_chunk(&session_id, 1, None, single(&CONTENT[16..20]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::InvalidInput(_)));
+
+        // An oversized chunk is cut off mid-stream and nothing is kept
+        let oversized: ContentStream = Box::pin(stream::iter(
+            CONTENT[..24]
+                .chunks(8)
+                .map(|c| Ok(c.to_vec()))
+                .collect::<Vec<_>>(),
+        ));
+        let err = fixture
+            .service
+            .upload_chunk(&session_id, 1, None, oversized)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::InvalidInput(_)));
+        assert!(fixture
+            .blob_store
+            .head(&created.session.chunk_key(1))
+            .await
+            .unwrap()
+            .is_none());
+
+        let status = fixture.service.get_session(&session_id).await.unwrap();
+        assert_eq!(status.missing_chunks, vec![0, 1]);
+        assert!(fixture.service.commit_session(&session_id).await.is_err());
+
+        let chunk_0 = utils::compute_checksum(&CONTENT[..16], HashAlgorithm::BLAKE3).unwrap();
+        fixture
+            .service
+            .upload_chunk(&session_id, 0, Some(chunk_0), single(&CONTENT[..16]))
+            .await
+            .unwrap();
+        fixture
+            .service
+            .upload_chunk(&session_id, 1, None, single(&CONTENT[16..32]))
+            .await
+            .unwrap();
+
+        let committed = fixture.service.commit_session(&session_id).await.unwrap();
+        assert_eq!(committed.size_bytes, CONTENT.len() as u64);
+        assert_eq!(
+            fixture
+                .blob_store
+                .get("chunked-asset/1.0.0/weights.bin")
+                .await
+                .unwrap(),
+            CONTENT
+        );
+        assert!(fixture
+            .blob_store
+            .head(&status.session.chunk_key(0))
+            .await
+            .unwrap()
+            .is_none());
+
+        let status = fixture.service.get_session(&session_id).await.unwrap();
+        assert_eq!(status.session.status, UploadSessionStatus::Committed);
+        assert!(fixture.service.commit_session(&session_id).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&fixture.dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_commit_reopens_session() {
+        let fixture = fixture(Duration::hours(1)).await;
+        let created = create(&fixture).await;
+        let session_id = created.session.id.clone();
+        for (index, range) in [(0, 0..16), (1, 16..32), (2, 32..CONTENT.len())] {
+            fixture
+                .service
+                .upload_chunk(&session_id, index, None, single(&CONTENT[range]))
+                .await
+                .unwrap();
+        }
+
+        // Losing a chunk's data fails the commit but leaves the session usable
+        fixture
+            .blob_store
+            .delete(&created.session.chunk_key(2))
+            .await
+            .unwrap();
+        assert!(fixture.service.commit_session(&session_id).await.is_err());
+        let status = fixture.service.get_session(&session_id).await.unwrap();
+        assert_eq!(status.session.status, UploadSessionStatus::Active);
+
+        fixture
+            .service
+            .upload_chunk(&session_id, 2, None, single(&CONTENT[32..]))
+            .await
+            .unwrap();
+        fixture.service.commit_session(&session_id).await.unwrap();
+
+        let _ = std::fs::remove_dir_all(&fixture.dir);
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_sessions() {
+        let fixture = fixture(Duration::zero()).await;
+        let created = create(&fixture).await;
+        let session_id = created.session.id.clone();
+
+        // An expired session rejects chunks, and GC removes what it had
+        let err = fixture
+            .service
+            .upload_chunk(&session_id, 0, None, single(&CONTENT[..16]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::InvalidInput(_)));
+
+        assert_eq!(fixture.service.purge_expired_sessions().await.unwrap(), 1);
+        assert!(matches!(
+            fixture.service.get_session(&session_id).await.unwrap_err(),
+            ServiceError::NotFound(_)
+        ));
+
+        let _ = std::fs::remove_dir_all(&fixture.dir);
+    }
+}
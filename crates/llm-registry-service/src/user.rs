@@ -0,0 +1,377 @@
+//! User account service
+//!
+//! This module implements password login for registry users. Passwords are
+//! hashed with Argon2id and never leave this module in plaintext. Repeated
+//! failed logins lock the account for a configurable period, and disabled
+//! accounts cannot log in at all. The roles stored with each account are the
+//! roles the API layer puts into issued tokens.
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use llm_registry_db::{User, UserStore};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::{info, instrument, warn};
+
+use crate::dto::CreateUserRequest;
+use crate::error::{ServiceError, ServiceResult};
+
+/// Minimum accepted password length
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Default number of consecutive failed logins before an account is locked
+pub const DEFAULT_MAX_LOGIN_ATTEMPTS: u32 = 5;
+
+/// Default lockout period after too many failed logins (15 minutes)
+pub const DEFAULT_LOCKOUT_SECS: i64 = 15 * 60;
+
+/// Trait for user account operations
+#[async_trait]
+pub trait UserService: Send + Sync {
+    /// Verify a username and password, returning the account on success
+    ///
+    /// Unknown users and wrong passwords both fail with `InvalidCredentials`,
+    /// and take as long to check. Disabled and locked accounts fail with
+    /// `NotPermitted` whatever the password, so a locked account cannot be
+    /// used to check guesses.
+    async fn authenticate(&self, username: &str, password: &str) -> ServiceResult<User>;
+
+    /// Create a new account
+    async fn create_user(&self, request: CreateUserRequest) -> ServiceResult<User>;
+
+    /// Get an account by ID
+    async fn get_user(&self, id: &str) -> ServiceResult<User>;
+
+    /// List every account
+    async fn list_users(&self) -> ServiceResult<Vec<User>>;
+
+    /// Replace an account's password and clear any lockout
+    async fn reset_password(&self, id: &str, password: &str) -> ServiceResult<User>;
+
+    /// Replace the roles granted to an account
+    async fn set_roles(&self, id: &str, roles: Vec<String>) -> ServiceResult<User>;
+
+    /// Disable or re-enable an account
+    ///
+    /// Re-enabling an account also clears any lockout.
+    async fn set_disabled(&self, id: &str, disabled: bool) -> ServiceResult<User>;
+}
+
+/// Default implementation of UserService
+pub struct DefaultUserService {
+    store: Arc<dyn UserStore>,
+    max_login_attempts: u32,
+    lockout: Duration,
+    /// Hash verified for unknown usernames, so they cannot be told apart
+    /// from wrong passwords by timing
+    dummy_hash: OnceCell<String>,
+}
+
+impl DefaultUserService {
+    /// Create a new user service
+    pub fn new(store: Arc<dyn UserStore>) -> Self {
+        Self {
+            store,
+            max_login_attempts: DEFAULT_MAX_LOGIN_ATTEMPTS,
+            lockout: Duration::seconds(DEFAULT_LOCKOUT_SECS),
+            dummy_hash: OnceCell::new(),
+        }
+    }
+
+    /// Set how many consecutive failed logins lock an account, and for how long
+    pub fn with_lockout(mut self, max_attempts: u32, duration: Duration) -> Self {
+        self.max_login_attempts = max_attempts.max(1);
+        self.lockout = duration;
+        self
+    }
+
+    async fn dummy_hash(&self) -> ServiceResult<&str> {
+        self.dummy_hash
+            .get_or_try_init(|| hash_password("dummy password for unknown users"))
+            .await
+            .map(String::as_str)
+    }
+
+    async fn find_user(&self, id: &str) -> ServiceResult<User> {
+        self.store
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("User {}", id)))
+    }
+}
+
+#[async_trait]
+impl UserService for DefaultUserService {
+    #[instrument(skip(self, password))]
+    async fn authenticate(&self, username: &str, password: &str) -> ServiceResult<User> {
+        let Some(user) = self.store.find_by_username(username).await? else {
+            verify_password(password, self.dummy_hash().await?).await?;
+            return Err(ServiceError::InvalidCredentials);
+        };
+
+        let now = Utc::now();
+        let verified = verify_password(password, &user.password_hash).await?;
+
+        // An unavailable account gives the same answer whatever the password,
+        // so it cannot be used to check guesses, and failures do not extend
+        // its lockout
+        if user.disabled {
+            return Err(ServiceError::NotPermitted(
+                "Account is disabled".to_string(),
+            ));
+        }
+        if user.is_locked(now) {
+            return Err(ServiceError::NotPermitted(
+                "Account is temporarily locked after repeated failed logins".to_string(),
+            ));
+        }
+
+        if !verified {
+            let user = self
+                .store
+                .record_login_failure(&user.id, self.max_login_attempts, now + self.lockout)
+                .await?;
+            if user.is_locked(now) {
+                warn!(
+                    "Locked account {} after repeated failed logins",
+                    user.username
+                );
+            }
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        Ok(self.store.record_login_success(&user.id, now).await?)
+    }
+
+    #[instrument(skip(self, request), fields(username = %request.username))]
+    async fn create_user(&self, request: CreateUserRequest) -> ServiceResult<User> {
+        let username = request.username.trim();
+        if username.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "Username must not be empty".to_string(),
+            ));
+        }
+        validate_password(&request.password)?;
+        validate_roles(&request.roles)?;
+
+        if self.store.find_by_username(username).await?.is_some() {
+            return Err(ServiceError::InvalidInput(format!(
+                "Username already taken: {}",
+                username
+            )));
+        }
+
+        let mut user =
+            User::new(username, hash_password(&request.password).await?).with_roles(request.roles);
+        user.email = request.email;
+
+        let user = self.store.create(user).await?;
+        info!("Created user {}", user.username);
+        Ok(user)
+    }
+
+    async fn get_user(&self, id: &str) -> ServiceResult<User> {
+        self.find_user(id).await
+    }
+
+    async fn list_users(&self) -> ServiceResult<Vec<User>> {
+        Ok(self.store.list().await?)
+    }
+
+    #[instrument(skip(self, password))]
+    async fn reset_password(&self, id: &str, password: &str) -> ServiceResult<User> {
+        validate_password(password)?;
+
+        let mut user = self.find_user(id).await?;
+        user.password_hash = hash_password(password).await?;
+        user.failed_login_attempts = 0;
+        user.locked_until = None;
+
+        let user = self.store.update(user).await?;
+        info!("Reset password of user {}", user.username);
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn set_roles(&self, id: &str, roles: Vec<String>) -> ServiceResult<User> {
+        validate_roles(&roles)?;
+
+        let mut user = self.find_user(id).await?;
+        user.roles = roles;
+        Ok(self.store.update(user).await?)
+    }
+
+    #[instrument(skip(self))]
+    async fn set_disabled(&self, id: &str, disabled: bool) -> ServiceResult<User> {
+        let mut user = self.find_user(id).await?;
+        user.disabled = disabled;
+        if !disabled {
+            user.failed_login_attempts = 0;
+            user.locked_until = None;
+        }
+        Ok(self.store.update(user).await?)
+    }
+}
+
+fn validate_password(password: &str) -> ServiceResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(ServiceError::InvalidInput(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+fn validate_roles(roles: &[String]) -> ServiceResult<()> {
+    if roles.iter().any(|role| role.trim().is_empty()) {
+        return Err(ServiceError::InvalidInput(
+            "Role names must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Hash a password with Argon2id and a random salt
+///
+/// Hashing is deliberately slow, so it runs on the blocking thread pool.
+async fn hash_password(password: &str) -> ServiceResult<String> {
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| ServiceError::Internal(format!("Failed to hash password: {}", e)))
+    })
+    .await
+    .map_err(|e| ServiceError::Internal(format!("Password hashing task failed: {}", e)))?
+}
+
+/// Check a password against a stored hash
+async fn verify_password(password: &str, hash: &str) -> ServiceResult<bool> {
+    let password = password.to_string();
+    let hash = hash.to_string();
+    tokio::task::spawn_blocking(move || {
+        let parsed = PasswordHash::new(&hash)
+            .map_err(|e| ServiceError::Internal(format!("Invalid stored password hash: {}", e)))?;
+        Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok())
+    })
+    .await
+    .map_err(|e| ServiceError::Internal(format!("Password verification task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_registry_db::InMemoryUserStore;
+
+    fn service() -> DefaultUserService {
+        DefaultUserService::new(Arc::new(InMemoryUserStore::new()))
+            .with_lockout(3, Duration::minutes(15))
+    }
+
+    fn request(username: &str, password: &str) -> CreateUserRequest {
+        CreateUserRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+            email: Some(format!("{}@example.org", username)),
+            roles: vec!["developer".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate() {
+        let service = service();
+        let user = service
+            .create_user(request("alice", "correct horse"))
+            .await
+            .unwrap();
+        assert_ne!(user.password_hash, "correct horse");
+        assert!(user.password_hash.starts_with("$argon2id$"));
+
+        let authenticated = service.authenticate("alice", "correct horse").await.unwrap();
+        assert_eq!(authenticated.id, user.id);
+        assert_eq!(authenticated.roles, vec!["developer"]);
+        assert!(authenticated.last_login_at.is_some());
+
+        assert!(matches!(
+            service.authenticate("alice", "wrong password").await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            service.authenticate("nobody", "correct horse").await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+        assert!(service.create_user(request("alice", "another pw")).await.is_err());
+        assert!(service.create_user(request("bob", "short")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lockout_and_reset() {
+        let service = service();
+        let user = service
+            .create_user(request("carol", "correct horse"))
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                service.authenticate("carol", "wrong password").await,
+                Err(ServiceError::InvalidCredentials)
+            ));
+        }
+        // Locked: the right and wrong passwords get the same answer, so the
+        // lockout does not confirm guesses
+        let right = service.authenticate("carol", "correct horse").await;
+        let wrong = service.authenticate("carol", "wrong password").await;
+        match (right, wrong) {
+            (Err(ServiceError::NotPermitted(right)), Err(ServiceError::NotPermitted(wrong))) => {
+                assert_eq!(right, wrong)
+            }
+            other => panic!("expected the same lockout error, got {:?}", other),
+        }
+
+        service.reset_password(&user.id, "battery staple").await.unwrap();
+        assert!(service.authenticate("carol", "battery staple").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_roles() {
+        let service = service();
+        let user = service
+            .create_user(request("dave", "correct horse"))
+            .await
+            .unwrap();
+
+        service.set_disabled(&user.id, true).await.unwrap();
+        assert!(matches!(
+            service.authenticate("dave", "correct horse").await,
+            Err(ServiceError::NotPermitted(_))
+        ));
+        assert!(matches!(
+            service.authenticate("dave", "wrong password").await,
+            Err(ServiceError::NotPermitted(_))
+        ));
+        service.set_disabled(&user.id, false).await.unwrap();
+
+        let updated = service
+            .set_roles(&user.id, vec!["admin".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(updated.roles, vec!["admin"]);
+        assert_eq!(
+            service.authenticate("dave", "correct horse").await.unwrap().roles,
+            vec!["admin"]
+        );
+        assert!(matches!(
+            service.get_user("missing").await,
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+}
@@ -12,6 +12,9 @@ use tracing::{debug, instrument, warn};
 use crate::dto::{ValidateAssetRequest, ValidationError, ValidationResult, ValidationWarning};
 use crate::error::{ServiceError, ServiceResult};
 
+/// Maximum asset size accepted by the size policy (10 GB)
+pub const MAX_ASSET_SIZE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
 /// Trait for validation operations
 #[async_trait]
 pub trait ValidationService: Send + Sync {
@@ -225,15 +228,14 @@ impl DefaultValidationService {
         let mut warnings = Vec::new();
 
         if let Some(size) = asset.metadata.size_bytes {
-            const MAX_SIZE: u64 = 10 * 1024 * 1024 * 1024; // 10 GB
             const WARN_SIZE: u64 = 1 * 1024 * 1024 * 1024; // 1 GB
 
-            if size > MAX_SIZE {
+            if size > MAX_ASSET_SIZE_BYTES {
                 errors.push(ValidationError {
                     field: "metadata.size_bytes".to_string(),
                     message: format!(
                         "Asset size {} exceeds maximum allowed size of {}",
-                        size, MAX_SIZE
+                        size, MAX_ASSET_SIZE_BYTES
                     ),
                     code: Some("SIZE_EXCEEDS_LIMIT".to_string()),
                 });
@@ -427,8 +429,16 @@ impl ValidationService for DefaultValidationService {
 mod tests {
     use super::*;
     use llm_registry_core::{AssetMetadata, Checksum, HashAlgorithm, StorageBackend, StorageLocation};
+    use llm_registry_db::{InMemoryAssetRepository, InMemoryEventStore};
     use semver::Version;
 
+    fn service() -> DefaultValidationService {
+        DefaultValidationService::new(
+            Arc::new(InMemoryAssetRepository::new()),
+            Arc::new(InMemoryEventStore::new()),
+        )
+    }
+
     fn create_test_asset() -> Asset {
         let metadata = AssetMetadata::new("test-asset", Version::parse("1.0.0").unwrap());
         let storage = StorageLocation::new(
@@ -452,10 +462,7 @@ mod tests {
 
     #[test]
     fn test_validate_schema_valid_asset() {
-        let service = DefaultValidationService {
-            repository: Arc::new(MockRepository),
-            event_store: Arc::new(MockEventStore),
-        };
+        let service = service();
 
         let asset = create_test_asset();
         let result = service.validate_schema(&asset);
@@ -465,10 +472,7 @@ mod tests {
 
     #[test]
     fn test_validate_schema_empty_name() {
-        let service = DefaultValidationService {
-            repository: Arc::new(MockRepository),
-            event_store: Arc::new(MockEventStore),
-        };
+        let service = service();
 
         let mut asset = create_test_asset();
         asset.metadata.name = String::new();
@@ -481,10 +485,7 @@ mod tests {
 
     #[test]
     fn test_validate_license_policy() {
-        let service = DefaultValidationService {
-            repository: Arc::new(MockRepository),
-            event_store: Arc::new(MockEventStore),
-        };
+        let service = service();
 
         let mut asset = create_test_asset();
         asset.metadata.license = Some("MIT".to_string());
@@ -492,97 +493,4 @@ mod tests {
         let result = service.validate_license_policy(&asset);
         assert!(result.valid);
     }
-
-    // Mock implementations for testing
-    struct MockRepository;
-    struct MockEventStore;
-
-    #[async_trait]
-    impl AssetRepository for MockRepository {
-        async fn create(&self, _: Asset) -> llm_registry_db::DbResult<Asset> {
-            unimplemented!()
-        }
-        async fn find_by_id(&self, _: &AssetId) -> llm_registry_db::DbResult<Option<Asset>> {
-            Ok(None)
-        }
-        async fn find_by_name_and_version(&self, _: &str, _: &semver::Version) -> llm_registry_db::DbResult<Option<Asset>> {
-            Ok(None)
-        }
-        async fn find_by_ids(&self, _: &[AssetId]) -> llm_registry_db::DbResult<Vec<Asset>> {
-            Ok(vec![])
-        }
-        async fn search(&self, _: &llm_registry_db::SearchQuery) -> llm_registry_db::DbResult<llm_registry_db::SearchResults> {
-            unimplemented!()
-        }
-        async fn update(&self, asset: Asset) -> llm_registry_db::DbResult<Asset> {
-            Ok(asset)
-        }
-        async fn delete(&self, _: &AssetId) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-        async fn list_versions(&self, _: &str) -> llm_registry_db::DbResult<Vec<Asset>> {
-            Ok(vec![])
-        }
-        async fn list_dependencies(&self, _: &AssetId) -> llm_registry_db::DbResult<Vec<Asset>> {
-            Ok(vec![])
-        }
-        async fn list_reverse_dependencies(&self, _: &AssetId) -> llm_registry_db::DbResult<Vec<Asset>> {
-            Ok(vec![])
-        }
-        async fn add_tag(&self, _: &AssetId, _: &str) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-        async fn remove_tag(&self, _: &AssetId, _: &str) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-        async fn get_tags(&self, _: &AssetId) -> llm_registry_db::DbResult<Vec<String>> {
-            Ok(vec![])
-        }
-        async fn list_all_tags(&self) -> llm_registry_db::DbResult<Vec<String>> {
-            Ok(vec![])
-        }
-        async fn add_dependency(&self, _: &AssetId, _: &AssetId, _: Option<&str>) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-        async fn remove_dependency(&self, _: &AssetId, _: &AssetId) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-        async fn count_assets(&self) -> llm_registry_db::DbResult<i64> {
-            Ok(0)
-        }
-        async fn count_by_type(&self, _: &AssetType) -> llm_registry_db::DbResult<i64> {
-            Ok(0)
-        }
-        async fn health_check(&self) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-    }
-
-    #[async_trait]
-    impl EventStore for MockEventStore {
-        async fn append(&self, event: RegistryEvent) -> llm_registry_db::DbResult<RegistryEvent> {
-            Ok(event)
-        }
-        async fn append_batch(&self, events: Vec<RegistryEvent>) -> llm_registry_db::DbResult<Vec<RegistryEvent>> {
-            Ok(events)
-        }
-        async fn query(&self, _: &llm_registry_db::EventQuery) -> llm_registry_db::DbResult<llm_registry_db::EventQueryResults> {
-            unimplemented!()
-        }
-        async fn get_asset_events(&self, _: &AssetId, _: i64) -> llm_registry_db::DbResult<Vec<RegistryEvent>> {
-            Ok(vec![])
-        }
-        async fn get_latest_event(&self, _: &AssetId) -> llm_registry_db::DbResult<Option<RegistryEvent>> {
-            Ok(None)
-        }
-        async fn count_events(&self) -> llm_registry_db::DbResult<i64> {
-            Ok(0)
-        }
-        async fn count_by_type(&self, _: &str) -> llm_registry_db::DbResult<i64> {
-            Ok(0)
-        }
-        async fn health_check(&self) -> llm_registry_db::DbResult<()> {
-            Ok(())
-        }
-    }
 }
@@ -203,8 +203,9 @@ impl VersioningService for DefaultVersioningService {
         // Set status to deprecated
         asset.set_status(AssetStatus::Deprecated);
 
-        // Update in repository
-        let updated = self.repository.update(asset).await?;
+        // Update in repository, failing if the asset changed since it was read
+        let revision = asset.revision;
+        let updated = self.repository.update(asset, Some(revision)).await?;
 
         // Emit status change event
         let event = RegistryEvent::new(EventType::AssetStatusChanged {
@@ -224,7 +225,12 @@ impl VersioningService for DefaultVersioningService {
             updated_copy
                 .metadata
                 .add_annotation("deprecation_reason", reason_text);
-            return self.repository.update(updated_copy).await.map_err(Into::into);
+            let revision = updated_copy.revision;
+            return self
+                .repository
+                .update(updated_copy, Some(revision))
+                .await
+                .map_err(Into::into);
         }
 
         Ok(updated)
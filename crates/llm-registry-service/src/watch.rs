@@ -0,0 +1,463 @@
+//! Watch service
+//!
+//! This module turns the event store into a live feed of registry events.
+//! Watchers may resume from the ID of the last event they received, in which
+//! case every event they missed is replayed before live events are delivered.
+//! New events are picked up by polling the event store in sequence order
+//! rather than event ID order: event IDs are generated by each registry
+//! instance, while sequence numbers are assigned by the store and become
+//! visible in increasing order. Watchers backed by a shared PostgreSQL store
+//! therefore see events appended by every instance; the in-memory store only
+//! holds the events of its own process.
+
+use async_trait::async_trait;
+use futures::stream::{self, Stream};
+use llm_registry_core::{Asset, EventType, RegistryEvent};
+use llm_registry_db::{AssetRepository, EventStore, StoredEvent};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+use crate::dto::{WatchAssetsRequest, WatchedEvent};
+use crate::error::{ServiceError, ServiceResult};
+
+/// Stream of events delivered to a watcher
+pub type EventStream = Pin<Box<dyn Stream<Item = ServiceResult<WatchedEvent>> + Send>>;
+
+/// Default interval between polls of the event store when a watcher is idle
+pub const DEFAULT_WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maximum number of events read from the store per poll
+const WATCH_BATCH_SIZE: i64 = 100;
+
+/// Trait for watching registry events
+#[async_trait]
+pub trait WatchService: Send + Sync {
+    /// Watch registry events matching the request's filters
+    ///
+    /// The stream never ends on its own. It yields an error and ends if the
+    /// event store or repository fails; clients should reconnect with the ID
+    /// of the last event they received.
+    async fn watch_assets(&self, request: WatchAssetsRequest) -> ServiceResult<EventStream>;
+}
+
+/// Default implementation of WatchService
+pub struct DefaultWatchService {
+    repository: Arc<dyn AssetRepository>,
+    event_store: Arc<dyn EventStore>,
+    poll_interval: Duration,
+}
+
+impl DefaultWatchService {
+    /// Create a new watch service
+    pub fn new(repository: Arc<dyn AssetRepository>, event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            repository,
+            event_store,
+            poll_interval: DEFAULT_WATCH_POLL_INTERVAL,
+        }
+    }
+
+    /// Set how often idle watchers poll the event store
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+#[async_trait]
+impl WatchService for DefaultWatchService {
+    #[instrument(skip(self))]
+    async fn watch_assets(&self, mut request: WatchAssetsRequest) -> ServiceResult<EventStream> {
+        let cursor = match request.resume_after.take() {
+            Some(cursor) => {
+                cursor.parse::<ulid::Ulid>().map_err(|e| {
+                    ServiceError::InvalidInput(format!("Invalid resume cursor '{}': {}", cursor, e))
+                })?;
+                let sequence = self.event_store.find_sequence(&cursor).await?;
+                Some(sequence.ok_or_else(|| {
+                    ServiceError::InvalidInput(format!("Unknown resume cursor '{}'", cursor))
+                })?)
+            }
+            None => self.event_store.latest_sequence().await?,
+        };
+
+        debug!("Starting watch after event {:?}", cursor);
+
+        let watcher = Watcher {
+            repository: self.repository.clone(),
+            event_store: self.event_store.clone(),
+            poll_interval: self.poll_interval,
+            filter: request,
+            cursor,
+            pending: VecDeque::new(),
+            failed: false,
+        };
+
+        Ok(Box::pin(stream::unfold(watcher, |mut watcher| async move {
+            watcher.next_event().await.map(|item| (item, watcher))
+        })))
+    }
+}
+
+/// State of a single watch stream
+struct Watcher {
+    repository: Arc<dyn AssetRepository>,
+    event_store: Arc<dyn EventStore>,
+    poll_interval: Duration,
+    filter: WatchAssetsRequest,
+    cursor: Option<i64>,
+    pending: VecDeque<StoredEvent>,
+    failed: bool,
+}
+
+impl Watcher {
+    /// Wait for the next matching event, or `None` once the stream has failed
+    async fn next_event(&mut self) -> Option<ServiceResult<WatchedEvent>> {
+        if self.failed {
+            return None;
+        }
+
+        loop {
+            if let Some(stored) = self.pending.pop_front() {
+                self.cursor = Some(stored.sequence);
+                match self.resolve(stored).await {
+                    Ok(Some(event)) => return Some(Ok(event)),
+                    Ok(None) => continue,
+                    Err(e) => return Some(Err(self.fail(e))),
+                }
+            }
+
+            match self
+                .event_store
+                .read_after(self.cursor, WATCH_BATCH_SIZE)
+                .await
+            {
+                Ok(events) if events.is_empty() => tokio::time::sleep(self.poll_interval).await,
+                Ok(events) => self.pending.extend(events),
+                Err(e) => return Some(Err(self.fail(e.into()))),
+            }
+        }
+    }
+
+    /// Load the event's asset and apply the filters
+    async fn resolve(&self, stored: StoredEvent) -> ServiceResult<Option<WatchedEvent>> {
+        let filter = &self.filter;
+        if !filter.event_types.is_empty()
+            && !filter
+                .event_types
+                .iter()
+                .any(|t| t == stored.event.event_type.event_name())
+        {
+            return Ok(None);
+        }
+
+        let asset_id = stored.event.event_type.asset_id();
+        if !filter.asset_ids.is_empty()
+            && !asset_id.is_some_and(|id| filter.asset_ids.contains(&id))
+        {
+            return Ok(None);
+        }
+
+        let asset = match asset_id {
+            Some(id) => self.repository.find_by_id(&id).await?,
+            None => None,
+        };
+
+        if !matches_filter(filter, &stored.event, asset.as_ref()) {
+            return Ok(None);
+        }
+
+        Ok(Some(WatchedEvent {
+            event_id: stored.event_id,
+            event: stored.event,
+            asset,
+        }))
+    }
+
+    fn fail(&mut self, error: ServiceError) -> ServiceError {
+        self.failed = true;
+        error
+    }
+}
+
+/// Check an event against the asset name, type and tag filters
+///
+/// Type and tag filters need the asset itself, so events for assets that no
+/// longer exist only pass them when those filters are empty. Name filters
+/// fall back to the name recorded in the event.
+fn matches_filter(filter: &WatchAssetsRequest, event: &RegistryEvent, asset: Option<&Asset>) -> bool {
+    if !filter.asset_names.is_empty() {
+        let name = asset
+            .map(|a| a.metadata.name.as_str())
+            .or_else(|| event_asset_name(&event.event_type));
+        if !name.is_some_and(|name| filter.asset_names.iter().any(|n| n == name)) {
+            return false;
+        }
+    }
+
+    if !filter.asset_types.is_empty()
+        && !asset.is_some_and(|a| filter.asset_types.contains(&a.asset_type))
+    {
+        return false;
+    }
+
+    if !filter.tags.is_empty()
+        && !asset.is_some_and(|a| a.metadata.tags.iter().any(|t| filter.tags.contains(t)))
+    {
+        return false;
+    }
+
+    true
+}
+
+/// Asset name recorded in the event payload, if any
+fn event_asset_name(event_type: &EventType) -> Option<&str> {
+    match event_type {
+        EventType::AssetRegistered { asset_name, .. }
+        | EventType::AssetUpdated { asset_name, .. }
+        | EventType::AssetDeleted { asset_name, .. }
+        | EventType::AssetStatusChanged { asset_name, .. }
+        | EventType::AssetDownloaded { asset_name, .. } => Some(asset_name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use llm_registry_core::{
+        AssetId, AssetMetadata, AssetType, Checksum, HashAlgorithm, StorageBackend,
+        StorageLocation,
+    };
+    use llm_registry_db::{DbResult, InMemoryAssetRepository};
+    use semver::Version;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockEventStore {
+        events: Mutex<Vec<StoredEvent>>,
+    }
+
+    fn asset(name: &str, asset_type: AssetType, tags: &[&str]) -> Asset {
+        let mut metadata = AssetMetadata::new(name, Version::parse("1.0.0").unwrap());
+        metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        let storage = StorageLocation::new(
+            StorageBackend::FileSystem {
+                base_path: "/tmp".to_string(),
+            },
+            format!("{}.bin", name),
+            None,
+        )
+        .unwrap();
+        let checksum = Checksum::new(HashAlgorithm::SHA256, "a".repeat(64)).unwrap();
+        Asset::new(AssetId::new(), asset_type, metadata, storage, checksum).unwrap()
+    }
+
+    fn registered(asset: &Asset) -> RegistryEvent {
+        RegistryEvent::new(EventType::AssetRegistered {
+            asset_id: asset.id,
+            asset_name: asset.metadata.name.clone(),
+            asset_version: asset.metadata.version.to_string(),
+            asset_type: asset.asset_type.to_string(),
+        })
+    }
+
+    async fn service(assets: Vec<Asset>, events: Arc<MockEventStore>) -> DefaultWatchService {
+        let repository = InMemoryAssetRepository::new();
+        for asset in assets {
+            repository.create(asset).await.unwrap();
+        }
+        DefaultWatchService::new(Arc::new(repository), events)
+            .with_poll_interval(Duration::from_millis(10))
+    }
+
+    async fn next(stream: &mut EventStream) -> WatchedEvent {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for event")
+            .expect("stream ended")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_watch_resumes_then_streams_live_events() {
+        let model = asset("gpt-small", AssetType::Model, &["nlp"]);
+        let dataset = asset("corpus", AssetType::Dataset, &["nlp"]);
+        let events = Arc::new(MockEventStore::default());
+
+        events.append(registered(&model)).await.unwrap();
+        let cursor = events.events.lock().unwrap()[0].event_id.clone();
+        events.append(registered(&dataset)).await.unwrap();
+
+        let service = service(vec![model.clone(), dataset.clone()], events.clone()).await;
+
+        // Resuming replays the event appended after the cursor
+        let mut stream = service
+            .watch_assets(WatchAssetsRequest {
+                resume_after: Some(cursor),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let missed = next(&mut stream).await;
+        assert_eq!(missed.asset.unwrap().id, dataset.id);
+
+        // Events appended while watching are delivered live
+        events.append(registered(&model)).await.unwrap();
+        let live = next(&mut stream).await;
+        assert_eq!(live.asset.unwrap().id, model.id);
+        // Delivery follows the store's sequence, not event ID order
+        assert!(live.event_id < missed.event_id);
+
+        let unknown = service
+            .watch_assets(WatchAssetsRequest {
+                resume_after: Some(ulid::Ulid::new().to_string()),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(
+            unknown.err().unwrap(),
+            ServiceError::InvalidInput(_)
+        ));
+
+        // Without a cursor, only new events are delivered
+        let mut stream = service
+            .watch_assets(WatchAssetsRequest::default())
+            .await
+            .unwrap();
+        events.append(registered(&dataset)).await.unwrap();
+        assert_eq!(next(&mut stream).await.asset.unwrap().id, dataset.id);
+    }
+
+    #[tokio::test]
+    async fn test_watch_filters() {
+        let model = asset("gpt-small", AssetType::Model, &["nlp"]);
+        let dataset = asset("corpus", AssetType::Dataset, &["vision"]);
+        let deleted = asset("gone", AssetType::Model, &["nlp"]);
+        let events = Arc::new(MockEventStore::default());
+
+        // Watchers below resume after this event
+        events.append(registered(&dataset)).await.unwrap();
+        let first = events.events.lock().unwrap()[0].event_id.clone();
+
+        events.append(registered(&dataset)).await.unwrap();
+        events.append(registered(&model)).await.unwrap();
+        events
+            .append(RegistryEvent::new(EventType::AssetDeleted {
+                asset_id: deleted.id,
+                asset_name: "gone".to_string(),
+                asset_version: "1.0.0".to_string(),
+            }))
+            .await
+            .unwrap();
+        events.append(registered(&dataset)).await.unwrap();
+
+        let service = service(vec![model.clone(), dataset.clone()], events.clone()).await;
+
+        let mut by_id = service
+            .watch_assets(WatchAssetsRequest {
+                asset_ids: vec![model.id],
+                resume_after: Some(first.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(next(&mut by_id).await.asset.unwrap().id, model.id);
+
+        let mut by_type = service
+            .watch_assets(WatchAssetsRequest {
+                asset_types: vec![AssetType::Model],
+                tags: vec!["nlp".to_string()],
+                resume_after: Some(first.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(next(&mut by_type).await.asset.unwrap().id, model.id);
+
+        // Name filters fall back to the name recorded in the event
+        let mut by_name = service
+            .watch_assets(WatchAssetsRequest {
+                asset_names: vec!["gone".to_string(), "corpus".to_string()],
+                event_types: vec!["asset_deleted".to_string()],
+                resume_after: Some(first),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let event = next(&mut by_name).await;
+        assert!(event.asset.is_none());
+        assert_eq!(event.event.event_type.asset_id(), Some(deleted.id));
+
+        let result = service
+            .watch_assets(WatchAssetsRequest {
+                resume_after: Some("not-a-cursor".to_string()),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[async_trait]
+    impl EventStore for MockEventStore {
+        async fn append(&self, event: RegistryEvent) -> DbResult<RegistryEvent> {
+            // Later events get lower IDs, as when instances' clocks disagree
+            let mut events = self.events.lock().unwrap();
+            let sequence = events.len() as i64 + 1;
+            let event_id = ulid::Ulid::from_parts(1_000 - sequence as u64, 0).to_string();
+            events.push(StoredEvent {
+                event_id,
+                sequence,
+                event: event.clone(),
+            });
+            Ok(event)
+        }
+        async fn append_batch(&self, events: Vec<RegistryEvent>) -> DbResult<Vec<RegistryEvent>> {
+            for event in &events {
+                self.append(event.clone()).await?;
+            }
+            Ok(events)
+        }
+        async fn query(&self, _: &llm_registry_db::EventQuery) -> DbResult<llm_registry_db::EventQueryResults> {
+            unimplemented!()
+        }
+        async fn get_asset_events(&self, _: &llm_registry_core::AssetId, _: i64) -> DbResult<Vec<RegistryEvent>> {
+            Ok(vec![])
+        }
+        async fn get_latest_event(&self, _: &llm_registry_core::AssetId) -> DbResult<Option<RegistryEvent>> {
+            Ok(None)
+        }
+        async fn read_after(&self, after: Option<i64>, limit: i64) -> DbResult<Vec<StoredEvent>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .filter(|e| after.map_or(true, |after| e.sequence > after))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+        async fn latest_sequence(&self) -> DbResult<Option<i64>> {
+            Ok(self.events.lock().unwrap().last().map(|e| e.sequence))
+        }
+        async fn find_sequence(&self, event_id: &str) -> DbResult<Option<i64>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .find(|e| e.event_id == event_id)
+                .map(|e| e.sequence))
+        }
+        async fn count_events(&self) -> DbResult<i64> {
+            Ok(self.events.lock().unwrap().len() as i64)
+        }
+        async fn count_by_type(&self, _: &str) -> DbResult<i64> {
+            Ok(0)
+        }
+        async fn health_check(&self) -> DbResult<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,52 @@
+-- Resumable chunked upload sessions
+-- Migration: 20250301000001_upload_sessions
+
+-- Upload sessions table: One row per in-progress or finished chunked upload
+CREATE TABLE upload_sessions (
+    -- Session identifier (ULID format)
+    id VARCHAR(26) PRIMARY KEY,
+
+    -- Asset receiving the content
+    asset_id VARCHAR(26) NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+
+    -- Declared layout of the upload
+    total_size BIGINT NOT NULL,
+    chunk_size BIGINT NOT NULL,
+
+    -- Session lifecycle: active, committed, aborted
+    status VARCHAR(20) NOT NULL DEFAULT 'active',
+
+    -- Who created the session
+    created_by VARCHAR(255),
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+
+    -- Constraints
+    CHECK (total_size > 0),
+    CHECK (chunk_size > 0)
+);
+
+CREATE INDEX idx_upload_sessions_asset ON upload_sessions(asset_id);
+CREATE INDEX idx_upload_sessions_expires_at ON upload_sessions(expires_at);
+
+-- Upload session chunks table: Chunks received so far
+CREATE TABLE upload_session_chunks (
+    session_id VARCHAR(26) NOT NULL REFERENCES upload_sessions(id) ON DELETE CASCADE,
+    chunk_index INTEGER NOT NULL,
+    size_bytes BIGINT NOT NULL,
+
+    -- Per-chunk integrity
+    checksum_algorithm VARCHAR(50) NOT NULL,
+    checksum_value VARCHAR(128) NOT NULL,
+
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (session_id, chunk_index),
+    CHECK (chunk_index >= 0),
+    CHECK (size_bytes >= 0)
+);
+
+COMMENT ON TABLE upload_sessions IS 'Resumable chunked uploads of asset content';
+COMMENT ON TABLE upload_session_chunks IS 'Chunks received for an upload session, with per-chunk checksums';
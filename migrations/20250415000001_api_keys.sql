@@ -0,0 +1,32 @@
+-- Managed API keys
+-- Migration: 20250415000001_api_keys
+
+-- API keys table: One row per key, secrets are stored hashed
+CREATE TABLE api_keys (
+    -- Key identifier (ULID format), embedded in the key itself
+    id VARCHAR(26) PRIMARY KEY,
+
+    -- Display name and owning principal
+    name VARCHAR(255) NOT NULL,
+    owner VARCHAR(255) NOT NULL,
+
+    -- Owner's roles at creation and the permissions the key is restricted to
+    roles TEXT[] NOT NULL DEFAULT '{}',
+    permissions TEXT[] NOT NULL,
+
+    -- SHA-256 hash of the key secret
+    secret_hash VARCHAR(128) NOT NULL,
+
+    -- Lifecycle
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    CHECK (cardinality(permissions) > 0)
+);
+
+CREATE INDEX idx_api_keys_owner ON api_keys(owner);
+
+COMMENT ON TABLE api_keys IS 'Hashed, scoped API keys that act on behalf of their owner';
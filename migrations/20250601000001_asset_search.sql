@@ -0,0 +1,79 @@
+-- Full-text search over assets
+-- Migration: 20250601000001_asset_search
+
+-- Weighted search document of an asset: name (A), tags (B), description (C)
+-- and selected annotations (D). The annotation keys must match
+-- SEARCHABLE_ANNOTATIONS in the db crate's text_query module.
+CREATE OR REPLACE FUNCTION asset_search_vector(
+    name TEXT,
+    description TEXT,
+    metadata JSONB,
+    tags TEXT
+) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', coalesce(name, '')), 'A')
+        || setweight(to_tsvector('english', coalesce(tags, '')), 'B')
+        || setweight(to_tsvector('english', coalesce(description, '')), 'C')
+        || setweight(to_tsvector('english', concat_ws(' ',
+               metadata->>'summary',
+               metadata->>'keywords',
+               metadata->>'task',
+               metadata->>'framework')), 'D')
+$$ LANGUAGE sql IMMUTABLE;
+
+ALTER TABLE assets ADD COLUMN search_vector tsvector NOT NULL DEFAULT ''::tsvector;
+
+-- Keep the vector current when an asset is written
+CREATE OR REPLACE FUNCTION update_asset_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = asset_search_vector(
+        NEW.name,
+        NEW.description,
+        NEW.metadata,
+        (SELECT string_agg(tag, ' ') FROM asset_tags WHERE asset_id = NEW.id)
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_assets_search_vector
+    BEFORE INSERT OR UPDATE ON assets
+    FOR EACH ROW
+    EXECUTE FUNCTION update_asset_search_vector();
+
+-- Tags live in their own table, so changing them rewrites the asset row,
+-- which recomputes its vector
+CREATE OR REPLACE FUNCTION refresh_asset_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE assets SET search_vector = search_vector
+    WHERE id = CASE WHEN TG_OP = 'DELETE' THEN OLD.asset_id ELSE NEW.asset_id END;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER refresh_assets_search_vector
+    AFTER INSERT OR UPDATE OR DELETE ON asset_tags
+    FOR EACH ROW
+    EXECUTE FUNCTION refresh_asset_search_vector();
+
+-- A vector refresh is not an edit, so it must not bump updated_at
+CREATE OR REPLACE FUNCTION update_updated_at_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF pg_trigger_depth() > 1 THEN
+        RETURN NEW;
+    END IF;
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Fill in existing assets
+ALTER TABLE assets DISABLE TRIGGER update_assets_updated_at;
+UPDATE assets SET search_vector = search_vector;
+ALTER TABLE assets ENABLE TRIGGER update_assets_updated_at;
+
+CREATE INDEX idx_assets_search_vector ON assets USING GIN(search_vector);
+
+COMMENT ON COLUMN assets.search_vector IS 'Weighted full-text search document, maintained by triggers';
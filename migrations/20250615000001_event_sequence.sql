@@ -0,0 +1,33 @@
+-- Store-assigned event sequence numbers
+-- Migration: 20250615000001_event_sequence
+
+-- Position of each event in commit order. Event IDs are generated by each
+-- registry instance and can commit out of order, so readers tailing the log
+-- resume from a sequence number instead. Appends hold an advisory lock until
+-- they commit, so sequence numbers become visible in increasing order.
+ALTER TABLE registry_events ADD COLUMN sequence BIGINT;
+
+CREATE SEQUENCE registry_events_sequence_seq OWNED BY registry_events.sequence;
+
+-- Number existing events in event ID order
+UPDATE registry_events
+SET sequence = numbered.position
+FROM (
+    SELECT event_id, ROW_NUMBER() OVER (ORDER BY event_id) AS position
+    FROM registry_events
+) numbered
+WHERE registry_events.event_id = numbered.event_id;
+
+SELECT setval(
+    'registry_events_sequence_seq',
+    COALESCE((SELECT MAX(sequence) FROM registry_events), 0) + 1,
+    false
+);
+
+ALTER TABLE registry_events
+    ALTER COLUMN sequence SET DEFAULT nextval('registry_events_sequence_seq'),
+    ALTER COLUMN sequence SET NOT NULL;
+
+CREATE UNIQUE INDEX idx_registry_events_sequence ON registry_events(sequence);
+
+COMMENT ON COLUMN registry_events.sequence IS 'Position in commit order, used as the resume cursor for event readers';
@@ -41,7 +41,7 @@ async fn test_rate_limit_headers() {
         .expect("Failed to send request");
 
     // Should have rate limit headers
-    assert!(response.headers().contains_key("x-ratelimit-limit") ||
+    assert!(response.headers().contains_key("ratelimit-limit") ||
             response.status().is_success());
 }
 